@@ -0,0 +1,23 @@
+package com.example.annotations;
+
+@Unconfigured
+@Deprecated
+public class Annotated {
+
+    @Deprecated
+    public int legacy;
+
+    @Unconfigured
+    private String hidden;
+
+    public @NonNull String @NonNull [] tags;
+
+    @Marker
+    @Unconfigured
+    public void mark() {
+    }
+
+    public @NonNull String greet(@Named("who") String who, @Unconfigured int times, @NonNull String @NonNull [] lines) {
+        return who;
+    }
+}
@@ -0,0 +1,27 @@
+package com.example.annotations;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+
+@Retention(RetentionPolicy.RUNTIME)
+@Target(ElementType.TYPE)
+public @interface Entity {
+    String table();
+
+    String schema() default "public";
+
+    Kind kind() default Kind.TABLE;
+
+    int version() default 1;
+
+    Class<?>[] listeners() default {};
+
+    Index[] indexes() default {};
+
+    enum Kind {
+        TABLE,
+        VIEW
+    }
+}
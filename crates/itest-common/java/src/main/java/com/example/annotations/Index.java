@@ -0,0 +1,5 @@
+package com.example.annotations;
+
+public @interface Index {
+    String[] columns();
+}
@@ -0,0 +1,4 @@
+package com.example.annotations;
+
+public @interface Unconfigured {
+}
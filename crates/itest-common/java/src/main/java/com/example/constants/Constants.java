@@ -0,0 +1,14 @@
+package com.example.constants;
+
+public class Constants {
+    public static final int ANSWER = 42;
+    public static final long BIG = 1L << 40;
+    public static final float RATIO = 1.5f;
+    public static final double PRECISE = 2.25;
+    public static final String NAME = "constants";
+    public static final String SMILE = "\uD83D\uDE00\0";
+
+    public static double total(int count) {
+        return count * 100000 + BIG + RATIO + PRECISE + NAME.length();
+    }
+}
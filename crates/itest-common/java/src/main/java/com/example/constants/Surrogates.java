@@ -0,0 +1,5 @@
+package com.example.constants;
+
+public class Surrogates {
+    public static final String LONE = "a\uD800b";
+}
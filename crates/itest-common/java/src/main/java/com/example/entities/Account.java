@@ -0,0 +1,13 @@
+package com.example.entities;
+
+import com.example.annotations.Entity;
+import com.example.annotations.Index;
+
+@Entity(
+        table = "accounts",
+        kind = Entity.Kind.VIEW,
+        version = 100000,
+        listeners = Object.class,
+        indexes = {@Index(columns = "id"), @Index(columns = {"name", "email"})})
+public class Account {
+}
@@ -0,0 +1,11 @@
+package com.example.enums;
+
+public enum Color {
+    RED,
+    GREEN,
+    BLUE;
+
+    public Color next() {
+        return values()[(ordinal() + 1) % values().length];
+    }
+}
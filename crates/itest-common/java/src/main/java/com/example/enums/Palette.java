@@ -0,0 +1,15 @@
+package com.example.enums;
+
+public class Palette {
+
+    public static int warmth(Color color) {
+        switch (color) {
+            case RED:
+                return 2;
+            case GREEN:
+                return 1;
+            default:
+                return 0;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+package com.example.flow;
+
+public class Branches {
+
+    public static int sum(int[] values) {
+        int total = 0;
+        for (int i = 0; i < values.length; i++) {
+            total += values[i];
+        }
+        return total;
+    }
+
+    public static int choose(boolean flag) {
+        int chosen = flag ? 1 : 2;
+        return chosen;
+    }
+
+    public static int parse(String value) {
+        try {
+            return Integer.parseInt(value);
+        } catch (NumberFormatException e) {
+            return -1;
+        }
+    }
+
+    public static String name(int day) {
+        switch (day) {
+            case 0:
+                return "zero";
+            case 1:
+                return "one";
+            case 2:
+                return "two";
+            default:
+                return "many";
+        }
+    }
+}
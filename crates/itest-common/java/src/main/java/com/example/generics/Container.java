@@ -0,0 +1,33 @@
+package com.example.generics;
+
+import java.util.AbstractList;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+import java.util.RandomAccess;
+
+public class Container<T extends Comparable<? super T>> extends AbstractList<T> implements RandomAccess {
+
+    private final List<T> items = new ArrayList<>();
+    public Map<String, ? extends Number> counts;
+    public Inner first;
+    public int size;
+
+    @Override
+    public T get(int index) {
+        return items.get(index);
+    }
+
+    @Override
+    public int size() {
+        return items.size();
+    }
+
+    public <E extends Exception> void drainTo(List<? super T> sink, int[] order) throws E {
+        sink.addAll(items);
+    }
+
+    public class Inner {
+        public T value;
+    }
+}
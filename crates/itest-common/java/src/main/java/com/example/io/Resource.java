@@ -0,0 +1,13 @@
+package com.example.io;
+
+import java.io.IOException;
+
+public class Resource implements AutoCloseable {
+
+    public void open(String path) throws IOException, InterruptedException {
+    }
+
+    @Override
+    public void close() throws IOException {
+    }
+}
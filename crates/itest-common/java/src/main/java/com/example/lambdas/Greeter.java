@@ -0,0 +1,12 @@
+package com.example.lambdas;
+
+public class Greeter {
+
+    public static Runnable greeting(String name) {
+        return () -> System.out.println("hello " + name);
+    }
+
+    public static Runnable notifier(Object lock) {
+        return lock::notify;
+    }
+}
@@ -0,0 +1,8 @@
+package com.example.lambdas;
+
+public class Task implements Runnable {
+
+    @Override
+    public void run() {
+    }
+}
@@ -0,0 +1,14 @@
+package com.example.natives;
+
+public class Natives {
+
+    public native int add(int a, int b);
+
+    public native long add(long a, String b);
+
+    public static native void init_lib(String[] args);
+
+    public int twice(int value) {
+        return add(value, value);
+    }
+}
@@ -0,0 +1,24 @@
+package com.example.nested;
+
+public class Outer {
+
+    public static class Nested {
+    }
+
+    private class Inner {
+    }
+
+    public Runnable anonymous() {
+        return new Runnable() {
+            @Override
+            public void run() {
+            }
+        };
+    }
+
+    public Object local() {
+        class Local {
+        }
+        return new Local();
+    }
+}
@@ -0,0 +1,13 @@
+package com.example.obfuscated;
+
+public class a {
+    private Object b;
+
+    public Object c() {
+        return b;
+    }
+
+    public void c(Object d) {
+        b = d;
+    }
+}
@@ -0,0 +1,4 @@
+package com.example.records;
+
+public record Point(double x, double y) {
+}
@@ -0,0 +1,14 @@
+package com.example.records;
+
+public record Range(int low, long high, Object tag) {
+
+    public Range {
+        if (low > high) {
+            throw new IllegalArgumentException();
+        }
+    }
+
+    public long high() {
+        return Math.max(low, high);
+    }
+}
@@ -0,0 +1,6 @@
+package com.example.records;
+
+import com.example.annotations.Named;
+
+public record Tagged(@Named("label") String name, int weight) {
+}
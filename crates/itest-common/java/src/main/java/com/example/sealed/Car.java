@@ -0,0 +1,9 @@
+package com.example.sealed;
+
+public final class Car implements Vehicle {
+
+    @Override
+    public int wheels() {
+        return 4;
+    }
+}
@@ -0,0 +1,9 @@
+package com.example.sealed;
+
+public non-sealed class Truck implements Vehicle {
+
+    @Override
+    public int wheels() {
+        return 6;
+    }
+}
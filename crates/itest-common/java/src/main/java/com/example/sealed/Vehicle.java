@@ -0,0 +1,5 @@
+package com.example.sealed;
+
+public sealed interface Vehicle permits Car, Truck {
+    int wheels();
+}
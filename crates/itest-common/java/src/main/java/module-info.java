@@ -0,0 +1,14 @@
+module com.example {
+    requires transitive java.logging;
+    requires static java.sql;
+
+    exports com.example;
+    exports com.example.annotations;
+    exports com.example.records to java.logging;
+
+    opens com.example.entities;
+
+    uses java.lang.Runnable;
+
+    provides java.lang.Runnable with com.example.lambdas.Task;
+}
@@ -1,17 +1,80 @@
+use std::fs::File;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::ZipWriter;
 
 /// Gets the generated jar file
 pub fn jar_file() -> PathBuf {
-    let out_dir = option_env!("OUT_DIR").expect("OUT_DIR not set");
+    let out_dir = env!("OUT_DIR");
     Path::new(&out_dir).join("java.jar")
 }
 
 /// Gets the path to the generated classes directory
 pub fn classes() -> PathBuf {
-    let out_dir = option_env!("OUT_DIR").expect("OUT_DIR not set");
+    let out_dir = env!("OUT_DIR");
     Path::new(&out_dir).join("classes")
 }
 
+/// Gets the paths of every generated class file
+pub fn class_files() -> Vec<PathBuf> {
+    fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(&path, files);
+            } else if path.extension() == Some("class".as_ref()) {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = vec![];
+    visit(&classes(), &mut files);
+    files
+}
+
+/// Reads the class file of a generated class, such as `com/example/Square`
+pub fn class_bytes(name: &str) -> Vec<u8> {
+    std::fs::read(classes().join(format!("{name}.class"))).unwrap()
+}
+
+/// Writes a jar with the given entries to `path`
+pub fn write_jar_at<B: AsRef<[u8]>>(path: &Path, entries: &[(&str, B)]) {
+    let mut jar = ZipWriter::new(File::create(path).unwrap());
+    for (entry, contents) in entries {
+        jar.start_file(*entry, FileOptions::default()).unwrap();
+        jar.write_all(contents.as_ref()).unwrap();
+    }
+    jar.finish().unwrap();
+}
+
+/// Writes a jar with the given entries to the temporary directory, returning its path. Jars are
+/// named after `name` and the current process, so test binaries running at the same time don't
+/// overwrite each other's jars.
+pub fn write_jar<B: AsRef<[u8]>>(name: &str, entries: &[(&str, B)]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("itest-{}-{name}.jar", std::process::id()));
+    write_jar_at(&path, entries);
+    path
+}
+
+/// Replaces a utf8 constant in the constant pool of a class file, for creating class files that
+/// can't be compiled. Constants are prefixed by their length, so they can be resized safely.
+pub fn replace_utf8(bytes: &[u8], old: &str, new: &str) -> Vec<u8> {
+    let mut entry = vec![1];
+    entry.extend((old.len() as u16).to_be_bytes());
+    entry.extend(old.as_bytes());
+    let start = bytes
+        .windows(entry.len())
+        .position(|window| window == entry)
+        .expect("constant not found");
+    let mut output = bytes[..start + 1].to_vec();
+    output.extend((new.len() as u16).to_be_bytes());
+    output.extend(new.as_bytes());
+    output.extend(&bytes[start + entry.len()..]);
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{classes, jar_file};
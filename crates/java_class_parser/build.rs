@@ -0,0 +1,6 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // `strict_mode` is set by building with the `strict` feature, which isn't a published feature
+    println!("cargo:rustc-check-cfg=cfg(strict_mode)");
+    println!("cargo:rustc-check-cfg=cfg(feature, values(\"strict\"))");
+}
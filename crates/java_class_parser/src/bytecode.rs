@@ -0,0 +1,687 @@
+//! Decoding of the instructions within a [`Code`](crate::attributes::Code) attribute, as described
+//! in [chapter 6](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html) of the JVM
+//! specification.
+//!
+//! # Example
+//! ```
+//! # use java_class_parser::bytecode::{decode, Opcode};
+//! // aload_0, areturn
+//! let instructions = decode(&[0x2a, 0xb0])
+//!     .map(|instruction| instruction.map(|i| i.opcode()))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! assert_eq!(instructions, [Opcode::Aload0, Opcode::Areturn]);
+//! ```
+
+use crate::utility::ByteReader;
+use crate::{FQName, JavaClass, MemberRef, ResolvedConstant};
+use std::fmt::{Display, Formatter};
+
+pub mod metrics;
+
+macro_rules! opcodes {
+    ($($name:ident = $value:literal => $mnemonic:literal),* $(,)?) => {
+        /// The opcode of an instruction
+        #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+        #[allow(missing_docs)]
+        #[repr(u8)]
+        pub enum Opcode {
+            $($name = $value),*
+        }
+
+        impl Opcode {
+            /// Gets the opcode represented by a byte, if the byte is a known opcode
+            pub fn from_u8(byte: u8) -> Option<Self> {
+                match byte {
+                    $($value => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            /// The mnemonic of the opcode, as used by `javap`
+            pub fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(Self::$name => $mnemonic),*
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    Nop = 0x00 => "nop",
+    AconstNull = 0x01 => "aconst_null",
+    IconstM1 = 0x02 => "iconst_m1",
+    Iconst0 = 0x03 => "iconst_0",
+    Iconst1 = 0x04 => "iconst_1",
+    Iconst2 = 0x05 => "iconst_2",
+    Iconst3 = 0x06 => "iconst_3",
+    Iconst4 = 0x07 => "iconst_4",
+    Iconst5 = 0x08 => "iconst_5",
+    Lconst0 = 0x09 => "lconst_0",
+    Lconst1 = 0x0a => "lconst_1",
+    Fconst0 = 0x0b => "fconst_0",
+    Fconst1 = 0x0c => "fconst_1",
+    Fconst2 = 0x0d => "fconst_2",
+    Dconst0 = 0x0e => "dconst_0",
+    Dconst1 = 0x0f => "dconst_1",
+    Bipush = 0x10 => "bipush",
+    Sipush = 0x11 => "sipush",
+    Ldc = 0x12 => "ldc",
+    LdcW = 0x13 => "ldc_w",
+    Ldc2W = 0x14 => "ldc2_w",
+    Iload = 0x15 => "iload",
+    Lload = 0x16 => "lload",
+    Fload = 0x17 => "fload",
+    Dload = 0x18 => "dload",
+    Aload = 0x19 => "aload",
+    Iload0 = 0x1a => "iload_0",
+    Iload1 = 0x1b => "iload_1",
+    Iload2 = 0x1c => "iload_2",
+    Iload3 = 0x1d => "iload_3",
+    Lload0 = 0x1e => "lload_0",
+    Lload1 = 0x1f => "lload_1",
+    Lload2 = 0x20 => "lload_2",
+    Lload3 = 0x21 => "lload_3",
+    Fload0 = 0x22 => "fload_0",
+    Fload1 = 0x23 => "fload_1",
+    Fload2 = 0x24 => "fload_2",
+    Fload3 = 0x25 => "fload_3",
+    Dload0 = 0x26 => "dload_0",
+    Dload1 = 0x27 => "dload_1",
+    Dload2 = 0x28 => "dload_2",
+    Dload3 = 0x29 => "dload_3",
+    Aload0 = 0x2a => "aload_0",
+    Aload1 = 0x2b => "aload_1",
+    Aload2 = 0x2c => "aload_2",
+    Aload3 = 0x2d => "aload_3",
+    Iaload = 0x2e => "iaload",
+    Laload = 0x2f => "laload",
+    Faload = 0x30 => "faload",
+    Daload = 0x31 => "daload",
+    Aaload = 0x32 => "aaload",
+    Baload = 0x33 => "baload",
+    Caload = 0x34 => "caload",
+    Saload = 0x35 => "saload",
+    Istore = 0x36 => "istore",
+    Lstore = 0x37 => "lstore",
+    Fstore = 0x38 => "fstore",
+    Dstore = 0x39 => "dstore",
+    Astore = 0x3a => "astore",
+    Istore0 = 0x3b => "istore_0",
+    Istore1 = 0x3c => "istore_1",
+    Istore2 = 0x3d => "istore_2",
+    Istore3 = 0x3e => "istore_3",
+    Lstore0 = 0x3f => "lstore_0",
+    Lstore1 = 0x40 => "lstore_1",
+    Lstore2 = 0x41 => "lstore_2",
+    Lstore3 = 0x42 => "lstore_3",
+    Fstore0 = 0x43 => "fstore_0",
+    Fstore1 = 0x44 => "fstore_1",
+    Fstore2 = 0x45 => "fstore_2",
+    Fstore3 = 0x46 => "fstore_3",
+    Dstore0 = 0x47 => "dstore_0",
+    Dstore1 = 0x48 => "dstore_1",
+    Dstore2 = 0x49 => "dstore_2",
+    Dstore3 = 0x4a => "dstore_3",
+    Astore0 = 0x4b => "astore_0",
+    Astore1 = 0x4c => "astore_1",
+    Astore2 = 0x4d => "astore_2",
+    Astore3 = 0x4e => "astore_3",
+    Iastore = 0x4f => "iastore",
+    Lastore = 0x50 => "lastore",
+    Fastore = 0x51 => "fastore",
+    Dastore = 0x52 => "dastore",
+    Aastore = 0x53 => "aastore",
+    Bastore = 0x54 => "bastore",
+    Castore = 0x55 => "castore",
+    Sastore = 0x56 => "sastore",
+    Pop = 0x57 => "pop",
+    Pop2 = 0x58 => "pop2",
+    Dup = 0x59 => "dup",
+    DupX1 = 0x5a => "dup_x1",
+    DupX2 = 0x5b => "dup_x2",
+    Dup2 = 0x5c => "dup2",
+    Dup2X1 = 0x5d => "dup2_x1",
+    Dup2X2 = 0x5e => "dup2_x2",
+    Swap = 0x5f => "swap",
+    Iadd = 0x60 => "iadd",
+    Ladd = 0x61 => "ladd",
+    Fadd = 0x62 => "fadd",
+    Dadd = 0x63 => "dadd",
+    Isub = 0x64 => "isub",
+    Lsub = 0x65 => "lsub",
+    Fsub = 0x66 => "fsub",
+    Dsub = 0x67 => "dsub",
+    Imul = 0x68 => "imul",
+    Lmul = 0x69 => "lmul",
+    Fmul = 0x6a => "fmul",
+    Dmul = 0x6b => "dmul",
+    Idiv = 0x6c => "idiv",
+    Ldiv = 0x6d => "ldiv",
+    Fdiv = 0x6e => "fdiv",
+    Ddiv = 0x6f => "ddiv",
+    Irem = 0x70 => "irem",
+    Lrem = 0x71 => "lrem",
+    Frem = 0x72 => "frem",
+    Drem = 0x73 => "drem",
+    Ineg = 0x74 => "ineg",
+    Lneg = 0x75 => "lneg",
+    Fneg = 0x76 => "fneg",
+    Dneg = 0x77 => "dneg",
+    Ishl = 0x78 => "ishl",
+    Lshl = 0x79 => "lshl",
+    Ishr = 0x7a => "ishr",
+    Lshr = 0x7b => "lshr",
+    Iushr = 0x7c => "iushr",
+    Lushr = 0x7d => "lushr",
+    Iand = 0x7e => "iand",
+    Land = 0x7f => "land",
+    Ior = 0x80 => "ior",
+    Lor = 0x81 => "lor",
+    Ixor = 0x82 => "ixor",
+    Lxor = 0x83 => "lxor",
+    Iinc = 0x84 => "iinc",
+    I2l = 0x85 => "i2l",
+    I2f = 0x86 => "i2f",
+    I2d = 0x87 => "i2d",
+    L2i = 0x88 => "l2i",
+    L2f = 0x89 => "l2f",
+    L2d = 0x8a => "l2d",
+    F2i = 0x8b => "f2i",
+    F2l = 0x8c => "f2l",
+    F2d = 0x8d => "f2d",
+    D2i = 0x8e => "d2i",
+    D2l = 0x8f => "d2l",
+    D2f = 0x90 => "d2f",
+    I2b = 0x91 => "i2b",
+    I2c = 0x92 => "i2c",
+    I2s = 0x93 => "i2s",
+    Lcmp = 0x94 => "lcmp",
+    Fcmpl = 0x95 => "fcmpl",
+    Fcmpg = 0x96 => "fcmpg",
+    Dcmpl = 0x97 => "dcmpl",
+    Dcmpg = 0x98 => "dcmpg",
+    Ifeq = 0x99 => "ifeq",
+    Ifne = 0x9a => "ifne",
+    Iflt = 0x9b => "iflt",
+    Ifge = 0x9c => "ifge",
+    Ifgt = 0x9d => "ifgt",
+    Ifle = 0x9e => "ifle",
+    IfIcmpeq = 0x9f => "if_icmpeq",
+    IfIcmpne = 0xa0 => "if_icmpne",
+    IfIcmplt = 0xa1 => "if_icmplt",
+    IfIcmpge = 0xa2 => "if_icmpge",
+    IfIcmpgt = 0xa3 => "if_icmpgt",
+    IfIcmple = 0xa4 => "if_icmple",
+    IfAcmpeq = 0xa5 => "if_acmpeq",
+    IfAcmpne = 0xa6 => "if_acmpne",
+    Goto = 0xa7 => "goto",
+    Jsr = 0xa8 => "jsr",
+    Ret = 0xa9 => "ret",
+    Tableswitch = 0xaa => "tableswitch",
+    Lookupswitch = 0xab => "lookupswitch",
+    Ireturn = 0xac => "ireturn",
+    Lreturn = 0xad => "lreturn",
+    Freturn = 0xae => "freturn",
+    Dreturn = 0xaf => "dreturn",
+    Areturn = 0xb0 => "areturn",
+    Return = 0xb1 => "return",
+    Getstatic = 0xb2 => "getstatic",
+    Putstatic = 0xb3 => "putstatic",
+    Getfield = 0xb4 => "getfield",
+    Putfield = 0xb5 => "putfield",
+    Invokevirtual = 0xb6 => "invokevirtual",
+    Invokespecial = 0xb7 => "invokespecial",
+    Invokestatic = 0xb8 => "invokestatic",
+    Invokeinterface = 0xb9 => "invokeinterface",
+    Invokedynamic = 0xba => "invokedynamic",
+    New = 0xbb => "new",
+    Newarray = 0xbc => "newarray",
+    Anewarray = 0xbd => "anewarray",
+    Arraylength = 0xbe => "arraylength",
+    Athrow = 0xbf => "athrow",
+    Checkcast = 0xc0 => "checkcast",
+    Instanceof = 0xc1 => "instanceof",
+    Monitorenter = 0xc2 => "monitorenter",
+    Monitorexit = 0xc3 => "monitorexit",
+    Wide = 0xc4 => "wide",
+    Multianewarray = 0xc5 => "multianewarray",
+    Ifnull = 0xc6 => "ifnull",
+    Ifnonnull = 0xc7 => "ifnonnull",
+    GotoW = 0xc8 => "goto_w",
+    JsrW = 0xc9 => "jsr_w",
+    Breakpoint = 0xca => "breakpoint",
+    Impdep1 = 0xfe => "impdep1",
+    Impdep2 = 0xff => "impdep2",
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+/// The operands of an instruction. Branch targets are absolute offsets into the code array.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Operands {
+    /// The instruction has no operands
+    None,
+    /// A local variable index, used by loads, stores and `ret`
+    Local(u16),
+    /// An index into the constant pool
+    Constant(u16),
+    /// An immediate value, pushed by `bipush` and `sipush`
+    Immediate(i16),
+    /// The target of a branch
+    Branch(u32),
+    /// The operands of `iinc`
+    Iinc {
+        /// The local variable index
+        index: u16,
+        /// The amount to increment by
+        increment: i16,
+    },
+    /// The operands of `invokeinterface`
+    InvokeInterface {
+        /// The constant pool index of the interface method
+        index: u16,
+        /// The number of argument slots, including the receiver
+        count: u8,
+    },
+    /// The primitive array type code of `newarray`
+    ArrayType(u8),
+    /// The operands of `multianewarray`
+    MultiANewArray {
+        /// The constant pool index of the array class
+        index: u16,
+        /// The number of dimensions to create
+        dimensions: u8,
+    },
+    /// The jump table of `tableswitch`
+    TableSwitch {
+        /// The target when no case matches
+        default: u32,
+        /// The lowest case value
+        low: i32,
+        /// The targets of each case value, starting from `low`
+        targets: Vec<u32>,
+    },
+    /// The match pairs of `lookupswitch`
+    LookupSwitch {
+        /// The target when no case matches
+        default: u32,
+        /// Each case value and its target
+        pairs: Vec<(i32, u32)>,
+    },
+}
+
+/// A single decoded instruction
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Instruction {
+    offset: u32,
+    len: u32,
+    opcode: Opcode,
+    wide: bool,
+    operands: Operands,
+}
+
+impl Instruction {
+    /// The offset of the instruction within the code array
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// The length of the instruction in bytes, including its operands and any padding
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Instructions are never empty, this always returns `false`
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The offset of the instruction directly following this one
+    pub fn next_offset(&self) -> u32 {
+        self.offset + self.len
+    }
+
+    /// The opcode of the instruction. For instructions modified by `wide`, this is the modified
+    /// opcode.
+    pub fn opcode(&self) -> Opcode {
+        self.opcode
+    }
+
+    /// Whether the instruction was modified by a `wide` prefix
+    pub fn is_wide(&self) -> bool {
+        self.wide
+    }
+
+    /// The operands of the instruction
+    pub fn operands(&self) -> &Operands {
+        &self.operands
+    }
+
+    /// The constant pool index referenced by this instruction, if any
+    pub fn constant_index(&self) -> Option<u16> {
+        match self.operands {
+            Operands::Constant(index)
+            | Operands::InvokeInterface { index, .. }
+            | Operands::MultiANewArray { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Every offset this instruction may explicitly branch to, not including falling through to
+    /// the next instruction.
+    pub fn branch_targets(&self) -> Vec<u32> {
+        match &self.operands {
+            Operands::Branch(target) => vec![*target],
+            Operands::TableSwitch {
+                default, targets, ..
+            } => std::iter::once(*default)
+                .chain(targets.iter().copied())
+                .collect(),
+            Operands::LookupSwitch { default, pairs } => std::iter::once(*default)
+                .chain(pairs.iter().map(|&(_, target)| target))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// An error that occurred while decoding bytecode
+#[derive(Debug, Eq, PartialEq, Clone, thiserror::Error)]
+pub enum DecodeError {
+    /// The byte at the offset is not a known opcode
+    #[error("{opcode:#04x} at offset {offset} is not a known opcode")]
+    UnknownOpcode {
+        /// The unknown opcode
+        opcode: u8,
+        /// The offset of the opcode
+        offset: u32,
+    },
+    /// The code ended in the middle of the instruction at the offset
+    #[error("instruction at offset {0} is truncated")]
+    Truncated(u32),
+    /// The instruction at the offset branches outside of the code array
+    #[error("instruction at offset {0} branches to a negative offset")]
+    InvalidBranch(u32),
+}
+
+/// An iterator over the instructions of a code array. Iteration stops after the first error.
+#[derive(Debug, Clone)]
+pub struct Instructions<'a> {
+    reader: ByteReader<'a>,
+    failed: bool,
+}
+
+/// Decodes the instructions in a code array
+pub fn decode(code: &[u8]) -> Instructions<'_> {
+    Instructions {
+        reader: ByteReader::new(code),
+        failed: false,
+    }
+}
+
+impl Iterator for Instructions<'_> {
+    type Item = Result<Instruction, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.is_empty() {
+            return None;
+        }
+        let result = decode_instruction(&mut self.reader);
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+/// An instruction with the constant it references resolved against the constant pool of its
+/// class, such as the owner, name and descriptor of the method called by `invokevirtual`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedInstruction<'a> {
+    instruction: Instruction,
+    constant: Option<ResolvedConstant<'a>>,
+}
+
+impl<'a> ResolvedInstruction<'a> {
+    /// The decoded instruction
+    pub fn instruction(&self) -> &Instruction {
+        &self.instruction
+    }
+
+    /// The opcode of the instruction
+    pub fn opcode(&self) -> Opcode {
+        self.instruction.opcode
+    }
+
+    /// The constant referenced by the instruction, if any. Indices that don't refer to a valid
+    /// constant are resolved as [`Invalid`](ResolvedConstant::Invalid).
+    pub fn constant(&self) -> Option<&ResolvedConstant<'a>> {
+        self.constant.as_ref()
+    }
+
+    /// The field or method referenced by the instruction, such as by `getfield` or
+    /// `invokevirtual`
+    pub fn member(&self) -> Option<MemberRef<'a>> {
+        match self.constant {
+            Some(
+                ResolvedConstant::FieldRef(member)
+                | ResolvedConstant::MethodRef(member)
+                | ResolvedConstant::InterfaceMethodRef(member),
+            ) => Some(member),
+            _ => None,
+        }
+    }
+
+    /// The class referenced by the instruction, such as by `new` or `checkcast`
+    pub fn class(&self) -> Option<&'a FQName> {
+        match self.constant {
+            Some(ResolvedConstant::Class(class)) => Some(class),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over the instructions of a code array, with their constants resolved. Iteration
+/// stops after the first error.
+#[derive(Debug, Clone)]
+pub struct ResolvedInstructions<'a> {
+    instructions: Instructions<'a>,
+    class: &'a JavaClass,
+}
+
+impl<'a> ResolvedInstructions<'a> {
+    pub(crate) fn new(instructions: Instructions<'a>, class: &'a JavaClass) -> Self {
+        Self {
+            instructions,
+            class,
+        }
+    }
+}
+
+impl<'a> Iterator for ResolvedInstructions<'a> {
+    type Item = Result<ResolvedInstruction<'a>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = match self.instructions.next()? {
+            Ok(instruction) => instruction,
+            Err(error) => return Some(Err(error)),
+        };
+        let constant = instruction.constant_index().map(|index| {
+            self.class
+                .constant(index)
+                .unwrap_or(ResolvedConstant::Invalid)
+        });
+        Some(Ok(ResolvedInstruction {
+            instruction,
+            constant,
+        }))
+    }
+}
+
+fn decode_instruction(reader: &mut ByteReader) -> Result<Instruction, DecodeError> {
+    let offset = reader.position() as u32;
+    let truncated = || DecodeError::Truncated(offset);
+    let branch = |relative: i32| {
+        u32::try_from(offset as i64 + relative as i64)
+            .map_err(|_| DecodeError::InvalidBranch(offset))
+    };
+
+    let byte = reader.u8().ok_or_else(truncated)?;
+    let mut opcode = Opcode::from_u8(byte).ok_or(DecodeError::UnknownOpcode {
+        opcode: byte,
+        offset,
+    })?;
+    let wide = opcode == Opcode::Wide;
+    if wide {
+        let byte = reader.u8().ok_or_else(truncated)?;
+        opcode = Opcode::from_u8(byte).ok_or(DecodeError::UnknownOpcode {
+            opcode: byte,
+            offset: offset + 1,
+        })?;
+    }
+
+    use Opcode::*;
+    let operands = match opcode {
+        Bipush => Operands::Immediate(reader.i8().ok_or_else(truncated)? as i16),
+        Sipush => Operands::Immediate(reader.i16().ok_or_else(truncated)?),
+        Ldc => Operands::Constant(reader.u8().ok_or_else(truncated)? as u16),
+        LdcW | Ldc2W | Getstatic | Putstatic | Getfield | Putfield | Invokevirtual
+        | Invokespecial | Invokestatic | New | Anewarray | Checkcast | Instanceof => {
+            Operands::Constant(reader.u16().ok_or_else(truncated)?)
+        }
+        Invokedynamic => {
+            let index = reader.u16().ok_or_else(truncated)?;
+            reader.take(2).ok_or_else(truncated)?;
+            Operands::Constant(index)
+        }
+        Invokeinterface => {
+            let index = reader.u16().ok_or_else(truncated)?;
+            let count = reader.u8().ok_or_else(truncated)?;
+            reader.u8().ok_or_else(truncated)?;
+            Operands::InvokeInterface { index, count }
+        }
+        Iload | Lload | Fload | Dload | Aload | Istore | Lstore | Fstore | Dstore | Astore
+        | Ret => {
+            let index = if wide {
+                reader.u16()
+            } else {
+                reader.u8().map(u16::from)
+            };
+            Operands::Local(index.ok_or_else(truncated)?)
+        }
+        Iinc => {
+            let (index, increment) = if wide {
+                (reader.u16(), reader.i16())
+            } else {
+                (reader.u8().map(u16::from), reader.i8().map(i16::from))
+            };
+            Operands::Iinc {
+                index: index.ok_or_else(truncated)?,
+                increment: increment.ok_or_else(truncated)?,
+            }
+        }
+        Ifeq | Ifne | Iflt | Ifge | Ifgt | Ifle | IfIcmpeq | IfIcmpne | IfIcmplt | IfIcmpge
+        | IfIcmpgt | IfIcmple | IfAcmpeq | IfAcmpne | Goto | Jsr | Ifnull | Ifnonnull => {
+            Operands::Branch(branch(reader.i16().ok_or_else(truncated)? as i32)?)
+        }
+        GotoW | JsrW => Operands::Branch(branch(reader.i32().ok_or_else(truncated)?)?),
+        Newarray => Operands::ArrayType(reader.u8().ok_or_else(truncated)?),
+        Multianewarray => Operands::MultiANewArray {
+            index: reader.u16().ok_or_else(truncated)?,
+            dimensions: reader.u8().ok_or_else(truncated)?,
+        },
+        Tableswitch | Lookupswitch => {
+            // switch operands are aligned to a multiple of 4 bytes from the start of the code
+            let padding = (4 - reader.position() % 4) % 4;
+            reader.take(padding).ok_or_else(truncated)?;
+            let default = branch(reader.i32().ok_or_else(truncated)?)?;
+            if opcode == Tableswitch {
+                let low = reader.i32().ok_or_else(truncated)?;
+                let high = reader.i32().ok_or_else(truncated)?;
+                let count = (high as i64 - low as i64 + 1).max(0);
+                let targets = (0..count)
+                    .map(|_| branch(reader.i32().ok_or_else(truncated)?))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Operands::TableSwitch {
+                    default,
+                    low,
+                    targets,
+                }
+            } else {
+                let count = reader.i32().ok_or_else(truncated)?.max(0);
+                let pairs = (0..count)
+                    .map(|_| {
+                        let value = reader.i32().ok_or_else(truncated)?;
+                        Ok((value, branch(reader.i32().ok_or_else(truncated)?)?))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Operands::LookupSwitch { default, pairs }
+            }
+        }
+        _ => Operands::None,
+    };
+
+    Ok(Instruction {
+        offset,
+        len: reader.position() as u32 - offset,
+        opcode,
+        wide,
+        operands,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_wide_and_switches() {
+        let code = [
+            0xc4, 0x84, 0x01, 0x00, 0xff, 0xfe, // wide iinc 256, -2
+            0xaa, 0x00, // tableswitch, padded to offset 8
+            0x00, 0x00, 0x00, 0x14, // default: +20
+            0x00, 0x00, 0x00, 0x01, // low
+            0x00, 0x00, 0x00, 0x02, // high
+            0x00, 0x00, 0x00, 0x16, // 1: +22
+            0x00, 0x00, 0x00, 0x18, // 2: +24
+            0xb1, // return
+        ];
+        let instructions = decode(&code).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(instructions.len(), 3);
+        assert!(instructions[0].is_wide());
+        assert_eq!(
+            instructions[0].operands(),
+            &Operands::Iinc {
+                index: 256,
+                increment: -2
+            }
+        );
+        assert_eq!(instructions[1].offset(), 6);
+        assert_eq!(instructions[1].branch_targets(), [26, 28, 30]);
+        assert_eq!(instructions[2].offset(), 28);
+        assert_eq!(instructions[2].opcode(), Opcode::Return);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            decode(&[0xcb]).next(),
+            Some(Err(DecodeError::UnknownOpcode {
+                opcode: 0xcb,
+                offset: 0
+            }))
+        );
+        assert_eq!(
+            decode(&[0x00, 0x11, 0x01]).nth(1),
+            Some(Err(DecodeError::Truncated(1)))
+        );
+        assert_eq!(
+            decode(&[0xa7, 0xff, 0x00]).next(),
+            Some(Err(DecodeError::InvalidBranch(0)))
+        );
+    }
+}
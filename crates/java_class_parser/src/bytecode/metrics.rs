@@ -0,0 +1,184 @@
+//! Statistics about the bytecode of a method, for enforcing code quality gates at build time.
+//!
+//! The cyclomatic complexity of a method is computed from its control flow graph, where every
+//! basic block is a node and every possible transfer of control between blocks is an edge. Blocks
+//! that return or throw are connected to a single exit node, and the entry of each exception
+//! handler is connected to the block starting its protected range.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Foo").unwrap();
+//! for method in class.methods() {
+//!     if let Some(metrics) = method.metrics().unwrap() {
+//!         if metrics.complexity() > 10 {
+//!             println!("{} is too complex", method.name());
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::attributes::Code;
+use crate::bytecode::{DecodeError, Instruction, Opcode};
+use crate::{HasAttributes, Method};
+use std::collections::{BTreeSet, HashSet};
+
+/// Statistics about the bytecode of a method
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct MethodMetrics {
+    instructions: usize,
+    code_size: usize,
+    basic_blocks: usize,
+    complexity: usize,
+    call_sites: usize,
+    max_stack: u16,
+    max_locals: u16,
+}
+
+impl MethodMetrics {
+    /// Computes the metrics of the bytecode of a method
+    ///
+    /// # Error
+    /// Will return an error if the bytecode can't be decoded
+    pub fn of(code: &Code) -> Result<Self, DecodeError> {
+        let instructions = code.instructions().collect::<Result<Vec<_>, _>>()?;
+
+        // the first instruction of every basic block
+        let mut leaders = BTreeSet::from([0]);
+        for instruction in &instructions {
+            leaders.extend(instruction.branch_targets());
+            if !instruction.branch_targets().is_empty() || ends_flow(instruction.opcode()) {
+                leaders.insert(instruction.next_offset());
+            }
+        }
+        for exception in code.exception_table() {
+            leaders.insert(exception.start_pc() as u32);
+            leaders.insert(exception.handler_pc() as u32);
+        }
+        let code_size = code.code().len();
+        leaders.retain(|&leader| (leader as usize) < code_size);
+        let block_of = |offset: u32| leaders.range(..=offset).next_back().copied();
+
+        const EXIT: u32 = u32::MAX;
+        let mut edges = HashSet::new();
+        for (index, instruction) in instructions.iter().enumerate() {
+            let Some(block) = block_of(instruction.offset()) else {
+                continue;
+            };
+            let next = instructions.get(index + 1).map(Instruction::offset);
+            let ends_block = next.map_or(true, |next| leaders.contains(&next));
+            if !ends_block {
+                continue;
+            }
+            for target in instruction.branch_targets() {
+                edges.insert((block, target));
+            }
+            if is_exit(instruction.opcode()) {
+                edges.insert((block, EXIT));
+            } else if !is_unconditional(instruction.opcode()) {
+                if let Some(next) = next {
+                    edges.insert((block, next));
+                }
+            }
+        }
+        for exception in code.exception_table() {
+            if let Some(block) = block_of(exception.start_pc() as u32) {
+                edges.insert((block, exception.handler_pc() as u32));
+            }
+        }
+        // every block, and the exit node
+        let nodes = leaders.len() + 1;
+
+        Ok(Self {
+            instructions: instructions.len(),
+            code_size,
+            basic_blocks: leaders.len(),
+            complexity: (edges.len() + 2).saturating_sub(nodes).max(1),
+            call_sites: instructions
+                .iter()
+                .filter(|instruction| is_invoke(instruction.opcode()))
+                .count(),
+            max_stack: code.max_stack(),
+            max_locals: code.max_locals(),
+        })
+    }
+
+    /// The number of instructions
+    pub fn instructions(&self) -> usize {
+        self.instructions
+    }
+
+    /// The size of the bytecode in bytes
+    pub fn code_size(&self) -> usize {
+        self.code_size
+    }
+
+    /// The number of basic blocks in the control flow graph
+    pub fn basic_blocks(&self) -> usize {
+        self.basic_blocks
+    }
+
+    /// The cyclomatic complexity, the number of linearly independent paths through the method.
+    /// A method without any branches has a complexity of 1.
+    pub fn complexity(&self) -> usize {
+        self.complexity
+    }
+
+    /// The number of `invoke*` instructions
+    pub fn call_sites(&self) -> usize {
+        self.call_sites
+    }
+
+    /// The maximum depth of the operand stack
+    pub fn max_stack(&self) -> u16 {
+        self.max_stack
+    }
+
+    /// The number of local variable slots, including parameters
+    pub fn max_locals(&self) -> u16 {
+        self.max_locals
+    }
+}
+
+/// Bytecode metrics
+impl Method<'_> {
+    /// Computes the metrics of the bytecode of the method. Returns `None` if the method has no
+    /// code, such as abstract and native methods.
+    ///
+    /// # Error
+    /// Will return an error if the bytecode can't be decoded
+    pub fn metrics(&self) -> Result<Option<MethodMetrics>, DecodeError> {
+        self.get::<Code>()
+            .map(|code| MethodMetrics::of(&code))
+            .transpose()
+    }
+}
+
+/// Instructions after which control never falls through to the next instruction
+fn ends_flow(opcode: Opcode) -> bool {
+    is_exit(opcode) || is_unconditional(opcode)
+}
+
+/// Instructions that leave the method
+fn is_exit(opcode: Opcode) -> bool {
+    use Opcode::*;
+    matches!(
+        opcode,
+        Ireturn | Lreturn | Freturn | Dreturn | Areturn | Return | Athrow | Ret
+    )
+}
+
+/// Instructions that always branch
+fn is_unconditional(opcode: Opcode) -> bool {
+    use Opcode::*;
+    matches!(opcode, Goto | GotoW | Tableswitch | Lookupswitch)
+}
+
+fn is_invoke(opcode: Opcode) -> bool {
+    use Opcode::*;
+    matches!(
+        opcode,
+        Invokevirtual | Invokespecial | Invokestatic | Invokeinterface | Invokedynamic
+    )
+}
@@ -0,0 +1,220 @@
+//! Controls how a [`JavaClassParser`](crate::JavaClassParser) caches the classes it finds.
+//!
+//! By default every class found is cached for the lifetime of the parser. Long running tools
+//! that look up many classes can bound the cache, evicting the least recently used classes, or
+//! disable it entirely.
+//!
+//! Names that couldn't be found are also remembered, so looking up a missing class again, which
+//! happens often while building inheritance graphs, doesn't search the whole classpath. A bounded
+//! cache remembers as many missing names as classes. If the contents of the classpath change,
+//! [`clear_missing`](crate::JavaClassParser::clear_missing) forgets them.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::cache::CachePolicy;
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar").with_cache_policy(CachePolicy::Bounded(256));
+//! let class = parser.find("com/example/Foo").unwrap();
+//! parser.clear_cache();
+//! ```
+
+use crate::{FQName, FQNameBuf, JavaClass};
+use std::collections::{BTreeMap, HashMap};
+
+/// How many classes a parser caches
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum CachePolicy {
+    /// Every class found is cached
+    #[default]
+    Unbounded,
+    /// At most this many classes are cached, evicting the least recently used class. As many
+    /// missing names are remembered.
+    Bounded(usize),
+    /// Classes are parsed again every time they're found
+    Disabled,
+}
+
+/// A cache of classes, following a [`CachePolicy`]
+#[derive(Debug, Default)]
+pub(crate) struct ClassCache {
+    policy: CachePolicy,
+    classes: Lru<JavaClass>,
+    /// Names that couldn't be found. These are bounded by the policy like classes are.
+    missing: Lru<()>,
+}
+
+impl ClassCache {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    /// The most names cached at once
+    fn capacity(&self) -> usize {
+        match self.policy {
+            CachePolicy::Unbounded => usize::MAX,
+            CachePolicy::Bounded(capacity) => capacity,
+            CachePolicy::Disabled => 0,
+        }
+    }
+
+    pub(crate) fn get(&mut self, name: &FQName) -> Option<JavaClass> {
+        self.classes.get(name).cloned()
+    }
+
+    pub(crate) fn insert(&mut self, name: FQNameBuf, class: JavaClass) {
+        let capacity = self.capacity();
+        self.classes.insert(name, class, capacity);
+    }
+
+    /// Checks if a name is known to be missing
+    pub(crate) fn is_missing(&mut self, name: &FQName) -> bool {
+        self.missing.get(name).is_some()
+    }
+
+    /// Remembers that a name couldn't be found, unless caching is disabled
+    pub(crate) fn insert_missing(&mut self, name: FQNameBuf) {
+        let capacity = self.capacity();
+        self.missing.insert(name, (), capacity);
+    }
+
+    pub(crate) fn remove(&mut self, name: &FQName) {
+        self.classes.remove(name);
+        self.missing.remove(name);
+    }
+
+    pub(crate) fn clear_missing(&mut self) {
+        self.missing.clear();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.classes.clear();
+        self.missing.clear();
+    }
+}
+
+/// Values by name, evicting the least recently used value once full
+#[derive(Debug)]
+struct Lru<V> {
+    /// Every value, with the tick it was last used at
+    values: HashMap<FQNameBuf, (V, u64)>,
+    /// The name of every value, by the tick it was last used at
+    recent: BTreeMap<u64, FQNameBuf>,
+    tick: u64,
+}
+
+impl<V> Default for Lru<V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            recent: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+}
+
+impl<V> Lru<V> {
+    fn get(&mut self, name: &FQName) -> Option<&V> {
+        let (value, last_used) = self.values.get_mut(name)?;
+        self.tick += 1;
+        if let Some(name) = self.recent.remove(last_used) {
+            self.recent.insert(self.tick, name);
+        }
+        *last_used = self.tick;
+        Some(value)
+    }
+
+    fn insert(&mut self, name: FQNameBuf, value: V, capacity: usize) {
+        self.remove(&name);
+        if capacity == 0 {
+            return;
+        }
+        while self.values.len() >= capacity {
+            let Some(&least_recent) = self.recent.keys().next() else {
+                break;
+            };
+            if let Some(name) = self.recent.remove(&least_recent) {
+                self.values.remove(&name);
+            }
+        }
+        self.tick += 1;
+        self.recent.insert(self.tick, name.clone());
+        self.values.insert(name, (value, self.tick));
+    }
+
+    fn remove(&mut self, name: &FQName) {
+        if let Some((_, last_used)) = self.values.remove(name) {
+            self.recent.remove(&last_used);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.recent.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JavaClassParser;
+
+    fn class() -> JavaClass {
+        JavaClassParser::from(itest_common::jar_file())
+            .find_class(FQName::new("com/example/Square"))
+            .unwrap()
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = ClassCache::new(CachePolicy::Bounded(2));
+        cache.insert(FQName::new("A").to_fqname_buf(), class());
+        cache.insert(FQName::new("B").to_fqname_buf(), class());
+        assert!(cache.get(FQName::new("A")).is_some());
+        cache.insert(FQName::new("C").to_fqname_buf(), class());
+        assert_eq!(cache.classes.values.len(), 2);
+        assert_eq!(cache.classes.recent.len(), 2);
+        assert!(cache.get(FQName::new("B")).is_none());
+        assert!(cache.get(FQName::new("A")).is_some());
+        assert!(cache.get(FQName::new("C")).is_some());
+    }
+
+    #[test]
+    fn disabled_cache() {
+        let mut cache = ClassCache::new(CachePolicy::Disabled);
+        cache.insert(FQName::new("A").to_fqname_buf(), class());
+        assert_eq!(cache.classes.values.len(), 0);
+        cache.insert_missing(FQName::new("B").to_fqname_buf());
+        assert!(!cache.is_missing(FQName::new("B")));
+    }
+
+    #[test]
+    fn bounded_missing_names() {
+        let mut cache = ClassCache::new(CachePolicy::Bounded(2));
+        for name in ["A", "B", "C"] {
+            cache.insert_missing(FQName::new(name).to_fqname_buf());
+        }
+        assert_eq!(cache.missing.values.len(), 2);
+        assert!(!cache.is_missing(FQName::new("A")));
+        assert!(cache.is_missing(FQName::new("B")));
+        assert!(cache.is_missing(FQName::new("C")));
+    }
+
+    #[test]
+    fn missing_names() {
+        let mut cache = ClassCache::new(CachePolicy::Unbounded);
+        cache.insert_missing(FQName::new("A").to_fqname_buf());
+        assert!(cache.is_missing(FQName::new("A")));
+        cache.remove(FQName::new("A"));
+        assert!(!cache.is_missing(FQName::new("A")));
+        cache.insert_missing(FQName::new("A").to_fqname_buf());
+        cache.clear_missing();
+        assert!(!cache.is_missing(FQName::new("A")));
+    }
+}
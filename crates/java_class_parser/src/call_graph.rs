@@ -0,0 +1,359 @@
+//! Extracts the methods called by the methods of a class, and renders them as a graphviz graph.
+//!
+//! Every `invoke*` instruction in the bytecode of a class becomes an edge from the method
+//! containing it to the method it invokes. Calls to the same method with the same kind of
+//! invocation are only recorded once.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::call_graph::CallGraph;
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Suspicious").unwrap();
+//! std::fs::write("calls.dot", CallGraph::of(&class).unwrap().to_dot()).unwrap();
+//! ```
+//!
+//! The methods invoked by a class can also be listed without building a graph, using
+//! [`JavaClass::invoked_methods`] and [`Method::invocations`]. These link `invokedynamic` call
+//! sites to the method they're bootstrapped with, such as the body of a lambda.
+
+use crate::attributes::{AttributeKind, Code};
+use crate::bytecode::Opcode;
+use crate::dynamic::InvokeDynamic;
+use crate::error::Error;
+use crate::utility::dot_quoted;
+use crate::{FQName, FQNameBuf, HasAttributes, JavaClass, Method, ResolvedConstant};
+use petgraph::prelude::*;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write as _};
+
+/// A method, identified by its class, name and descriptor
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MethodId {
+    class: Option<FQNameBuf>,
+    name: String,
+    descriptor: String,
+}
+
+impl MethodId {
+    /// Creates a new method id
+    pub fn new<C: AsRef<FQName>>(class: C, name: &str, descriptor: &str) -> Self {
+        Self {
+            class: Some(class.as_ref().to_fqname_buf()),
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+        }
+    }
+
+    /// The class declaring the method. This is `None` for call sites linked by `invokedynamic`,
+    /// which aren't bound to a class until runtime.
+    pub fn class(&self) -> Option<&FQName> {
+        self.class.as_deref()
+    }
+
+    /// The name of the method
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The descriptor of the method
+    pub fn descriptor(&self) -> &str {
+        &self.descriptor
+    }
+}
+
+impl Display for MethodId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(class) = &self.class {
+            write!(f, "{}.", class)?;
+        }
+        write!(f, "{}{}", self.name, self.descriptor)
+    }
+}
+
+/// How a method is invoked
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum InvokeKind {
+    /// `invokevirtual`
+    Virtual,
+    /// `invokespecial`, used for constructors, private and super methods
+    Special,
+    /// `invokestatic`
+    Static,
+    /// `invokeinterface`
+    Interface,
+    /// `invokedynamic`
+    Dynamic,
+}
+
+impl InvokeKind {
+    fn from_opcode(opcode: Opcode) -> Option<Self> {
+        match opcode {
+            Opcode::Invokevirtual => Some(Self::Virtual),
+            Opcode::Invokespecial => Some(Self::Special),
+            Opcode::Invokestatic => Some(Self::Static),
+            Opcode::Invokeinterface => Some(Self::Interface),
+            Opcode::Invokedynamic => Some(Self::Dynamic),
+            _ => None,
+        }
+    }
+
+    /// The mnemonic of the instruction used for this kind of invocation
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            InvokeKind::Virtual => "invokevirtual",
+            InvokeKind::Special => "invokespecial",
+            InvokeKind::Static => "invokestatic",
+            InvokeKind::Interface => "invokeinterface",
+            InvokeKind::Dynamic => "invokedynamic",
+        }
+    }
+}
+
+/// The calls made by the methods of a single class
+#[derive(Debug, Clone)]
+pub struct CallGraph {
+    class: FQNameBuf,
+    graph: DiGraph<MethodId, InvokeKind>,
+    nodes: HashMap<MethodId, NodeIndex>,
+}
+
+impl CallGraph {
+    /// Extracts the calls made by every method of a class
+    ///
+    /// # Error
+    /// Will return an error if the bytecode of any method can't be decoded
+    pub fn of(class: &JavaClass) -> Result<Self, Error> {
+        let mut graph = Self {
+            class: class.this().to_fqname_buf(),
+            graph: DiGraph::new(),
+            nodes: HashMap::new(),
+        };
+        for method in class.methods() {
+            let caller = graph.add_method(MethodId::new(
+                class.this(),
+                method.name(),
+                &method.signature().jni(),
+            ));
+            for attribute in method.attributes() {
+                let AttributeKind::Code(code) = attribute.kind() else {
+                    continue;
+                };
+                for instruction in code.instructions() {
+                    let instruction = instruction?;
+                    let Some(kind) = InvokeKind::from_opcode(instruction.opcode()) else {
+                        continue;
+                    };
+                    let Some(callee) = instruction
+                        .constant_index()
+                        .and_then(|index| invoked_method(class, index))
+                    else {
+                        continue;
+                    };
+                    let callee = graph.add_method(callee);
+                    let exists = graph
+                        .graph
+                        .edges_connecting(caller, callee)
+                        .any(|edge| *edge.weight() == kind);
+                    if !exists {
+                        graph.graph.add_edge(caller, callee, kind);
+                    }
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn add_method(&mut self, method: MethodId) -> NodeIndex {
+        match self.nodes.get(&method) {
+            Some(&index) => index,
+            None => {
+                let index = self.graph.add_node(method.clone());
+                self.nodes.insert(method, index);
+                index
+            }
+        }
+    }
+
+    /// The name of the class the graph was extracted from
+    pub fn class(&self) -> &FQName {
+        &self.class
+    }
+
+    /// Every method in the graph, both the methods of the class and the methods they call
+    pub fn methods(&self) -> impl Iterator<Item = &MethodId> {
+        self.graph.node_weights()
+    }
+
+    /// Every call in the graph, as the caller, the callee and how it was invoked
+    pub fn calls(&self) -> impl Iterator<Item = (&MethodId, &MethodId, InvokeKind)> {
+        self.graph.edge_references().map(|edge| {
+            (
+                &self.graph[edge.source()],
+                &self.graph[edge.target()],
+                *edge.weight(),
+            )
+        })
+    }
+
+    /// The methods called by a method
+    pub fn callees(&self, method: &MethodId) -> Vec<(&MethodId, InvokeKind)> {
+        let Some(&index) = self.nodes.get(method) else {
+            return vec![];
+        };
+        self.graph
+            .edges(index)
+            .map(|edge| (&self.graph[edge.target()], *edge.weight()))
+            .collect()
+    }
+
+    /// Checks if a method is declared by the class the graph was extracted from
+    pub fn is_internal(&self, method: &MethodId) -> bool {
+        method.class() == Some(&*self.class)
+    }
+
+    /// Renders the graph in the graphviz dot language. Methods of the class are grouped into a
+    /// cluster, and every edge is labelled with the instruction used to make the call.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph {\n    node [shape=box];\n");
+        let _ = writeln!(
+            dot,
+            "    subgraph cluster_class {{\n        label={};",
+            dot_quoted(self.class.as_str())
+        );
+        for index in self.graph.node_indices() {
+            let method = &self.graph[index];
+            if self.is_internal(method) {
+                let label = format!("{}{}", method.name, method.descriptor);
+                let _ = writeln!(
+                    dot,
+                    "        n{} [label={}];",
+                    index.index(),
+                    dot_quoted(&label)
+                );
+            }
+        }
+        dot.push_str("    }\n");
+        for index in self.graph.node_indices() {
+            let method = &self.graph[index];
+            if !self.is_internal(method) {
+                let _ = writeln!(
+                    dot,
+                    "    n{} [label={}, style=dashed];",
+                    index.index(),
+                    dot_quoted(&method.to_string())
+                );
+            }
+        }
+        for edge in self.graph.edge_references() {
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [label={}];",
+                edge.source().index(),
+                edge.target().index(),
+                dot_quoted(edge.weight().mnemonic())
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Invoked methods
+impl JavaClass {
+    /// Every method invoked by the methods of the class, in the order they're first invoked.
+    /// See [`Method::invocations`].
+    ///
+    /// # Error
+    /// Will return an error if the bytecode of a method can't be decoded
+    pub fn invoked_methods(&self) -> Result<Vec<MethodId>, Error> {
+        let mut invoked = vec![];
+        for method in self.methods() {
+            for target in method.invocations()? {
+                if !invoked.contains(&target) {
+                    invoked.push(target);
+                }
+            }
+        }
+        Ok(invoked)
+    }
+}
+
+/// Invoked methods
+impl Method<'_> {
+    /// Every method invoked by the bytecode of the method, in the order they're first invoked.
+    ///
+    /// Call sites of `invokedynamic` are linked to the first method handle passed to their
+    /// bootstrap method, which is the implementation of a lambda or method reference. Call sites
+    /// without one, such as string concatenation, are returned without a class.
+    ///
+    /// # Error
+    /// Will return an error if the bytecode can't be decoded
+    pub fn invocations(&self) -> Result<Vec<MethodId>, Error> {
+        let Some(code) = self.get::<Code>() else {
+            return Ok(vec![]);
+        };
+        let mut invocations = vec![];
+        for instruction in code.resolved_instructions() {
+            let instruction = instruction?;
+            if InvokeKind::from_opcode(instruction.opcode()).is_none() {
+                continue;
+            }
+            let target = match instruction.constant() {
+                Some(
+                    ResolvedConstant::MethodRef(method)
+                    | ResolvedConstant::InterfaceMethodRef(method),
+                ) => MethodId::new(method.class(), method.name(), method.descriptor()),
+                Some(ResolvedConstant::InvokeDynamic(call_site)) => {
+                    linked_method(code.class(), call_site)
+                }
+                _ => continue,
+            };
+            if !invocations.contains(&target) {
+                invocations.push(target);
+            }
+        }
+        Ok(invocations)
+    }
+}
+
+/// Gets the method a call site is linked to by its bootstrap method, or the unbound call site
+/// if there isn't one
+fn linked_method(class: &JavaClass, call_site: &InvokeDynamic) -> MethodId {
+    class
+        .bootstrap_method(call_site)
+        .and_then(|bootstrap| {
+            bootstrap
+                .arguments()
+                .iter()
+                .find_map(|argument| match argument {
+                    ResolvedConstant::MethodHandle(handle) if !handle.kind().is_field() => Some(
+                        MethodId::new(handle.class(), handle.name(), &handle.descriptor().jni()),
+                    ),
+                    _ => None,
+                })
+        })
+        .unwrap_or_else(|| MethodId {
+            class: None,
+            name: call_site.name().to_string(),
+            descriptor: call_site.descriptor().jni(),
+        })
+}
+
+/// Gets the method referred to by the constant pool entry of an invoke instruction
+fn invoked_method(class: &JavaClass, index: u16) -> Option<MethodId> {
+    if let Some(call_site) = class.invoke_dynamic(index) {
+        return Some(MethodId {
+            class: None,
+            name: call_site.name().to_string(),
+            descriptor: call_site.descriptor().jni(),
+        });
+    }
+    let method = class.constant_pool().get_method_ref(index)?;
+    Some(MethodId::new(
+        method.class(),
+        method.name(),
+        method.descriptor(),
+    ))
+}
@@ -0,0 +1,214 @@
+//! Enumerates the classes available on the classpath of a [`JavaClassParser`].
+//!
+//! Directories are walked recursively in sorted order, and the entries of jar files are listed in
+//! the order they're stored in. Jar files nested in other jars, such as
+//! `app.jar!/BOOT-INF/lib/guava.jar`, are read from the jar containing them. Entries are visited lazily in classpath order, so a class that's
+//! shadowed by an earlier entry is only listed once.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! for class in parser.parse_all() {
+//!     let class = class.unwrap();
+//!     println!("{} has {} methods", class.this(), class.methods().len());
+//! }
+//! ```
+//!
+//! Classes can also be searched for by a glob [`ClassPattern`], such as `com/example/**/*Service`.
+//!
+//! Classes defined by more than one entry, where every entry but the first is shadowed, can be
+//! found with [`JavaClassParser::duplicates`], and each definition of one class with
+//! [`JavaClassParser::find_all`].
+
+use crate::error::Error;
+use crate::{entry_class_name, FQName, FQNameBuf, JavaClass, JavaClassParser};
+use java_classpaths::{Classpath, ClasspathError, Resources};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// An iterator over the names of every class on a classpath, created by
+/// [`JavaClassParser::classes`]
+#[derive(Debug)]
+pub struct ClassNames {
+    resources: Resources,
+    seen: HashSet<FQNameBuf>,
+}
+
+impl ClassNames {
+    pub(crate) fn new(parser: &JavaClassParser) -> Self {
+        Self {
+            resources: parser.class_path.resources(),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl Iterator for ClassNames {
+    type Item = Result<FQNameBuf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match class_name(self.resources.next()?) {
+                Some(Ok(name)) if !self.seen.insert(name.clone()) => continue,
+                Some(result) => return Some(result),
+                None => continue,
+            }
+        }
+    }
+}
+
+/// Lists the names of the classes in a single classpath entry
+pub(crate) fn entry_class_names(entry: &Path) -> impl Iterator<Item = Result<FQNameBuf, Error>> {
+    Classpath::from(entry).resources().filter_map(class_name)
+}
+
+/// Gets the class name of a resource, if it's a class
+fn class_name(resource: Result<String, ClasspathError>) -> Option<Result<FQNameBuf, Error>> {
+    match resource {
+        Ok(resource) => entry_class_name(&resource).map(Ok),
+        Err(e) => Some(Err(e.into())),
+    }
+}
+
+/// A class defined by a classpath entry, found by [`JavaClassParser::find_all`]
+#[derive(Debug, Clone)]
+pub struct ClassDefinition {
+    entry: PathBuf,
+    class: JavaClass,
+}
+
+impl ClassDefinition {
+    pub(crate) fn new(entry: PathBuf, class: JavaClass) -> Self {
+        Self { entry, class }
+    }
+
+    /// The classpath entry defining the class
+    pub fn entry(&self) -> &Path {
+        &self.entry
+    }
+
+    /// The class
+    pub fn class(&self) -> &JavaClass {
+        &self.class
+    }
+}
+
+/// A class defined by more than one classpath entry, found by [`JavaClassParser::duplicates`].
+/// Only the definition in the first entry is ever found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateClass {
+    name: FQNameBuf,
+    entries: Vec<PathBuf>,
+}
+
+impl DuplicateClass {
+    pub(crate) fn new(name: FQNameBuf, entries: Vec<PathBuf>) -> Self {
+        Self { name, entries }
+    }
+
+    /// The name of the class
+    pub fn name(&self) -> &FQName {
+        &self.name
+    }
+
+    /// Every entry defining the class, in classpath order
+    pub fn entries(&self) -> &[PathBuf] {
+        &self.entries
+    }
+}
+
+/// A glob pattern matching fully qualified class names, used by
+/// [`JavaClassParser::find_matching`].
+///
+/// Patterns may be written in either binary or internal form. Within a component of the name,
+/// `*` matches any number of characters and `?` matches a single character. A `**` component
+/// matches any number of whole components, including none, so `com/example/**/*Service` matches
+/// both `com/example/UserService` and `com/example/web/api/UserService`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassPattern {
+    components: Vec<String>,
+}
+
+impl ClassPattern {
+    /// Creates a new pattern
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            components: pattern.split(['/', '.']).map(str::to_string).collect(),
+        }
+    }
+
+    /// Checks if a name matches this pattern
+    pub fn matches<N: AsRef<FQName> + ?Sized>(&self, name: &N) -> bool {
+        let name = name.as_ref().components().collect::<Vec<_>>();
+        let pattern = self
+            .components
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        matches_components(&pattern, &name)
+    }
+}
+
+fn matches_components(pattern: &[&str], name: &[&str]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            matches_components(rest, name)
+                || (!name.is_empty() && matches_components(pattern, &name[1..]))
+        }
+        (Some((component_pattern, pattern_rest)), Some((component, name_rest))) => {
+            matches_component(component_pattern.as_bytes(), component.as_bytes())
+                && matches_components(pattern_rest, name_rest)
+        }
+        _ => false,
+    }
+}
+
+fn matches_component(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches_component(rest, name)
+                || (!name.is_empty() && matches_component(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name_rest))) => matches_component(rest, name_rest),
+        (Some((c, rest)), Some((n, name_rest))) => c == n && matches_component(rest, name_rest),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_patterns() {
+        let pattern = ClassPattern::new("com/example/**/*Service");
+        assert!(pattern.matches(FQName::new("com/example/UserService")));
+        assert!(pattern.matches(FQName::new("com/example/web/api/UserService")));
+        assert!(!pattern.matches(FQName::new("com/example/UserServiceImpl")));
+        assert!(!pattern.matches(FQName::new("org/example/UserService")));
+
+        let pattern = ClassPattern::new("com.example.?hape*");
+        assert!(pattern.matches(FQName::new("com/example/Shape")));
+        assert!(!pattern.matches(FQName::new("com/example/nested/Shape")));
+        assert!(ClassPattern::new("**").matches(FQName::new("Foo")));
+    }
+
+    #[test]
+    fn entry_names() {
+        assert_eq!(
+            entry_class_name("my-lib/com/example/Square.class").unwrap(),
+            "my-lib/com/example/Square"
+        );
+        assert_eq!(
+            entry_class_name("com/example/Square$1.class").unwrap(),
+            "com/example/Square$1"
+        );
+        assert!(entry_class_name("module-info.class").is_none());
+        assert!(entry_class_name("com/example/package-info.class").is_none());
+        assert!(entry_class_name("META-INF/versions/11/com/example/Square.class").is_none());
+        assert!(entry_class_name("com/example/Square.java").is_none());
+    }
+}
@@ -0,0 +1,652 @@
+//! End to end comparison of two jars, for release notes and upgrade audits.
+//!
+//! Classes are compared semantically rather than byte for byte, so recompiling a class or only
+//! changing its debug information doesn't mark it as changed. Method bodies are compared by their
+//! instructions, with constant pool references compared by what they refer to instead of by index.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::compare::compare_jars;
+//! let comparison = compare_jars("app-1.0.jar", "app-1.1.jar").expect("couldn't compare");
+//! println!("{comparison}");
+//! ```
+
+use crate::bytecode::decode;
+use crate::constant_pool::values::{
+    Class, Dynamic, FieldRef, InterfaceMethodRef, InvokeDynamic, MethodRef, NameAndType,
+    StringValue,
+};
+use crate::error::Error;
+use crate::raw_java_class::RawMethodInfo;
+use crate::utility::ByteReader;
+use crate::{entry_class_name, parse_bytes, ConstantPoolInfo, FQName, FQNameBuf, JavaClass};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+const MANIFEST: &str = "META-INF/MANIFEST.MF";
+
+/// A semantic change to a class
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ClassChange {
+    /// The class file version changed
+    VersionChanged {
+        /// The old `(major, minor)` version
+        from: (u16, u16),
+        /// The new `(major, minor)` version
+        to: (u16, u16),
+    },
+    /// The access flags of the class changed
+    AccessFlagsChanged {
+        /// The old flags
+        from: u16,
+        /// The new flags
+        to: u16,
+    },
+    /// The super class changed
+    SuperClassChanged {
+        /// The old super class
+        from: Option<FQNameBuf>,
+        /// The new super class
+        to: Option<FQNameBuf>,
+    },
+    /// The class started implementing an interface
+    InterfaceAdded(FQNameBuf),
+    /// The class stopped implementing an interface
+    InterfaceRemoved(FQNameBuf),
+    /// A field was added
+    FieldAdded(Member),
+    /// A field was removed
+    FieldRemoved(Member),
+    /// The access flags of a field changed
+    FieldAccessFlagsChanged {
+        /// The field
+        field: Member,
+        /// The old flags
+        from: u16,
+        /// The new flags
+        to: u16,
+    },
+    /// A method was added
+    MethodAdded(Member),
+    /// A method was removed
+    MethodRemoved(Member),
+    /// The access flags of a method changed
+    MethodAccessFlagsChanged {
+        /// The method
+        method: Member,
+        /// The old flags
+        from: u16,
+        /// The new flags
+        to: u16,
+    },
+    /// The instructions of a method changed
+    MethodBodyChanged(Member),
+}
+
+/// Identifies a field or method by its name and descriptor
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+pub struct Member {
+    /// The name of the member
+    pub name: String,
+    /// The descriptor of the member
+    pub descriptor: String,
+}
+
+impl Member {
+    /// Creates a member from its name and descriptor
+    pub fn new(name: &str, descriptor: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+        }
+    }
+}
+
+impl Display for Member {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name, self.descriptor)
+    }
+}
+
+impl Display for ClassChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = |name: &Option<FQNameBuf>| {
+            name.as_ref()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| "<none>".to_string())
+        };
+        match self {
+            ClassChange::VersionChanged { from, to } => write!(
+                f,
+                "class file version changed from {}.{} to {}.{}",
+                from.0, from.1, to.0, to.1
+            ),
+            ClassChange::AccessFlagsChanged { from, to } => {
+                write!(f, "access flags changed from {from:#06x} to {to:#06x}")
+            }
+            ClassChange::SuperClassChanged { from, to } => {
+                write!(f, "super class changed from {} to {}", name(from), name(to))
+            }
+            ClassChange::InterfaceAdded(interface) => write!(f, "implements {interface}"),
+            ClassChange::InterfaceRemoved(interface) => {
+                write!(f, "no longer implements {interface}")
+            }
+            ClassChange::FieldAdded(field) => write!(f, "added field {field}"),
+            ClassChange::FieldRemoved(field) => write!(f, "removed field {field}"),
+            ClassChange::FieldAccessFlagsChanged { field, from, to } => write!(
+                f,
+                "access flags of field {field} changed from {from:#06x} to {to:#06x}"
+            ),
+            ClassChange::MethodAdded(method) => write!(f, "added method {method}"),
+            ClassChange::MethodRemoved(method) => write!(f, "removed method {method}"),
+            ClassChange::MethodAccessFlagsChanged { method, from, to } => write!(
+                f,
+                "access flags of method {method} changed from {from:#06x} to {to:#06x}"
+            ),
+            ClassChange::MethodBodyChanged(method) => write!(f, "body of {method} changed"),
+        }
+    }
+}
+
+/// The semantic changes made to a single class
+#[derive(Debug, Clone)]
+pub struct ClassDiff {
+    name: FQNameBuf,
+    changes: Vec<ClassChange>,
+}
+
+impl ClassDiff {
+    /// Compares two versions of a class. The classes don't need to have the same name.
+    pub fn between(old: &JavaClass, new: &JavaClass) -> Self {
+        let mut changes = vec![];
+        let (old_raw, new_raw) = (old.raw(), new.raw());
+
+        if old.class_file_version() != new.class_file_version() {
+            changes.push(ClassChange::VersionChanged {
+                from: old.class_file_version(),
+                to: new.class_file_version(),
+            });
+        }
+        if old_raw.access_flags != new_raw.access_flags {
+            changes.push(ClassChange::AccessFlagsChanged {
+                from: old_raw.access_flags,
+                to: new_raw.access_flags,
+            });
+        }
+        let (old_super, new_super) = (super_name(old), super_name(new));
+        if old_super != new_super {
+            changes.push(ClassChange::SuperClassChanged {
+                from: old_super,
+                to: new_super,
+            });
+        }
+
+        let interfaces = |class: &JavaClass| {
+            class
+                .interfaces()
+                .into_iter()
+                .map(|interface| interface.to_fqname_buf())
+                .collect::<Vec<_>>()
+        };
+        let (old_interfaces, new_interfaces) = (interfaces(old), interfaces(new));
+        for interface in &new_interfaces {
+            if !old_interfaces.contains(interface) {
+                changes.push(ClassChange::InterfaceAdded(interface.clone()));
+            }
+        }
+        for interface in &old_interfaces {
+            if !new_interfaces.contains(interface) {
+                changes.push(ClassChange::InterfaceRemoved(interface.clone()));
+            }
+        }
+
+        let fields = |class: &JavaClass| {
+            class
+                .fields()
+                .iter()
+                .map(|field| {
+                    (
+                        Member::new(field.name(), &field.signature().jni()),
+                        field.access_flags().bits(),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let (old_fields, new_fields) = (fields(old), fields(new));
+        for (field, &flags) in &new_fields {
+            match old_fields.get(field) {
+                None => changes.push(ClassChange::FieldAdded(field.clone())),
+                Some(&old_flags) if old_flags != flags => {
+                    changes.push(ClassChange::FieldAccessFlagsChanged {
+                        field: field.clone(),
+                        from: old_flags,
+                        to: flags,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for field in old_fields.keys() {
+            if !new_fields.contains_key(field) {
+                changes.push(ClassChange::FieldRemoved(field.clone()));
+            }
+        }
+
+        let (old_methods, new_methods) = (methods(old), methods(new));
+        for (method, new_method) in &new_methods {
+            let Some(old_method) = old_methods.get(method) else {
+                changes.push(ClassChange::MethodAdded(method.clone()));
+                continue;
+            };
+            if old_method.access_flags != new_method.access_flags {
+                changes.push(ClassChange::MethodAccessFlagsChanged {
+                    method: method.clone(),
+                    from: old_method.access_flags,
+                    to: new_method.access_flags,
+                });
+            }
+            if normalized_code(old, old_method) != normalized_code(new, new_method) {
+                changes.push(ClassChange::MethodBodyChanged(method.clone()));
+            }
+        }
+        for method in old_methods.keys() {
+            if !new_methods.contains_key(method) {
+                changes.push(ClassChange::MethodRemoved(method.clone()));
+            }
+        }
+
+        Self {
+            name: new.this().to_fqname_buf(),
+            changes,
+        }
+    }
+
+    /// The name of the class
+    pub fn name(&self) -> &FQNameBuf {
+        &self.name
+    }
+
+    /// The changes made to the class
+    pub fn changes(&self) -> &[ClassChange] {
+        &self.changes[..]
+    }
+
+    /// Checks if the class is semantically unchanged
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A change to a main attribute of the jar manifest
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ManifestChange {
+    /// An attribute was added
+    Added {
+        /// The attribute name
+        key: String,
+        /// The attribute value
+        value: String,
+    },
+    /// An attribute was removed
+    Removed {
+        /// The attribute name
+        key: String,
+        /// The attribute value
+        value: String,
+    },
+    /// The value of an attribute changed
+    Changed {
+        /// The attribute name
+        key: String,
+        /// The old value
+        from: String,
+        /// The new value
+        to: String,
+    },
+}
+
+/// The result of comparing two jars
+#[derive(Debug, Clone, Default)]
+pub struct JarComparison {
+    added_classes: Vec<FQNameBuf>,
+    removed_classes: Vec<FQNameBuf>,
+    changed_classes: Vec<ClassDiff>,
+    added_resources: Vec<String>,
+    removed_resources: Vec<String>,
+    changed_resources: Vec<String>,
+    manifest_changes: Vec<ManifestChange>,
+}
+
+impl JarComparison {
+    /// Classes only present in the new jar
+    pub fn added_classes(&self) -> &[FQNameBuf] {
+        &self.added_classes[..]
+    }
+
+    /// Classes only present in the old jar
+    pub fn removed_classes(&self) -> &[FQNameBuf] {
+        &self.removed_classes[..]
+    }
+
+    /// Classes present in both jars that changed semantically
+    pub fn changed_classes(&self) -> &[ClassDiff] {
+        &self.changed_classes[..]
+    }
+
+    /// Resources, entries that aren't classes, only present in the new jar
+    pub fn added_resources(&self) -> &[String] {
+        &self.added_resources[..]
+    }
+
+    /// Resources only present in the old jar
+    pub fn removed_resources(&self) -> &[String] {
+        &self.removed_resources[..]
+    }
+
+    /// Resources present in both jars with different contents
+    pub fn changed_resources(&self) -> &[String] {
+        &self.changed_resources[..]
+    }
+
+    /// Changes to the main attributes of the manifest
+    pub fn manifest_changes(&self) -> &[ManifestChange] {
+        &self.manifest_changes[..]
+    }
+
+    /// Checks if the jars are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.added_classes.is_empty()
+            && self.removed_classes.is_empty()
+            && self.changed_classes.is_empty()
+            && self.added_resources.is_empty()
+            && self.removed_resources.is_empty()
+            && self.changed_resources.is_empty()
+            && self.manifest_changes.is_empty()
+    }
+}
+
+impl Display for JarComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for class in &self.added_classes {
+            writeln!(f, "+ class {class}")?;
+        }
+        for class in &self.removed_classes {
+            writeln!(f, "- class {class}")?;
+        }
+        for diff in &self.changed_classes {
+            writeln!(f, "~ class {}", diff.name)?;
+            for change in &diff.changes {
+                writeln!(f, "    {change}")?;
+            }
+        }
+        for resource in &self.added_resources {
+            writeln!(f, "+ resource {resource}")?;
+        }
+        for resource in &self.removed_resources {
+            writeln!(f, "- resource {resource}")?;
+        }
+        for resource in &self.changed_resources {
+            writeln!(f, "~ resource {resource}")?;
+        }
+        for change in &self.manifest_changes {
+            match change {
+                ManifestChange::Added { key, value } => writeln!(f, "+ manifest {key}: {value}")?,
+                ManifestChange::Removed { key, value } => writeln!(f, "- manifest {key}: {value}")?,
+                ManifestChange::Changed { key, from, to } => {
+                    writeln!(f, "~ manifest {key}: {from} -> {to}")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares two jars, where `old` is the baseline that `new` is compared against
+///
+/// # Error
+/// Will return an error if either jar can't be read, or a class that differs between the jars
+/// can't be parsed.
+pub fn compare_jars<P1: AsRef<Path>, P2: AsRef<Path>>(
+    old: P1,
+    new: P2,
+) -> Result<JarComparison, Error> {
+    let mut old = ZipArchive::new(File::open(old)?)?;
+    let mut new = ZipArchive::new(File::open(new)?)?;
+    let old_entries = entry_checksums(&mut old)?;
+    let new_entries = entry_checksums(&mut new)?;
+
+    let mut comparison = JarComparison::default();
+    let mut old_classes = BTreeMap::new();
+    let mut new_classes = BTreeMap::new();
+    for (entry, &crc) in &new_entries {
+        let old_crc = old_entries.get(entry);
+        if entry == MANIFEST {
+            continue;
+        }
+        if entry_class_name(entry).is_some() {
+            // the name of a class is taken from the class itself, in case it doesn't match the entry
+            if old_crc != Some(&crc) {
+                let class = parse_bytes(new.by_name(entry)?)?;
+                new_classes.insert(class.this().to_string(), class);
+            }
+            if matches!(old_crc, Some(&old_crc) if old_crc != crc) {
+                let class = parse_bytes(old.by_name(entry)?)?;
+                old_classes.insert(class.this().to_string(), class);
+            }
+        } else {
+            match old_crc {
+                None => comparison.added_resources.push(entry.clone()),
+                Some(&old_crc) if old_crc != crc => {
+                    comparison.changed_resources.push(entry.clone())
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    for entry in old_entries.keys() {
+        if new_entries.contains_key(entry) || entry == MANIFEST {
+            continue;
+        }
+        if entry_class_name(entry).is_some() {
+            let class = parse_bytes(old.by_name(entry)?)?;
+            old_classes.insert(class.this().to_string(), class);
+        } else {
+            comparison.removed_resources.push(entry.clone());
+        }
+    }
+
+    for (name, new_class) in &new_classes {
+        match old_classes.get(name) {
+            None => comparison
+                .added_classes
+                .push(FQName::new(name).to_fqname_buf()),
+            Some(old_class) => {
+                let diff = ClassDiff::between(old_class, new_class);
+                if !diff.is_empty() {
+                    comparison.changed_classes.push(diff);
+                }
+            }
+        }
+    }
+    for name in old_classes.keys() {
+        if !new_classes.contains_key(name) {
+            comparison
+                .removed_classes
+                .push(FQName::new(name).to_fqname_buf());
+        }
+    }
+
+    let old_manifest = read_manifest(&mut old)?;
+    let new_manifest = read_manifest(&mut new)?;
+    for (key, value) in &new_manifest {
+        match old_manifest.get(key) {
+            None => comparison.manifest_changes.push(ManifestChange::Added {
+                key: key.clone(),
+                value: value.clone(),
+            }),
+            Some(old_value) if old_value != value => {
+                comparison.manifest_changes.push(ManifestChange::Changed {
+                    key: key.clone(),
+                    from: old_value.clone(),
+                    to: value.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in &old_manifest {
+        if !new_manifest.contains_key(key) {
+            comparison.manifest_changes.push(ManifestChange::Removed {
+                key: key.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+
+    Ok(comparison)
+}
+
+/// The crc32 of every file entry, by entry name
+fn entry_checksums(archive: &mut ZipArchive<File>) -> Result<BTreeMap<String, u32>, Error> {
+    let mut checksums = BTreeMap::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if !entry.is_dir() {
+            checksums.insert(entry.name().to_string(), entry.crc32());
+        }
+    }
+    Ok(checksums)
+}
+
+/// Reads the main attributes of the manifest, if the jar has one
+fn read_manifest(archive: &mut ZipArchive<File>) -> Result<BTreeMap<String, String>, Error> {
+    let mut manifest = String::new();
+    match archive.by_name(MANIFEST) {
+        Ok(mut entry) => {
+            entry.read_to_string(&mut manifest)?;
+        }
+        Err(zip::result::ZipError::FileNotFound) => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e.into()),
+    }
+
+    let mut attributes = BTreeMap::new();
+    let mut last_key: Option<String> = None;
+    for line in manifest.lines() {
+        if line.is_empty() {
+            // the main section ends at the first blank line
+            break;
+        }
+        if let Some(continuation) = line.strip_prefix(' ') {
+            if let Some(value) = last_key.as_ref().and_then(|key| attributes.get_mut(key)) {
+                let value: &mut String = value;
+                value.push_str(continuation);
+            }
+        } else if let Some((key, value)) = line.split_once(':') {
+            attributes.insert(key.to_string(), value.trim_start().to_string());
+            last_key = Some(key.to_string());
+        }
+    }
+    Ok(attributes)
+}
+
+fn methods(class: &JavaClass) -> BTreeMap<Member, &RawMethodInfo> {
+    class
+        .raw()
+        .methods
+        .iter()
+        .filter_map(|method| {
+            let name = class.get_string(method.name_index)?;
+            let descriptor = class.get_string(method.descriptor_index)?;
+            Some((Member::new(name, descriptor), method))
+        })
+        .collect()
+}
+
+pub(crate) fn super_name(class: &JavaClass) -> Option<FQNameBuf> {
+    class.super_class().map(FQName::to_fqname_buf)
+}
+
+/// The instructions of a method, with constant pool indices replaced by descriptions of the
+/// constants they refer to. Returns `None` for methods without code.
+fn normalized_code(class: &JavaClass, method: &RawMethodInfo) -> Option<Vec<String>> {
+    let code = method.attributes.iter().find_map(|attribute| {
+        (class.get_string(attribute.attribute_name_index) == Some("Code"))
+            .then_some(&attribute.info[..])
+    })?;
+    let mut reader = ByteReader::new(code);
+    reader.take(4)?;
+    let code_length = reader.u32()?;
+    let code = reader.take(code_length as usize)?;
+
+    let mut instructions = vec![];
+    for instruction in decode(code) {
+        let Ok(instruction) = instruction else {
+            // undecodable code is compared as is
+            instructions.push(format!("{code:?}"));
+            break;
+        };
+        let operands = match instruction.constant_index() {
+            Some(index) => describe_constant(class, index),
+            None => format!("{:?}", instruction.operands()),
+        };
+        instructions.push(format!("{} {}", instruction.opcode(), operands));
+    }
+    Some(instructions)
+}
+
+fn describe_constant(class: &JavaClass, index: u16) -> String {
+    let string = |index: u16| class.get_string(index).unwrap_or("?");
+    let class_name = |index: u16| match class.get_class_info(index) {
+        Some(Class { name_index }) => string(*name_index),
+        None => "?",
+    };
+    let name_and_type = |index: u16| match class.get_at_index(index) {
+        Some(ConstantPoolInfo::NameAndType(NameAndType {
+            name_index,
+            descriptor_index,
+        })) => format!("{}:{}", string(*name_index), string(*descriptor_index)),
+        _ => "?".to_string(),
+    };
+    match class.get_at_index(index) {
+        Some(ConstantPoolInfo::Class(Class { name_index })) => string(*name_index).to_string(),
+        Some(ConstantPoolInfo::String(StringValue { string_index })) => {
+            format!("{:?}", string(*string_index))
+        }
+        Some(ConstantPoolInfo::FieldRef(FieldRef {
+            class_index,
+            name_and_type_index,
+        }))
+        | Some(ConstantPoolInfo::MethodRef(MethodRef {
+            class_index,
+            name_and_type_index,
+        }))
+        | Some(ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+            class_index,
+            name_and_type_index,
+        })) => format!(
+            "{}.{}",
+            class_name(*class_index),
+            name_and_type(*name_and_type_index)
+        ),
+        Some(ConstantPoolInfo::Dynamic(Dynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        }))
+        | Some(ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        })) => format!(
+            "#{}:{}",
+            bootstrap_method_attr_index,
+            name_and_type(*name_and_type_index)
+        ),
+        other => format!("{other:?}"),
+    }
+}
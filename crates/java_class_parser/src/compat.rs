@@ -0,0 +1,865 @@
+//! Checks whether a new version of a library can replace an old version, by comparing their
+//! public APIs, like [japicmp](https://siom79.github.io/japicmp/).
+//!
+//! Only the API of a library is compared: public classes, and their public and protected members.
+//! Each change is classified by whether it's binary compatible, meaning classes compiled against
+//! the old version still link against the new version, and whether it's source compatible,
+//! meaning code written against the old version still compiles against the new version.
+//!
+//! Classes are compared without resolving their hierarchies, so changes are classified
+//! conservatively. For example, changing the super class of a class is always breaking, even if
+//! the old super class is still an ancestor.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::compat::diff_jars;
+//! let report = diff_jars("lib-1.0.jar", "lib-1.1.jar").expect("couldn't compare");
+//! for change in report.binary_breaking() {
+//!     println!("{change}");
+//! }
+//! ```
+
+use crate::compare::{super_name, Member};
+use crate::error::Error;
+use crate::{ClassAccessFlags, FQName, FQNameBuf, JavaClass, JavaClassParser, Method};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+const PUBLIC: u16 = 0x0001;
+const PRIVATE: u16 = 0x0002;
+const PROTECTED: u16 = 0x0004;
+const STATIC: u16 = 0x0008;
+const FINAL: u16 = 0x0010;
+const ABSTRACT: u16 = 0x0400;
+
+/// The kind of type a class declares
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum TypeKind {
+    /// A class
+    Class,
+    /// An interface
+    Interface,
+    /// An enum
+    Enum,
+    /// An annotation interface
+    Annotation,
+}
+
+impl TypeKind {
+    fn of(flags: ClassAccessFlags) -> Self {
+        if flags.is_annotation() {
+            TypeKind::Annotation
+        } else if flags.is_interface() {
+            TypeKind::Interface
+        } else if flags.is_enum() {
+            TypeKind::Enum
+        } else {
+            TypeKind::Class
+        }
+    }
+}
+
+impl Display for TypeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeKind::Class => write!(f, "class"),
+            TypeKind::Interface => write!(f, "interface"),
+            TypeKind::Enum => write!(f, "enum"),
+            TypeKind::Annotation => write!(f, "annotation"),
+        }
+    }
+}
+
+/// A change to the API of a class
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ApiChangeKind {
+    /// A public class was added
+    ClassAdded,
+    /// A public class was removed, or is no longer public
+    ClassRemoved,
+    /// The modifiers of the class changed
+    ClassAccessChanged {
+        /// The old access flags
+        from: u16,
+        /// The new access flags
+        to: u16,
+    },
+    /// The class changed between being a class, interface, enum or annotation
+    TypeKindChanged {
+        /// The old kind of type
+        from: TypeKind,
+        /// The new kind of type
+        to: TypeKind,
+    },
+    /// The super class changed
+    SuperClassChanged {
+        /// The old super class
+        from: Option<FQNameBuf>,
+        /// The new super class
+        to: Option<FQNameBuf>,
+    },
+    /// The class started implementing an interface
+    InterfaceAdded(FQNameBuf),
+    /// The class stopped implementing an interface
+    InterfaceRemoved(FQNameBuf),
+    /// The generic signature of the class, a field or a method changed, without changing its
+    /// descriptor
+    GenericSignatureChanged {
+        /// The field or method, or `None` for the class itself
+        member: Option<Member>,
+        /// The old signature
+        from: Option<String>,
+        /// The new signature
+        to: Option<String>,
+    },
+    /// A field was added to the API
+    FieldAdded(Member),
+    /// A field was removed from the API
+    FieldRemoved(Member),
+    /// The type of a field changed
+    FieldTypeChanged {
+        /// The field, with its old descriptor
+        field: Member,
+        /// The new descriptor
+        to: String,
+    },
+    /// The modifiers of a field changed
+    FieldAccessChanged {
+        /// The field
+        field: Member,
+        /// The old access flags
+        from: u16,
+        /// The new access flags
+        to: u16,
+    },
+    /// A method was added to the API
+    MethodAdded {
+        /// The method
+        method: Member,
+        /// If the method is abstract
+        is_abstract: bool,
+    },
+    /// A method was removed from the API
+    MethodRemoved(Member),
+    /// The return type of a method changed
+    ReturnTypeChanged {
+        /// The method, with its old descriptor
+        method: Member,
+        /// The new descriptor
+        to: String,
+    },
+    /// The modifiers of a method changed
+    MethodAccessChanged {
+        /// The method
+        method: Member,
+        /// The old access flags
+        from: u16,
+        /// The new access flags
+        to: u16,
+    },
+    /// A method started declaring that it throws an exception
+    ExceptionAdded {
+        /// The method
+        method: Member,
+        /// The exception
+        exception: FQNameBuf,
+    },
+    /// A method stopped declaring that it throws an exception
+    ExceptionRemoved {
+        /// The method
+        method: Member,
+        /// The exception
+        exception: FQNameBuf,
+    },
+}
+
+impl ApiChangeKind {
+    /// The name of the kind of change, such as `MethodRemoved`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApiChangeKind::ClassAdded => "ClassAdded",
+            ApiChangeKind::ClassRemoved => "ClassRemoved",
+            ApiChangeKind::ClassAccessChanged { .. } => "ClassAccessChanged",
+            ApiChangeKind::TypeKindChanged { .. } => "TypeKindChanged",
+            ApiChangeKind::SuperClassChanged { .. } => "SuperClassChanged",
+            ApiChangeKind::InterfaceAdded(_) => "InterfaceAdded",
+            ApiChangeKind::InterfaceRemoved(_) => "InterfaceRemoved",
+            ApiChangeKind::GenericSignatureChanged { .. } => "GenericSignatureChanged",
+            ApiChangeKind::FieldAdded(_) => "FieldAdded",
+            ApiChangeKind::FieldRemoved(_) => "FieldRemoved",
+            ApiChangeKind::FieldTypeChanged { .. } => "FieldTypeChanged",
+            ApiChangeKind::FieldAccessChanged { .. } => "FieldAccessChanged",
+            ApiChangeKind::MethodAdded { .. } => "MethodAdded",
+            ApiChangeKind::MethodRemoved(_) => "MethodRemoved",
+            ApiChangeKind::ReturnTypeChanged { .. } => "ReturnTypeChanged",
+            ApiChangeKind::MethodAccessChanged { .. } => "MethodAccessChanged",
+            ApiChangeKind::ExceptionAdded { .. } => "ExceptionAdded",
+            ApiChangeKind::ExceptionRemoved { .. } => "ExceptionRemoved",
+        }
+    }
+}
+
+/// A change to the API of a class, and whether it breaks code using the old API
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ApiChange {
+    class: FQNameBuf,
+    kind: ApiChangeKind,
+    /// Whether the class could be extended or implemented outside its library before the change
+    extendable: bool,
+}
+
+impl ApiChange {
+    /// The class that changed
+    pub fn class(&self) -> &FQName {
+        &self.class
+    }
+
+    /// The change
+    pub fn kind(&self) -> &ApiChangeKind {
+        &self.kind
+    }
+
+    /// Checks if classes compiled against the old API still link against the new API
+    pub fn is_binary_compatible(&self) -> bool {
+        match &self.kind {
+            ApiChangeKind::ClassAdded
+            | ApiChangeKind::InterfaceAdded(_)
+            | ApiChangeKind::GenericSignatureChanged { .. }
+            | ApiChangeKind::FieldAdded(_)
+            | ApiChangeKind::MethodAdded { .. }
+            | ApiChangeKind::ExceptionAdded { .. }
+            | ApiChangeKind::ExceptionRemoved { .. } => true,
+            ApiChangeKind::ClassRemoved
+            | ApiChangeKind::TypeKindChanged { .. }
+            | ApiChangeKind::SuperClassChanged { .. }
+            | ApiChangeKind::InterfaceRemoved(_)
+            | ApiChangeKind::FieldRemoved(_)
+            | ApiChangeKind::FieldTypeChanged { .. }
+            | ApiChangeKind::MethodRemoved(_)
+            | ApiChangeKind::ReturnTypeChanged { .. } => false,
+            &ApiChangeKind::ClassAccessChanged { from, to } => !self.class_access_breaks(from, to),
+            &ApiChangeKind::FieldAccessChanged { from, to, .. } => {
+                !reduces_visibility(from, to)
+                    && !added(from, to, FINAL | STATIC)
+                    && !removed(from, to, STATIC)
+            }
+            &ApiChangeKind::MethodAccessChanged { from, to, .. } => {
+                !self.method_access_breaks(from, to)
+            }
+        }
+    }
+
+    /// Checks if code written against the old API still compiles against the new API
+    pub fn is_source_compatible(&self) -> bool {
+        match &self.kind {
+            ApiChangeKind::ClassAdded
+            | ApiChangeKind::InterfaceAdded(_)
+            | ApiChangeKind::FieldAdded(_) => true,
+            // implementations of the class have to implement new abstract methods
+            &ApiChangeKind::MethodAdded { is_abstract, .. } => !is_abstract || !self.extendable,
+            // new exceptions have to be caught, and catching exceptions that can't be thrown
+            // doesn't compile
+            ApiChangeKind::ExceptionAdded { .. }
+            | ApiChangeKind::ExceptionRemoved { .. }
+            | ApiChangeKind::GenericSignatureChanged { .. } => false,
+            _ => self.is_binary_compatible(),
+        }
+    }
+
+    /// Checks if the change is both binary and source compatible
+    pub fn is_compatible(&self) -> bool {
+        self.is_binary_compatible() && self.is_source_compatible()
+    }
+
+    fn class_access_breaks(&self, from: u16, to: u16) -> bool {
+        reduces_visibility(from, to)
+            || (self.extendable && added(from, to, FINAL))
+            || added(from, to, ABSTRACT)
+    }
+
+    fn method_access_breaks(&self, from: u16, to: u16) -> bool {
+        reduces_visibility(from, to)
+            || added(from, to, STATIC)
+            || removed(from, to, STATIC)
+            || (self.extendable && added(from, to, FINAL | ABSTRACT))
+    }
+}
+
+impl Display for ApiChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let compatibility = match (self.is_binary_compatible(), self.is_source_compatible()) {
+            (true, true) => "compatible",
+            (true, false) => "source breaking",
+            (false, true) => "binary breaking",
+            (false, false) => "breaking",
+        };
+        write!(f, "[{compatibility}] {}: {}", self.class, self.kind)
+    }
+}
+
+/// Serialized as its `class`, the name of its `kind`, a `description` of the change, and whether
+/// it's `binary_compatible` and `source_compatible`
+#[cfg(feature = "serde")]
+impl serde::Serialize for ApiChange {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut change = serializer.serialize_struct("ApiChange", 5)?;
+        change.serialize_field("class", &self.class)?;
+        change.serialize_field("kind", self.kind.name())?;
+        change.serialize_field("description", &self.kind.to_string())?;
+        change.serialize_field("binary_compatible", &self.is_binary_compatible())?;
+        change.serialize_field("source_compatible", &self.is_source_compatible())?;
+        change.end()
+    }
+}
+
+/// Describes the change without the class it was made to, such as `removed method foo()V`
+impl Display for ApiChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = |name: &Option<FQNameBuf>| {
+            name.as_ref()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| "<none>".to_string())
+        };
+        match self {
+            ApiChangeKind::ClassAdded => write!(f, "added"),
+            ApiChangeKind::ClassRemoved => write!(f, "removed"),
+            ApiChangeKind::ClassAccessChanged { from, to } => write!(
+                f,
+                "modifiers changed from `{}` to `{}`",
+                modifiers(*from),
+                modifiers(*to)
+            ),
+            ApiChangeKind::TypeKindChanged { from, to } => {
+                write!(f, "changed from {from} to {to}")
+            }
+            ApiChangeKind::SuperClassChanged { from, to } => {
+                write!(f, "super class changed from {} to {}", name(from), name(to))
+            }
+            ApiChangeKind::InterfaceAdded(interface) => write!(f, "implements {interface}"),
+            ApiChangeKind::InterfaceRemoved(interface) => {
+                write!(f, "no longer implements {interface}")
+            }
+            ApiChangeKind::GenericSignatureChanged { member, from, to } => {
+                let signature = |signature: &Option<String>| {
+                    signature.clone().unwrap_or_else(|| "<none>".to_string())
+                };
+                match member {
+                    Some(member) => write!(f, "signature of {member}")?,
+                    None => write!(f, "signature")?,
+                }
+                write!(f, " changed from {} to {}", signature(from), signature(to))
+            }
+            ApiChangeKind::FieldAdded(field) => write!(f, "added field {field}"),
+            ApiChangeKind::FieldRemoved(field) => write!(f, "removed field {field}"),
+            ApiChangeKind::FieldTypeChanged { field, to } => {
+                write!(f, "type of field {field} changed to {to}")
+            }
+            ApiChangeKind::FieldAccessChanged { field, from, to } => write!(
+                f,
+                "modifiers of field {field} changed from `{}` to `{}`",
+                modifiers(*from),
+                modifiers(*to)
+            ),
+            ApiChangeKind::MethodAdded {
+                method,
+                is_abstract: true,
+            } => write!(f, "added abstract method {method}"),
+            ApiChangeKind::MethodAdded { method, .. } => write!(f, "added method {method}"),
+            ApiChangeKind::MethodRemoved(method) => write!(f, "removed method {method}"),
+            ApiChangeKind::ReturnTypeChanged { method, to } => {
+                write!(f, "return type of method {method} changed to {to}")
+            }
+            ApiChangeKind::MethodAccessChanged { method, from, to } => write!(
+                f,
+                "modifiers of method {method} changed from `{}` to `{}`",
+                modifiers(*from),
+                modifiers(*to)
+            ),
+            ApiChangeKind::ExceptionAdded { method, exception } => {
+                write!(f, "method {method} throws {exception}")
+            }
+            ApiChangeKind::ExceptionRemoved { method, exception } => {
+                write!(f, "method {method} no longer throws {exception}")
+            }
+        }
+    }
+}
+
+/// The changes between two versions of an API
+#[derive(Debug, Default, Clone)]
+pub struct CompatReport {
+    changes: Vec<ApiChange>,
+}
+
+impl CompatReport {
+    /// Every change, ordered by class
+    pub fn changes(&self) -> &[ApiChange] {
+        &self.changes[..]
+    }
+
+    /// The changes that aren't binary compatible
+    pub fn binary_breaking(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes
+            .iter()
+            .filter(|change| !change.is_binary_compatible())
+    }
+
+    /// The changes that aren't source compatible
+    pub fn source_breaking(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes
+            .iter()
+            .filter(|change| !change.is_source_compatible())
+    }
+
+    /// Checks if every change is binary compatible
+    pub fn is_binary_compatible(&self) -> bool {
+        self.binary_breaking().next().is_none()
+    }
+
+    /// Checks if every change is source compatible
+    pub fn is_source_compatible(&self) -> bool {
+        self.source_breaking().next().is_none()
+    }
+
+    /// Checks if the APIs are the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Serialized as a sequence of its changes
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompatReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.changes.serialize(serializer)
+    }
+}
+
+impl Display for CompatReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the APIs of two versions of a class, where `old` is the baseline that `new` is
+/// compared against. Both classes are expected to have the same name, and changes are reported
+/// for the name of the new class.
+pub fn diff_classes(old: &JavaClass, new: &JavaClass) -> CompatReport {
+    let mut differ = Differ {
+        class: new.this().to_fqname_buf(),
+        extendable: is_extendable(old),
+        changes: vec![],
+    };
+    differ.diff(old, new);
+    CompatReport {
+        changes: differ.changes,
+    }
+}
+
+/// Compares the APIs of every class on two classpaths, such as two versions of a jar, where `old`
+/// is the baseline that `new` is compared against. Classpath entries are separated by the
+/// platform's [classpath separator](java_classpaths::CLASSPATH_SEPARATOR).
+///
+/// # Error
+/// Will return an error if either classpath can't be read, or a class on either can't be parsed
+pub fn diff_jars<S1: AsRef<str>, S2: AsRef<str>>(
+    old_classpath: S1,
+    new_classpath: S2,
+) -> Result<CompatReport, Error> {
+    let old = api_classes(&JavaClassParser::new(old_classpath))?;
+    let new = api_classes(&JavaClassParser::new(new_classpath))?;
+
+    let mut report = CompatReport::default();
+    for (name, old_class) in &old {
+        match new.get(name) {
+            Some(new_class) => report
+                .changes
+                .extend(diff_classes(old_class, new_class).changes),
+            None if old_class.access_flags().is_public() => report.changes.push(ApiChange {
+                class: old_class.this().to_fqname_buf(),
+                kind: ApiChangeKind::ClassRemoved,
+                extendable: is_extendable(old_class),
+            }),
+            None => {}
+        }
+    }
+    for (name, new_class) in &new {
+        if !old.contains_key(name) && new_class.access_flags().is_public() {
+            report.changes.push(ApiChange {
+                class: new_class.this().to_fqname_buf(),
+                kind: ApiChangeKind::ClassAdded,
+                extendable: false,
+            });
+        }
+    }
+    report
+        .changes
+        .sort_by(|left, right| left.class.cmp(&right.class));
+    Ok(report)
+}
+
+/// Parses the classes of a classpath that could be part of its API, by name
+fn api_classes(parser: &JavaClassParser) -> Result<BTreeMap<FQNameBuf, JavaClass>, Error> {
+    let mut classes = BTreeMap::new();
+    for class in parser.parse_all() {
+        let class = class?;
+        let flags = class.access_flags();
+        if flags.is_module() || flags.is_synthetic() {
+            continue;
+        }
+        classes.entry(class.this().to_fqname_buf()).or_insert(class);
+    }
+    Ok(classes)
+}
+
+struct Differ {
+    class: FQNameBuf,
+    extendable: bool,
+    changes: Vec<ApiChange>,
+}
+
+impl Differ {
+    fn push(&mut self, kind: ApiChangeKind) {
+        self.changes.push(ApiChange {
+            class: self.class.clone(),
+            kind,
+            extendable: self.extendable,
+        });
+    }
+
+    fn diff(&mut self, old: &JavaClass, new: &JavaClass) {
+        let old_flags = old.access_flags();
+        let new_flags = new.access_flags();
+        match (old_flags.is_public(), new_flags.is_public()) {
+            (false, false) => return,
+            (false, true) => return self.push(ApiChangeKind::ClassAdded),
+            (true, false) => return self.push(ApiChangeKind::ClassRemoved),
+            (true, true) => {}
+        }
+
+        let (old_kind, new_kind) = (TypeKind::of(old_flags), TypeKind::of(new_flags));
+        if old_kind != new_kind {
+            return self.push(ApiChangeKind::TypeKindChanged {
+                from: old_kind,
+                to: new_kind,
+            });
+        }
+        let modifiers = PUBLIC | FINAL | ABSTRACT;
+        let (from, to) = (old_flags.bits() & modifiers, new_flags.bits() & modifiers);
+        // interfaces are always abstract
+        if from != to && old_kind != TypeKind::Interface && old_kind != TypeKind::Annotation {
+            self.push(ApiChangeKind::ClassAccessChanged { from, to });
+        }
+
+        let (old_super, new_super) = (super_name(old), super_name(new));
+        if old_super != new_super {
+            self.push(ApiChangeKind::SuperClassChanged {
+                from: old_super,
+                to: new_super,
+            });
+        }
+        let old_interfaces = old.interfaces();
+        let new_interfaces = new.interfaces();
+        for &interface in &new_interfaces {
+            if !old_interfaces.contains(&interface) {
+                self.push(ApiChangeKind::InterfaceAdded(interface.to_fqname_buf()));
+            }
+        }
+        for &interface in &old_interfaces {
+            if !new_interfaces.contains(&interface) {
+                self.push(ApiChangeKind::InterfaceRemoved(interface.to_fqname_buf()));
+            }
+        }
+        let (from, to) = (
+            old.generic_signature()
+                .map(|signature| signature.to_string()),
+            new.generic_signature()
+                .map(|signature| signature.to_string()),
+        );
+        if from != to {
+            self.push(ApiChangeKind::GenericSignatureChanged {
+                member: None,
+                from,
+                to,
+            });
+        }
+
+        self.diff_fields(old, new);
+        self.diff_methods(old, new);
+    }
+
+    fn diff_fields(&mut self, old: &JavaClass, new: &JavaClass) {
+        let old_fields = old
+            .fields()
+            .into_iter()
+            .filter(|field| is_api_member(field.access_flags().bits()))
+            .map(|field| (field.name().to_string(), field))
+            .collect::<BTreeMap<_, _>>();
+        let new_fields = new
+            .fields()
+            .into_iter()
+            .filter(|field| is_api_member(field.access_flags().bits()))
+            .map(|field| (field.name().to_string(), field))
+            .collect::<BTreeMap<_, _>>();
+
+        for (name, old_field) in &old_fields {
+            let field = Member::new(name, &old_field.signature().jni());
+            let Some(new_field) = new_fields.get(name) else {
+                self.push(ApiChangeKind::FieldRemoved(field));
+                continue;
+            };
+            let descriptor = new_field.signature().jni();
+            if descriptor != field.descriptor {
+                self.push(ApiChangeKind::FieldTypeChanged {
+                    field,
+                    to: descriptor,
+                });
+                continue;
+            }
+            let (from, to) = (
+                member_modifiers(old_field.access_flags().bits()),
+                member_modifiers(new_field.access_flags().bits()),
+            );
+            if from != to {
+                self.push(ApiChangeKind::FieldAccessChanged {
+                    field: field.clone(),
+                    from,
+                    to,
+                });
+            }
+            let (from, to) = (
+                old_field
+                    .generic_signature()
+                    .map(|signature| signature.to_string()),
+                new_field
+                    .generic_signature()
+                    .map(|signature| signature.to_string()),
+            );
+            if from != to {
+                self.push(ApiChangeKind::GenericSignatureChanged {
+                    member: Some(field),
+                    from,
+                    to,
+                });
+            }
+        }
+        for (name, new_field) in &new_fields {
+            if !old_fields.contains_key(name) {
+                self.push(ApiChangeKind::FieldAdded(Member::new(
+                    name,
+                    &new_field.signature().jni(),
+                )));
+            }
+        }
+    }
+
+    fn diff_methods(&mut self, old: &JavaClass, new: &JavaClass) {
+        let old_methods = api_methods(old);
+        let new_methods = api_methods(new);
+
+        for (method, old_method) in &old_methods {
+            let Some(new_method) = new_methods.get(method) else {
+                // a method with the same parameters and a different return type replaced it
+                let signature = parameters(&method.descriptor);
+                let replaced = new_methods.keys().find(|new_method| {
+                    new_method.name == method.name
+                        && !old_methods.contains_key(new_method)
+                        && parameters(&new_method.descriptor) == signature
+                });
+                match replaced {
+                    Some(replaced) => self.push(ApiChangeKind::ReturnTypeChanged {
+                        method: method.clone(),
+                        to: replaced.descriptor.clone(),
+                    }),
+                    None => self.push(ApiChangeKind::MethodRemoved(method.clone())),
+                }
+                continue;
+            };
+            let (from, to) = (
+                member_modifiers(old_method.access_flags().bits()),
+                member_modifiers(new_method.access_flags().bits()),
+            );
+            if from != to {
+                self.push(ApiChangeKind::MethodAccessChanged {
+                    method: method.clone(),
+                    from,
+                    to,
+                });
+            }
+            let old_exceptions = old_method.thrown_exceptions();
+            let new_exceptions = new_method.thrown_exceptions();
+            for &exception in &new_exceptions {
+                if !old_exceptions.contains(&exception) {
+                    self.push(ApiChangeKind::ExceptionAdded {
+                        method: method.clone(),
+                        exception: exception.to_fqname_buf(),
+                    });
+                }
+            }
+            for &exception in &old_exceptions {
+                if !new_exceptions.contains(&exception) {
+                    self.push(ApiChangeKind::ExceptionRemoved {
+                        method: method.clone(),
+                        exception: exception.to_fqname_buf(),
+                    });
+                }
+            }
+            let (from, to) = (
+                old_method
+                    .generic_signature()
+                    .map(|signature| signature.to_string()),
+                new_method
+                    .generic_signature()
+                    .map(|signature| signature.to_string()),
+            );
+            if from != to {
+                self.push(ApiChangeKind::GenericSignatureChanged {
+                    member: Some(method.clone()),
+                    from,
+                    to,
+                });
+            }
+        }
+        for (method, new_method) in &new_methods {
+            if old_methods.contains_key(method) {
+                continue;
+            }
+            let signature = parameters(&method.descriptor);
+            let replaces = old_methods.keys().any(|old_method| {
+                old_method.name == method.name
+                    && !new_methods.contains_key(old_method)
+                    && parameters(&old_method.descriptor) == signature
+            });
+            if replaces {
+                continue;
+            }
+            self.push(ApiChangeKind::MethodAdded {
+                method: method.clone(),
+                is_abstract: new_method.access_flags().is_abstract(),
+            });
+        }
+    }
+}
+
+/// The methods of a class that are part of its API
+fn api_methods(class: &JavaClass) -> BTreeMap<Member, Method<'_>> {
+    class
+        .methods()
+        .into_iter()
+        .filter(|method| {
+            let flags = method.access_flags();
+            is_api_member(flags.bits())
+                && !flags.is_synthetic()
+                && !flags.is_bridge()
+                && method.name() != "<clinit>"
+        })
+        .map(|method| {
+            (
+                Member::new(method.name(), &method.signature().jni()),
+                method,
+            )
+        })
+        .collect()
+}
+
+/// Checks if a class can be extended or implemented outside of its library
+fn is_extendable(class: &JavaClass) -> bool {
+    let flags = class.access_flags();
+    flags.is_public() && !flags.is_final() && !flags.is_enum() && !class.is_sealed()
+}
+
+/// The parameters of a method descriptor, without its return type
+fn parameters(descriptor: &str) -> &str {
+    descriptor
+        .split_once(')')
+        .map_or(descriptor, |(parameters, _)| parameters)
+}
+
+/// Public and protected members are part of the API
+fn is_api_member(flags: u16) -> bool {
+    flags & (PUBLIC | PROTECTED) != 0
+}
+
+/// The flags of a member that matter to its callers and overriders
+fn member_modifiers(flags: u16) -> u16 {
+    flags & (PUBLIC | PRIVATE | PROTECTED | STATIC | FINAL | ABSTRACT)
+}
+
+fn visibility(flags: u16) -> u8 {
+    if flags & PUBLIC != 0 {
+        3
+    } else if flags & PROTECTED != 0 {
+        2
+    } else if flags & PRIVATE != 0 {
+        0
+    } else {
+        1
+    }
+}
+
+fn reduces_visibility(from: u16, to: u16) -> bool {
+    visibility(to) < visibility(from)
+}
+
+fn added(from: u16, to: u16, flags: u16) -> bool {
+    to & !from & flags != 0
+}
+
+fn removed(from: u16, to: u16, flags: u16) -> bool {
+    from & !to & flags != 0
+}
+
+/// Describes flags as the modifiers they're declared with
+fn modifiers(flags: u16) -> String {
+    let modifiers = [
+        (PUBLIC, "public"),
+        (PROTECTED, "protected"),
+        (PRIVATE, "private"),
+        (ABSTRACT, "abstract"),
+        (STATIC, "static"),
+        (FINAL, "final"),
+    ]
+    .into_iter()
+    .filter(|&(flag, _)| flags & flag != 0)
+    .map(|(_, modifier)| modifier)
+    .collect::<Vec<_>>();
+    if modifiers.is_empty() {
+        "package-private".to_string()
+    } else {
+        modifiers.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method_access_change(extendable: bool, from: u16, to: u16) -> ApiChange {
+        ApiChange {
+            class: FQName::new("com/example/Foo").to_fqname_buf(),
+            kind: ApiChangeKind::MethodAccessChanged {
+                method: Member::new("run", "()V"),
+                from,
+                to,
+            },
+            extendable,
+        }
+    }
+
+    #[test]
+    fn classify_access_changes() {
+        assert!(!method_access_change(false, PUBLIC, PROTECTED).is_binary_compatible());
+        assert!(method_access_change(false, PROTECTED, PUBLIC).is_compatible());
+        assert!(!method_access_change(false, PUBLIC, PUBLIC | STATIC).is_compatible());
+        assert!(!method_access_change(true, PUBLIC, PUBLIC | FINAL).is_compatible());
+        assert!(method_access_change(false, PUBLIC, PUBLIC | FINAL).is_compatible());
+        assert!(method_access_change(true, PUBLIC | FINAL, PUBLIC).is_compatible());
+        assert_eq!(modifiers(PUBLIC | STATIC | FINAL), "public static final");
+        assert_eq!(modifiers(0), "package-private");
+    }
+}
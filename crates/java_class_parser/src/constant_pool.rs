@@ -1,14 +1,21 @@
 //! contains the raw definitions for the constant pool
 
-use std::ops::Index;
+use crate::dynamic::{
+    InvokeDynamic as ResolvedInvokeDynamic, MethodHandle as ResolvedMethodHandle,
+};
+use crate::{FQName, Signature};
 use values::{
-    Class, Double, FieldRef, Float, Integer, InterfaceMethodRef, InvokeDynamic, Long, MethodHandle,
-    MethodRef, MethodType, NameAndType, StringValue, Utf8,
+    Class, Double, Dynamic, FieldRef, Float, Integer, InterfaceMethodRef, InvokeDynamic, Long,
+    MethodHandle, MethodRef, MethodType, Module, NameAndType, Package, StringValue, Utf8,
 };
 
+mod builder;
 pub mod parser;
+pub(crate) mod references;
 pub mod values;
 
+pub use builder::ConstantPoolBuilder;
+
 /// Config values
 pub mod cfg {
     pub const CLASS_TAG: u8 = 7;
@@ -24,7 +31,10 @@ pub mod cfg {
     pub const UTF8_TAG: u8 = 1;
     pub const METHOD_HANDLE_TAG: u8 = 15;
     pub const METHOD_TYPE_TAG: u8 = 16;
+    pub const DYNAMIC_TAG: u8 = 17;
     pub const INVOKE_DYNAMIC_TAG: u8 = 18;
+    pub const MODULE_TAG: u8 = 19;
+    pub const PACKAGE_TAG: u8 = 20;
 }
 
 /// The `cp_info` structure, represents in a constant
@@ -44,33 +54,379 @@ pub enum ConstantPoolInfo {
     Utf8(Utf8),
     MethodHandle(MethodHandle),
     MethodType(MethodType),
+    Dynamic(Dynamic),
     InvokeDynamic(InvokeDynamic),
+    Module(Module),
+    Package(Package),
+}
+
+impl ConstantPoolInfo {
+    /// Long and double constants take up two entries in the constant pool, the second of which
+    /// is unusable
+    pub fn is_wide(&self) -> bool {
+        matches!(
+            self,
+            ConstantPoolInfo::Long(_) | ConstantPoolInfo::Double(_)
+        )
+    }
+
+    /// Writes this constant as it's stored in a class file, starting with its tag
+    pub(crate) fn write(&self, out: &mut Vec<u8>) {
+        use cfg::*;
+
+        let (tag, first, second) = match self {
+            ConstantPoolInfo::Class(Class { name_index }) => (CLASS_TAG, *name_index, None),
+            ConstantPoolInfo::FieldRef(FieldRef {
+                class_index,
+                name_and_type_index,
+            }) => (FIELD_REF_TAG, *class_index, Some(*name_and_type_index)),
+            ConstantPoolInfo::MethodRef(MethodRef {
+                class_index,
+                name_and_type_index,
+            }) => (METHOD_REF_TAG, *class_index, Some(*name_and_type_index)),
+            ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            }) => (
+                INTERFACE_METHOD_REF_TAG,
+                *class_index,
+                Some(*name_and_type_index),
+            ),
+            ConstantPoolInfo::String(StringValue { string_index }) => {
+                (STRING_TAG, *string_index, None)
+            }
+            ConstantPoolInfo::NameAndType(NameAndType {
+                name_index,
+                descriptor_index,
+            }) => (NAME_AND_TYPE_TAG, *name_index, Some(*descriptor_index)),
+            ConstantPoolInfo::MethodType(MethodType { descriptor_index }) => {
+                (METHOD_TYPE_TAG, *descriptor_index, None)
+            }
+            ConstantPoolInfo::Dynamic(Dynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            }) => (
+                DYNAMIC_TAG,
+                *bootstrap_method_attr_index,
+                Some(*name_and_type_index),
+            ),
+            ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            }) => (
+                INVOKE_DYNAMIC_TAG,
+                *bootstrap_method_attr_index,
+                Some(*name_and_type_index),
+            ),
+            ConstantPoolInfo::Module(Module { name_index }) => (MODULE_TAG, *name_index, None),
+            ConstantPoolInfo::Package(Package { name_index }) => (PACKAGE_TAG, *name_index, None),
+            ConstantPoolInfo::Integer(Integer { int }) => {
+                out.push(INTEGER_TAG);
+                out.extend(int.to_be_bytes());
+                return;
+            }
+            ConstantPoolInfo::Float(Float { float }) => {
+                out.push(FLOAT_TAG);
+                out.extend(float.to_bits().to_be_bytes());
+                return;
+            }
+            ConstantPoolInfo::Long(Long { long }) => {
+                out.push(LONG_TAG);
+                out.extend(long.to_be_bytes());
+                return;
+            }
+            ConstantPoolInfo::Double(Double { double }) => {
+                out.push(DOUBLE_TAG);
+                out.extend(double.to_bits().to_be_bytes());
+                return;
+            }
+            ConstantPoolInfo::Utf8(utf8) => {
+                out.push(UTF8_TAG);
+                out.extend((utf8.bytes.len() as u16).to_be_bytes());
+                out.extend(&*utf8.bytes);
+                return;
+            }
+            ConstantPoolInfo::MethodHandle(MethodHandle {
+                reference_kind,
+                reference_index,
+            }) => {
+                out.extend([METHOD_HANDLE_TAG, *reference_kind]);
+                out.extend(reference_index.to_be_bytes());
+                return;
+            }
+        };
+        out.push(tag);
+        out.extend(first.to_be_bytes());
+        if let Some(second) = second {
+            out.extend(second.to_be_bytes());
+        }
+    }
+}
+
+/// A reference to a field or method, with its indices resolved
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MemberRef<'a> {
+    class: &'a FQName,
+    name: &'a str,
+    descriptor: &'a str,
+}
+
+impl<'a> MemberRef<'a> {
+    /// The class the member is referenced through
+    pub fn class(&self) -> &'a FQName {
+        self.class
+    }
+
+    /// The name of the member
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The descriptor of the member
+    pub fn descriptor(&self) -> &'a str {
+        self.descriptor
+    }
 }
 
-/// The constant pool contains an array of constants
+/// A constant from the constant pool, with the indices it refers to resolved. Created by
+/// [`JavaClass::constants`](crate::JavaClass::constants).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedConstant<'a> {
+    /// A string used for names and descriptors
+    Utf8(&'a str),
+    /// An `int`, or a `boolean`, `byte`, `char` or `short`
+    Integer(i32),
+    /// A `float`
+    Float(f32),
+    /// A `long`
+    Long(i64),
+    /// A `double`
+    Double(f64),
+    /// A class or interface
+    Class(&'a FQName),
+    /// A `java.lang.String` literal
+    String(&'a str),
+    /// A reference to a field
+    FieldRef(MemberRef<'a>),
+    /// A reference to a method of a class
+    MethodRef(MemberRef<'a>),
+    /// A reference to a method of an interface
+    InterfaceMethodRef(MemberRef<'a>),
+    /// A name and descriptor, without a class
+    NameAndType {
+        /// The name of the field or method
+        name: &'a str,
+        /// The descriptor of the field or method
+        descriptor: &'a str,
+    },
+    /// A method handle
+    MethodHandle(ResolvedMethodHandle<'a>),
+    /// A method type, as a method descriptor
+    MethodType(Signature<'a>),
+    /// A dynamically computed constant
+    Dynamic(ResolvedInvokeDynamic<'a>),
+    /// A dynamically computed call site
+    InvokeDynamic(ResolvedInvokeDynamic<'a>),
+    /// A module, only found in `module-info.class`
+    Module(&'a str),
+    /// A package exported or opened by a module, only found in `module-info.class`
+    Package(&'a str),
+    /// A constant whose indices don't refer to constants of the correct kind
+    Invalid,
+}
+
+/// The constant pool contains an array of constants. Constants are accessed by their index in
+/// the class file, starting at 1.
+///
+/// Accessors return `None` if there is no constant at the index, or the constant is of the wrong
+/// kind.
 #[derive(Debug, Clone)]
 pub struct ConstantPool {
-    pool: Vec<ConstantPoolInfo>,
+    pool: Vec<Option<ConstantPoolInfo>>,
 }
 
 impl ConstantPool {
-    /// Creates a new constant pool from an iterator
-    pub(crate) fn new<I: IntoIterator<Item = ConstantPoolInfo>>(pool: I) -> Self {
+    /// Creates a new constant pool from an iterator. Unusable entries following long and double
+    /// constants are `None`.
+    pub(crate) fn new<I: IntoIterator<Item = Option<ConstantPoolInfo>>>(pool: I) -> Self {
         Self {
             pool: pool.into_iter().collect(),
         }
     }
 
+    /// The number of entries in the pool, including unusable entries
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Checks if the pool has no entries
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    /// Writes every constant as it's stored in a class file, without the constant pool count.
+    /// Unusable entries take up no bytes.
+    pub(crate) fn write(&self, out: &mut Vec<u8>) {
+        for info in self.pool.iter().flatten() {
+            info.write(out);
+        }
+    }
+
+    /// Iterates over the indices and raw values of every usable entry
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (u16, &ConstantPoolInfo)> {
+        self.pool
+            .iter()
+            .enumerate()
+            .filter_map(|(index, info)| Some((index as u16 + 1, info.as_ref()?)))
+    }
+
     /// Constant pools are accessed using u16 values.
-    pub fn get(&self, index: u16) -> Option<&ConstantPoolInfo> {
-        self.pool.get(index as usize - 1)
+    pub(crate) fn get(&self, index: u16) -> Option<&ConstantPoolInfo> {
+        self.pool.get((index as usize).checked_sub(1)?)?.as_ref()
     }
-}
 
-impl Index<u16> for ConstantPool {
-    type Output = ConstantPoolInfo;
+    /// Gets the string of a utf8 constant. Returns `None` if the constant isn't valid modified
+    /// utf8.
+    pub fn get_utf8(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::Utf8(utf8) => utf8.as_str().ok(),
+            _ => None,
+        }
+    }
 
-    fn index(&self, index: u16) -> &Self::Output {
-        self.get(index).expect("index out of bounds")
+    /// Gets the string of a utf8 constant, with anything that isn't valid modified utf8 replaced
+    /// with `U+FFFD`.
+    pub fn get_utf8_lossy(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::Utf8(utf8) => Some(utf8.as_str_lossy()),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of a class constant
+    pub fn get_class_name(&self, index: u16) -> Option<&FQName> {
+        match self.get(index)? {
+            ConstantPoolInfo::Class(Class { name_index }) => {
+                self.get_utf8(*name_index).map(FQName::new)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the name of a module constant, such as `java.base`
+    pub fn get_module_name(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::Module(Module { name_index }) => self.get_utf8(*name_index),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of a package constant, in internal form such as `java/lang`
+    pub fn get_package_name(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::Package(Package { name_index }) => self.get_utf8(*name_index),
+            _ => None,
+        }
+    }
+
+    /// Gets the name and descriptor of a name and type constant
+    pub fn get_name_and_type(&self, index: u16) -> Option<(&str, &str)> {
+        match self.get(index)? {
+            ConstantPoolInfo::NameAndType(NameAndType {
+                name_index,
+                descriptor_index,
+            }) => Some((
+                self.get_utf8(*name_index)?,
+                self.get_utf8(*descriptor_index)?,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Gets a field reference constant
+    pub fn get_field_ref(&self, index: u16) -> Option<MemberRef<'_>> {
+        match self.get(index)? {
+            ConstantPoolInfo::FieldRef(FieldRef {
+                class_index,
+                name_and_type_index,
+            }) => self.member_ref(*class_index, *name_and_type_index),
+            _ => None,
+        }
+    }
+
+    /// Gets a method reference constant. Both class and interface method references are accepted.
+    pub fn get_method_ref(&self, index: u16) -> Option<MemberRef<'_>> {
+        match self.get(index)? {
+            ConstantPoolInfo::MethodRef(MethodRef {
+                class_index,
+                name_and_type_index,
+            })
+            | ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            }) => self.member_ref(*class_index, *name_and_type_index),
+            _ => None,
+        }
+    }
+
+    fn member_ref(&self, class_index: u16, name_and_type_index: u16) -> Option<MemberRef<'_>> {
+        let (name, descriptor) = self.get_name_and_type(name_and_type_index)?;
+        Some(MemberRef {
+            class: self.get_class_name(class_index)?,
+            name,
+            descriptor,
+        })
+    }
+
+    /// Gets the value of an integer constant
+    pub fn get_integer(&self, index: u16) -> Option<i32> {
+        match self.get(index)? {
+            ConstantPoolInfo::Integer(Integer { int }) => Some(*int as i32),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a float constant
+    pub fn get_float(&self, index: u16) -> Option<f32> {
+        match self.get(index)? {
+            ConstantPoolInfo::Float(Float { float }) => Some(*float),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a long constant
+    pub fn get_long(&self, index: u16) -> Option<i64> {
+        match self.get(index)? {
+            ConstantPoolInfo::Long(Long { long }) => Some(*long as i64),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a double constant
+    pub fn get_double(&self, index: u16) -> Option<f64> {
+        match self.get(index)? {
+            ConstantPoolInfo::Double(Double { double }) => Some(*double),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a string constant, following its index into a utf8 constant
+    pub fn get_string(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::String(StringValue { string_index }) => self.get_utf8(*string_index),
+            _ => None,
+        }
+    }
+
+    /// Gets the value of a string constant like [`get_string`](Self::get_string), with anything
+    /// that isn't valid modified utf8 replaced with `U+FFFD`. Strings can hold surrogates that
+    /// aren't part of a pair, which can't be decoded.
+    pub fn get_string_lossy(&self, index: u16) -> Option<&str> {
+        match self.get(index)? {
+            ConstantPoolInfo::String(StringValue { string_index }) => {
+                self.get_utf8_lossy(*string_index)
+            }
+            _ => None,
+        }
     }
 }
@@ -0,0 +1,257 @@
+//! Builds constant pools, for creating and editing class files
+
+use crate::constant_pool::values::{
+    encode_modified_utf8, Class, Double, FieldRef, Float, Integer, InterfaceMethodRef, Long,
+    MethodRef, MethodType, NameAndType, StringValue, Utf8,
+};
+use crate::constant_pool::{ConstantPool, ConstantPoolInfo};
+use crate::error::{Error, ErrorKind};
+use crate::AsFullyQualifiedName;
+use std::collections::HashMap;
+
+/// Builds a constant pool by adding constants to it. Adding a constant that's already in the pool
+/// returns the index of the existing constant instead of adding it again.
+///
+/// A builder created from an existing pool keeps every constant at the same index, so anything
+/// referring to the existing pool can refer to the built pool instead.
+///
+/// # Example
+/// ```
+/// # use java_class_parser::ConstantPoolBuilder;
+/// let mut builder = ConstantPoolBuilder::new();
+/// let object = builder.class("java/lang/Object").unwrap();
+/// assert_eq!(builder.class("java.lang.Object").unwrap(), object);
+/// let pool = builder.build();
+/// assert_eq!(pool.get_class_name(object).unwrap().as_str(), "java/lang/Object");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConstantPoolBuilder {
+    pool: Vec<Option<ConstantPoolInfo>>,
+    /// The index of every constant, by how it's stored in a class file
+    indices: HashMap<Vec<u8>, u16>,
+}
+
+impl ConstantPoolBuilder {
+    /// Creates a builder for an empty constant pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of entries in the pool, including unusable entries
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Checks if the pool has no entries
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    /// Builds the constant pool
+    pub fn build(self) -> ConstantPool {
+        ConstantPool::new(self.pool)
+    }
+
+    /// Adds a utf8 constant, used for names, descriptors and string values
+    ///
+    /// # Error
+    /// Will return an error if the string is longer than 65535 bytes as modified utf8, or the pool
+    /// is full
+    pub fn utf8(&mut self, value: &str) -> Result<u16, Error> {
+        let bytes = encode_modified_utf8(value);
+        if bytes.len() > u16::MAX as usize {
+            return Err(ErrorKind::ConstantTooLong(bytes.len()).into());
+        }
+        self.add(ConstantPoolInfo::Utf8(Utf8::new(&bytes)))
+    }
+
+    /// Adds a class constant, and the utf8 constant naming it. Names are stored in their
+    /// internal form, such as `java/lang/Object`.
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn class<N: AsFullyQualifiedName + ?Sized>(&mut self, name: &N) -> Result<u16, Error> {
+        let name = name.as_fcq().as_str().replace('.', "/");
+        let name_index = self.utf8(&name)?;
+        self.add(ConstantPoolInfo::Class(Class { name_index }))
+    }
+
+    /// Adds a `java.lang.String` constant, and the utf8 constant holding its value
+    ///
+    /// # Error
+    /// Will return an error if the string is too long, or the pool is full
+    pub fn string(&mut self, value: &str) -> Result<u16, Error> {
+        let string_index = self.utf8(value)?;
+        self.add(ConstantPoolInfo::String(StringValue { string_index }))
+    }
+
+    /// Adds an `int` constant
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn integer(&mut self, value: i32) -> Result<u16, Error> {
+        self.add(ConstantPoolInfo::Integer(Integer { int: value as u32 }))
+    }
+
+    /// Adds a `float` constant
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn float(&mut self, value: f32) -> Result<u16, Error> {
+        self.add(ConstantPoolInfo::Float(Float { float: value }))
+    }
+
+    /// Adds a `long` constant, which takes up two entries
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn long(&mut self, value: i64) -> Result<u16, Error> {
+        self.add(ConstantPoolInfo::Long(Long { long: value as u64 }))
+    }
+
+    /// Adds a `double` constant, which takes up two entries
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn double(&mut self, value: f64) -> Result<u16, Error> {
+        self.add(ConstantPoolInfo::Double(Double { double: value }))
+    }
+
+    /// Adds a name and type constant, and the utf8 constants for its name and descriptor
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn name_and_type(&mut self, name: &str, descriptor: &str) -> Result<u16, Error> {
+        let name_index = self.utf8(name)?;
+        let descriptor_index = self.utf8(descriptor)?;
+        self.add(ConstantPoolInfo::NameAndType(NameAndType {
+            name_index,
+            descriptor_index,
+        }))
+    }
+
+    /// Adds a reference to a field, and the constants it refers to
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn field_ref<N: AsFullyQualifiedName + ?Sized>(
+        &mut self,
+        class: &N,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, Error> {
+        let (class_index, name_and_type_index) = self.member(class, name, descriptor)?;
+        self.add(ConstantPoolInfo::FieldRef(FieldRef {
+            class_index,
+            name_and_type_index,
+        }))
+    }
+
+    /// Adds a reference to a method of a class, and the constants it refers to
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn method_ref<N: AsFullyQualifiedName + ?Sized>(
+        &mut self,
+        class: &N,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, Error> {
+        let (class_index, name_and_type_index) = self.member(class, name, descriptor)?;
+        self.add(ConstantPoolInfo::MethodRef(MethodRef {
+            class_index,
+            name_and_type_index,
+        }))
+    }
+
+    /// Adds a reference to a method of an interface, and the constants it refers to
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn interface_method_ref<N: AsFullyQualifiedName + ?Sized>(
+        &mut self,
+        class: &N,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, Error> {
+        let (class_index, name_and_type_index) = self.member(class, name, descriptor)?;
+        self.add(ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+            class_index,
+            name_and_type_index,
+        }))
+    }
+
+    /// Adds a method type constant, and the utf8 constant for its descriptor
+    ///
+    /// # Error
+    /// Will return an error if the pool is full
+    pub fn method_type(&mut self, descriptor: &str) -> Result<u16, Error> {
+        let descriptor_index = self.utf8(descriptor)?;
+        self.add(ConstantPoolInfo::MethodType(MethodType {
+            descriptor_index,
+        }))
+    }
+
+    fn member<N: AsFullyQualifiedName + ?Sized>(
+        &mut self,
+        class: &N,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<(u16, u16), Error> {
+        Ok((self.class(class)?, self.name_and_type(name, descriptor)?))
+    }
+
+    /// Replaces the constant at an index with a constant of the same width. Constants that
+    /// were only found at the index can no longer be found when adding constants.
+    pub(crate) fn replace(&mut self, index: u16, info: ConstantPoolInfo) {
+        let Some(slot) = self.pool.get_mut((index as usize).wrapping_sub(1)) else {
+            return;
+        };
+        let mut key = vec![];
+        info.write(&mut key);
+        if let Some(old) = slot.replace(info) {
+            let mut old_key = vec![];
+            old.write(&mut old_key);
+            if self.indices.get(&old_key) == Some(&index) {
+                self.indices.remove(&old_key);
+            }
+        }
+        self.indices.entry(key).or_insert(index);
+    }
+
+    /// Adds a constant if it isn't in the pool already, returning its index
+    pub(crate) fn add(&mut self, info: ConstantPoolInfo) -> Result<u16, Error> {
+        let mut key = vec![];
+        info.write(&mut key);
+        if let Some(&index) = self.indices.get(&key) {
+            return Ok(index);
+        }
+        let slots = if info.is_wide() { 2 } else { 1 };
+        // the constant pool count is one more than the number of entries
+        if self.pool.len() + slots >= u16::MAX as usize {
+            return Err(ErrorKind::ConstantPoolFull.into());
+        }
+        let index = self.pool.len() as u16 + 1;
+        self.pool.push(Some(info));
+        if slots == 2 {
+            self.pool.push(None);
+        }
+        self.indices.insert(key, index);
+        Ok(index)
+    }
+}
+
+impl From<&ConstantPool> for ConstantPoolBuilder {
+    fn from(pool: &ConstantPool) -> Self {
+        let mut indices = HashMap::new();
+        for (index, info) in pool.iter() {
+            let mut key = vec![];
+            info.write(&mut key);
+            indices.entry(key).or_insert(index);
+        }
+        Self {
+            pool: pool.pool.clone(),
+            indices,
+        }
+    }
+}
@@ -1,24 +1,26 @@
 use crate::constant_pool::cfg::*;
 use crate::constant_pool::values::{
-    Class, FieldRef, InterfaceMethodRef, MethodRef, NameAndType, Utf8,
+    Class, Double, Dynamic, FieldRef, Float, Integer, InterfaceMethodRef, InvokeDynamic, Long,
+    MethodHandle, MethodRef, MethodType, Module, NameAndType, Package, StringValue, Utf8,
 };
 use crate::constant_pool::{ConstantPool, ConstantPoolInfo};
 
-pub use crate::raw_java_class::{RawAttributeInfo, RawFieldInfo, RawJavaClass, RawMethodInfo};
+pub use crate::raw_java_class::{RawAttributeInfo, RawFieldInfo, RawMethodInfo};
 
 use nom::bytes::complete::take;
 use nom::combinator::map;
-use nom::error::ParseError;
+use nom::error::{ErrorKind, ParseError};
 use nom::multi;
 use nom::multi::count;
-use nom::number::complete::{be_u16, be_u32};
+use nom::number::complete::{be_f32, be_f64, be_u16, be_u32, be_u64};
 use nom::number::streaming::be_u8;
 use nom::sequence::tuple;
 use nom::IResult;
 
-fn parse_data_info<'a, E: ParseError<&'a [u8]>>(
-    bytes: &'a [u8],
-) -> IResult<&'a [u8], (u16, u16, u16, u16, Box<[RawAttributeInfo]>), E> {
+/// access flags, name index, descriptor index, attributes count and attributes
+type DataInfo = (u16, u16, u16, u16, Box<[RawAttributeInfo]>);
+
+fn parse_data_info<'a, E: ParseError<&'a [u8]>>(bytes: &'a [u8]) -> IResult<&'a [u8], DataInfo, E> {
     tuple((be_u16, be_u16, be_u16, be_u16))(bytes).and_then(
         |(bytes, (access_flags, name, descriptor, attributes_count))| {
             map(
@@ -88,7 +90,7 @@ pub(crate) fn parse_attribute_info<'a, E: ParseError<&'a [u8]>>(
 fn parse_constant_pool_info<'a, E: ParseError<&'a [u8]>>(
     bytes: &'a [u8],
 ) -> IResult<&'a [u8], ConstantPoolInfo, E> {
-    let (bytes, tag) = if let (bytes, &[tag]) = take(1 as usize)(bytes)? {
+    let (bytes, tag) = if let (bytes, &[tag]) = take(1_usize)(bytes)? {
         (bytes, tag)
     } else {
         unreachable!()
@@ -118,21 +120,13 @@ fn parse_constant_pool_info<'a, E: ParseError<&'a [u8]>>(
                 name_and_type_index,
             })
         })(bytes),
-        STRING_TAG => {
-            todo!()
-        }
-        INTEGER_TAG => {
-            todo!()
-        }
-        FLOAT_TAG => {
-            todo!()
-        }
-        LONG_TAG => {
-            todo!()
-        }
-        DOUBLE_TAG => {
-            todo!()
-        }
+        STRING_TAG => map(be_u16, |string_index| {
+            ConstantPoolInfo::String(StringValue { string_index })
+        })(bytes),
+        INTEGER_TAG => map(be_u32, |int| ConstantPoolInfo::Integer(Integer { int }))(bytes),
+        FLOAT_TAG => map(be_f32, |float| ConstantPoolInfo::Float(Float { float }))(bytes),
+        LONG_TAG => map(be_u64, |long| ConstantPoolInfo::Long(Long { long }))(bytes),
+        DOUBLE_TAG => map(be_f64, |double| ConstantPoolInfo::Double(Double { double }))(bytes),
         NAME_AND_TYPE_TAG => map(parsed_ref_info, |(name_index, descriptor_index)| {
             ConstantPoolInfo::NameAndType(NameAndType {
                 name_index,
@@ -142,35 +136,74 @@ fn parse_constant_pool_info<'a, E: ParseError<&'a [u8]>>(
         UTF8_TAG => {
             let (bytes, length) = be_u16(bytes)?;
             let (bytes, char_bytes) = take(length)(bytes)?;
-            let vector = Vec::from(char_bytes);
-            Ok((
-                bytes,
-                ConstantPoolInfo::Utf8(Utf8 {
-                    bytes: vector.into_boxed_slice(),
-                }),
-            ))
-        }
-        METHOD_HANDLE_TAG => {
-            todo!()
-        }
-        METHOD_TYPE_TAG => {
-            todo!()
-        }
-        INVOKE_DYNAMIC_TAG => {
-            todo!()
+            Ok((bytes, ConstantPoolInfo::Utf8(Utf8::new(char_bytes))))
         }
-        _ => panic!("unknown tag: {:x}", tag),
+        METHOD_HANDLE_TAG => map(
+            tuple((be_u8, be_u16)),
+            |(reference_kind, reference_index)| {
+                ConstantPoolInfo::MethodHandle(MethodHandle {
+                    reference_kind,
+                    reference_index,
+                })
+            },
+        )(bytes),
+        METHOD_TYPE_TAG => map(be_u16, |descriptor_index| {
+            ConstantPoolInfo::MethodType(MethodType { descriptor_index })
+        })(bytes),
+        DYNAMIC_TAG => map(
+            parsed_ref_info,
+            |(bootstrap_method_attr_index, name_and_type_index)| {
+                ConstantPoolInfo::Dynamic(Dynamic {
+                    bootstrap_method_attr_index,
+                    name_and_type_index,
+                })
+            },
+        )(bytes),
+        INVOKE_DYNAMIC_TAG => map(
+            parsed_ref_info,
+            |(bootstrap_method_attr_index, name_and_type_index)| {
+                ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                    bootstrap_method_attr_index,
+                    name_and_type_index,
+                })
+            },
+        )(bytes),
+        MODULE_TAG => map(be_u16, |name_index| {
+            ConstantPoolInfo::Module(Module { name_index })
+        })(bytes),
+        PACKAGE_TAG => map(be_u16, |name_index| {
+            ConstantPoolInfo::Package(Package { name_index })
+        })(bytes),
+        // unknown tags can't be skipped over as their length isn't known
+        _ => Err(nom::Err::Failure(E::from_error_kind(bytes, ErrorKind::Tag))),
     }
 }
 
-/// parses an entire constant pool of a predetermined length
+/// parses an entire constant pool of a predetermined length. Long and double constants take up
+/// two entries of the length.
 pub fn parse_constant_pool<'a, E: ParseError<&'a [u8]>>(
     length: u16,
 ) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], ConstantPool, E> {
-    nom::combinator::map(
-        multi::count(parse_constant_pool_info, length as usize),
-        |vec| ConstantPool::new(vec),
-    )
+    move |mut bytes| {
+        let mut pool = Vec::with_capacity(length as usize);
+        while pool.len() < length as usize {
+            let (rest, info) = parse_constant_pool_info(bytes)?;
+            let is_wide = info.is_wide();
+            pool.push(Some(info));
+            if is_wide {
+                // the unusable second slot must still be counted by the constant pool count
+                if pool.len() == length as usize {
+                    return Err(nom::Err::Failure(E::from_error_kind(
+                        bytes,
+                        ErrorKind::Count,
+                    )));
+                }
+                pool.push(None);
+            }
+            bytes = rest;
+        }
+        Ok((bytes, ConstantPool::new(pool)))
+    }
 }
 
 #[cfg(test)]
@@ -178,11 +211,8 @@ mod tests {
     use super::*;
     use crate::constant_pool::cfg::UTF8_TAG;
     use crate::constant_pool::parser::parse_constant_pool_info;
-    use crate::raw_java_class::parse_class_file_bytes;
     use crate::utility::match_as;
     use nom::Finish;
-    use std::path::PathBuf;
-    use std::{env, fs};
 
     #[test]
     fn parse_utf8_constant_pool_info() {
@@ -194,4 +224,83 @@ mod tests {
             .expect("should be a ut8 expression with no extra bytes");
         assert_eq!(utf8.to_string(), "abc");
     }
+
+    #[test]
+    fn parse_numeric_constant_pool_info() {
+        #[rustfmt::skip]
+        const CONSTANTS: [u8; 28] = [
+            INTEGER_TAG, 0xFF, 0xFF, 0xFF, 0xFE,
+            FLOAT_TAG, 0x3F, 0xC0, 0, 0,
+            LONG_TAG, 0, 0, 0, 1, 0, 0, 0, 0,
+            DOUBLE_TAG, 0x40, 0x02, 0, 0, 0, 0, 0, 0,
+        ];
+        let (rest, pool) = parse_constant_pool::<nom::error::Error<_>>(6)(&CONSTANTS)
+            .finish()
+            .expect("should be able to parse");
+        assert!(rest.is_empty());
+        assert_eq!(pool.get_integer(1), Some(-2));
+        assert_eq!(pool.get_float(2), Some(1.5));
+        assert_eq!(pool.get_long(3), Some(1 << 32));
+        assert!(pool.get(4).is_none());
+        assert_eq!(pool.get_double(5), Some(2.25));
+        assert!(pool.get(6).is_none());
+        assert_eq!(pool.get_integer(2), None);
+    }
+
+    #[test]
+    fn wide_constant_needs_two_slots() {
+        const CONSTANTS: [u8; 9] = [LONG_TAG, 0, 0, 0, 0, 0, 0, 0, 1];
+        assert!(parse_constant_pool::<nom::error::Error<_>>(1)(&CONSTANTS).is_err());
+        let (_, pool) = parse_constant_pool::<nom::error::Error<_>>(2)(&CONSTANTS)
+            .finish()
+            .expect("should be able to parse");
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get_long(1), Some(1));
+        assert!(pool.get(2).is_none());
+    }
+
+    #[test]
+    fn parse_string_constant_pool_info() {
+        const CONSTANTS: [u8; 9] = [STRING_TAG, 0, 2, UTF8_TAG, 0, 3, b'a', b'b', b'c'];
+        let (_, pool) = parse_constant_pool::<nom::error::Error<_>>(2)(&CONSTANTS)
+            .finish()
+            .expect("should be able to parse");
+        assert_eq!(pool.get_string(1), Some("abc"));
+        assert_eq!(pool.get_string(2), None);
+    }
+
+    #[test]
+    fn parse_java_9_constant_pool_info() {
+        #[rustfmt::skip]
+        const CONSTANTS: [u8; 11] = [
+            DYNAMIC_TAG, 0, 0, 0, 2,
+            MODULE_TAG, 0, 3,
+            PACKAGE_TAG, 0, 4,
+        ];
+        let (rest, pool) = parse_constant_pool::<nom::error::Error<_>>(3)(&CONSTANTS)
+            .finish()
+            .expect("should be able to parse");
+        assert!(rest.is_empty());
+        assert!(matches!(
+            pool.get(1),
+            Some(ConstantPoolInfo::Dynamic(Dynamic {
+                bootstrap_method_attr_index: 0,
+                name_and_type_index: 2
+            }))
+        ));
+        assert!(matches!(
+            pool.get(2),
+            Some(ConstantPoolInfo::Module(Module { name_index: 3 }))
+        ));
+        assert!(matches!(
+            pool.get(3),
+            Some(ConstantPoolInfo::Package(Package { name_index: 4 }))
+        ));
+    }
+
+    #[test]
+    fn unknown_tag_fails() {
+        const CONSTANTS: [u8; 3] = [2, 0, 0];
+        assert!(parse_constant_pool::<nom::error::Error<_>>(1)(&CONSTANTS).is_err());
+    }
 }
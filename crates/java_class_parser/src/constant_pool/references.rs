@@ -0,0 +1,606 @@
+//! Finds and rewrites every constant pool index in a class file, for editing the constant pool
+//! without breaking the class.
+//!
+//! Indices are found in the class file structure, the constants themselves, and the attributes
+//! defined by the JVM specification. Unknown attributes can't be searched, so are reported as
+//! errors.
+
+use crate::bytecode::{self, Opcode};
+use crate::constant_pool::values::{
+    Class, Dynamic, FieldRef, InterfaceMethodRef, InvokeDynamic, MethodHandle, MethodRef,
+    MethodType, Module, NameAndType, Package, StringValue,
+};
+use crate::constant_pool::{ConstantPool, ConstantPoolInfo};
+use crate::error::{Error, ErrorKind};
+use crate::raw_java_class::{RawAttributeInfo, RawJavaClass};
+use std::collections::BTreeSet;
+
+/// Maps a constant pool index to a new index, given what the index is used for. Indices of `0`,
+/// which mean no constant, are never mapped.
+pub(crate) type IndexMapper<'a> = dyn FnMut(u16, IndexUse) -> Result<u16, Error> + 'a;
+
+/// What a constant pool index is used for, for mappers that treat constants differently
+/// depending on how they're used. Indices given with a use are from before any mapping.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum IndexUse {
+    /// Any use not listed below
+    Other,
+    /// A utf8 field or method descriptor, or a return descriptor in an annotation
+    Descriptor,
+    /// A utf8 generic signature
+    Signature,
+    /// The utf8 name of a field or record component declared by the class
+    FieldName {
+        /// The utf8 descriptor of the field
+        descriptor: u16,
+    },
+    /// The utf8 name of a method declared by the class
+    MethodName {
+        /// The utf8 descriptor of the method
+        descriptor: u16,
+    },
+    /// The utf8 simple name of an inner class
+    InnerName {
+        /// The class constant of the inner class
+        inner_class: u16,
+    },
+    /// A name and type constant of a method of a class, used by `EnclosingMethod`
+    MethodOf {
+        /// The class constant of the class declaring the method
+        class: u16,
+    },
+}
+
+impl ConstantPoolInfo {
+    /// The indices of the other constants this constant refers to. The bootstrap method indices
+    /// of dynamic constants refer to the `BootstrapMethods` attribute, so aren't included.
+    pub(crate) fn indices_mut(&mut self) -> Vec<&mut u16> {
+        match self {
+            ConstantPoolInfo::Class(Class { name_index })
+            | ConstantPoolInfo::Module(Module { name_index })
+            | ConstantPoolInfo::Package(Package { name_index }) => vec![name_index],
+            ConstantPoolInfo::String(StringValue { string_index }) => vec![string_index],
+            ConstantPoolInfo::MethodType(MethodType { descriptor_index }) => {
+                vec![descriptor_index]
+            }
+            ConstantPoolInfo::MethodHandle(MethodHandle {
+                reference_index, ..
+            }) => vec![reference_index],
+            ConstantPoolInfo::FieldRef(FieldRef {
+                class_index,
+                name_and_type_index,
+            })
+            | ConstantPoolInfo::MethodRef(MethodRef {
+                class_index,
+                name_and_type_index,
+            })
+            | ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            }) => vec![class_index, name_and_type_index],
+            ConstantPoolInfo::NameAndType(NameAndType {
+                name_index,
+                descriptor_index,
+            }) => vec![name_index, descriptor_index],
+            ConstantPoolInfo::Dynamic(Dynamic {
+                name_and_type_index,
+                ..
+            })
+            | ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                name_and_type_index,
+                ..
+            }) => vec![name_and_type_index],
+            ConstantPoolInfo::Integer(_)
+            | ConstantPoolInfo::Float(_)
+            | ConstantPoolInfo::Long(_)
+            | ConstantPoolInfo::Double(_)
+            | ConstantPoolInfo::Utf8(_) => vec![],
+        }
+    }
+}
+
+/// Maps every constant pool index used by the structure and attributes of a class, but not the
+/// indices within the constant pool itself. The constant pool of the class is used to find the
+/// names of attributes, so must not have been changed yet.
+///
+/// # Error
+/// Will return an error if the class has an unknown or malformed attribute, or the mapper fails
+pub(crate) fn map_indices(class: &mut RawJavaClass, map: &mut IndexMapper) -> Result<(), Error> {
+    let pool = &class.constant_pool;
+    for index in [&mut class.this_class, &mut class.super_class]
+        .into_iter()
+        .chain(class.interfaces.iter_mut())
+    {
+        map_index(index, IndexUse::Other, map)?;
+    }
+    for field in class.fields.iter_mut() {
+        let descriptor = field.descriptor_index;
+        map_index(
+            &mut field.name_index,
+            IndexUse::FieldName { descriptor },
+            map,
+        )?;
+        map_index(&mut field.descriptor_index, IndexUse::Descriptor, map)?;
+        map_attributes(pool, &mut field.attributes, map)?;
+    }
+    for method in class.methods.iter_mut() {
+        let descriptor = method.descriptor_index;
+        map_index(
+            &mut method.name_index,
+            IndexUse::MethodName { descriptor },
+            map,
+        )?;
+        map_index(&mut method.descriptor_index, IndexUse::Descriptor, map)?;
+        map_attributes(pool, &mut method.attributes, map)?;
+    }
+    map_attributes(pool, &mut class.attributes, map)
+}
+
+/// Removes every constant that isn't used by a class, directly or through other constants. The
+/// remaining constants keep their order, and every index in the class is updated.
+///
+/// # Error
+/// Will return an error if the class has an unknown or malformed attribute, or refers to an index
+/// that isn't in the constant pool
+pub(crate) fn compact(class: &mut RawJavaClass) -> Result<(), Error> {
+    let mut used = BTreeSet::new();
+    map_indices(class, &mut |index, _| {
+        used.insert(index);
+        Ok(index)
+    })?;
+    let mut pending = used.iter().copied().collect::<Vec<_>>();
+    while let Some(index) = pending.pop() {
+        let mut info = class
+            .constant_pool
+            .get(index)
+            .ok_or(ErrorKind::InvalidConstantIndex(index))?
+            .clone();
+        for &mut child in info.indices_mut() {
+            if used.insert(child) {
+                pending.push(child);
+            }
+        }
+    }
+
+    let mut mapping = vec![0; class.constant_pool.len() + 1];
+    let mut pool = vec![];
+    for (index, info) in class.constant_pool.iter() {
+        if !used.contains(&index) {
+            continue;
+        }
+        mapping[index as usize] = pool.len() as u16 + 1;
+        pool.push(Some(info.clone()));
+        if info.is_wide() {
+            pool.push(None);
+        }
+    }
+    let mut map = |index: u16, _| -> Result<u16, Error> {
+        match mapping.get(index as usize) {
+            Some(&mapped) if mapped != 0 => Ok(mapped),
+            _ => Err(ErrorKind::InvalidConstantIndex(index).into()),
+        }
+    };
+    for info in pool.iter_mut().flatten() {
+        for index in info.indices_mut() {
+            map_index(index, IndexUse::Other, &mut map)?;
+        }
+    }
+    map_indices(class, &mut map)?;
+    class.constant_pool_count = pool.len() as u16 + 1;
+    class.constant_pool = ConstantPool::new(pool);
+    Ok(())
+}
+
+fn map_index(index: &mut u16, usage: IndexUse, map: &mut IndexMapper) -> Result<(), Error> {
+    if *index != 0 {
+        *index = map(*index, usage)?;
+    }
+    Ok(())
+}
+
+fn map_attributes(
+    pool: &ConstantPool,
+    attributes: &mut [RawAttributeInfo],
+    map: &mut IndexMapper,
+) -> Result<(), Error> {
+    for attribute in attributes {
+        let name = pool.get_utf8(attribute.attribute_name_index).ok_or(
+            ErrorKind::InvalidConstantIndex(attribute.attribute_name_index),
+        )?;
+        let mut patcher = Patcher {
+            pool,
+            bytes: &mut attribute.info,
+            position: 0,
+            map,
+        };
+        patcher
+            .attribute(name)?
+            .filter(|_| patcher.position == patcher.bytes.len())
+            .ok_or_else(|| ErrorKind::MalformedAttribute(name.to_string()))?;
+        map_index(&mut attribute.attribute_name_index, IndexUse::Other, map)?;
+    }
+    Ok(())
+}
+
+/// Maps the indices within the bytes of an attribute in place. Walking methods return `None` if
+/// the attribute ends early.
+struct Patcher<'a, 'm> {
+    pool: &'a ConstantPool,
+    bytes: &'a mut [u8],
+    position: usize,
+    map: &'a mut IndexMapper<'m>,
+}
+
+impl Patcher<'_, '_> {
+    fn skip(&mut self, len: usize) -> Option<()> {
+        let end = self.position.checked_add(len)?;
+        (end <= self.bytes.len()).then(|| self.position = end)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes([
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+        ]))
+    }
+
+    /// Reads a two byte value some bytes ahead, without moving
+    fn peek_u16(&self, ahead: usize) -> Option<u16> {
+        let start = self.position.checked_add(ahead)?;
+        let bytes = self.bytes.get(start..start.checked_add(2)?)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Maps a two byte index
+    fn index(&mut self) -> Result<Option<()>, Error> {
+        self.index_as(IndexUse::Other)
+    }
+
+    /// Maps a two byte index with a known use
+    fn index_as(&mut self, usage: IndexUse) -> Result<Option<()>, Error> {
+        let Some(mut index) = self.u16() else {
+            return Ok(None);
+        };
+        map_index(&mut index, usage, self.map)?;
+        self.bytes[self.position - 2..self.position].copy_from_slice(&index.to_be_bytes());
+        Ok(Some(()))
+    }
+
+    /// Maps a count of two byte indices followed by the indices
+    fn indices(&mut self) -> Result<Option<()>, Error> {
+        let Some(count) = self.u16() else {
+            return Ok(None);
+        };
+        for _ in 0..count {
+            if self.index()?.is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(()))
+    }
+
+    /// Maps the indices of an attribute with the given name
+    fn attribute(&mut self, name: &str) -> Result<Option<()>, Error> {
+        macro_rules! tri {
+            ($e:expr) => {
+                match $e {
+                    Some(value) => value,
+                    None => return Ok(None),
+                }
+            };
+        }
+
+        match name {
+            "ConstantValue" | "SourceFile" | "ModuleMainClass" | "NestHost" => {
+                return self.index();
+            }
+            "Signature" => return self.index_as(IndexUse::Signature),
+            "Exceptions" | "ModulePackages" | "NestMembers" | "PermittedSubclasses" => {
+                return self.indices();
+            }
+            "Synthetic" | "Deprecated" | "SourceDebugExtension" | "LineNumberTable" => {
+                self.position = self.bytes.len();
+            }
+            "Code" => {
+                tri!(self.skip(4));
+                let code_length = tri!(self.u32()) as usize;
+                let start = self.position;
+                tri!(self.skip(code_length));
+                self.code(start, code_length)?;
+                let exception_table_length = tri!(self.u16());
+                for _ in 0..exception_table_length {
+                    tri!(self.skip(6));
+                    tri!(self.index()?);
+                }
+                let attributes_count = tri!(self.u16());
+                for _ in 0..attributes_count {
+                    tri!(self.nested_attribute()?);
+                }
+            }
+            "StackMapTable" => {
+                let frames = tri!(self.u16());
+                for _ in 0..frames {
+                    let frame_type = tri!(self.u8());
+                    match frame_type {
+                        0..=63 => {}
+                        64..=127 => tri!(self.verification_types(1)?),
+                        247 => {
+                            tri!(self.skip(2));
+                            tri!(self.verification_types(1)?);
+                        }
+                        248..=251 => tri!(self.skip(2)),
+                        252..=254 => {
+                            tri!(self.skip(2));
+                            tri!(self.verification_types(frame_type as u16 - 251)?);
+                        }
+                        255 => {
+                            tri!(self.skip(2));
+                            let locals = tri!(self.u16());
+                            tri!(self.verification_types(locals)?);
+                            let stack = tri!(self.u16());
+                            tri!(self.verification_types(stack)?);
+                        }
+                        _ => return Ok(None),
+                    }
+                }
+            }
+            "InnerClasses" => {
+                let classes = tri!(self.u16());
+                for _ in 0..classes {
+                    let inner_class = tri!(self.peek_u16(0));
+                    tri!(self.index()?);
+                    tri!(self.index()?);
+                    tri!(self.index_as(IndexUse::InnerName { inner_class })?);
+                    tri!(self.skip(2));
+                }
+            }
+            "EnclosingMethod" => {
+                let class = tri!(self.peek_u16(0));
+                tri!(self.index()?);
+                tri!(self.index_as(IndexUse::MethodOf { class })?);
+            }
+            "LocalVariableTable" | "LocalVariableTypeTable" => {
+                let type_use = if name == "LocalVariableTable" {
+                    IndexUse::Descriptor
+                } else {
+                    IndexUse::Signature
+                };
+                let variables = tri!(self.u16());
+                for _ in 0..variables {
+                    tri!(self.skip(4));
+                    tri!(self.index()?);
+                    tri!(self.index_as(type_use)?);
+                    tri!(self.skip(2));
+                }
+            }
+            "RuntimeVisibleAnnotations" | "RuntimeInvisibleAnnotations" => {
+                tri!(self.annotations()?);
+            }
+            "RuntimeVisibleParameterAnnotations" | "RuntimeInvisibleParameterAnnotations" => {
+                let parameters = tri!(self.u8());
+                for _ in 0..parameters {
+                    tri!(self.annotations()?);
+                }
+            }
+            "RuntimeVisibleTypeAnnotations" | "RuntimeInvisibleTypeAnnotations" => {
+                let annotations = tri!(self.u16());
+                for _ in 0..annotations {
+                    tri!(self.type_annotation()?);
+                }
+            }
+            "AnnotationDefault" => tri!(self.element_value()?),
+            "BootstrapMethods" => {
+                let methods = tri!(self.u16());
+                for _ in 0..methods {
+                    tri!(self.index()?);
+                    tri!(self.indices()?);
+                }
+            }
+            "MethodParameters" => {
+                let parameters = tri!(self.u8());
+                for _ in 0..parameters {
+                    tri!(self.index()?);
+                    tri!(self.skip(2));
+                }
+            }
+            "Module" => {
+                tri!(self.index()?);
+                tri!(self.skip(2));
+                tri!(self.index()?);
+                let requires = tri!(self.u16());
+                for _ in 0..requires {
+                    tri!(self.index()?);
+                    tri!(self.skip(2));
+                    tri!(self.index()?);
+                }
+                // exports and opens have the same structure
+                for _ in 0..2 {
+                    let packages = tri!(self.u16());
+                    for _ in 0..packages {
+                        tri!(self.index()?);
+                        tri!(self.skip(2));
+                        tri!(self.indices()?);
+                    }
+                }
+                tri!(self.indices()?);
+                let provides = tri!(self.u16());
+                for _ in 0..provides {
+                    tri!(self.index()?);
+                    tri!(self.indices()?);
+                }
+            }
+            "Record" => {
+                let components = tri!(self.u16());
+                for _ in 0..components {
+                    let descriptor = tri!(self.peek_u16(2));
+                    tri!(self.index_as(IndexUse::FieldName { descriptor })?);
+                    tri!(self.index_as(IndexUse::Descriptor)?);
+                    let attributes_count = tri!(self.u16());
+                    for _ in 0..attributes_count {
+                        tri!(self.nested_attribute()?);
+                    }
+                }
+            }
+            _ => return Err(ErrorKind::UnknownAttribute(name.to_string()).into()),
+        }
+        Ok(Some(()))
+    }
+
+    /// Maps the indices of an attribute nested in another attribute, including its name
+    fn nested_attribute(&mut self) -> Result<Option<()>, Error> {
+        let (Some(name_index), Some(length)) = (self.u16(), self.u32()) else {
+            return Ok(None);
+        };
+        let name = self
+            .pool
+            .get_utf8(name_index)
+            .ok_or(ErrorKind::InvalidConstantIndex(name_index))?;
+        let start = self.position;
+        let Some(end) = start
+            .checked_add(length as usize)
+            .filter(|&end| end <= self.bytes.len())
+        else {
+            return Ok(None);
+        };
+        let mut nested = Patcher {
+            pool: self.pool,
+            bytes: &mut self.bytes[start..end],
+            position: 0,
+            map: self.map,
+        };
+        if nested.attribute(name)?.is_none() || nested.position != end - start {
+            return Err(ErrorKind::MalformedAttribute(name.to_string()).into());
+        }
+        let mut name_index = name_index;
+        map_index(&mut name_index, IndexUse::Other, self.map)?;
+        self.bytes[start - 6..start - 4].copy_from_slice(&name_index.to_be_bytes());
+        self.position = end;
+        Ok(Some(()))
+    }
+
+    /// Maps the constant indices used by bytecode instructions
+    fn code(&mut self, start: usize, len: usize) -> Result<(), Error> {
+        let code = &mut self.bytes[start..start + len];
+        let instructions = bytecode::decode(code).collect::<Result<Vec<_>, _>>()?;
+        for instruction in instructions {
+            let Some(index) = instruction.constant_index() else {
+                continue;
+            };
+            let operand = instruction.offset() as usize + 1;
+            let mut mapped = index;
+            map_index(&mut mapped, IndexUse::Other, self.map)?;
+            if instruction.opcode() == Opcode::Ldc {
+                code[operand] = u8::try_from(mapped)
+                    .map_err(|_| ErrorKind::MalformedAttribute("Code".to_string()))?;
+            } else {
+                code[operand..operand + 2].copy_from_slice(&mapped.to_be_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn verification_types(&mut self, count: u16) -> Result<Option<()>, Error> {
+        for _ in 0..count {
+            match self.u8() {
+                // Object variables refer to a class, Uninitialized variables to an offset
+                Some(7) => {
+                    if self.index()?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                Some(8) => {
+                    if self.skip(2).is_none() {
+                        return Ok(None);
+                    }
+                }
+                Some(0..=6) => {}
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(()))
+    }
+
+    fn annotations(&mut self) -> Result<Option<()>, Error> {
+        let Some(annotations) = self.u16() else {
+            return Ok(None);
+        };
+        for _ in 0..annotations {
+            if self.annotation()?.is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(()))
+    }
+
+    fn annotation(&mut self) -> Result<Option<()>, Error> {
+        if self.index_as(IndexUse::Descriptor)?.is_none() {
+            return Ok(None);
+        }
+        let Some(pairs) = self.u16() else {
+            return Ok(None);
+        };
+        for _ in 0..pairs {
+            if self.index()?.is_none() || self.element_value()?.is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(()))
+    }
+
+    fn element_value(&mut self) -> Result<Option<()>, Error> {
+        let Some(tag) = self.u8() else {
+            return Ok(None);
+        };
+        match tag {
+            b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b's' => self.index(),
+            b'c' => self.index_as(IndexUse::Descriptor),
+            b'e' => {
+                let type_name = self.index_as(IndexUse::Descriptor)?;
+                Ok(type_name.and(self.index()?))
+            }
+            b'@' => self.annotation(),
+            b'[' => {
+                let Some(values) = self.u16() else {
+                    return Ok(None);
+                };
+                for _ in 0..values {
+                    if self.element_value()?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                Ok(Some(()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn type_annotation(&mut self) -> Result<Option<()>, Error> {
+        let target_info = match self.u8() {
+            Some(0x00 | 0x01 | 0x16) => Some(1),
+            Some(0x10 | 0x11 | 0x12 | 0x17 | 0x42 | 0x43..=0x46) => Some(2),
+            Some(0x13..=0x15) => Some(0),
+            Some(0x47..=0x4B) => Some(3),
+            Some(0x40 | 0x41) => self.u16().map(|table_length| table_length as usize * 6),
+            _ => None,
+        };
+        let path = target_info
+            .and_then(|len| self.skip(len))
+            .and_then(|_| self.u8());
+        if path.and_then(|len| self.skip(len as usize * 2)).is_none() {
+            return Ok(None);
+        }
+        self.annotation()
+    }
+}
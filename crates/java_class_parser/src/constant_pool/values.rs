@@ -47,22 +47,149 @@ pub struct NameAndType {
 }
 #[derive(Debug, Clone)]
 pub struct Utf8 {
+    /// The modified utf8 bytes, as stored in the class file
     pub bytes: Box<[u8]>,
+    /// The decoded string, decoded once when the constant is parsed. Anything that isn't valid
+    /// modified utf8 is replaced with `U+FFFD`.
+    pub string: Box<str>,
+    /// Why the bytes aren't valid modified utf8, if they aren't
+    pub error: Option<ModifiedUtf8Error>,
 }
 
-impl AsRef<str> for Utf8 {
-    fn as_ref(&self) -> &str {
-        std::str::from_utf8(&*self.bytes).expect("invalid utf8")
+impl Utf8 {
+    pub fn new(bytes: &[u8]) -> Self {
+        let (string, error) = decode(bytes);
+        Self {
+            bytes: Box::from(bytes),
+            string: string.into_boxed_str(),
+            error,
+        }
+    }
+
+    /// Gets the decoded string
+    pub fn as_str(&self) -> Result<&str, ModifiedUtf8Error> {
+        match &self.error {
+            None => Ok(&self.string),
+            Some(error) => Err(error.clone()),
+        }
+    }
+
+    /// Gets the decoded string, with anything that isn't valid modified utf8 replaced with
+    /// `U+FFFD`
+    pub fn as_str_lossy(&self) -> &str {
+        &self.string
     }
 }
 
 impl Display for Utf8 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let as_string = String::from_utf8_lossy(&*self.bytes);
-        write!(f, "{}", as_string)
+        write!(f, "{}", self.string)
+    }
+}
+
+/// The bytes of a utf8 constant weren't valid modified utf8
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("invalid modified utf8 at byte {offset}")]
+pub struct ModifiedUtf8Error {
+    pub offset: usize,
+}
+
+/// Decodes the modified utf8 used by class files, as described in section
+/// [§4.4.7](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4.7) of the JVM
+/// specification.
+///
+/// Modified utf8 differs from standard utf8 in two ways. The null character is encoded with two
+/// bytes, and supplementary characters are encoded as a utf16 surrogate pair with three bytes for
+/// each surrogate. Surrogates that aren't part of a pair can't be represented in a rust string, so
+/// are reported as errors.
+pub fn decode_modified_utf8(bytes: &[u8]) -> Result<String, ModifiedUtf8Error> {
+    match decode(bytes) {
+        (string, None) => Ok(string),
+        (_, Some(error)) => Err(error),
     }
 }
 
+/// Decodes modified utf8, replacing anything that isn't valid, such as surrogates that aren't part
+/// of a pair, with `U+FFFD`. The error is where the first invalid character is.
+fn decode(bytes: &[u8]) -> (String, Option<ModifiedUtf8Error>) {
+    // most strings are plain ascii, which is encoded the same way
+    if bytes.iter().all(|&byte| byte != 0 && byte < 0x80) {
+        return (
+            String::from_utf8(bytes.to_vec()).expect("ascii is valid utf8"),
+            None,
+        );
+    }
+
+    let mut string = String::with_capacity(bytes.len());
+    let mut offset = 0;
+    // decodes a utf16 code unit, returning it and its length in bytes
+    let unit = |offset: usize| -> Option<(u16, usize)> {
+        let continuation = |index: usize| {
+            bytes
+                .get(offset + index)
+                .filter(|&&byte| byte & 0xC0 == 0x80)
+                .map(|&byte| (byte & 0x3F) as u16)
+        };
+        let first = *bytes.get(offset)?;
+        match first {
+            0x01..=0x7F => Some((first as u16, 1)),
+            0xC0..=0xDF => Some((((first & 0x1F) as u16) << 6 | continuation(1)?, 2)),
+            0xE0..=0xEF => Some((
+                ((first & 0x0F) as u16) << 12 | continuation(1)? << 6 | continuation(2)?,
+                3,
+            )),
+            _ => None,
+        }
+    };
+    let mut error = None;
+    while offset < bytes.len() {
+        // a byte that doesn't start a code unit is replaced on its own
+        let (c, len) = match unit(offset) {
+            Some((high @ 0xD800..=0xDBFF, len)) => {
+                match unit(offset + len).filter(|(low, _)| (0xDC00..=0xDFFF).contains(low)) {
+                    Some((low, low_len)) => {
+                        let code_point =
+                            0x10000 + (((high - 0xD800) as u32) << 10) + (low - 0xDC00) as u32;
+                        (char::from_u32(code_point), len + low_len)
+                    }
+                    None => (None, len),
+                }
+            }
+            Some((unit, len)) => (char::from_u32(unit as u32), len),
+            None => (None, 1),
+        };
+        if c.is_none() && error.is_none() {
+            error = Some(ModifiedUtf8Error { offset });
+        }
+        string.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+        offset += len;
+    }
+    (string, error)
+}
+
+/// Encodes a string as modified utf8, the inverse of [`decode_modified_utf8`]
+pub fn encode_modified_utf8(string: &str) -> Vec<u8> {
+    if string.bytes().all(|byte| byte != 0 && byte < 0x80) {
+        return string.as_bytes().to_vec();
+    }
+
+    let mut bytes = Vec::with_capacity(string.len());
+    for unit in string.encode_utf16() {
+        match unit {
+            0x01..=0x7F => bytes.push(unit as u8),
+            0x00 | 0x80..=0x7FF => {
+                bytes.extend([0xC0 | (unit >> 6) as u8, 0x80 | (unit & 0x3F) as u8])
+            }
+            _ => bytes.extend([
+                0xE0 | (unit >> 12) as u8,
+                0x80 | ((unit >> 6) & 0x3F) as u8,
+                0x80 | (unit & 0x3F) as u8,
+            ]),
+        }
+    }
+    bytes
+}
+
 #[derive(Debug, Clone)]
 pub struct MethodHandle {
     pub reference_kind: u8,
@@ -77,3 +204,88 @@ pub struct InvokeDynamic {
     pub bootstrap_method_attr_index: u16,
     pub name_and_type_index: u16,
 }
+#[derive(Debug, Clone)]
+pub struct Dynamic {
+    pub bootstrap_method_attr_index: u16,
+    pub name_and_type_index: u16,
+}
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name_index: u16,
+}
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub name_index: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_modified_utf8, encode_modified_utf8, ModifiedUtf8Error, Utf8};
+
+    #[test]
+    fn decode_ascii() {
+        assert_eq!(
+            decode_modified_utf8(b"java/lang/Object").unwrap(),
+            "java/lang/Object"
+        );
+    }
+
+    #[test]
+    fn decode_null() {
+        assert_eq!(
+            decode_modified_utf8(&[b'a', 0xC0, 0x80, b'b']).unwrap(),
+            "a\0b"
+        );
+        assert_eq!(
+            decode_modified_utf8(&[b'a', 0]),
+            Err(ModifiedUtf8Error { offset: 1 })
+        );
+    }
+
+    #[test]
+    fn decode_multi_byte() {
+        assert_eq!(decode_modified_utf8("é€".as_bytes()).unwrap(), "é€");
+    }
+
+    #[test]
+    fn decode_surrogate_pair() {
+        // U+1F600 is encoded as the surrogates D83D and DE00
+        let bytes = [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80];
+        assert_eq!(decode_modified_utf8(&bytes).unwrap(), "\u{1F600}");
+        assert_eq!(
+            decode_modified_utf8(&bytes[..3]),
+            Err(ModifiedUtf8Error { offset: 0 })
+        );
+        // standard 4 byte utf8 isn't valid modified utf8
+        assert!(decode_modified_utf8("\u{1F600}".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        for string in ["java/lang/Object", "a\0b", "é€", "\u{1F600}"] {
+            let encoded = encode_modified_utf8(string);
+            assert_eq!(decode_modified_utf8(&encoded).unwrap(), string);
+        }
+        assert_eq!(encode_modified_utf8("\0"), [0xC0, 0x80]);
+        assert_eq!(encode_modified_utf8("\u{1F600}").len(), 6);
+    }
+
+    #[test]
+    fn decode_lossy() {
+        // a high surrogate without its low surrogate, between 'a' and 'b'
+        let bytes = [b'a', 0xED, 0xA0, 0xBD, b'b'];
+        let utf8 = Utf8::new(&bytes);
+        assert_eq!(utf8.as_str(), Err(ModifiedUtf8Error { offset: 1 }));
+        assert_eq!(utf8.as_str_lossy(), "a\u{FFFD}b");
+        assert_eq!(utf8.to_string(), "a\u{FFFD}b");
+        assert_eq!(Utf8::new(&[b'a', 0, b'b']).as_str_lossy(), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn decode_truncated() {
+        assert_eq!(
+            decode_modified_utf8(&[b'a', 0xE2, 0x82]),
+            Err(ModifiedUtf8Error { offset: 1 })
+        );
+    }
+}
@@ -1,11 +1,16 @@
 //! Contains the error type that can be emitted
 
+use crate::bytecode::DecodeError;
+use crate::jimage::JImageError;
+use crate::mapping::MappingError;
 use crate::FQNameBuf;
+use java_classpaths::ClasspathError;
 use nom::Needed;
 use std::backtrace::Backtrace;
 use std::fmt::{Debug, Display, Formatter};
 use std::io;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// The error type
 pub struct Error {
@@ -61,6 +66,20 @@ pub enum ErrorKind {
     /// No class could be found for a given path
     #[error("No class found for path {0:?}")]
     NoClassFound(FQNameBuf),
+    /// A fully qualified name isn't made of valid java identifiers, or isn't a legal binary name
+    #[error("{0:?} is not a valid fully qualified name")]
+    InvalidName(String),
+    /// A name refers to an array or primitive type, which has no class file
+    #[error("{0:?} is not a class")]
+    NotAClass(String),
+    /// A class was registered under a name other than its own
+    #[error("expected class {expected}, but found class {found}")]
+    NameMismatch {
+        /// The name the class was registered under
+        expected: FQNameBuf,
+        /// The name of the class
+        found: FQNameBuf,
+    },
     /// Encountered an unsupported classpath entry
     #[error("Unsupported entry in classpath: {0:?}")]
     UnsupportedEntry(PathBuf),
@@ -73,6 +92,24 @@ pub enum ErrorKind {
     /// While parsing, some bytes were missing
     #[error("Missing {:?} bytes", 0)]
     MissingBytes(Needed),
+    /// A constant pool can't hold more than 65535 entries
+    #[error("the constant pool is full")]
+    ConstantPoolFull,
+    /// A utf8 constant can't be longer than 65535 bytes once encoded
+    #[error("{0} bytes is too long for a utf8 constant")]
+    ConstantTooLong(usize),
+    /// An index doesn't refer to a constant in the constant pool
+    #[error("{0} is not a valid constant pool index")]
+    InvalidConstantIndex(u16),
+    /// A field or method descriptor couldn't be parsed
+    #[error("{0:?} is not a valid descriptor")]
+    InvalidDescriptor(String),
+    /// An attribute isn't known, so the constants it refers to can't be found
+    #[error("unknown attribute {0}")]
+    UnknownAttribute(String),
+    /// An attribute doesn't have the structure required by its name
+    #[error("malformed {0} attribute")]
+    MalformedAttribute(String),
     /// A nom error occurred
     #[error(transparent)]
     NomError {
@@ -82,9 +119,40 @@ pub enum ErrorKind {
     /// A zip error occurred.
     #[error(transparent)]
     ZipError(#[from] zip::result::ZipError),
-    /// Adding inheritance failed
-    #[error("adding inheritance of {0} failed")]
-    AddingInheritanceFailed(FQNameBuf),
+    /// Classes inherit from each other in a cycle, listed from the class where the cycle was found
+    /// until it inherits from that class again
+    #[error("inheritance cycle: {}", display_cycle(.0))]
+    InheritanceCycle(Vec<FQNameBuf>),
+    /// Bytecode couldn't be decoded
+    #[error(transparent)]
+    DecodeError(#[from] DecodeError),
+    /// Annotation values couldn't be deserialized
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    DeserializeError(#[from] serde::de::value::Error),
+    /// A runtime image couldn't be read
+    #[error(transparent)]
+    JImageError(#[from] JImageError),
+    /// A mapping file couldn't be parsed
+    #[error(transparent)]
+    MappingError(#[from] MappingError),
+    /// Something on the classpath couldn't be read
+    #[error(transparent)]
+    ClasspathError(ClasspathError),
+    /// The fields, methods or attributes of a lazily parsed class are invalid. The error is shared
+    /// by every attempt to decode the class.
+    #[error(transparent)]
+    InvalidClassBody(Arc<Error>),
+}
+
+impl From<ClasspathError> for ErrorKind {
+    fn from(e: ClasspathError) -> Self {
+        match e {
+            ClasspathError::Io(e) => Self::IoError(e),
+            ClasspathError::UnsupportedEntry(entry) => Self::UnsupportedEntry(entry),
+            e => Self::ClasspathError(e),
+        }
+    }
 }
 
 impl<'a> From<nom::Err<nom::error::Error<&'a [u8]>>> for ErrorKind {
@@ -92,3 +160,11 @@ impl<'a> From<nom::Err<nom::error::Error<&'a [u8]>>> for ErrorKind {
         Self::NomError { kind: e.to_owned() }
     }
 }
+
+fn display_cycle(cycle: &[FQNameBuf]) -> String {
+    cycle
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
@@ -0,0 +1,184 @@
+//! Bulk export of the classes in a jar as JSON, for feeding whole artifacts into data pipelines.
+//!
+//! Classes are parsed and written one at a time, so only a single class is held in memory at once.
+//! Each class is written as an object of the form
+//! ```json
+//! {
+//!   "name": "com/example/Rectangle",
+//!   "major": 61,
+//!   "minor": 0,
+//!   "access_flags": 33,
+//!   "super": "java/lang/Object",
+//!   "interfaces": ["com/example/Shape"],
+//!   "fields": [{"name": "width", "descriptor": "D", "access_flags": 18}],
+//!   "methods": [{"name": "getArea", "descriptor": "()D", "access_flags": 1}],
+//!   "references": ["com/example/Shape", "java/lang/Object"]
+//! }
+//! ```
+//! where `references` is every other class the class refers to, sorted, as given by
+//! [`JavaClass::referenced_classes`].
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::export::{export_jar, ExportFormat};
+//! let stdout = std::io::stdout();
+//! export_jar("app.jar", stdout.lock(), ExportFormat::Ndjson).expect("couldn't export");
+//! ```
+
+use crate::error::Error;
+use crate::{entry_class_name, parse_bytes, FQName, JavaClass};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// How exported classes are laid out
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ExportFormat {
+    /// Newline delimited JSON, with one class object per line
+    Ndjson,
+    /// A single JSON array containing every class object
+    Json,
+}
+
+/// Exports every class in a jar to a writer, returning the number of classes written
+///
+/// # Error
+/// Will return an error if the jar can't be read, a class within it can't be parsed, or the
+/// writer fails. Classes exported before the error have already been written.
+pub fn export_jar<P: AsRef<Path>, W: Write>(
+    jar: P,
+    mut writer: W,
+    format: ExportFormat,
+) -> Result<usize, Error> {
+    let mut archive = ZipArchive::new(File::open(jar)?)?;
+    let mut exported = 0;
+    if format == ExportFormat::Json {
+        write!(writer, "[")?;
+    }
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if entry_class_name(entry.name()).is_none() {
+            continue;
+        }
+        let class = parse_bytes(entry)?;
+        let json = class_to_json(&class);
+        match format {
+            ExportFormat::Ndjson => writeln!(writer, "{json}")?,
+            ExportFormat::Json if exported == 0 => write!(writer, "{json}")?,
+            ExportFormat::Json => write!(writer, ",{json}")?,
+        }
+        exported += 1;
+    }
+    if format == ExportFormat::Json {
+        writeln!(writer, "]")?;
+    }
+    writer.flush()?;
+    Ok(exported)
+}
+
+/// Converts a class into a single line JSON object
+pub fn class_to_json(class: &JavaClass) -> String {
+    let raw = class.raw();
+    let mut json = String::new();
+    json.push_str("{\"name\":");
+    write_string(&mut json, class.this().as_str());
+    let _ = write!(
+        json,
+        ",\"major\":{},\"minor\":{},\"access_flags\":{}",
+        raw.major, raw.minor, raw.access_flags
+    );
+    json.push_str(",\"super\":");
+    match class.super_class() {
+        Some(super_class) => write_string(&mut json, super_class.as_str()),
+        None => json.push_str("null"),
+    }
+    json.push_str(",\"interfaces\":");
+    write_strings(
+        &mut json,
+        class.interfaces().into_iter().map(FQName::as_str),
+    );
+
+    json.push_str(",\"fields\":[");
+    for (index, field) in class.fields().iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        write_member(
+            &mut json,
+            field.name(),
+            &field.signature().jni(),
+            field.access_flags().bits(),
+        );
+    }
+    json.push_str("],\"methods\":[");
+    for (index, method) in class.methods().iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        write_member(
+            &mut json,
+            method.name(),
+            &method.signature().jni(),
+            method.access_flags().bits(),
+        );
+    }
+    json.push_str("],\"references\":");
+    write_strings(
+        &mut json,
+        class.referenced_classes().into_iter().map(FQName::as_str),
+    );
+    json.push('}');
+    json
+}
+
+fn write_member(json: &mut String, name: &str, descriptor: &str, access_flags: u16) {
+    json.push_str("{\"name\":");
+    write_string(json, name);
+    json.push_str(",\"descriptor\":");
+    write_string(json, descriptor);
+    let _ = write!(json, ",\"access_flags\":{access_flags}}}");
+}
+
+fn write_strings<'a>(json: &mut String, strings: impl Iterator<Item = &'a str>) {
+    json.push('[');
+    for (index, string) in strings.enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        write_string(json, string);
+    }
+    json.push(']');
+}
+
+/// Writes a string as a JSON string literal
+fn write_string(json: &mut String, string: &str) {
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_string;
+
+    #[test]
+    fn escape_strings() {
+        let mut json = String::new();
+        write_string(&mut json, "a\"b\\c\n\u{1}é");
+        assert_eq!(json, r#""a\"b\\c\n\u0001é""#);
+    }
+}
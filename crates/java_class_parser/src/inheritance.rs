@@ -1,17 +1,33 @@
 //! Provides mechanisms to inspect the inheritance structure of a class
+//!
+//! Super types that can't be found are kept in the graph as external nodes, so the graph always
+//! records every edge of the hierarchy. A parser with the [JDK](JavaClassParser::with_jdk), or
+//! [`inspect_complete`], finds the JDK classes every hierarchy ends in.
 
 use crate::error::{Error, ErrorKind};
+use crate::jimage::JImage;
+use crate::resolved::ClassRef;
 use crate::structures::FQName;
-use crate::{FQNameBuf, JavaClass, JavaClassParser};
+use crate::utility::dot_quoted;
+use crate::{
+    AsFullyQualifiedName, FQNameBuf, Field, JavaClass, JavaClassParser, Method, NamedType,
+    Signature,
+};
 use petgraph::prelude::*;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
 
-/// A graph representing interfaces and super classes of a given root class.
+const OBJECT: &str = "java/lang/Object";
+
+/// A graph representing interfaces and super classes of a given root class. More root classes can
+/// be added with [`extend`](Self::extend) or a [`GraphBuilder`], sharing the ancestors they have
+/// in common.
 #[derive(Debug)]
 pub struct InheritanceGraph {
     graph: DiGraph<FQNameBuf, InheritKind>,
-    mapping: HashMap<FQNameBuf, (JavaClass, NodeIndex)>,
-    root: FQNameBuf,
+    mapping: HashMap<FQNameBuf, (ClassRef<JavaClass>, NodeIndex)>,
+    roots: Vec<FQNameBuf>,
 }
 
 /// How a given type inherits another type
@@ -24,143 +40,1114 @@ pub enum InheritKind {
 }
 
 impl InheritanceGraph {
-    fn new(class: JavaClass) -> Self {
-        let fcq = class.this().to_owned();
-        let mut graph = DiGraph::new();
-        let index = graph.add_node(fcq.clone());
-        let map = HashMap::from([(fcq.clone(), (class, index))]);
+    fn new() -> Self {
         Self {
-            graph,
-            mapping: map,
-            root: fcq,
+            graph: DiGraph::new(),
+            mapping: HashMap::new(),
+            roots: vec![],
         }
     }
 
-    /// Adds a class. Returns true only if this class hasn't been added yet.
-    fn add_class(&mut self, class: JavaClass) -> bool {
-        if self.mapping.contains_key(class.this()) {
-            return false;
+    /// Adds the hierarchy of a class to this graph, making it a root of the graph. Ancestors that
+    /// are already in the graph aren't walked again.
+    ///
+    /// # Error
+    /// Will return an error if a super type can't be parsed
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::inheritance::inspect;
+    /// # use java_class_parser::JavaClassParser;
+    /// let parser = JavaClassParser::new("app.jar");
+    /// let square = parser.find("com/example/Square").unwrap();
+    /// let mut graph = inspect(&square, &parser).unwrap();
+    /// graph.extend(&parser.find("com/example/Circle").unwrap(), &parser).unwrap();
+    /// ```
+    pub fn extend(&mut self, class: &JavaClass, parser: &JavaClassParser) -> Result<(), Error> {
+        self.add_root(class, |name| find(parser, name))
+    }
+
+    fn add_root<F>(&mut self, class: &JavaClass, find: F) -> Result<(), Error>
+    where
+        F: FnMut(&FQName) -> Result<Option<JavaClass>, Error>,
+    {
+        let name = class.this();
+        if !self.roots.iter().any(|root| root == name) {
+            self.roots.push(name.to_fqname_buf());
         }
+        match self.mapping.get_mut(name) {
+            // its ancestors have already been walked
+            Some((ClassRef::Resolved(_), _)) => return Ok(()),
+            Some((external, _)) => *external = ClassRef::Resolved(class.clone()),
+            None => {
+                self.add_class(ClassRef::Resolved(class.clone()));
+            }
+        }
+        self.walk(class, find)
+    }
 
-        let index = self.graph.add_node(class.this().to_owned());
-        self.mapping.insert(class.this().to_owned(), (class, index));
-        true
+    /// Walks up from a class in the graph, adding every super type that isn't in the graph yet
+    fn walk<F>(&mut self, class: &JavaClass, mut find: F) -> Result<(), Error>
+    where
+        F: FnMut(&FQName) -> Result<Option<JavaClass>, Error>,
+    {
+        let mut stack = vec![class.clone()];
+        while let Some(class) = stack.pop() {
+            let mut inherits = vec![];
+            if let Some(super_class) = class.super_class() {
+                inherits.push((super_class, InheritKind::Extends));
+            }
+            inherits.extend(
+                class
+                    .interfaces()
+                    .into_iter()
+                    .map(|interface| (interface, InheritKind::Implements)),
+            );
+            for (name, kind) in inherits {
+                if !self.mapping.contains_key(name) {
+                    let found = match find(name)? {
+                        Some(found) => {
+                            stack.push(found.clone());
+                            ClassRef::Resolved(found)
+                        }
+                        None => ClassRef::Missing(name.to_fqname_buf()),
+                    };
+                    self.add_class(found);
+                }
+                self.add_inheritance(class.this(), name, kind)?;
+            }
+        }
+        Ok(())
     }
 
-    /// add inheritance. returns true only if both classes are in the graph and an existing inheritance
-    /// doesn't already exist
-    fn add_inheritance(&mut self, class: &FQName, inherits: &FQName, ty: InheritKind) -> bool {
-        let Some(&(_, class)) = self.mapping.get(class) else {
-            eprintln!("doesn't contain class {}", class);
-            return false;
-        };
-        let Some(&(_, inherits)) = self.mapping.get(inherits) else {
-            eprintln!("doesn't contain class {}", inherits);
+    /// Adds a class, or an external type if it couldn't be found. Returns true only if this type
+    /// hasn't been added yet.
+    fn add_class(&mut self, class: ClassRef<JavaClass>) -> bool {
+        if self.mapping.contains_key(class.name()) {
             return false;
-        };
+        }
 
+        let name = class.name().to_fqname_buf();
+        let index = self.graph.add_node(name.clone());
+        self.mapping.insert(name, (class, index));
+        true
+    }
+
+    /// Adds an inheritance between two classes in the graph. Returns true only if the inheritance
+    /// hasn't been added yet, which happens if a class lists the same super type twice.
+    ///
+    /// # Error
+    /// Will return an error if either class isn't in the graph, or if the inheritance would make a
+    /// class inherit from itself
+    fn add_inheritance(
+        &mut self,
+        class: &FQName,
+        inherits: &FQName,
+        ty: InheritKind,
+    ) -> Result<bool, Error> {
+        let class = self.index_of(class)?;
+        let inherits = self.index_of(inherits)?;
         if self.graph.contains_edge(class, inherits) {
-            eprintln!(
-                "already contains edge between {} -> {} ({:?})",
-                self.graph[class],
-                self.graph[inherits],
-                self.graph.find_edge(class, inherits).unwrap()
-            );
-            return false;
+            return Ok(false);
+        }
+        if let Some(path) = self.path_between(inherits, class) {
+            let cycle = std::iter::once(class)
+                .chain(path)
+                .map(|index| self.graph[index].clone())
+                .collect();
+            return Err(Error::new(ErrorKind::InheritanceCycle(cycle)));
         }
 
         self.graph.add_edge(class, inherits, ty);
-        true
+        Ok(true)
+    }
+
+    /// Finds the shortest path of inheritances from one class to another, including both classes
+    fn path_between(&self, from: NodeIndex, to: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let mut parents = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(index) = queue.pop_front() {
+            if index == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = parents[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.graph.neighbors(index) {
+                if let Entry::Vacant(entry) = parents.entry(next) {
+                    entry.insert(index);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
     }
 
-    fn get_class(&self, node_index: NodeIndex) -> &JavaClass {
+    fn get_class(&self, node_index: NodeIndex) -> ClassRef<&JavaClass> {
         let name = &*self.graph[node_index];
         let (class, _) = self
             .mapping
             .get(name)
             .expect("index didn't correspond to known class");
-        class
+        match class {
+            ClassRef::Resolved(class) => ClassRef::Resolved(class),
+            ClassRef::Missing(name) => ClassRef::Missing(name.clone()),
+        }
     }
 
     /// Gets the classes that this class extends or interfaces it implements that are present on
-    /// the originating classpath. Order is determined in breadth first order.
+    /// the originating classpath, in the order of its [ancestors](Self::ancestors).
     pub fn inherits<F: AsRef<FQName>>(
         &self,
         fqn: F,
     ) -> Result<Vec<(&JavaClass, InheritKind)>, Error> {
-        let fq_name = fqn.as_ref();
-        if !self.mapping.contains_key(fq_name) {
-            return Err(Error::from(ErrorKind::NoClassFound(
+        Ok(self
+            .ancestors(fqn)?
+            .into_iter()
+            .filter_map(|(class, kind)| class.resolved().map(|&class| (class, kind)))
+            .collect())
+    }
+
+    /// Gets every type that this class extends or implements, including
+    /// [external](Self::external_types) types that couldn't be found, in a deterministic order.
+    /// The chain of super classes comes first, from the direct super class up to
+    /// `java/lang/Object`, followed by every interface in breadth first order. Interfaces are
+    /// visited in the order they're declared, starting with the interfaces of this class and then
+    /// those of each super class.
+    pub fn ancestors<F: AsRef<FQName>>(
+        &self,
+        fqn: F,
+    ) -> Result<Vec<(ClassRef<&JavaClass>, InheritKind)>, Error> {
+        Ok(self
+            .ancestor_indices(fqn.as_ref())?
+            .into_iter()
+            .map(|(index, kind)| (self.get_class(index), kind))
+            .collect())
+    }
+
+    fn ancestor_indices(&self, fq_name: &FQName) -> Result<Vec<(NodeIndex, InheritKind)>, Error> {
+        Ok(self.ancestors_of(self.index_of(fq_name)?))
+    }
+
+    fn index_of(&self, fq_name: &FQName) -> Result<NodeIndex, Error> {
+        match self.mapping.get(fq_name) {
+            Some(&(_, index)) => Ok(index),
+            None => Err(Error::from(ErrorKind::NoClassFound(
                 fq_name.to_fqname_buf(),
-            )));
-        }
-
-        let mut outout = vec![];
-        let mut visited: HashSet<&FQName> = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(fq_name);
-        while let Some(ptr) = queue.pop_front() {
-            if !visited.contains(ptr) {
-                let (_, from_index) = self.mapping[ptr];
-                let inherits = self.graph.edges(from_index);
-                for edge in inherits {
-                    let &inherit = edge.weight();
-                    let to_class = self.get_class(edge.target());
-                    if !visited.contains(to_class.this()) {
-                        outout.push((to_class, inherit));
-                        queue.push_back(to_class.this())
-                    }
+            ))),
+        }
+    }
+
+    fn ancestors_of(&self, root: NodeIndex) -> Vec<(NodeIndex, InheritKind)> {
+        let mut output = vec![];
+        let mut chain = vec![root];
+        while let Some((super_class, _)) = self
+            .declared_edges(*chain.last().unwrap())
+            .into_iter()
+            .find(|&(_, kind)| kind == InheritKind::Extends)
+        {
+            // the graph shouldn't have cycles, but they mustn't hang the walk
+            if chain.contains(&super_class) {
+                break;
+            }
+            chain.push(super_class);
+            output.push((super_class, InheritKind::Extends));
+        }
+
+        // types are visited when they're queued, so each is only listed once
+        let mut visited = chain.iter().copied().collect::<HashSet<_>>();
+        let mut queue = chain.into_iter().collect::<VecDeque<_>>();
+        while let Some(index) = queue.pop_front() {
+            for (interface, kind) in self.declared_edges(index) {
+                if kind == InheritKind::Implements && visited.insert(interface) {
+                    output.push((interface, kind));
+                    queue.push_back(interface);
                 }
+            }
+        }
+        output
+    }
+
+    /// The types a node directly inherits, in the order they're declared
+    fn declared_edges(&self, index: NodeIndex) -> Vec<(NodeIndex, InheritKind)> {
+        let mut edges = self.graph.edges(index).collect::<Vec<_>>();
+        edges.sort_by_key(|edge| edge.id());
+        edges
+            .into_iter()
+            .map(|edge| (edge.target(), *edge.weight()))
+            .collect()
+    }
+
+    /// Finds the most specific type that two classes in the graph both inherit, which is the first
+    /// of the [ancestors](Self::ancestors) of `a`, or `a` itself, that `b` is a
+    /// [subtype](Self::is_subtype_of) of. `java/lang/Object` is only chosen if there's no other
+    /// common super type. Returns `None` if no common super type is known, because the hierarchy
+    /// is cut short by [external](Self::external_types) types.
+    ///
+    /// # Error
+    /// Will return an error if either class isn't in the graph
+    pub fn least_common_supertype<F: AsRef<FQName>, G: AsRef<FQName>>(
+        &self,
+        a: F,
+        b: G,
+    ) -> Result<Option<&FQName>, Error> {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let mut b_supertypes = self
+            .ancestor_indices(b)?
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<HashSet<_>>();
+        b_supertypes.insert(self.mapping[b].1);
+        let candidates = self
+            .ancestor_indices(a)?
+            .into_iter()
+            .map(|(index, _)| index);
+
+        let mut object = None;
+        for candidate in std::iter::once(self.mapping[a].1).chain(candidates) {
+            if !b_supertypes.contains(&candidate) {
+                continue;
+            }
+            let name = self.graph[candidate].as_ref();
+            if name.as_str() == OBJECT {
+                object = Some(name);
+            } else {
+                return Ok(Some(name));
+            }
+        }
+        Ok(object)
+    }
+
+    /// The names of the super types that couldn't be found, in no particular order. Nothing is
+    /// known about what these types inherit.
+    pub fn external_types(&self) -> impl Iterator<Item = &FQName> {
+        self.mapping
+            .iter()
+            .filter(|(_, (class, _))| class.is_missing())
+            .map(|(name, _)| name.as_ref())
+    }
+
+    /// Checks if a class in the graph is a subtype of another type, which is true if it's the same
+    /// type, or it extends or implements the type directly or indirectly. Every class is a subtype
+    /// of `java/lang/Object`.
+    ///
+    /// # Error
+    /// Will return an error if `sub` isn't in the graph
+    pub fn is_subtype_of<F: AsRef<FQName>, G: AsRef<FQName>>(
+        &self,
+        sub: F,
+        sup: G,
+    ) -> Result<bool, Error> {
+        let (sub, sup) = (sub.as_ref(), sup.as_ref());
+        if sub == sup || sup.as_str() == OBJECT {
+            return Ok(true);
+        }
+        Ok(self
+            .ancestors(sub)?
+            .iter()
+            .any(|(class, _)| class.name() == sup))
+    }
+
+    /// The class the graph was created from, which is the first of its [roots](Self::roots)
+    pub fn root(&self) -> &FQName {
+        &self.roots[0]
+    }
+
+    /// Gets the classes whose hierarchies were added to the graph, in the order they were added
+    pub fn roots(&self) -> impl Iterator<Item = &FQName> {
+        self.roots.iter().map(|root| root.as_ref())
+    }
+
+    /// Gets every type in the graph, in the order they were found, starting with the root
+    pub fn types(&self) -> impl Iterator<Item = ClassRef<&JavaClass>> {
+        self.graph.node_indices().map(|index| self.get_class(index))
+    }
+
+    /// Gets every edge in the graph as `(class, inherits, kind)`, in the order they were found
+    pub fn edges(&self) -> impl Iterator<Item = (&FQName, &FQName, InheritKind)> {
+        self.graph.edge_references().map(|edge| {
+            (
+                self.graph[edge.source()].as_ref(),
+                self.graph[edge.target()].as_ref(),
+                *edge.weight(),
+            )
+        })
+    }
+
+    /// Renders the graph in the graphviz dot language. [`Extends`](InheritKind::Extends) edges are
+    /// solid, [`Implements`](InheritKind::Implements) edges are dashed, and
+    /// [external](Self::external_types) types are drawn with a dashed outline.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::inheritance::inspect;
+    /// # use java_class_parser::JavaClassParser;
+    /// let parser = JavaClassParser::new("app.jar");
+    /// let class = parser.find("com/example/Square").unwrap();
+    /// let graph = inspect(&class, &parser).unwrap();
+    /// std::fs::write("hierarchy.dot", graph.to_dot()).unwrap();
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph {\n    node [shape=box];\n");
+        for index in self.graph.node_indices() {
+            let class = self.get_class(index);
+            let style = if class.is_missing() {
+                ", style=dashed"
+            } else if self.roots.iter().any(|root| class.name() == &**root) {
+                ", style=bold"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    n{} [label={}{style}];",
+                index.index(),
+                dot_quoted(class.name().as_str())
+            );
+        }
+        for edge in self.graph.edge_references() {
+            let style = match edge.weight() {
+                InheritKind::Extends => "solid",
+                InheritKind::Implements => "dashed",
+            };
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [style={style}];",
+                edge.source().index(),
+                edge.target().index()
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
 
-                visited.insert(ptr);
+    /// Resolves a method reference to the method it refers to, following the method resolution
+    /// rules of the JVM specification. Returns the method with the class that declares it, or
+    /// `None` if no method with the name and descriptor is found.
+    ///
+    /// For a class, as described in
+    /// [§5.4.3.3](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.3),
+    /// the class and its super classes are searched first, and then the maximally specific methods
+    /// of its super interfaces, preferring a single default method over abstract methods. For an
+    /// interface, as described in
+    /// [§5.4.3.4](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.4),
+    /// the interface is searched first, then the public methods of `java/lang/Object`, and then its
+    /// super interfaces in the same way.
+    ///
+    /// Methods declared by [external](Self::external_types) types can't be found.
+    ///
+    /// # Error
+    /// Will return an error if the class isn't in the graph
+    pub fn resolve_method<F: AsRef<FQName>>(
+        &self,
+        class: F,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<Option<(&JavaClass, Method<'_>)>, Error> {
+        let root = self.index_of(class.as_ref())?;
+        let is_interface = self
+            .class_at(root)
+            .map_or(false, |class| class.access_flags().is_interface());
+        if is_interface {
+            if let Some(found) = self.declared_method(root, name, descriptor) {
+                return Ok(Some(found));
+            }
+            let object = self
+                .declared_edges(root)
+                .into_iter()
+                .find(|&(_, kind)| kind == InheritKind::Extends)
+                .and_then(|(object, _)| self.declared_method(object, name, descriptor))
+                .filter(|(_, method)| {
+                    method.access_flags().is_public() && !method.access_flags().is_static()
+                });
+            if object.is_some() {
+                return Ok(object);
+            }
+        } else {
+            let mut chain = vec![root];
+            chain.extend(
+                self.ancestors_of(root)
+                    .into_iter()
+                    .take_while(|&(_, kind)| kind == InheritKind::Extends)
+                    .map(|(index, _)| index),
+            );
+            for index in chain {
+                if let Some(found) = self.declared_method(index, name, descriptor) {
+                    return Ok(Some(found));
+                }
             }
         }
+        Ok(self.maximally_specific_method(root, name, descriptor))
+    }
+
+    /// Finds the methods of the ancestors of a class that a method declared by the class
+    /// overrides, in the order of its [ancestors](Self::ancestors). Overriding follows
+    /// [§5.4.5](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.5) of the
+    /// JVM specification: private, static and initialization methods never override or are
+    /// overridden, and package private methods are only overridden in the same package.
+    ///
+    /// # Error
+    /// Will return an error if the class isn't in the graph
+    pub fn overrides_of<F: AsRef<FQName>>(
+        &self,
+        class: F,
+        method: &Method,
+    ) -> Result<Vec<(&JavaClass, Method<'_>)>, Error> {
+        let class = class.as_ref();
+        let root = self.index_of(class)?;
+        let flags = method.access_flags();
+        if flags.is_private() || flags.is_static() || method.name().starts_with('<') {
+            return Ok(vec![]);
+        }
+        let descriptor = method.signature().jni();
+        Ok(self
+            .ancestors_of(root)
+            .into_iter()
+            .filter_map(|(index, _)| self.declared_method(index, method.name(), &descriptor))
+            .filter(|(ancestor, overridden)| {
+                let flags = overridden.access_flags();
+                !flags.is_private()
+                    && !flags.is_static()
+                    && (flags.is_public()
+                        || flags.is_protected()
+                        || ancestor.this().package() == class.package())
+            })
+            .collect())
+    }
+
+    /// Gets the class of a node, unless it's external
+    fn class_at(&self, index: NodeIndex) -> Option<&JavaClass> {
+        let (class, _) = &self.mapping[&*self.graph[index]];
+        class.resolved()
+    }
+
+    /// Finds a method declared by the class of a node
+    fn declared_method(
+        &self,
+        index: NodeIndex,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<(&JavaClass, Method<'_>)> {
+        let class = self.class_at(index)?;
+        class
+            .methods()
+            .into_iter()
+            .find(|method| method.name() == name && method.signature().jni() == descriptor)
+            .map(|method| (class, method))
+    }
+
+    /// Finds the maximally specific super interface method of a class, which is a method declared
+    /// by a super interface that isn't overridden by a method declared by one of its sub
+    /// interfaces. A single maximally specific method that isn't abstract is chosen, and otherwise
+    /// the first super interface method.
+    fn maximally_specific_method(
+        &self,
+        root: NodeIndex,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<(&JavaClass, Method<'_>)> {
+        let candidates = self
+            .ancestors_of(root)
+            .into_iter()
+            .filter(|&(_, kind)| kind == InheritKind::Implements)
+            .filter_map(|(index, _)| {
+                let (class, method) = self.declared_method(index, name, descriptor)?;
+                let flags = method.access_flags();
+                (!flags.is_private() && !flags.is_static()).then_some((index, class, method))
+            })
+            .collect::<Vec<_>>();
+        let maximally_specific = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, (index, _, _))| {
+                !candidates.iter().any(|(other, _, _)| {
+                    other != index
+                        && self
+                            .ancestors_of(*other)
+                            .iter()
+                            .any(|(ancestor, _)| ancestor == index)
+                })
+            })
+            .filter(|(_, (_, _, method))| !method.access_flags().is_abstract())
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        let chosen = match maximally_specific[..] {
+            [position] => position,
+            _ => 0,
+        };
+        candidates
+            .into_iter()
+            .nth(chosen)
+            .map(|(_, class, method)| (class, method))
+    }
 
-        Ok(outout)
+    /// Checks if every super type was found, so the graph is the full hierarchy of the root class
+    pub fn is_complete(&self) -> bool {
+        self.external_types().next().is_none()
     }
 }
 
-/// Inspects a class to create an inheritance graph
+/// Inspects a class to create an inheritance graph. Super types that the parser can't find are
+/// added as [external](InheritanceGraph::external_types) types.
 pub fn inspect(class: &JavaClass, parser: &JavaClassParser) -> Result<InheritanceGraph, Error> {
-    let mut graph = InheritanceGraph::new(class.clone());
+    build(class, |name| find(parser, name))
+}
+
+/// Inspects a class to create an inheritance graph that reaches `java/lang/Object`. Super types
+/// that the parser can't find are looked for in the JDK at `JAVA_HOME`, or found on the path, even
+/// if the parser wasn't created [with it](JavaClassParser::with_jdk).
+///
+/// # Error
+/// Will return an error if a super type can't be found by the parser and no JDK can be found
+pub fn inspect_complete(
+    class: &JavaClass,
+    parser: &JavaClassParser,
+) -> Result<InheritanceGraph, Error> {
+    build(class, find_complete(parser))
+}
+
+/// Builds a single inheritance graph of many root classes. Ancestors that the roots have in
+/// common are only walked once, so the hierarchies of every class in a jar can be analyzed
+/// together without walking `java/lang/Object` and the interfaces of `java/util` for each class.
+///
+/// # Example
+/// ```no_run
+/// # use java_class_parser::inheritance::GraphBuilder;
+/// # use java_class_parser::JavaClassParser;
+/// let parser = JavaClassParser::new("app.jar");
+/// let graph = GraphBuilder::new(&parser)
+///     .roots(parser.parse_all().collect::<Result<Vec<_>, _>>().unwrap())
+///     .build()
+///     .unwrap()
+///     .expect("the jar has no classes");
+/// println!("{}", graph.to_dot());
+/// ```
+#[derive(Debug)]
+pub struct GraphBuilder<'a> {
+    parser: &'a JavaClassParser,
+    roots: Vec<JavaClass>,
+    complete: bool,
+}
+
+impl<'a> GraphBuilder<'a> {
+    /// Creates a builder with no roots, that finds classes with a parser
+    pub fn new(parser: &'a JavaClassParser) -> Self {
+        Self {
+            parser,
+            roots: vec![],
+            complete: false,
+        }
+    }
+
+    /// Adds a root class
+    pub fn root(mut self, class: JavaClass) -> Self {
+        self.roots.push(class);
+        self
+    }
+
+    /// Adds many root classes
+    pub fn roots<I: IntoIterator<Item = JavaClass>>(mut self, classes: I) -> Self {
+        self.roots.extend(classes);
+        self
+    }
+
+    /// Sets whether super types the parser can't find are looked for in the JDK, like
+    /// [`inspect_complete`]. Defaults to false.
+    pub fn complete(mut self, complete: bool) -> Self {
+        self.complete = complete;
+        self
+    }
+
+    /// Builds the graph, or returns `None` if no roots were added
+    ///
+    /// # Error
+    /// Will return an error if a super type can't be parsed, or, if the graph should be
+    /// [complete](Self::complete), no JDK can be found when one is needed
+    pub fn build(self) -> Result<Option<InheritanceGraph>, Error> {
+        if self.roots.is_empty() {
+            return Ok(None);
+        }
+        let mut graph = InheritanceGraph::new();
+        if self.complete {
+            let mut find = find_complete(self.parser);
+            for class in &self.roots {
+                graph.add_root(class, &mut find)?;
+            }
+        } else {
+            for class in &self.roots {
+                graph.add_root(class, |name| find(self.parser, name))?;
+            }
+        }
+        Ok(Some(graph))
+    }
+}
+
+/// Checks if a value of type `sub` can be assigned to a variable of type `sup`, following the
+/// subtyping rules of the JLS.
+///
+/// - A class is assignable to itself, its super classes and the interfaces it implements, and
+///   every class and interface is assignable to `java/lang/Object`.
+/// - An array is assignable to `java/lang/Object`, `java/lang/Cloneable` and
+///   `java/io/Serializable`, and to arrays with an assignable component type if its component
+///   type is a reference type. Arrays of primitives are only assignable to the same array type.
+/// - A primitive is assignable to the primitives it can be widened to, such as `int` to `long`.
+///   Boxing isn't considered, so primitives and reference types are never assignable to each
+///   other.
+pub(crate) fn is_assignable(
+    parser: &JavaClassParser,
+    sub: &FQName,
+    sup: &FQName,
+) -> Result<bool, Error> {
+    let (sub, sup) = (sub.canonicalize(), sup.canonicalize());
+    is_signature_assignable(parser, &signature_of(&sub), &signature_of(&sup))
+}
+
+fn signature_of(name: &FQName) -> Signature<'_> {
+    match name.as_type() {
+        NamedType::Class(class) => Signature::FullyQualifiedClass(class.as_str()),
+        NamedType::Array(signature) | NamedType::Primitive(signature) => signature,
+    }
+}
+
+fn is_signature_assignable(
+    parser: &JavaClassParser,
+    sub: &Signature,
+    sup: &Signature,
+) -> Result<bool, Error> {
+    use Signature::*;
+    match (sub, sup) {
+        (FullyQualifiedClass(sub), FullyQualifiedClass(sup)) => {
+            is_subclass(parser, FQName::new(sub), FQName::new(sup))
+        }
+        (Array(_), FullyQualifiedClass(sup)) => Ok(matches!(
+            *sup,
+            OBJECT | "java/lang/Cloneable" | "java/io/Serializable"
+        )),
+        (Array(sub), Array(sup)) => match (&**sub, &**sup) {
+            (FullyQualifiedClass(_) | Array(_), FullyQualifiedClass(_) | Array(_)) => {
+                is_signature_assignable(parser, sub, sup)
+            }
+            (sub, sup) => Ok(sub == sup),
+        },
+        (Method { .. }, _) | (_, Method { .. }) => Ok(false),
+        // primitives and reference types aren't assignable to each other
+        (FullyQualifiedClass(_) | Array(_), _) | (_, FullyQualifiedClass(_) | Array(_)) => {
+            Ok(false)
+        }
+        (sub, sup) => Ok(sub == sup || widens_to(sub, sup)),
+    }
+}
 
-    let mut stack = vec![];
-    stack.push(class.clone());
+/// Checks if there's a widening primitive conversion from one primitive to another
+fn widens_to(sub: &Signature, sup: &Signature) -> bool {
+    use Signature::*;
+    match sub {
+        Byte => matches!(sup, Short | Int | Long | Float | Double),
+        Short | Char => matches!(sup, Int | Long | Float | Double),
+        Int => matches!(sup, Long | Float | Double),
+        Long => matches!(sup, Float | Double),
+        Float => matches!(sup, Double),
+        _ => false,
+    }
+}
+
+/// Checks if a class extends or implements another class, directly or indirectly. Super types that
+/// can't be found are only an error if `sup` isn't found through another super type.
+fn is_subclass(parser: &JavaClassParser, sub: &FQName, sup: &FQName) -> Result<bool, Error> {
+    if sub == sup || sup.as_str() == OBJECT {
+        return Ok(true);
+    }
+    let mut visited = HashSet::from([sub.to_fqname_buf()]);
+    let mut stack = vec![parser.find(sub)?];
+    let mut missing = None;
     while let Some(class) = stack.pop() {
-        let super_class = match parser.find_super(&class) {
-            Ok(o) => Some(o),
-            Err(e) => {
-                if let ErrorKind::NoClassFound(_) = e.kind() {
-                    None
-                } else {
-                    return Err(e);
+        let mut supers = class.interfaces();
+        supers.extend(class.super_class());
+        for name in supers {
+            if name == sup {
+                return Ok(true);
+            }
+            if visited.insert(name.to_fqname_buf()) {
+                match find(parser, name)? {
+                    Some(class) => stack.push(class),
+                    None => missing = missing.or_else(|| Some(name.to_fqname_buf())),
                 }
             }
+        }
+    }
+    match missing {
+        Some(name) => Err(ErrorKind::NoClassFound(name).into()),
+        None => Ok(false),
+    }
+}
+
+/// A method of a class, either declared by the class or inherited from one of its ancestors.
+/// Created by [`JavaClassParser::all_methods`].
+#[derive(Debug, Clone)]
+pub struct ClassMethod {
+    declaring_class: JavaClass,
+    index: usize,
+    inherited: bool,
+}
+
+impl ClassMethod {
+    /// The class that declares the method
+    pub fn declaring_class(&self) -> &JavaClass {
+        &self.declaring_class
+    }
+
+    /// Checks if the method is inherited, rather than declared by the class itself
+    pub fn is_inherited(&self) -> bool {
+        self.inherited
+    }
+
+    /// The method
+    pub fn method(&self) -> Method<'_> {
+        Method::new(
+            &self.declaring_class.raw().methods[self.index],
+            &self.declaring_class,
+        )
+    }
+}
+
+/// A field of a class, either declared by the class or inherited from one of its ancestors.
+/// Created by [`JavaClassParser::all_fields`].
+#[derive(Debug, Clone)]
+pub struct ClassField {
+    declaring_class: JavaClass,
+    index: usize,
+    inherited: bool,
+}
+
+impl ClassField {
+    /// The class that declares the field
+    pub fn declaring_class(&self) -> &JavaClass {
+        &self.declaring_class
+    }
+
+    /// Checks if the field is inherited, rather than declared by the class itself
+    pub fn is_inherited(&self) -> bool {
+        self.inherited
+    }
+
+    /// The field
+    pub fn field(&self) -> Field<'_> {
+        Field::new(
+            &self.declaring_class.raw().fields[self.index],
+            &self.declaring_class,
+        )
+    }
+}
+
+/// Gets the methods of a class, which are its declared methods followed by the methods it
+/// inherits from its ancestors, in the order of its [ancestors](InheritanceGraph::ancestors).
+///
+/// Constructors, initializers, private methods, static interface methods and package private
+/// methods of other packages aren't inherited, and a method isn't inherited if a method with the
+/// same name and descriptor was found before it, because it's overridden or hidden.
+pub(crate) fn all_methods(
+    parser: &JavaClassParser,
+    class: &JavaClass,
+) -> Result<Vec<ClassMethod>, Error> {
+    let graph = inspect(class, parser)?;
+    let mut seen = HashSet::new();
+    let mut output = vec![];
+    for (index, method) in class.methods().iter().enumerate() {
+        seen.insert((method.name(), method.signature().jni()));
+        output.push(ClassMethod {
+            declaring_class: class.clone(),
+            index,
+            inherited: false,
+        });
+    }
+    for (ancestor, _) in graph.ancestors(class.this())? {
+        let Some(ancestor) = ancestor.resolved() else {
+            continue;
+        };
+        let is_interface = ancestor.access_flags().is_interface();
+        for (index, method) in ancestor.methods().iter().enumerate() {
+            let flags = method.access_flags();
+            let inherited = !method.name().starts_with('<')
+                && !flags.is_private()
+                && (!is_interface || !flags.is_static())
+                && is_accessible(class, ancestor, flags.is_public() || flags.is_protected());
+            if inherited && seen.insert((method.name(), method.signature().jni())) {
+                output.push(ClassMethod {
+                    declaring_class: (*ancestor).clone(),
+                    index,
+                    inherited: true,
+                });
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Gets the fields of a class, which are its declared fields followed by the fields it inherits
+/// from its ancestors, in the order of its [ancestors](InheritanceGraph::ancestors).
+///
+/// Private fields and package private fields of other packages aren't inherited, and a field
+/// isn't inherited if a field with the same name was found before it, because it's shadowed.
+pub(crate) fn all_fields(
+    parser: &JavaClassParser,
+    class: &JavaClass,
+) -> Result<Vec<ClassField>, Error> {
+    let graph = inspect(class, parser)?;
+    let mut seen = HashSet::new();
+    let mut output = vec![];
+    for (index, field) in class.fields().iter().enumerate() {
+        seen.insert(field.name());
+        output.push(ClassField {
+            declaring_class: class.clone(),
+            index,
+            inherited: false,
+        });
+    }
+    for (ancestor, _) in graph.ancestors(class.this())? {
+        let Some(ancestor) = ancestor.resolved() else {
+            continue;
         };
-        if let Some(super_class) = super_class {
-            let super_class_name = super_class.this().to_fqname_buf();
-            if graph.add_class(super_class.clone()) {
-                stack.push(super_class);
+        for (index, field) in ancestor.fields().iter().enumerate() {
+            let flags = field.access_flags();
+            let inherited = !flags.is_private()
+                && is_accessible(class, ancestor, flags.is_public() || flags.is_protected());
+            if inherited && seen.insert(field.name()) {
+                output.push(ClassField {
+                    declaring_class: (*ancestor).clone(),
+                    index,
+                    inherited: true,
+                });
             }
-            if !graph.add_inheritance(class.this(), &super_class_name, InheritKind::Extends) {
-                return Err(Error::new(ErrorKind::AddingInheritanceFailed(
-                    class.this().to_fqname_buf(),
-                )));
+        }
+    }
+    Ok(output)
+}
+
+/// Checks if a member of an ancestor that isn't private can be inherited by a class. Package
+/// private members are only inherited in the same package.
+fn is_accessible(class: &JavaClass, ancestor: &JavaClass, public_or_protected: bool) -> bool {
+    public_or_protected || class.this().package() == ancestor.this().package()
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for InheritKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            InheritKind::Extends => serializer.serialize_unit_variant("InheritKind", 0, "Extends"),
+            InheritKind::Implements => {
+                serializer.serialize_unit_variant("InheritKind", 1, "Implements")
             }
         }
-        let interfaces = parser.find_interfaces(&class)?;
-        for interface in interfaces {
-            let interface_name = interface.this().to_fqname_buf();
-            if graph.add_class(interface.clone()) {
-                stack.push(interface);
+    }
+}
+
+/// Serialized as the `root` class, every class in `roots`, the `types` in the graph with their `name` and whether they're
+/// `external`, and the `edges` of the graph with the `class` that `inherits` a type and their
+/// `kind`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for InheritanceGraph {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Type<'a>(ClassRef<&'a JavaClass>);
+        impl serde::Serialize for Type<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut node = serializer.serialize_struct("Type", 2)?;
+                node.serialize_field("name", self.0.name())?;
+                node.serialize_field("external", &self.0.is_missing())?;
+                node.end()
             }
-            if !graph.add_inheritance(class.this(), &interface_name, InheritKind::Implements) {
-                return Err(Error::new(ErrorKind::AddingInheritanceFailed(
-                    class.this().to_fqname_buf(),
-                )));
+        }
+
+        struct Edge<'a>(&'a FQName, &'a FQName, InheritKind);
+        impl serde::Serialize for Edge<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut edge = serializer.serialize_struct("Edge", 3)?;
+                edge.serialize_field("class", self.0)?;
+                edge.serialize_field("inherits", self.1)?;
+                edge.serialize_field("kind", &self.2)?;
+                edge.end()
             }
         }
+
+        let types = self.types().map(Type).collect::<Vec<_>>();
+        let edges = self
+            .edges()
+            .map(|(class, inherits, kind)| Edge(class, inherits, kind))
+            .collect::<Vec<_>>();
+        let mut graph = serializer.serialize_struct("InheritanceGraph", 4)?;
+        graph.serialize_field("root", self.root())?;
+        graph.serialize_field("roots", &self.roots)?;
+        graph.serialize_field("types", &types)?;
+        graph.serialize_field("edges", &edges)?;
+        graph.end()
+    }
+}
+
+/// Finds a class, or `None` if it isn't on the classpath
+fn find(parser: &JavaClassParser, name: &FQName) -> Result<Option<JavaClass>, Error> {
+    match parser.find(name) {
+        Ok(class) => Ok(Some(class)),
+        Err(e) if matches!(e.kind(), ErrorKind::NoClassFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Finds a class with a parser, or in the JDK if the parser can't find it. The JDK is only located
+/// the first time it's needed.
+fn find_complete(
+    parser: &JavaClassParser,
+) -> impl FnMut(&FQName) -> Result<Option<JavaClass>, Error> + '_ {
+    let mut jdk = None;
+    move |name| {
+        if let Some(class) = find(parser, name)? {
+            return Ok(Some(class));
+        }
+        let jdk = match &jdk {
+            Some(jdk) => jdk,
+            None => jdk.insert(JImage::locate()?),
+        };
+        jdk.find_class(name).transpose()
     }
+}
 
+fn build<F>(class: &JavaClass, find: F) -> Result<InheritanceGraph, Error>
+where
+    F: FnMut(&FQName) -> Result<Option<JavaClass>, Error>,
+{
+    let mut graph = InheritanceGraph::new();
+    graph.add_root(class, find)?;
     Ok(graph)
 }
+
+/// An index of the super types of every class on a classpath, which answers which types extend or
+/// implement a type. Unlike an [`InheritanceGraph`], which walks up from a single class, the index
+/// is walked down from super types. Super types don't need to be on the classpath themselves, so
+/// the subclasses of `java/lang/Object` can be found without the JDK.
+#[derive(Debug, Default)]
+pub struct InheritanceIndex {
+    /// Whether every indexed class is an interface
+    interfaces: HashMap<FQNameBuf, bool>,
+    /// The types that directly extend or implement a type, in the order they were indexed
+    subtypes: HashMap<FQNameBuf, Vec<(FQNameBuf, InheritKind)>>,
+}
+
+impl InheritanceIndex {
+    /// Indexes every class on the classpath of a parser
+    ///
+    /// # Error
+    /// Will return an error if a class on the classpath can't be read or parsed
+    pub fn build(parser: &JavaClassParser) -> Result<Self, Error> {
+        let mut index = Self::default();
+        for class in parser.parse_all() {
+            index.insert(&class?);
+        }
+        Ok(index)
+    }
+
+    /// Adds a class to the index. Classes that have already been indexed are ignored.
+    pub fn insert(&mut self, class: &JavaClass) {
+        let name = class.this().to_fqname_buf();
+        if self.interfaces.contains_key(&name) {
+            return;
+        }
+        self.interfaces
+            .insert(name.clone(), class.access_flags().is_interface());
+        if let Some(super_class) = class.super_class() {
+            self.subtypes
+                .entry(super_class.to_fqname_buf())
+                .or_default()
+                .push((name.clone(), InheritKind::Extends));
+        }
+        for interface in class.interfaces() {
+            self.subtypes
+                .entry(interface.to_fqname_buf())
+                .or_default()
+                .push((name.clone(), InheritKind::Implements));
+        }
+    }
+
+    /// Checks if a class has been indexed
+    pub fn contains<P: AsFullyQualifiedName + ?Sized>(&self, fqn: &P) -> bool {
+        self.interfaces
+            .contains_key(fqn.as_fcq().canonicalize().as_ref())
+    }
+
+    /// The number of indexed classes
+    pub fn len(&self) -> usize {
+        self.interfaces.len()
+    }
+
+    /// Checks if no classes have been indexed
+    pub fn is_empty(&self) -> bool {
+        self.interfaces.is_empty()
+    }
+
+    /// Gets the types that directly extend or implement a type. Interfaces that extend an
+    /// interface [implement](InheritKind::Implements) it, like in class files.
+    pub fn direct_subtypes<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        fqn: &P,
+    ) -> impl Iterator<Item = (&FQName, InheritKind)> {
+        self.subtypes
+            .get(fqn.as_fcq().canonicalize().as_ref())
+            .into_iter()
+            .flatten()
+            .map(|(name, kind)| (name.as_ref(), *kind))
+    }
+
+    /// Gets every class that extends a class, directly or indirectly, in breadth first order
+    pub fn subclasses_of<P: AsFullyQualifiedName + ?Sized>(&self, fqn: &P) -> Vec<&FQName> {
+        self.walk_down(&fqn.as_fcq().canonicalize(), |kind| {
+            kind == InheritKind::Extends
+        })
+        .into_iter()
+        .filter(|name| !self.is_interface(name))
+        .collect()
+    }
+
+    /// Gets every class that implements an interface, directly, through another interface, or by
+    /// extending a class that implements it, in breadth first order. Interfaces aren't included.
+    pub fn implementors_of<P: AsFullyQualifiedName + ?Sized>(&self, fqn: &P) -> Vec<&FQName> {
+        self.walk_down(&fqn.as_fcq().canonicalize(), |_| true)
+            .into_iter()
+            .filter(|name| !self.is_interface(name))
+            .collect()
+    }
+
+    /// Gets every type that extends or implements a type, directly or indirectly, in breadth
+    /// first order. Unlike [`implementors_of`](Self::implementors_of), interfaces are included.
+    pub fn subtypes_of<P: AsFullyQualifiedName + ?Sized>(&self, fqn: &P) -> Vec<&FQName> {
+        self.walk_down(&fqn.as_fcq().canonicalize(), |_| true)
+    }
+
+    fn is_interface(&self, name: &FQName) -> bool {
+        self.interfaces.get(name).copied().unwrap_or(false)
+    }
+
+    /// Walks down the edges that pass a filter, in breadth first order
+    fn walk_down<'a, P: Fn(InheritKind) -> bool>(
+        &'a self,
+        fqn: &FQName,
+        filter: P,
+    ) -> Vec<&'a FQName> {
+        let mut output = vec![];
+        let mut visited = HashSet::from([fqn]);
+        let mut queue = VecDeque::from([fqn]);
+        while let Some(name) = queue.pop_front() {
+            let subtypes = self.subtypes.get(name).into_iter().flatten();
+            for (subtype, kind) in subtypes.map(|(name, kind)| (name.as_ref(), *kind)) {
+                if filter(kind) && visited.insert(subtype) {
+                    output.push(subtype);
+                    queue.push_back(subtype);
+                }
+            }
+        }
+        output
+    }
+}
@@ -0,0 +1,739 @@
+//! Renders a class as text, in a format closely matching the output of `javap -c -p -v`.
+//!
+//! The output contains the header of the class, its constant pool, its fields, and its methods
+//! with their disassembled code, exception tables, line number tables and local variable tables.
+//! Constant pool indices are followed by a comment describing the constant they refer to.
+//! Less of the class can be rendered with [`JavapOptions`], which mirror the flags of `javap`.
+//!
+//! ```text
+//! public class com.example.Square extends com.example.Rectangle implements java.lang.Comparable
+//!   minor version: 0
+//!   major version: 61
+//!   flags: (0x0021) ACC_PUBLIC, ACC_SUPER
+//! ...
+//!   public com.example.Square(double);
+//!     descriptor: (D)V
+//!     flags: (0x0001) ACC_PUBLIC
+//!     Code:
+//!       stack=5, locals=3, args_size=2
+//!          0: aload_0
+//!          1: dload_1
+//!          2: dload_1
+//!          3: invokespecial #1                  // Method com/example/Rectangle."<init>":(DD)V
+//!          6: return
+//! ```
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! # use java_class_parser::javap::JavapOptions;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Square").unwrap();
+//! println!("{}", class.javap());
+//! println!("{}", class.javap_with(JavapOptions::new().code(true)));
+//! ```
+
+use crate::attributes::{AttributeKind, Code, JavaConstant, LineNumberTable, SourceFile};
+use crate::bytecode::{Instruction, Operands};
+use crate::constant_pool::ConstantPoolInfo;
+use crate::dynamic::{MethodHandle, ReferenceKind};
+use crate::{
+    Field, FieldAccessFlags, HasAttributes, JavaClass, MemberRef, Method, MethodAccessFlags,
+    ResolvedConstant, Signature,
+};
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// What's rendered of a class, matching the flags of `javap`. Without any options, only the
+/// declarations of the class and its non-private members are rendered.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct JavapOptions {
+    private: bool,
+    code: bool,
+    verbose: bool,
+}
+
+impl JavapOptions {
+    /// Creates options matching `javap` without any flags
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates options matching `javap -c -p -v`
+    pub fn all() -> Self {
+        Self {
+            private: true,
+            code: true,
+            verbose: true,
+        }
+    }
+
+    /// Whether private members are rendered, like `javap -p`
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+
+    /// Whether the code of methods is disassembled, like `javap -c`
+    pub fn code(mut self, code: bool) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Whether the constant pool, descriptors, flags, line number tables, local variable tables
+    /// and attributes are rendered, like `javap -v`. Verbose output includes the code of methods.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    fn shows_code(&self) -> bool {
+        self.code || self.verbose
+    }
+}
+
+/// Renders a class in the format of `javap` when displayed. Created by [`JavaClass::javap`] and
+/// [`JavaClass::javap_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct Javap<'a> {
+    class: &'a JavaClass,
+    options: JavapOptions,
+}
+
+impl Display for Javap<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let options = &self.options;
+        write_header(f, self.class, options)?;
+        if options.verbose {
+            write_constant_pool(f, self.class)?;
+            writeln!(f, "{{")?;
+        }
+        // javap only separates members by blank lines when it shows more than their declarations
+        let separated = options.shows_code();
+        let mut first = true;
+        for field in self.class.fields() {
+            if field.access_flags().is_private() && !options.private {
+                continue;
+            }
+            if !first && separated {
+                writeln!(f)?;
+            }
+            first = false;
+            write_field(f, &field, options)?;
+        }
+        for method in self.class.methods() {
+            if method.access_flags().is_private() && !options.private {
+                continue;
+            }
+            if !first && separated {
+                writeln!(f)?;
+            }
+            first = false;
+            write_method(f, self.class, &method, options)?;
+        }
+        writeln!(f, "}}")?;
+        if options.verbose {
+            write_class_attributes(f, self.class)?;
+        }
+        Ok(())
+    }
+}
+
+/// Disassembly
+impl JavaClass {
+    /// Renders the class in the format of `javap -c -p -v` when displayed
+    pub fn javap(&self) -> Javap<'_> {
+        self.javap_with(JavapOptions::all())
+    }
+
+    /// Renders the class in the format of `javap` with the given options when displayed
+    pub fn javap_with(&self, options: JavapOptions) -> Javap<'_> {
+        Javap {
+            class: self,
+            options,
+        }
+    }
+
+    /// Renders the class in the format of `javap -c -p -v`
+    pub fn to_javap_string(&self) -> String {
+        self.javap().to_string()
+    }
+}
+
+fn write_header(f: &mut Formatter<'_>, class: &JavaClass, options: &JavapOptions) -> Result {
+    if let Some(source_file) = class.get::<SourceFile>() {
+        let indent = if options.verbose { "  " } else { "" };
+        writeln!(
+            f,
+            "{indent}Compiled from \"{}\"",
+            source_file.path().display()
+        )?;
+    }
+    let flags = class.access_flags();
+    let super_class = class
+        .constant_pool()
+        .get_class_name(class.header().super_class);
+    let interfaces = class
+        .interfaces()
+        .iter()
+        .map(|interface| java_name(interface.as_str()))
+        .collect::<Vec<_>>()
+        .join(",");
+    // without the constant pool in between, the body opens on the same line as the declaration
+    let open = if options.verbose { "" } else { " {" };
+    if flags.is_module() {
+        let name = class.as_module().map(|module| module.name().to_string());
+        writeln!(f, "module {}{open}", name.unwrap_or_default())?;
+    } else {
+        let mut declaration = String::new();
+        if flags.is_public() {
+            declaration.push_str("public ");
+        }
+        if flags.is_abstract() && !flags.is_interface() {
+            declaration.push_str("abstract ");
+        }
+        if flags.is_final() {
+            declaration.push_str("final ");
+        }
+        if flags.is_interface() {
+            declaration.push_str("interface ");
+        } else {
+            declaration.push_str("class ");
+        }
+        declaration.push_str(&java_name(class.this().as_str()));
+        if flags.is_interface() {
+            if !interfaces.is_empty() {
+                let _ = write!(declaration, " extends {interfaces}");
+            }
+        } else {
+            if let Some(super_class) = super_class.filter(|name| *name != "java/lang/Object") {
+                let _ = write!(declaration, " extends {}", java_name(super_class.as_str()));
+            }
+            if !interfaces.is_empty() {
+                let _ = write!(declaration, " implements {interfaces}");
+            }
+        }
+        writeln!(f, "{declaration}{open}")?;
+    }
+    if !options.verbose {
+        return Ok(());
+    }
+
+    let (major, minor) = class.class_file_version();
+    writeln!(f, "  minor version: {minor}")?;
+    writeln!(f, "  major version: {major}")?;
+    writeln!(
+        f,
+        "  flags: {}",
+        flags_string(flags.bits(), flags.iter_names().map(|(name, _)| name))
+    )?;
+    writeln!(
+        f,
+        "  {:<40}// {}",
+        format!("this_class: #{}", class.header().this_class),
+        class.this()
+    )?;
+    match super_class {
+        Some(super_class) => writeln!(
+            f,
+            "  {:<40}// {}",
+            format!("super_class: #{}", class.header().super_class),
+            super_class
+        )?,
+        None => writeln!(f, "  super_class: #0")?,
+    }
+    writeln!(
+        f,
+        "  interfaces: {}, fields: {}, methods: {}, attributes: {}",
+        class.raw().interfaces.len(),
+        class.raw().fields.len(),
+        class.raw().methods.len(),
+        class.raw().attributes.len()
+    )
+}
+
+fn write_constant_pool(f: &mut Formatter<'_>, class: &JavaClass) -> Result {
+    writeln!(f, "Constant pool:")?;
+    for (index, info) in class.constant_pool().iter() {
+        let (tag, operands) = match info {
+            ConstantPoolInfo::Class(class) => ("Class", format!("#{}", class.name_index)),
+            ConstantPoolInfo::FieldRef(field) => (
+                "Fieldref",
+                format!("#{}.#{}", field.class_index, field.name_and_type_index),
+            ),
+            ConstantPoolInfo::MethodRef(method) => (
+                "Methodref",
+                format!("#{}.#{}", method.class_index, method.name_and_type_index),
+            ),
+            ConstantPoolInfo::InterfaceMethodRef(method) => (
+                "InterfaceMethodref",
+                format!("#{}.#{}", method.class_index, method.name_and_type_index),
+            ),
+            ConstantPoolInfo::String(string) => ("String", format!("#{}", string.string_index)),
+            ConstantPoolInfo::Integer(integer) => ("Integer", format!("{}", integer.int as i32)),
+            ConstantPoolInfo::Float(float) => ("Float", format!("{:?}f", float.float)),
+            ConstantPoolInfo::Long(long) => ("Long", format!("{}l", long.long as i64)),
+            ConstantPoolInfo::Double(double) => ("Double", format!("{:?}d", double.double)),
+            ConstantPoolInfo::NameAndType(name_and_type) => (
+                "NameAndType",
+                format!(
+                    "#{}:#{}",
+                    name_and_type.name_index, name_and_type.descriptor_index
+                ),
+            ),
+            ConstantPoolInfo::Utf8(utf8) => ("Utf8", utf8.as_str().map(escape).unwrap_or_default()),
+            ConstantPoolInfo::MethodHandle(handle) => (
+                "MethodHandle",
+                format!("{}:#{}", handle.reference_kind, handle.reference_index),
+            ),
+            ConstantPoolInfo::MethodType(method_type) => {
+                ("MethodType", format!("#{}", method_type.descriptor_index))
+            }
+            ConstantPoolInfo::Dynamic(dynamic) => (
+                "Dynamic",
+                format!(
+                    "#{}:#{}",
+                    dynamic.bootstrap_method_attr_index, dynamic.name_and_type_index
+                ),
+            ),
+            ConstantPoolInfo::InvokeDynamic(dynamic) => (
+                "InvokeDynamic",
+                format!(
+                    "#{}:#{}",
+                    dynamic.bootstrap_method_attr_index, dynamic.name_and_type_index
+                ),
+            ),
+            ConstantPoolInfo::Module(module) => ("Module", format!("#{}", module.name_index)),
+            ConstantPoolInfo::Package(package) => ("Package", format!("#{}", package.name_index)),
+        };
+        let line = format!("{:>5} = {:<18} {}", format!("#{index}"), tag, operands);
+        let comment = match class.constant(index) {
+            Some(ResolvedConstant::Utf8(_))
+            | Some(ResolvedConstant::Integer(_))
+            | Some(ResolvedConstant::Float(_))
+            | Some(ResolvedConstant::Long(_))
+            | Some(ResolvedConstant::Double(_))
+            | None => None,
+            Some(constant) => describe(class, &constant, false),
+        };
+        match comment {
+            Some(comment) => writeln!(f, "{line:<42}// {comment}")?,
+            None => writeln!(f, "{line}")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_field(f: &mut Formatter<'_>, field: &Field, options: &JavapOptions) -> Result {
+    let flags = field.access_flags();
+    let mut modifiers = String::new();
+    for (present, modifier) in [
+        (flags.is_public(), "public "),
+        (flags.is_private(), "private "),
+        (flags.is_protected(), "protected "),
+        (flags.is_static(), "static "),
+        (flags.is_final(), "final "),
+        (flags.contains(FieldAccessFlags::VOLATILE), "volatile "),
+        (flags.contains(FieldAccessFlags::TRANSIENT), "transient "),
+    ] {
+        if present {
+            modifiers.push_str(modifier);
+        }
+    }
+    writeln!(
+        f,
+        "  {}{} {};",
+        modifiers,
+        field.signature().to_java(),
+        field.name()
+    )?;
+    if !options.verbose {
+        return Ok(());
+    }
+    writeln!(f, "    descriptor: {}", field.signature().jni())?;
+    writeln!(
+        f,
+        "    flags: {}",
+        flags_string(flags.bits(), flags.iter_names().map(|(name, _)| name))
+    )?;
+    if let Some(value) = field.constant_value() {
+        let value = match value {
+            JavaConstant::Integer(value) => format!("int {value}"),
+            JavaConstant::Float(value) => format!("float {value:?}f"),
+            JavaConstant::Long(value) => format!("long {value}l"),
+            JavaConstant::Double(value) => format!("double {value:?}d"),
+            JavaConstant::String(value) => format!("String {}", escape(value)),
+        };
+        writeln!(f, "    ConstantValue: {value}")?;
+    }
+    Ok(())
+}
+
+fn write_method(
+    f: &mut Formatter<'_>,
+    class: &JavaClass,
+    method: &Method,
+    options: &JavapOptions,
+) -> Result {
+    let flags = method.access_flags();
+    let Signature::Method { args, ret_type } = method.signature() else {
+        return Ok(());
+    };
+    let mut declaration = String::new();
+    for (present, modifier) in [
+        (flags.is_public(), "public "),
+        (flags.is_private(), "private "),
+        (flags.is_protected(), "protected "),
+        (flags.is_static(), "static "),
+        (flags.is_final(), "final "),
+        (
+            flags.contains(MethodAccessFlags::SYNCHRONIZED),
+            "synchronized ",
+        ),
+        (flags.is_native(), "native "),
+        (flags.is_abstract(), "abstract "),
+    ] {
+        if present {
+            declaration.push_str(modifier);
+        }
+    }
+    let args = args
+        .iter()
+        .map(Signature::to_java)
+        .collect::<Vec<_>>()
+        .join(", ");
+    match method.name() {
+        "<clinit>" => declaration.push_str("{}"),
+        "<init>" => {
+            let _ = write!(
+                declaration,
+                "{}({})",
+                java_name(class.this().as_str()),
+                args
+            );
+        }
+        name => {
+            let _ = write!(declaration, "{} {}({})", ret_type.to_java(), name, args);
+        }
+    }
+    let thrown = method
+        .thrown_exceptions()
+        .iter()
+        .map(|exception| java_name(exception.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !thrown.is_empty() {
+        let _ = write!(declaration, " throws {thrown}");
+    }
+    writeln!(f, "  {declaration};")?;
+    if options.verbose {
+        writeln!(f, "    descriptor: {}", method.signature().jni())?;
+        writeln!(
+            f,
+            "    flags: {}",
+            flags_string(flags.bits(), flags.iter_names().map(|(name, _)| name))
+        )?;
+    }
+    if let Some(code) = method.get::<Code>().filter(|_| options.shows_code()) {
+        write_code(f, class, method, &code, options)?;
+    }
+    if !thrown.is_empty() && options.verbose {
+        writeln!(f, "    Exceptions:")?;
+        writeln!(f, "      throws {thrown}")?;
+    }
+    Ok(())
+}
+
+fn write_code(
+    f: &mut Formatter<'_>,
+    class: &JavaClass,
+    method: &Method,
+    code: &Code,
+    options: &JavapOptions,
+) -> Result {
+    let args_size =
+        method.signature().parameter_count() + usize::from(!method.access_flags().is_static());
+    writeln!(f, "    Code:")?;
+    if options.verbose {
+        writeln!(
+            f,
+            "      stack={}, locals={}, args_size={}",
+            code.max_stack(),
+            code.max_locals(),
+            args_size
+        )?;
+    }
+    for instruction in code.instructions() {
+        match instruction {
+            Ok(instruction) => write_instruction(f, class, &instruction)?,
+            Err(error) => {
+                writeln!(f, "      // {error}")?;
+                break;
+            }
+        }
+    }
+
+    if !code.exception_table().is_empty() {
+        writeln!(f, "      Exception table:")?;
+        writeln!(f, "         from    to  target type")?;
+        for exception in code.exception_table() {
+            let catch_type = match exception.catch_type() {
+                Some(catch_type) => format!("Class {catch_type}"),
+                None => "any".to_string(),
+            };
+            writeln!(
+                f,
+                "        {:>5} {:>5} {:>5}   {}",
+                exception.start_pc(),
+                exception.end_pc(),
+                exception.handler_pc(),
+                catch_type
+            )?;
+        }
+    }
+
+    if !options.verbose {
+        return Ok(());
+    }
+    if let Some(lines) = code.get::<LineNumberTable>() {
+        writeln!(f, "      LineNumberTable:")?;
+        for &(start_pc, line) in lines.entries() {
+            writeln!(f, "        line {line}: {start_pc}")?;
+        }
+    }
+
+    let variables = code.local_variables();
+    if !variables.is_empty() {
+        writeln!(f, "      LocalVariableTable:")?;
+        writeln!(f, "        Start  Length  Slot  Name   Signature")?;
+        for variable in variables {
+            writeln!(
+                f,
+                "        {:>5} {:>7} {:>5} {:>5}   {}",
+                variable.start_pc(),
+                variable.length(),
+                variable.index(),
+                variable.name(),
+                variable.descriptor().jni()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn write_instruction(
+    f: &mut Formatter<'_>,
+    class: &JavaClass,
+    instruction: &Instruction,
+) -> Result {
+    let mnemonic = instruction.opcode().mnemonic();
+    let offset = instruction.offset();
+    let constant = |operands: String, index: u16| {
+        let comment = class
+            .constant(index)
+            .and_then(|constant| describe(class, &constant, true))
+            .unwrap_or_default();
+        format!("{mnemonic:<13} {operands:<20}// {comment}")
+    };
+    let text = match instruction.operands() {
+        Operands::None => mnemonic.to_string(),
+        Operands::Local(index) => format!("{mnemonic:<13} {index}"),
+        Operands::Immediate(value) => format!("{mnemonic:<13} {value}"),
+        Operands::Branch(target) => format!("{mnemonic:<13} {target}"),
+        Operands::Constant(index) => constant(format!("#{index}"), *index),
+        Operands::Iinc { index, increment } => format!("{mnemonic:<13} {index}, {increment}"),
+        Operands::InvokeInterface { index, count } => {
+            constant(format!("#{index},  {count}"), *index)
+        }
+        Operands::ArrayType(array_type) => {
+            let name = match array_type {
+                4 => "boolean",
+                5 => "char",
+                6 => "float",
+                7 => "double",
+                8 => "byte",
+                9 => "short",
+                10 => "int",
+                11 => "long",
+                _ => "?",
+            };
+            format!("{mnemonic:<13} {name}")
+        }
+        Operands::MultiANewArray { index, dimensions } => {
+            constant(format!("#{index},  {dimensions}"), *index)
+        }
+        Operands::TableSwitch {
+            default,
+            low,
+            targets,
+        } => {
+            let high = *low as i64 + targets.len() as i64 - 1;
+            let mut text = format!("{mnemonic:<13} {{ // {low} to {high}\n");
+            for (value, target) in (*low as i64..).zip(targets) {
+                let _ = writeln!(text, "{value:>24}: {target}");
+            }
+            let _ = write!(text, "{:>24}: {default}\n{:>13}", "default", "}");
+            text
+        }
+        Operands::LookupSwitch { default, pairs } => {
+            let mut text = format!("{mnemonic:<13} {{ // {}\n", pairs.len());
+            for (value, target) in pairs {
+                let _ = writeln!(text, "{value:>24}: {target}");
+            }
+            let _ = write!(text, "{:>24}: {default}\n{:>13}", "default", "}");
+            text
+        }
+    };
+    writeln!(f, "{offset:>10}: {}", text.trim_end())
+}
+
+fn write_class_attributes(f: &mut Formatter<'_>, class: &JavaClass) -> Result {
+    if let Some(source_file) = class.get::<SourceFile>() {
+        writeln!(f, "SourceFile: \"{}\"", source_file.path().display())?;
+    }
+    let bootstrap_methods = class.bootstrap_methods();
+    if !bootstrap_methods.is_empty() {
+        writeln!(f, "BootstrapMethods:")?;
+        for (index, bootstrap_method) in bootstrap_methods.iter().enumerate() {
+            writeln!(f, "  {index}: {}", handle(bootstrap_method.method()))?;
+            writeln!(f, "    Method arguments:")?;
+            for argument in bootstrap_method.arguments() {
+                if let Some(argument) = describe(class, argument, false) {
+                    writeln!(f, "      {argument}")?;
+                }
+            }
+        }
+    }
+    for attribute in class.attributes() {
+        if let AttributeKind::Unknown(bytes) = attribute.kind() {
+            writeln!(
+                f,
+                "{}: length = {:#x} (unknown attribute)",
+                attribute.attribute_name(),
+                bytes.len()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Describes a constant, as found in the comments of the constant pool. Within code, the kind of
+/// constant is included, and members of the class itself aren't qualified by its name.
+fn describe(class: &JavaClass, constant: &ResolvedConstant, in_code: bool) -> Option<String> {
+    let member = |kind: &str, member: &MemberRef| {
+        let name = format!("{}:{}", quote(member.name()), member.descriptor());
+        let qualified = format!("{}.{}", quote(member.class().as_str()), name);
+        match in_code {
+            true if member.class() == class.this() => format!("{kind} {name}"),
+            true => format!("{kind} {qualified}"),
+            false => qualified,
+        }
+    };
+    let (kind, description) = match constant {
+        ResolvedConstant::Utf8(string) => ("", escape(string)),
+        ResolvedConstant::Integer(value) => ("int", value.to_string()),
+        ResolvedConstant::Float(value) => ("float", format!("{value:?}f")),
+        ResolvedConstant::Long(value) => ("long", format!("{value}l")),
+        ResolvedConstant::Double(value) => ("double", format!("{value:?}d")),
+        ResolvedConstant::Class(name) => ("class", quote(name.as_str())),
+        ResolvedConstant::String(string) => ("String", escape(string)),
+        ResolvedConstant::FieldRef(field) => return Some(member("Field", field)),
+        ResolvedConstant::MethodRef(method) => return Some(member("Method", method)),
+        ResolvedConstant::InterfaceMethodRef(method) => {
+            return Some(member("InterfaceMethod", method))
+        }
+        ResolvedConstant::NameAndType { name, descriptor } => {
+            ("NameAndType", format!("{}:{}", quote(name), descriptor))
+        }
+        ResolvedConstant::MethodHandle(method_handle) => ("MethodHandle", handle(method_handle)),
+        ResolvedConstant::MethodType(descriptor) => ("MethodType", descriptor.jni()),
+        ResolvedConstant::Dynamic(dynamic) => (
+            "Dynamic",
+            format!(
+                "#{}:{}:{}",
+                dynamic.bootstrap_method_attr_index(),
+                quote(dynamic.name()),
+                dynamic.descriptor().jni()
+            ),
+        ),
+        ResolvedConstant::InvokeDynamic(dynamic) => (
+            "InvokeDynamic",
+            format!(
+                "#{}:{}:{}",
+                dynamic.bootstrap_method_attr_index(),
+                quote(dynamic.name()),
+                dynamic.descriptor().jni()
+            ),
+        ),
+        ResolvedConstant::Module(name) => ("Module", name.to_string()),
+        ResolvedConstant::Package(name) => ("Package", name.to_string()),
+        ResolvedConstant::Invalid => return None,
+    };
+    match in_code && !kind.is_empty() {
+        true => Some(format!("{kind} {description}")),
+        false => Some(description),
+    }
+}
+
+/// Describes a method handle as its kind and member
+fn handle(handle: &MethodHandle) -> String {
+    let kind = match handle.kind() {
+        ReferenceKind::GetField => "REF_getField",
+        ReferenceKind::GetStatic => "REF_getStatic",
+        ReferenceKind::PutField => "REF_putField",
+        ReferenceKind::PutStatic => "REF_putStatic",
+        ReferenceKind::InvokeVirtual => "REF_invokeVirtual",
+        ReferenceKind::InvokeStatic => "REF_invokeStatic",
+        ReferenceKind::InvokeSpecial => "REF_invokeSpecial",
+        ReferenceKind::NewInvokeSpecial => "REF_newInvokeSpecial",
+        ReferenceKind::InvokeInterface => "REF_invokeInterface",
+    };
+    format!(
+        "{} {}.{}:{}",
+        kind,
+        quote(handle.class().as_str()),
+        quote(handle.name()),
+        handle.descriptor().jni()
+    )
+}
+
+/// Formats access flags as their value followed by their names, such as
+/// `(0x0021) ACC_PUBLIC, ACC_SUPER`
+fn flags_string<'a>(bits: u16, names: impl Iterator<Item = &'a str>) -> String {
+    let names = names
+        .map(|name| format!("ACC_{name}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("({bits:#06x}) {names}")
+}
+
+/// Converts an internal name to the dotted form used in java source
+fn java_name(name: &str) -> String {
+    name.replace('/', ".")
+}
+
+/// Converts a field descriptor to the form used in java source
+/// Quotes names that aren't valid java identifiers, like `"<init>"` and array classes
+fn quote(name: &str) -> String {
+    if name.starts_with('<') || name.starts_with('[') {
+        format!("\"{name}\"")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Escapes control characters in strings, like java string literals
+fn escape(string: &str) -> String {
+    let mut escaped = String::new();
+    for c in string.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
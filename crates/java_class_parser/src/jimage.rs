@@ -0,0 +1,288 @@
+//! Reads classes out of a java runtime image, the `lib/modules` file of a JDK installation.
+//!
+//! Since java 9, the classes of the JDK are no longer stored in jar files, but in a single
+//! runtime image using the jimage format. The format isn't part of the JVM specification, and is
+//! only described by the JDK's own reader in `jdk.internal.jimage`. An image starts with a header
+//! and an index of the location of every resource, followed by the contents of the resources.
+//!
+//! Resources are named `/<module>/<path>`, such as `/java.base/java/lang/Object.class`. Classes are
+//! looked up by their name alone, so the module is found from the package of the class.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::jimage::JImage;
+//! # use java_class_parser::FQName;
+//! let image = JImage::open("/usr/lib/jvm/java-17-openjdk/lib/modules").unwrap();
+//! let class = image.find_class(FQName::new("java/lang/Object")).unwrap().unwrap();
+//! assert_eq!(class.this(), "java/lang/Object");
+//! ```
+
+use crate::error::Error;
+use crate::{parse_bytes, FQName, FQNameBuf, JavaClass};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const MAGIC: u32 = 0xCAFE_DADA;
+const MAJOR_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 7 * 4;
+
+const ATTRIBUTE_END: u8 = 0;
+const ATTRIBUTE_MODULE: u8 = 1;
+const ATTRIBUTE_PARENT: u8 = 2;
+const ATTRIBUTE_BASE: u8 = 3;
+const ATTRIBUTE_EXTENSION: u8 = 4;
+const ATTRIBUTE_OFFSET: u8 = 5;
+const ATTRIBUTE_COMPRESSED: u8 = 6;
+const ATTRIBUTE_UNCOMPRESSED: u8 = 7;
+
+/// An error reading a runtime image
+#[derive(Debug, thiserror::Error)]
+pub enum JImageError {
+    /// The file doesn't start with the jimage magic number
+    #[error("{0:?} is not a jimage file")]
+    NotAnImage(PathBuf),
+    /// The image was written by an unsupported version of the format
+    #[error("unsupported jimage version {major}.{minor}")]
+    UnsupportedVersion {
+        /// The major version
+        major: u32,
+        /// The minor version
+        minor: u32,
+    },
+    /// The index of the image is truncated or refers outside itself
+    #[error("the index of the jimage is corrupt")]
+    CorruptIndex,
+    /// The resource is compressed, which isn't supported
+    #[error("resource {0} is compressed")]
+    Compressed(String),
+    /// No JDK could be found
+    #[error("couldn't locate a JDK: {0}")]
+    NoJdk(String),
+}
+
+/// A resource in a runtime image
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    offset: u64,
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+/// A java runtime image
+#[derive(Debug)]
+pub struct JImage {
+    path: PathBuf,
+    /// The offset of the contents of the resources, which follow the index
+    contents_start: u64,
+    /// The module of every package in the image, with packages in internal form
+    packages: HashMap<String, String>,
+    /// Every resource in the image, by its full name
+    resources: HashMap<String, Location>,
+}
+
+impl JImage {
+    /// Opens a runtime image, reading its index
+    ///
+    /// # Error
+    /// Will return an error if the file can't be read, or isn't a runtime image
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let mut header = [0; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        if LittleEndian::read_u32(&header) == MAGIC {
+            Self::read_index::<LittleEndian>(path, file, &header)
+        } else if BigEndian::read_u32(&header) == MAGIC {
+            Self::read_index::<BigEndian>(path, file, &header)
+        } else {
+            Err(JImageError::NotAnImage(path.to_path_buf()).into())
+        }
+    }
+
+    /// Opens the runtime image of the JDK at `JAVA_HOME`, or found on the path
+    ///
+    /// # Error
+    /// Will return an error if no JDK can be found, or its runtime image can't be read
+    pub fn locate() -> Result<Self, Error> {
+        let java_home =
+            java_locator::locate_java_home().map_err(|e| JImageError::NoJdk(e.to_string()))?;
+        Self::open(Path::new(&java_home).join("lib").join("modules"))
+    }
+
+    fn read_index<B: ByteOrder>(path: &Path, mut file: File, header: &[u8]) -> Result<Self, Error> {
+        let field = |index: usize| B::read_u32(&header[index * 4..]);
+        let version = field(1);
+        let (major, minor) = (version >> 16, version & 0xFFFF);
+        if major != MAJOR_VERSION {
+            return Err(JImageError::UnsupportedVersion { major, minor }.into());
+        }
+        let table_length = field(4) as usize;
+        let locations_size = field(5) as usize;
+        let strings_size = field(6) as usize;
+
+        let index_size = table_length * 8 + locations_size + strings_size;
+        let mut index = vec![0; index_size];
+        file.read_exact(&mut index)?;
+        // the redirect table is only needed for hashed lookups, which aren't used
+        let offsets = &index[table_length * 4..table_length * 8];
+        let locations = &index[table_length * 8..][..locations_size];
+        let strings = &index[table_length * 8 + locations_size..];
+
+        let string = |offset: u64| -> Result<&str, Error> {
+            let bytes = strings
+                .get(offset as usize..)
+                .ok_or(JImageError::CorruptIndex)?;
+            let end = bytes
+                .iter()
+                .position(|&byte| byte == 0)
+                .ok_or(JImageError::CorruptIndex)?;
+            Ok(std::str::from_utf8(&bytes[..end]).map_err(|_| JImageError::CorruptIndex)?)
+        };
+
+        let mut packages = HashMap::new();
+        let mut resources = HashMap::with_capacity(table_length);
+        for entry in 0..table_length {
+            let offset = B::read_u32(&offsets[entry * 4..]) as usize;
+            let attributes =
+                read_attributes(locations.get(offset..).ok_or(JImageError::CorruptIndex)?)?;
+            let module = string(attributes[ATTRIBUTE_MODULE as usize])?;
+            let parent = string(attributes[ATTRIBUTE_PARENT as usize])?;
+            let base = string(attributes[ATTRIBUTE_BASE as usize])?;
+            let extension = string(attributes[ATTRIBUTE_EXTENSION as usize])?;
+
+            let mut name = String::new();
+            if !module.is_empty() {
+                name.push_str(module);
+                name.push('/');
+            }
+            if !parent.is_empty() {
+                name.push_str(parent);
+                name.push('/');
+            }
+            name.push_str(base);
+            if !extension.is_empty() {
+                name.push('.');
+                name.push_str(extension);
+            }
+            if extension == "class" && !matches!(module, "" | "modules" | "packages") {
+                packages
+                    .entry(parent.to_string())
+                    .or_insert_with(|| module.to_string());
+            }
+            resources.insert(
+                name,
+                Location {
+                    offset: attributes[ATTRIBUTE_OFFSET as usize],
+                    compressed_size: attributes[ATTRIBUTE_COMPRESSED as usize],
+                    uncompressed_size: attributes[ATTRIBUTE_UNCOMPRESSED as usize],
+                },
+            );
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            contents_start: (HEADER_SIZE + index_size) as u64,
+            packages,
+            resources,
+        })
+    }
+
+    /// The path of the image
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the module containing a package, given in internal form
+    pub fn module_of(&self, package: &str) -> Option<&str> {
+        self.packages.get(package).map(String::as_str)
+    }
+
+    /// Reads a resource by its full name, such as `java.base/java/lang/Object.class`. Returns
+    /// `None` if there's no such resource.
+    pub fn get(&self, name: &str) -> Option<Result<Vec<u8>, Error>> {
+        let name = name.trim_start_matches('/');
+        let location = *self.resources.get(name)?;
+        Some(self.read(name, location))
+    }
+
+    /// Finds and parses a class. Returns `None` if the image doesn't contain the class.
+    pub fn find_class(&self, name: &FQName) -> Option<Result<JavaClass, Error>> {
+        let bytes = self.read_class(name)?;
+        Some(bytes.and_then(|bytes| parse_bytes(&bytes[..])))
+    }
+
+    /// Reads the class file of a class. Returns `None` if the image doesn't contain the class.
+    pub fn read_class(&self, name: &FQName) -> Option<Result<Vec<u8>, Error>> {
+        let name = name.canonicalize();
+        let module = self.module_of(name.package().map_or("", FQName::as_str))?;
+        self.get(&format!("{module}/{name}.class"))
+    }
+
+    /// Lists the names of every class in the image, in no particular order
+    pub fn class_names(&self) -> impl Iterator<Item = FQNameBuf> + '_ {
+        self.resources.keys().filter_map(|name| {
+            let (module, path) = name.split_once('/')?;
+            if matches!(module, "modules" | "packages") {
+                return None;
+            }
+            crate::entry_class_name(path)
+        })
+    }
+
+    fn read(&self, name: &str, location: Location) -> Result<Vec<u8>, Error> {
+        if location.compressed_size != 0 {
+            return Err(JImageError::Compressed(name.to_string()).into());
+        }
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.contents_start + location.offset))?;
+        let mut bytes = vec![0; location.uncompressed_size as usize];
+        file.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Reads the attributes of a location. Each attribute starts with a byte holding its kind in the
+/// upper five bits and its length minus one in the lower three, followed by a big endian value.
+fn read_attributes(mut bytes: &[u8]) -> Result<[u64; 8], Error> {
+    let mut attributes = [0; 8];
+    loop {
+        let (&header, rest) = bytes.split_first().ok_or(JImageError::CorruptIndex)?;
+        let kind = header >> 3;
+        if kind == ATTRIBUTE_END {
+            return Ok(attributes);
+        }
+        let length = (header & 0x7) as usize + 1;
+        let value = rest.get(..length).ok_or(JImageError::CorruptIndex)?;
+        if let Some(attribute) = attributes.get_mut(kind as usize) {
+            *attribute = value
+                .iter()
+                .fold(0, |value, &byte| (value << 8) | byte as u64);
+        }
+        bytes = &rest[length..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_attributes() {
+        let bytes = [
+            ATTRIBUTE_MODULE << 3,
+            0x07,
+            (ATTRIBUTE_OFFSET << 3) | 1,
+            0x01,
+            0x02,
+            ATTRIBUTE_END,
+        ];
+        let attributes = read_attributes(&bytes).unwrap();
+        assert_eq!(attributes[ATTRIBUTE_MODULE as usize], 7);
+        assert_eq!(attributes[ATTRIBUTE_OFFSET as usize], 0x0102);
+        assert_eq!(attributes[ATTRIBUTE_BASE as usize], 0);
+        assert!(read_attributes(&bytes[..2]).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Names of the native functions implementing `native` methods, as described in the
+//! [JNI specification](https://docs.oracle.com/en/java/javase/17/docs/specs/jni/design.html#resolving-native-method-names).
+//!
+//! The JVM looks up the implementation of a native method by a symbol built from the class and
+//! method name, such as `Java_com_example_Foo_method`. Overloaded native methods additionally
+//! append their mangled argument descriptor, such as `Java_com_example_Foo_method__ILjava_lang_String_2`.
+//!
+//! C headers declaring these functions can be generated with [`header`], in the same form as
+//! `javac -h`, so native libraries can be built against a jar without its sources. Native
+//! libraries written in Rust can use the bindings generated for the `jni` crate by the `bindings`
+//! module instead, which is only available with the `jni-bindings` feature.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Foo").unwrap();
+//! for method in class.methods() {
+//!     if method.access_flags().is_native() {
+//!         println!("{}", method.jni_symbol_name(&class));
+//!     }
+//! }
+//! ```
+
+#[cfg(feature = "jni-bindings")]
+pub mod bindings;
+
+use crate::attributes::JavaConstant;
+use crate::{FQName, JavaClass, Method, Signature};
+use std::fmt::Write as _;
+
+/// Mangles a name so it can be used in a JNI symbol. Slashes become underscores, and underscores,
+/// semicolons, brackets and non-alphanumeric characters are escaped.
+pub fn mangle(name: &str) -> String {
+    let mut mangled = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '/' | '.' => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    let _ = write!(mangled, "_0{unit:04x}");
+                }
+            }
+        }
+    }
+    mangled
+}
+
+/// The short symbol name of a native method, `Java_` followed by the mangled class and method
+/// names
+pub fn short_symbol_name(class: &FQName, method: &str) -> String {
+    format!("Java_{}_{}", mangle(class.as_str()), mangle(method))
+}
+
+/// The long symbol name of a native method, used for overloaded methods. This is the short name
+/// followed by two underscores and the mangled argument descriptor.
+pub fn long_symbol_name(class: &FQName, method: &str, signature: &Signature) -> String {
+    let arguments = signature
+        .parameters()
+        .iter()
+        .map(Signature::jni)
+        .collect::<String>();
+    format!(
+        "{}__{}",
+        short_symbol_name(class, method),
+        mangle(&arguments)
+    )
+}
+
+/// The C type of a value in a native function, such as `jint` or `jobjectArray`
+pub fn c_type(signature: &Signature) -> &'static str {
+    match signature {
+        Signature::Boolean => "jboolean",
+        Signature::Byte => "jbyte",
+        Signature::Char => "jchar",
+        Signature::Short => "jshort",
+        Signature::Int => "jint",
+        Signature::Long => "jlong",
+        Signature::Float => "jfloat",
+        Signature::Double => "jdouble",
+        Signature::Void => "void",
+        Signature::FullyQualifiedClass(class) => match *class {
+            "java/lang/String" => "jstring",
+            "java/lang/Class" => "jclass",
+            "java/lang/Throwable" => "jthrowable",
+            _ => "jobject",
+        },
+        Signature::Array(component) => match **component {
+            Signature::Boolean => "jbooleanArray",
+            Signature::Byte => "jbyteArray",
+            Signature::Char => "jcharArray",
+            Signature::Short => "jshortArray",
+            Signature::Int => "jintArray",
+            Signature::Long => "jlongArray",
+            Signature::Float => "jfloatArray",
+            Signature::Double => "jdoubleArray",
+            _ => "jobjectArray",
+        },
+        Signature::Method { .. } => "jobject",
+    }
+}
+
+/// Generates a C header declaring the native functions of a class, in the same form as
+/// `javac -h`. Constant fields with primitive values are defined as macros.
+///
+/// # Example
+/// ```no_run
+/// # use java_class_parser::{jni, JavaClassParser};
+/// let parser = JavaClassParser::new("app.jar");
+/// let class = parser.find("com/example/Foo").unwrap();
+/// std::fs::write("com_example_Foo.h", jni::header(&class)).unwrap();
+/// ```
+pub fn header(class: &JavaClass) -> String {
+    let name = mangle(class.this().as_str());
+    let mut header = String::new();
+    let _ = writeln!(
+        header,
+        "/* DO NOT EDIT THIS FILE - it is machine generated */"
+    );
+    let _ = writeln!(header, "#include <jni.h>");
+    let _ = writeln!(header, "/* Header for class {name} */");
+    let _ = writeln!(header);
+    let _ = writeln!(header, "#ifndef _Included_{name}");
+    let _ = writeln!(header, "#define _Included_{name}");
+    let _ = writeln!(header, "#ifdef __cplusplus");
+    let _ = writeln!(header, "extern \"C\" {{");
+    let _ = writeln!(header, "#endif");
+    for field in class.fields() {
+        let flags = field.access_flags();
+        if !flags.is_static() || !flags.is_final() {
+            continue;
+        }
+        let value = match field.constant_value() {
+            Some(JavaConstant::Integer(int)) => format!("{int}L"),
+            Some(JavaConstant::Long(long)) => format!("{long}LL"),
+            Some(JavaConstant::Float(float)) => c_float(float as f64, "f"),
+            Some(JavaConstant::Double(double)) => c_float(double, ""),
+            _ => continue,
+        };
+        let macro_name = format!("{name}_{}", mangle(field.name()));
+        let _ = writeln!(header, "#undef {macro_name}");
+        let _ = writeln!(header, "#define {macro_name} {value}");
+    }
+    for method in class.methods() {
+        if !method.access_flags().is_native() {
+            continue;
+        }
+        let signature = method.signature();
+        let mut parameters = vec![
+            "JNIEnv *",
+            if method.access_flags().is_static() {
+                "jclass"
+            } else {
+                "jobject"
+            },
+        ];
+        parameters.extend(signature.parameters().iter().map(c_type));
+        let return_type = signature.return_type().map_or("void", c_type);
+        let _ = writeln!(header, "/*");
+        let _ = writeln!(header, " * Class:     {name}");
+        let _ = writeln!(header, " * Method:    {}", mangle(method.name()));
+        let _ = writeln!(header, " * Signature: {}", signature.jni());
+        let _ = writeln!(header, " */");
+        let _ = writeln!(
+            header,
+            "JNIEXPORT {return_type} JNICALL {}",
+            method.jni_symbol_name(class)
+        );
+        let _ = writeln!(header, "  ({});", parameters.join(", "));
+        let _ = writeln!(header);
+    }
+    let _ = writeln!(header, "#ifdef __cplusplus");
+    let _ = writeln!(header, "}}");
+    let _ = writeln!(header, "#endif");
+    let _ = writeln!(header, "#endif");
+    header
+}
+
+/// Writes a floating point constant as a C literal, using division for infinities and NaN
+fn c_float(value: f64, suffix: &str) -> String {
+    if value.is_nan() {
+        format!("(0.0{suffix}/0.0{suffix})")
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("({sign}1.0{suffix}/0.0{suffix})")
+    } else {
+        format!("{value:?}{suffix}")
+    }
+}
+
+/// JNI symbols
+impl Method<'_> {
+    /// The name of the native function implementing this method, where `class` is the class
+    /// declaring it. The long form is used if the class declares other native methods with the
+    /// same name, as the short form would be ambiguous.
+    pub fn jni_symbol_name(&self, class: &JavaClass) -> String {
+        let overloaded = class
+            .methods()
+            .iter()
+            .filter(|method| method.access_flags().is_native() && method.name() == self.name())
+            .count()
+            > 1;
+        if overloaded {
+            self.jni_long_symbol_name(class)
+        } else {
+            short_symbol_name(class.this(), self.name())
+        }
+    }
+
+    /// The long form of the name of the native function implementing this method, which includes
+    /// the mangled argument descriptor
+    pub fn jni_long_symbol_name(&self, class: &JavaClass) -> String {
+        long_symbol_name(class.this(), self.name(), self.signature())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mangle_names() {
+        assert_eq!(mangle("com/example/Foo"), "com_example_Foo");
+        assert_eq!(mangle("init_lib"), "init_1lib");
+        assert_eq!(mangle("[Ljava/lang/String;"), "_3Ljava_lang_String_2");
+        assert_eq!(mangle("Outer$Inner"), "Outer_00024Inner");
+        assert_eq!(mangle("café"), "caf_000e9");
+    }
+
+    #[test]
+    fn c_types() {
+        let signature = Signature::new("([ZLjava/lang/String;[[ILjava/lang/Class;)J").unwrap();
+        let parameters = signature
+            .parameters()
+            .iter()
+            .map(c_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parameters,
+            ["jbooleanArray", "jstring", "jobjectArray", "jclass"]
+        );
+        assert_eq!(c_type(signature.return_type().unwrap()), "jlong");
+        assert_eq!(c_float(1.5, "f"), "1.5f");
+        assert_eq!(c_float(f64::NEG_INFINITY, ""), "(-1.0/0.0)");
+    }
+}
@@ -0,0 +1,371 @@
+//! Generates Rust bindings for a class, for use with the [`jni`](https://docs.rs/jni/0.21) crate.
+//! Only available with the `jni-bindings` feature.
+//!
+//! The generated code has two parts:
+//! - An `extern "system"` function for every `native` method, exported with the symbol the JVM
+//!   looks for, whose body is left for you to implement.
+//! - A wrapper around instances of the class, with a function calling each public method and
+//!   constructor through JNI, using the method's descriptor as its signature.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::{jni, JavaClassParser};
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Foo").unwrap();
+//! std::fs::write("src/foo.rs", jni::bindings::generate(&class)).unwrap();
+//! ```
+
+use crate::jni::mangle;
+use crate::{JavaClass, Method, Signature};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+
+/// Rust keywords, which have to be written as raw identifiers to be used as names
+const KEYWORDS: [&str; 45] = [
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "final", "macro", "override", "priv", "typeof", "unsized",
+    "virtual", "yield",
+];
+
+/// Keywords that can't be written as raw identifiers
+const RESERVED: [&str; 4] = ["self", "Self", "super", "crate"];
+
+/// Generates the Rust bindings of a class, as the contents of a module
+pub fn generate(class: &JavaClass) -> String {
+    let mut generator = Generator::default();
+    let skeletons = generator.skeletons(class);
+    let wrapper = generator.wrapper(class);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated from {}. Native functions need to be implemented.",
+        class.this()
+    );
+    let _ = writeln!(out);
+    if !generator.objects.is_empty() {
+        let objects = generator.objects.into_iter().collect::<Vec<_>>();
+        let _ = writeln!(out, "use jni::objects::{{{}}};", objects.join(", "));
+    }
+    if !generator.sys.is_empty() {
+        let sys = generator.sys.into_iter().collect::<Vec<_>>();
+        let _ = writeln!(out, "use jni::sys::{{{}}};", sys.join(", "));
+    }
+    let _ = writeln!(out, "use jni::JNIEnv;");
+    out.push_str(&skeletons);
+    out.push_str(&wrapper);
+    out
+}
+
+/// The name of a method as a Rust identifier, in snake case
+pub fn rust_name(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if previous_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            previous_lower = false;
+        } else if c.is_alphanumeric() || c == '_' {
+            snake.push(c);
+            previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        } else {
+            snake.push('_');
+            previous_lower = false;
+        }
+    }
+    identifier(&snake)
+}
+
+/// Makes a name usable as an identifier, escaping keywords
+fn identifier(name: &str) -> String {
+    if RESERVED.contains(&name) {
+        format!("{name}_")
+    } else if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// The accessor converting the value returned by a method call, and the Rust type it returns
+fn returned(signature: &Signature) -> (&'static str, &'static str) {
+    match signature {
+        Signature::Boolean => ("z", "bool"),
+        Signature::Byte => ("b", "jbyte"),
+        Signature::Char => ("c", "jchar"),
+        Signature::Short => ("s", "jshort"),
+        Signature::Int => ("i", "jint"),
+        Signature::Long => ("j", "jlong"),
+        Signature::Float => ("f", "jfloat"),
+        Signature::Double => ("d", "jdouble"),
+        Signature::Void => ("v", "()"),
+        _ => ("l", "JObject<'local>"),
+    }
+}
+
+#[derive(Default)]
+struct Generator {
+    /// Types imported from `jni::objects`
+    objects: BTreeSet<&'static str>,
+    /// Types imported from `jni::sys`
+    sys: BTreeSet<&'static str>,
+}
+
+impl Generator {
+    /// The type of a parameter of a native function
+    fn parameter_type(&mut self, signature: &Signature) -> String {
+        let object = match signature {
+            Signature::FullyQualifiedClass(class) => match *class {
+                "java/lang/String" => "JString",
+                "java/lang/Class" => "JClass",
+                "java/lang/Throwable" => "JThrowable",
+                _ => "JObject",
+            },
+            Signature::Array(component) => match **component {
+                Signature::Boolean => "JBooleanArray",
+                Signature::Byte => "JByteArray",
+                Signature::Char => "JCharArray",
+                Signature::Short => "JShortArray",
+                Signature::Int => "JIntArray",
+                Signature::Long => "JLongArray",
+                Signature::Float => "JFloatArray",
+                Signature::Double => "JDoubleArray",
+                _ => "JObjectArray",
+            },
+            primitive => return self.sys_type(primitive).to_string(),
+        };
+        self.objects.insert(object);
+        format!("{object}<'local>")
+    }
+
+    /// The raw type used by a native function, as in the C function
+    fn sys_type(&mut self, signature: &Signature) -> &'static str {
+        let sys = super::c_type(signature);
+        if sys != "void" {
+            self.sys.insert(sys);
+        }
+        sys
+    }
+
+    /// The type of an argument of a wrapper function
+    fn argument_type(&mut self, signature: &Signature) -> String {
+        match signature {
+            Signature::Boolean => "bool".to_string(),
+            Signature::FullyQualifiedClass(_) | Signature::Array(_) => {
+                self.objects.insert("JObject");
+                "&JObject<'_>".to_string()
+            }
+            primitive => self.sys_type(primitive).to_string(),
+        }
+    }
+
+    /// The value passed to a method call for an argument of a wrapper function
+    fn argument_value(&mut self, signature: &Signature, name: &str) -> String {
+        self.objects.insert("JValue");
+        match signature {
+            Signature::Boolean => format!("JValue::Bool(u8::from({name}))"),
+            Signature::Byte => format!("JValue::Byte({name})"),
+            Signature::Char => format!("JValue::Char({name})"),
+            Signature::Short => format!("JValue::Short({name})"),
+            Signature::Int => format!("JValue::Int({name})"),
+            Signature::Long => format!("JValue::Long({name})"),
+            Signature::Float => format!("JValue::Float({name})"),
+            Signature::Double => format!("JValue::Double({name})"),
+            _ => format!("JValue::Object({name})"),
+        }
+    }
+
+    /// Generates the functions implementing native methods
+    fn skeletons(&mut self, class: &JavaClass) -> String {
+        let mut out = String::new();
+        for method in class.methods() {
+            if !method.access_flags().is_native() {
+                continue;
+            }
+            let is_static = method.access_flags().is_static();
+            let receiver = if is_static {
+                self.objects.insert("JClass");
+                "class: JClass<'local>"
+            } else {
+                self.objects.insert("JObject");
+                "this: JObject<'local>"
+            };
+            let mut parameters = vec!["env: JNIEnv<'local>".to_string(), receiver.to_string()];
+            for (name, signature) in parameter_names(&method, &["env", "class", "this"]) {
+                parameters.push(format!("{name}: {}", self.parameter_type(&signature)));
+            }
+            let return_type = match method.signature().return_type() {
+                Some(Signature::Void) | None => String::new(),
+                Some(returned) => format!(" -> {}", self.sys_type(returned)),
+            };
+
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "/// Implements `{}.{}{}`",
+                class.this(),
+                method.name(),
+                method.signature().jni()
+            );
+            let _ = writeln!(out, "#[no_mangle]");
+            let _ = writeln!(out, "#[allow(non_snake_case, unused_variables)]");
+            let _ = writeln!(
+                out,
+                "pub extern \"system\" fn {}<'local>(",
+                method.jni_symbol_name(class)
+            );
+            for parameter in parameters {
+                let _ = writeln!(out, "    {parameter},");
+            }
+            let _ = writeln!(out, "){return_type} {{");
+            let _ = writeln!(out, "    todo!()");
+            let _ = writeln!(out, "}}");
+        }
+        out
+    }
+
+    /// Generates the wrapper calling the public methods of the class
+    fn wrapper(&mut self, class: &JavaClass) -> String {
+        let simple_name = class.this().as_str().rsplit('/').next().unwrap_or_default();
+        let struct_name = simple_name.replace('$', "_");
+        let methods = class
+            .methods()
+            .into_iter()
+            .filter(|method| {
+                let flags = method.access_flags();
+                flags.is_public()
+                    && !flags.is_synthetic()
+                    && !flags.is_bridge()
+                    && method.name() != "<clinit>"
+            })
+            .collect::<Vec<_>>();
+        let mut overloads = HashMap::<&str, usize>::new();
+        for method in &methods {
+            *overloads.entry(method.name()).or_default() += 1;
+        }
+
+        self.objects.insert("JObject");
+        let mut out = String::new();
+        let _ = writeln!(out);
+        let _ = writeln!(out, "/// An instance of `{}`", class.this());
+        let _ = writeln!(out, "#[repr(transparent)]");
+        let _ = writeln!(
+            out,
+            "pub struct {struct_name}<'local>(pub JObject<'local>);"
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "#[allow(non_snake_case)]");
+        let _ = writeln!(out, "impl<'local> {struct_name}<'local> {{");
+        let _ = writeln!(
+            out,
+            "    /// The name of the class, for finding it with JNI"
+        );
+        let _ = writeln!(out, "    pub const CLASS: &str = \"{}\";", class.this());
+        for method in methods {
+            let signature = method.signature();
+            let is_constructor = method.name() == "<init>";
+            let is_static = method.access_flags().is_static();
+            let mut name = if is_constructor {
+                "new".to_string()
+            } else {
+                rust_name(method.name())
+            };
+            if overloads[method.name()] > 1 {
+                let arguments = signature
+                    .parameters()
+                    .iter()
+                    .map(Signature::jni)
+                    .collect::<String>();
+                name = format!("{}__{}", name.trim_start_matches("r#"), mangle(&arguments));
+            }
+
+            let mut parameters = vec![];
+            if !is_constructor && !is_static {
+                parameters.push("&self".to_string());
+            }
+            parameters.push("env: &mut JNIEnv<'local>".to_string());
+            let mut arguments = vec![];
+            for (argument, signature) in parameter_names(&method, &["env"]) {
+                parameters.push(format!("{argument}: {}", self.argument_type(&signature)));
+                arguments.push(self.argument_value(&signature, &argument));
+            }
+            let arguments = format!("&[{}]", arguments.join(", "));
+            let descriptor = signature.jni();
+            let (accessor, return_type) = if is_constructor {
+                ("", "Self")
+            } else {
+                returned(signature.return_type().unwrap_or(&Signature::Void))
+            };
+            if return_type.starts_with('j') {
+                self.sys.insert(return_type);
+            }
+
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "    /// Calls `{}{}`",
+                if is_constructor { "new" } else { method.name() },
+                descriptor
+            );
+            let _ = writeln!(
+                out,
+                "    pub fn {name}({}) -> jni::errors::Result<{return_type}> {{",
+                parameters.join(", ")
+            );
+            let call = if is_constructor {
+                format!("env.new_object(Self::CLASS, \"{descriptor}\", {arguments}).map(Self)")
+            } else if is_static {
+                format!(
+                    "env.call_static_method(Self::CLASS, \"{}\", \"{descriptor}\", {arguments})?.{accessor}()",
+                    method.name()
+                )
+            } else {
+                format!(
+                    "env.call_method(&self.0, \"{}\", \"{descriptor}\", {arguments})?.{accessor}()",
+                    method.name()
+                )
+            };
+            let _ = writeln!(out, "        {call}");
+            let _ = writeln!(out, "    }}");
+        }
+        let _ = writeln!(out, "}}");
+        out
+    }
+}
+
+/// The names of the parameters of a method as identifiers, avoiding names that are already used
+fn parameter_names<'a>(method: &Method<'a>, used: &[&str]) -> Vec<(String, Signature<'a>)> {
+    method
+        .parameters()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, _, signature))| {
+            let name = match name {
+                Some(name) if used.contains(&name) => format!("{name}_"),
+                Some(name) => identifier(name),
+                None => format!("arg{index}"),
+            };
+            (name, signature)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_names() {
+        assert_eq!(rust_name("getArea"), "get_area");
+        assert_eq!(rust_name("init_lib"), "init_lib");
+        assert_eq!(rust_name("toURL"), "to_url");
+        assert_eq!(rust_name("type"), "r#type");
+        assert_eq!(rust_name("self"), "self_");
+        assert_eq!(rust_name("lambda$run$0"), "lambda_run_0");
+    }
+}
@@ -27,33 +27,67 @@
 #![deny(missing_docs)]
 
 use crate::constant_pool::ConstantPoolInfo;
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::rc::Rc;
 
-use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use java_classpaths::Classpath;
+use crate::annotations::RetentionPolicy;
+use crate::classpath::{
+    entry_class_names, ClassDefinition, ClassNames, ClassPattern, DuplicateClass,
+};
+use crate::inheritance::{ClassField, ClassMethod};
+use crate::jimage::JImage;
+use crate::resolved::ResolvedClass;
+use java_classpaths::{Classpath, ClasspathError, Resource, CLASSPATH_SEPARATOR};
 use std::io::Read;
 use std::path::{Path, PathBuf};
-use zip::result::ZipError;
-use zip::ZipArchive;
 
+pub mod bytecode;
+pub mod cache;
+pub mod call_graph;
+pub mod classpath;
+pub mod compare;
+pub mod compat;
 mod constant_pool;
 pub mod error;
+pub mod export;
 pub mod inheritance;
+pub mod javap;
+pub mod jimage;
+pub mod jni;
+pub mod mapping;
+pub mod obfuscation;
 pub(crate) mod raw_java_class;
+pub mod remap;
+pub mod report;
+pub mod resolved;
+pub mod scan;
+pub mod sealed;
+pub mod sources;
+pub mod stats;
 mod structures;
+pub mod transform;
 pub(crate) mod utility;
 
+use crate::cache::{CachePolicy, ClassCache};
 use crate::error::{Error, ErrorKind};
+pub use constant_pool::{ConstantPool, ConstantPoolBuilder, MemberRef, ResolvedConstant};
 pub use structures::*;
 
 /// Parses java classes from `.class` files. Produces a [`JavaClass`][crate::JavaClass] if successful.
 #[derive(Debug, Default)]
 pub struct JavaClassParser {
     class_path: Classpath,
-    cache: RefCell<HashMap<FQNameBuf, JavaClass>>,
+    jdk: Option<JImage>,
+    cache: RefCell<ClassCache>,
+    lazy: bool,
+    /// Classes registered from memory, which take precedence over the classpath
+    registered: HashMap<FQNameBuf, JavaClass>,
+    parent: Option<Rc<JavaClassParser>>,
+    #[cfg(feature = "watch")]
+    watcher: Option<java_classpaths::ClasspathWatcher>,
 }
 
 impl JavaClassParser {
@@ -64,18 +98,26 @@ impl JavaClassParser {
         Ok(JavaClass::new(raw_class))
     }
 
-    /// Creates a new java class parser with a given classpath.
+    /// Creates a new java class parser with a given classpath, with entries separated by the
+    /// platform's [classpath separator](CLASSPATH_SEPARATOR).
     pub fn new<S: AsRef<str>>(classpath: S) -> Self {
         Self {
             class_path: classpath
                 .as_ref()
-                .split(";")
-                .map(|s| PathBuf::from(s))
+                .split(CLASSPATH_SEPARATOR)
+                .map(PathBuf::from)
                 .collect(),
             ..Default::default()
         }
     }
 
+    /// Creates a new java class parser using the classpath in the `CLASSPATH` environment
+    /// variable, resolved against the current directory like `java` does.
+    /// See [`Classpath::from_env`].
+    pub fn from_env() -> Self {
+        Self::with_classpath(Classpath::from_env())
+    }
+
     /// Creates a new java class parser with an actual classpath
     pub fn with_classpath<C: Into<Classpath>>(classpath: C) -> Self {
         Self {
@@ -84,6 +126,49 @@ impl JavaClassParser {
         }
     }
 
+    /// Also finds the classes of the JDK at `JAVA_HOME`, or found on the path, by reading its
+    /// [runtime image](jimage). Classes on the classpath take precedence over JDK classes.
+    ///
+    /// # Error
+    /// Will return an error if no JDK can be found, or its runtime image can't be read
+    pub fn with_jdk(self) -> Result<Self, Error> {
+        Ok(self.with_jdk_image(JImage::locate()?))
+    }
+
+    /// Also finds the classes in a runtime image, such as the `lib/modules` file of a JDK
+    pub fn with_jdk_image(self, image: JImage) -> Self {
+        Self {
+            jdk: Some(image),
+            ..self
+        }
+    }
+
+    /// Delegates to a parent parser, like a java class loader. Classes are looked for in the parent
+    /// first, and only on this parser's classpath if the parent can't find them. Classes found by
+    /// the parent are only cached by the parent, so one parser for the JDK can be shared between
+    /// many parsers.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::rc::Rc;
+    /// # use java_class_parser::JavaClassParser;
+    /// let jdk = Rc::new(JavaClassParser::default().with_jdk().unwrap());
+    /// let app = JavaClassParser::new("app.jar").with_parent(jdk.clone());
+    /// let lib = JavaClassParser::new("lib.jar").with_parent(jdk);
+    /// let object = app.find("java/lang/Object").unwrap();
+    /// ```
+    pub fn with_parent(self, parent: Rc<JavaClassParser>) -> Self {
+        Self {
+            parent: Some(parent),
+            ..self
+        }
+    }
+
+    /// The parser this parser delegates to first, if any
+    pub fn parent(&self) -> Option<&JavaClassParser> {
+        self.parent.as_deref()
+    }
+
     /// Finds a class based on a fully qualified path.
     ///
     /// For example, if the given classpath contains some directory `output`
@@ -96,21 +181,114 @@ impl JavaClassParser {
     /// ```
     /// Attempting to look up a class with fully qualified path `com/example/Square` would
     /// result in the `output/com/example/Square.java` file being parsed. This also works
-    /// if a file on the classpath is a jar file.
+    /// if a file on the classpath is a jar file. The binary form `com.example.Square` finds the
+    /// same class.
     ///
+    /// # Error
+    /// Will return an [`InvalidName`](ErrorKind::InvalidName) error if the path isn't a legal
+    /// [binary name](FQName::is_binary_name), a [`NotAClass`](ErrorKind::NotAClass) error if it names an array
+    /// or primitive type, and a [`NoClassFound`](ErrorKind::NoClassFound) error if no class
+    /// with the path is on the classpath.
     pub fn find<P: AsFullyQualifiedName + ?Sized>(&self, path: &P) -> Result<JavaClass, Error> {
-        let fcq = path.as_fcq();
-        if !self.cache.borrow().contains_key(fcq) {
-            let class = self.find_class(fcq)?;
-            self.cache.borrow_mut().insert(fcq.to_fqname_buf(), class);
+        let fcq = class_name(path.as_fcq())?;
+        if let Some(parent) = &self.parent {
+            match parent.find(fcq.as_ref()) {
+                Err(e) if matches!(e.kind(), ErrorKind::NoClassFound(_)) => {}
+                result => return result,
+            }
+        }
+        #[cfg(feature = "watch")]
+        self.apply_changes();
+        if let Some(class) = self.cache.borrow_mut().get(&fcq) {
+            return Ok(class);
+        }
+        if self.cache.borrow_mut().is_missing(&fcq) {
+            return Err(ErrorKind::NoClassFound(fcq.to_fqname_buf()).into());
+        }
+        let class = match self.find_class(&fcq) {
+            Err(e) if matches!(e.kind(), ErrorKind::NoClassFound(_)) => {
+                self.cache.borrow_mut().insert_missing(fcq.to_fqname_buf());
+                return Err(e);
+            }
+            result => result?,
+        };
+        self.cache
+            .borrow_mut()
+            .insert(fcq.to_fqname_buf(), class.clone());
+        Ok(class)
+    }
+
+    /// Sets how many of the classes found by [`find`](Self::find) are cached, emptying the cache.
+    pub fn with_cache_policy(self, policy: CachePolicy) -> Self {
+        Self {
+            cache: RefCell::new(ClassCache::new(policy)),
+            ..self
+        }
+    }
+
+    /// The policy used to cache classes
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.cache.borrow().policy()
+    }
+
+    /// Empties the cache, so classes are parsed again the next time they're found, and classes
+    /// that couldn't be found are looked for again
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Forgets which classes couldn't be found, so they're looked for again. This should be used
+    /// when classes are added to the classpath, such as when a compiler writes to a directory on
+    /// the classpath.
+    pub fn clear_missing(&self) {
+        self.cache.borrow_mut().clear_missing();
+    }
+
+    /// Watches the classpath for changes, so classes that are recompiled or rebuilt into a jar are
+    /// parsed again the next time they're found, and new classes can be found. Only available with
+    /// the `watch` feature.
+    ///
+    /// # Error
+    /// Will return an error if the classpath can't be watched
+    #[cfg(feature = "watch")]
+    pub fn with_watcher(self) -> Result<Self, Error> {
+        Ok(Self {
+            watcher: Some(self.class_path.watch()?),
+            ..self
+        })
+    }
+
+    /// Invalidates the cached classes affected by changes to the classpath. A changed class file
+    /// only evicts its class, while a changed archive, or missed changes, empty the cache.
+    #[cfg(feature = "watch")]
+    fn apply_changes(&self) {
+        use java_classpaths::ClasspathEvent;
+
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        let mut cache = self.cache.borrow_mut();
+        for event in watcher.try_iter() {
+            match event {
+                Ok(ClasspathEvent::ResourceChanged { resource, .. }) => {
+                    if let Some(name) = entry_class_name(&resource) {
+                        cache.remove(&name);
+                    }
+                    cache.clear_missing();
+                }
+                _ => cache.clear(),
+            }
         }
-        Ok(self.cache.borrow()[fcq].clone())
     }
 
-    /// Tries to find the super class of a java class on the classpath
-    pub fn find_super(&self, class: &JavaClass) -> Result<JavaClass, Error> {
-        let super_class = class.super_name();
-        self.find(super_class)
+    /// Tries to find the super class of a java class on the classpath. Returns `None` if the class
+    /// has no [super class](JavaClass::super_class), which is only the case for `java/lang/Object`
+    /// and modules.
+    pub fn find_super(&self, class: &JavaClass) -> Result<Option<JavaClass>, Error> {
+        class
+            .super_class()
+            .map(|super_class| self.find(super_class))
+            .transpose()
     }
 
     /// Finds a list of interfaces that are available on the classpath
@@ -128,25 +306,332 @@ impl JavaClassParser {
             .collect()
     }
 
+    /// Checks if a value of type `sub` can be assigned to a variable of type `sup`, walking the
+    /// super classes and interfaces of `sub`. Names may be classes, arrays named by their
+    /// descriptor, such as `[Ljava/lang/String;`, or primitives named by their keyword, such as
+    /// `int`. Arrays are covariant, and primitives are assignable to the primitives they widen to.
+    ///
+    /// # Error
+    /// Will return an error if a class that must be checked can't be found
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::JavaClassParser;
+    /// let parser = JavaClassParser::default().with_jdk().unwrap();
+    /// assert!(parser.is_assignable("java/util/ArrayList", "java/util/List").unwrap());
+    /// assert!(parser.is_assignable("[Ljava/lang/String;", "[Ljava/lang/Object;").unwrap());
+    /// assert!(parser.is_assignable("int", "long").unwrap());
+    /// ```
+    pub fn is_assignable<S, T>(&self, sub: &S, sup: &T) -> Result<bool, Error>
+    where
+        S: AsFullyQualifiedName + ?Sized,
+        T: AsFullyQualifiedName + ?Sized,
+    {
+        inheritance::is_assignable(self, sub.as_fcq(), sup.as_fcq())
+    }
+
+    /// Gets every method of a class, which are the methods it declares followed by the methods it
+    /// inherits from its super classes and interfaces, each with the class that declares it.
+    /// Methods that are overridden, private or inaccessible from the package of the class aren't
+    /// inherited. Ancestors that can't be found are skipped.
+    ///
+    /// # Error
+    /// Will return an error if an ancestor can't be parsed
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::JavaClassParser;
+    /// let parser = JavaClassParser::default().with_jdk().unwrap();
+    /// let list = parser.find("java/util/ArrayList").unwrap();
+    /// for member in parser.all_methods(&list).unwrap() {
+    ///     println!("{} from {}", member.method().name(), member.declaring_class().this());
+    /// }
+    /// ```
+    pub fn all_methods(&self, class: &JavaClass) -> Result<Vec<ClassMethod>, Error> {
+        inheritance::all_methods(self, class)
+    }
+
+    /// Gets every field of a class, which are the fields it declares followed by the fields it
+    /// inherits from its super classes and interfaces, each with the class that declares it.
+    /// Fields that are shadowed, private or inaccessible from the package of the class aren't
+    /// inherited. Ancestors that can't be found are skipped.
+    ///
+    /// # Error
+    /// Will return an error if an ancestor can't be parsed
+    pub fn all_fields(&self, class: &JavaClass) -> Result<Vec<ClassField>, Error> {
+        inheritance::all_fields(self, class)
+    }
+
+    /// Finds a class and eagerly resolves the types it refers to. Its super classes and interfaces
+    /// are resolved recursively, and the declared types of its fields and methods are resolved to
+    /// the classes they name.
+    ///
+    /// # Error
+    /// Will return an error if the class itself can't be found. Referenced types that can't be
+    /// found are represented as [`Missing`](resolved::ClassRef::Missing) instead.
+    pub fn resolve<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        path: &P,
+    ) -> Result<ResolvedClass, Error> {
+        let class = self.find(path)?;
+        resolved::resolve(self, class, &mut vec![])
+    }
+
+    /// Finds every annotation type on the classpath with a [`Runtime`](RetentionPolicy::Runtime)
+    /// retention policy, which are the annotations that can be discovered reflectively. Classes
+    /// that can't be parsed are skipped.
+    ///
+    /// # Error
+    /// Will return an error if the classpath can't be read
+    pub fn find_runtime_annotations(&self) -> Result<Vec<JavaClass>, Error> {
+        let mut output = vec![];
+        for name in self.classes() {
+            let Ok(class) = self.find(&name?) else {
+                continue;
+            };
+            if class.retention() == Some(RetentionPolicy::Runtime) {
+                output.push(class);
+            }
+        }
+        Ok(output)
+    }
+
     /// Gets the classpath of the parser
     pub fn classpath(&self) -> impl Iterator<Item = &Path> {
         (&self.class_path).into_iter()
     }
 
+    /// Lists the names of every class on the classpath, in classpath order. The classpath is read
+    /// lazily as the iterator advances, and classes that are shadowed by an earlier entry are only
+    /// listed once.
+    pub fn classes(&self) -> ClassNames {
+        ClassNames::new(self)
+    }
+
+    /// Finds every definition of a class on the classpath, in classpath order. Only the first
+    /// definition is found by [`find`](Self::find), and the rest are shadowed by it. Registered
+    /// classes, and classes found by the parent or in the JDK, aren't included.
+    ///
+    /// # Error
+    /// Will return an error if the name isn't a valid class name, or a definition can't be read
+    /// or parsed
+    pub fn find_all<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        path: &P,
+    ) -> Result<Vec<ClassDefinition>, Error> {
+        let class_path = format!("{}.class", class_name(path.as_fcq())?);
+        let mut definitions = vec![];
+        for entry in self.classpath() {
+            match Classpath::from(entry).get(&class_path) {
+                Ok(resource) => {
+                    let class = self.parse_class_bytes(read_resource(resource)?)?;
+                    definitions.push(ClassDefinition::new(entry.to_path_buf(), class));
+                }
+                Err(ClasspathError::NotFound(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(definitions)
+    }
+
+    /// Finds every class defined by more than one classpath entry, sorted by name. Only the
+    /// definition in the first entry is ever found, which often causes hard to debug errors.
+    ///
+    /// # Error
+    /// Will return an error if a classpath entry can't be read
+    pub fn duplicates(&self) -> Result<Vec<DuplicateClass>, Error> {
+        let mut definitions = BTreeMap::<FQNameBuf, Vec<PathBuf>>::new();
+        for entry in self.classpath() {
+            let names = entry_class_names(entry).collect::<Result<HashSet<_>, _>>()?;
+            for name in names {
+                definitions
+                    .entry(name)
+                    .or_default()
+                    .push(entry.to_path_buf());
+            }
+        }
+        Ok(definitions
+            .into_iter()
+            .filter(|(_, entries)| entries.len() > 1)
+            .map(|(name, entries)| DuplicateClass::new(name, entries))
+            .collect())
+    }
+
+    /// Parses every class on the classpath, in classpath order. Unlike [`find`](Self::find), the
+    /// parsed classes aren't cached.
+    pub fn parse_all(&self) -> impl Iterator<Item = Result<JavaClass, Error>> + '_ {
+        self.classes()
+            .map(move |name| name.and_then(|name| self.find_class(&name)))
+    }
+
+    /// Lists the names of the classes in a package, in classpath order. If `recursive` is true,
+    /// classes in subpackages are included too. The package may be given in either binary or
+    /// internal form, and the empty string is the default package.
+    pub fn classes_in_package<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        package: &P,
+        recursive: bool,
+    ) -> impl Iterator<Item = Result<FQNameBuf, Error>> + '_ {
+        let package = package.as_fcq().canonicalize().into_owned();
+        self.classes().filter(move |name| {
+            let Ok(name) = name else {
+                return true;
+            };
+            match name.package() {
+                None => package.as_str().is_empty(),
+                Some(name_package) if recursive => {
+                    package.as_str().is_empty()
+                        || name_package == package.as_str()
+                        || name_package
+                            .as_str()
+                            .strip_prefix(package.as_str())
+                            .map_or(false, |rest| rest.starts_with('/'))
+                }
+                Some(name_package) => name_package == package.as_str(),
+            }
+        })
+    }
+
+    /// Parses the classes in a package, in classpath order. If `recursive` is true, classes in
+    /// subpackages are included too. Unlike [`find`](Self::find), the parsed classes aren't
+    /// cached.
+    pub fn parse_package<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        package: &P,
+        recursive: bool,
+    ) -> impl Iterator<Item = Result<JavaClass, Error>> + '_ {
+        self.classes_in_package(package, recursive)
+            .map(move |name| name.and_then(|name| self.find_class(&name)))
+    }
+
+    /// Lists the names of the classes matching a glob pattern, such as `com/example/**/*Service`,
+    /// in classpath order. See [`ClassPattern`] for the pattern syntax.
+    pub fn find_matching(
+        &self,
+        pattern: &str,
+    ) -> impl Iterator<Item = Result<FQNameBuf, Error>> + '_ {
+        let pattern = ClassPattern::new(pattern);
+        self.find_where(move |name| pattern.matches(name))
+    }
+
+    /// Lists the names of the classes for which a predicate returns true, in classpath order
+    pub fn find_where<'a, F: FnMut(&FQName) -> bool + 'a>(
+        &'a self,
+        mut predicate: F,
+    ) -> impl Iterator<Item = Result<FQNameBuf, Error>> + 'a {
+        self.classes()
+            .filter(move |name| name.as_ref().map_or(true, |name| predicate(name)))
+    }
+
+    /// Registers a class from the bytes of its class file, so it can be found without being on
+    /// the classpath. Registered classes take precedence over classes on the classpath, but aren't
+    /// listed by [`classes`](Self::classes).
+    ///
+    /// # Error
+    /// Will return an error if the bytes aren't a valid class, or the class isn't named `name`
+    pub fn register_bytes<P, B>(&mut self, name: &P, bytes: B) -> Result<JavaClass, Error>
+    where
+        P: AsFullyQualifiedName + ?Sized,
+        B: AsRef<[u8]>,
+    {
+        let class = parse_bytes(bytes.as_ref())?;
+        let expected = name.as_fcq().canonicalize();
+        if class.this() != expected.as_ref() {
+            return Err(ErrorKind::NameMismatch {
+                expected: expected.to_fqname_buf(),
+                found: class.this().to_fqname_buf(),
+            }
+            .into());
+        }
+        self.register_class(class.clone());
+        Ok(class)
+    }
+
+    /// Registers a class, so it can be found without being on the classpath. Registered classes
+    /// take precedence over classes on the classpath, but aren't listed by
+    /// [`classes`](Self::classes).
+    pub fn register_class(&mut self, class: JavaClass) {
+        let name = class.this().to_fqname_buf();
+        self.cache.get_mut().remove(&name);
+        self.registered.insert(name, class);
+    }
+
+    /// Sets whether classes are parsed lazily. A lazily parsed class only has its header, up to
+    /// and including its interfaces, parsed when it's found. Its fields, methods and attributes are
+    /// parsed the first time they're used, which makes scanning many classes for their names,
+    /// super classes, interfaces or flags much faster.
+    ///
+    /// Lazily parsed classes with invalid fields, methods or attributes have none; use
+    /// [`JavaClass::decode`] or [`JavaClass::decode_error`] to check if a class is valid.
+    pub fn with_lazy_parsing(self, lazy: bool) -> Self {
+        Self { lazy, ..self }
+    }
+
     /// scans through the classpath to find a file. In terms of complexity,
     /// directories are easiest.
     fn find_class(&self, path: &FQName) -> Result<JavaClass, Error> {
-        let class_path = path.as_path().with_extension("class");
-        match self.class_path.get(class_path.to_str().unwrap()) {
-            Some(result) => {
-                let resource = result?;
-                parse_bytes(resource)
+        if let Some(class) = self.registered.get(path) {
+            return Ok(class.clone());
+        }
+        let class_path = format!("{path}.class");
+        let bytes = match self.class_path.get(&class_path) {
+            Ok(resource) => read_resource(resource)?,
+            Err(ClasspathError::NotFound(_)) => {
+                match self.jdk.as_ref().and_then(|jdk| jdk.read_class(path)) {
+                    Some(result) => result?.into(),
+                    None => return Err(Error::from(ErrorKind::NoClassFound(path.to_fqname_buf()))),
+                }
             }
-            None => Err(Error::from(ErrorKind::NoClassFound(path.to_fqname_buf()))),
+            Err(e) => return Err(e.into()),
+        };
+        self.parse_class_bytes(bytes)
+    }
+
+    /// Parses a class file, lazily if this parser is lazy
+    fn parse_class_bytes(&self, bytes: Box<[u8]>) -> Result<JavaClass, Error> {
+        if self.lazy {
+            let header = raw_java_class::parse_class_header_bytes(&bytes)?;
+            Ok(JavaClass::lazy(header, bytes))
+        } else {
+            raw_java_class::parse_class_file_bytes(&bytes).map(JavaClass::new)
         }
     }
 }
 
+/// Checks that a name is a legal class name in the JVM, converting it to internal form. Classes
+/// don't have to be named with java identifiers, so only binary names the JVM rejects are invalid.
+fn class_name(name: &FQName) -> Result<Cow<'_, FQName>, Error> {
+    if !matches!(name.as_type(), NamedType::Class(_)) {
+        return Err(ErrorKind::NotAClass(name.to_string()).into());
+    }
+    if !name.is_binary_name() {
+        return Err(ErrorKind::InvalidName(name.to_string()).into());
+    }
+    Ok(name.canonicalize())
+}
+
+/// Reads the bytes of a classpath resource. Lazy classes keep these until they're parsed.
+fn read_resource(mut resource: Resource) -> Result<Box<[u8]>, Error> {
+    let mut bytes = vec![];
+    resource.read_to_end(&mut bytes)?;
+    Ok(bytes.into())
+}
+
+/// Gets the class name of a classpath entry, if the entry is a class file. The `module-info` and
+/// `package-info` class files don't declare a class, and the versioned classes of multi-release
+/// jars under `META-INF/versions` can't be found by their entry, so these are ignored.
+pub(crate) fn entry_class_name(entry: &str) -> Option<FQNameBuf> {
+    let name = entry.strip_suffix(".class")?;
+    if name.starts_with("META-INF/versions/") {
+        return None;
+    }
+    let simple_name = name.rsplit('/').next().unwrap_or(name);
+    if matches!(simple_name, "module-info" | "package-info") {
+        return None;
+    }
+    Some(FQName::new(name).to_fqname_buf())
+}
+
 impl<P: AsRef<Path>> From<P> for JavaClassParser {
     fn from(p: P) -> Self {
         Self::from_iter([p])
@@ -173,6 +658,17 @@ pub fn parse_bytes<R: Read>(mut read: R) -> Result<JavaClass, Error> {
     raw_java_class::parse_class_file_bytes(&buffer[..]).map(JavaClass::new)
 }
 
+/// Parses the header of a class, up to and including its interfaces, from its bytes. The rest of
+/// the class is parsed the first time it's used.
+///
+/// # Error
+/// Will return an error if the header of the class is invalid
+pub fn parse_bytes_lazy<B: Into<Box<[u8]>>>(bytes: B) -> Result<JavaClass, Error> {
+    let bytes = bytes.into();
+    let header = raw_java_class::parse_class_header_bytes(&bytes)?;
+    Ok(JavaClass::lazy(header, bytes))
+}
+
 /// Parses the contents of a file into a java class
 ///
 /// # Error
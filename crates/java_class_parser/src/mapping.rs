@@ -0,0 +1,789 @@
+//! ProGuard and R8 mapping files, and de-obfuscated views of classes built from them.
+//!
+//! A mapping file records the original name of every class and member that was renamed during
+//! obfuscation. The views created here show those original names alongside the obfuscated ones,
+//! without modifying the classes themselves.
+//!
+//! Mapping files also record how line numbers changed when methods were inlined or moved, so
+//! lines from an obfuscated stack trace can be [retraced](Mapping::retrace) to their original
+//! methods and lines.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::mapping::Mapping;
+//! # use java_class_parser::JavaClassParser;
+//! let mapping = Mapping::from_file("mapping.txt").unwrap();
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("a/b").unwrap();
+//! for method in class.deobfuscate(&mapping).methods() {
+//!     println!("{} ({})", method.original_name(), method.name());
+//! }
+//! ```
+
+use crate::error::Error;
+use crate::{AsFullyQualifiedName, FQName, FQNameBuf, JavaClass, Signature};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// An error that occurred while parsing a mapping file
+#[derive(Debug, thiserror::Error)]
+#[error("invalid mapping on line {line}: {reason}")]
+pub struct MappingError {
+    line: usize,
+    reason: String,
+}
+
+impl MappingError {
+    fn new<S: AsRef<str>>(line: usize, reason: S) -> Self {
+        Self {
+            line,
+            reason: reason.as_ref().to_string(),
+        }
+    }
+
+    /// The line number the error occurred on, starting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// The original name of a field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMapping {
+    original_name: String,
+    obfuscated_name: String,
+    original_descriptor: String,
+}
+
+impl FieldMapping {
+    /// The name of the field before obfuscation
+    pub fn original_name(&self) -> &str {
+        &self.original_name
+    }
+
+    /// The name of the field after obfuscation
+    pub fn obfuscated_name(&self) -> &str {
+        &self.obfuscated_name
+    }
+
+    /// The descriptor of the field, using original class names
+    pub fn original_descriptor(&self) -> &str {
+        &self.original_descriptor
+    }
+}
+
+/// The original name of a method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodMapping {
+    original_name: String,
+    obfuscated_name: String,
+    original_descriptor: String,
+    /// The first and last line of the method in the obfuscated class
+    obfuscated_lines: Option<(u32, u32)>,
+    /// The first and last line of the method in the original source
+    original_lines: Option<(u32, u32)>,
+}
+
+impl MethodMapping {
+    /// The name of the method before obfuscation
+    pub fn original_name(&self) -> &str {
+        &self.original_name
+    }
+
+    /// The name of the method after obfuscation
+    pub fn obfuscated_name(&self) -> &str {
+        &self.obfuscated_name
+    }
+
+    /// The descriptor of the method, using original class names
+    pub fn original_descriptor(&self) -> &str {
+        &self.original_descriptor
+    }
+
+    /// The lines of the obfuscated method this mapping applies to, if the mapping has line numbers
+    pub fn obfuscated_lines(&self) -> Option<RangeInclusive<u32>> {
+        self.obfuscated_lines.map(|(start, end)| start..=end)
+    }
+
+    /// The lines of the method in the original source, if the mapping has line numbers
+    pub fn original_lines(&self) -> Option<RangeInclusive<u32>> {
+        self.original_lines.map(|(start, end)| start..=end)
+    }
+
+    /// Gets the original line of a line in the obfuscated method. Lines are unchanged if the
+    /// mapping has no line numbers, and every line maps to the same line if the original source
+    /// only has one, as happens for the call site of an inlined method.
+    ///
+    /// Returns `None` if the line isn't one of the [obfuscated lines](Self::obfuscated_lines).
+    pub fn original_line(&self, line: u32) -> Option<u32> {
+        let Some((start, end)) = self.obfuscated_lines else {
+            return Some(line);
+        };
+        if line < start || line > end {
+            return None;
+        }
+        match self.original_lines {
+            None => Some(line),
+            Some((original_start, original_end)) if original_start == original_end => {
+                Some(original_start)
+            }
+            Some((original_start, original_end)) => {
+                Some((original_start + (line - start)).min(original_end))
+            }
+        }
+    }
+
+    /// The class the method was originally declared in, if it was inlined from another class
+    pub fn original_class(&self) -> Option<FQNameBuf> {
+        self.original_name
+            .rsplit_once('.')
+            .map(|(class, _)| internal_name(class))
+    }
+
+    /// The name of the method before obfuscation, without the class it was inlined from
+    pub fn original_simple_name(&self) -> &str {
+        self.original_name
+            .rsplit_once('.')
+            .map_or(&self.original_name, |(_, name)| name)
+    }
+}
+
+/// The original names of a class and its members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassMapping {
+    original_name: FQNameBuf,
+    obfuscated_name: FQNameBuf,
+    fields: Vec<FieldMapping>,
+    methods: Vec<MethodMapping>,
+}
+
+impl ClassMapping {
+    /// The name of the class before obfuscation
+    pub fn original_name(&self) -> &FQName {
+        &self.original_name
+    }
+
+    /// The name of the class after obfuscation
+    pub fn obfuscated_name(&self) -> &FQName {
+        &self.obfuscated_name
+    }
+
+    /// The mappings of the fields of the class
+    pub fn fields(&self) -> &[FieldMapping] {
+        &self.fields[..]
+    }
+
+    /// The mappings of the methods of the class. Methods that were inlined into another method
+    /// are included, with their original names qualified by their original class.
+    pub fn methods(&self) -> &[MethodMapping] {
+        &self.methods[..]
+    }
+
+    /// Finds the original methods and lines of a line in an obfuscated method. Methods inlined
+    /// at the line each get a frame, starting with the innermost method, like the frames of a
+    /// stack trace.
+    ///
+    /// Every method with the obfuscated name is returned if the mapping has no line numbers for
+    /// it, as the method can't be told apart from its overloads.
+    pub fn retrace(&self, obfuscated_method: &str, line: u32) -> Vec<RetracedFrame> {
+        let methods = self
+            .methods
+            .iter()
+            .filter(|method| method.obfuscated_name == obfuscated_method)
+            .collect::<Vec<_>>();
+        let at_line = methods
+            .iter()
+            .filter(|method| {
+                method
+                    .obfuscated_lines()
+                    .map_or(false, |lines| lines.contains(&line))
+            })
+            .collect::<Vec<_>>();
+        let matched = if at_line.is_empty() {
+            methods
+                .iter()
+                .filter(|method| method.obfuscated_lines.is_none())
+                .collect()
+        } else {
+            at_line
+        };
+        matched
+            .into_iter()
+            .map(|method| RetracedFrame {
+                class: method
+                    .original_class()
+                    .unwrap_or_else(|| self.original_name.clone()),
+                method: method.original_simple_name().to_string(),
+                line: method.original_line(line).unwrap_or(line),
+            })
+            .collect()
+    }
+}
+
+/// A frame of a stack trace using original names and lines. Created by [`Mapping::retrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetracedFrame {
+    class: FQNameBuf,
+    method: String,
+    line: u32,
+}
+
+impl RetracedFrame {
+    /// The original class of the method
+    pub fn class(&self) -> &FQName {
+        &self.class
+    }
+
+    /// The original name of the method
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The original line in the method
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+}
+
+/// A parsed ProGuard or R8 mapping file
+#[derive(Debug, Clone, Default)]
+pub struct Mapping {
+    classes: Vec<ClassMapping>,
+    by_obfuscated: HashMap<String, usize>,
+    by_original: HashMap<String, usize>,
+}
+
+impl Mapping {
+    /// Parses the contents of a mapping file
+    pub fn parse(mapping: &str) -> Result<Self, MappingError> {
+        let mut classes: Vec<ClassMapping> = vec![];
+        for (index, line) in mapping.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let error = |reason: &str| MappingError::new(line_number, reason);
+            let (left, right) = trimmed
+                .split_once(" -> ")
+                .ok_or_else(|| error("expected ` -> `"))?;
+
+            if !line.starts_with(char::is_whitespace) {
+                let obfuscated = right
+                    .strip_suffix(':')
+                    .ok_or_else(|| error("expected class mapping to end with `:`"))?;
+                classes.push(ClassMapping {
+                    original_name: internal_name(left),
+                    obfuscated_name: internal_name(obfuscated),
+                    fields: vec![],
+                    methods: vec![],
+                });
+                continue;
+            }
+
+            let class = classes
+                .last_mut()
+                .ok_or_else(|| error("member mapping outside of a class"))?;
+            let (obfuscated_lines, left, original_lines) =
+                split_line_numbers(left).map_err(error)?;
+            let obfuscated_name = right.to_string();
+            match left.split_once('(') {
+                Some((return_and_name, rest)) => {
+                    let (return_type, name) = return_and_name
+                        .split_once(' ')
+                        .ok_or_else(|| error("expected a return type and method name"))?;
+                    let (parameters, _) = rest
+                        .split_once(')')
+                        .ok_or_else(|| error("expected `)` after parameters"))?;
+                    let parameters = parameters
+                        .split(',')
+                        .filter(|parameter| !parameter.is_empty())
+                        .map(java_type_descriptor)
+                        .collect::<String>();
+                    class.methods.push(MethodMapping {
+                        original_name: name.to_string(),
+                        obfuscated_name,
+                        original_descriptor: format!(
+                            "({}){}",
+                            parameters,
+                            java_type_descriptor(return_type)
+                        ),
+                        obfuscated_lines,
+                        original_lines,
+                    });
+                }
+                None => {
+                    let (field_type, name) = left
+                        .split_once(' ')
+                        .ok_or_else(|| error("expected a field type and name"))?;
+                    class.fields.push(FieldMapping {
+                        original_name: name.to_string(),
+                        obfuscated_name,
+                        original_descriptor: java_type_descriptor(field_type),
+                    });
+                }
+            }
+        }
+
+        let by_obfuscated = classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.obfuscated_name.to_string(), index))
+            .collect();
+        let by_original = classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.original_name.to_string(), index))
+            .collect();
+        Ok(Self {
+            classes,
+            by_obfuscated,
+            by_original,
+        })
+    }
+
+    /// Reads and parses a mapping file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&contents)?)
+    }
+
+    /// Every class in the mapping
+    pub fn classes(&self) -> &[ClassMapping] {
+        &self.classes[..]
+    }
+
+    /// Gets the mapping of a class by its obfuscated name
+    pub fn class<P: AsFullyQualifiedName + ?Sized>(&self, obfuscated: &P) -> Option<&ClassMapping> {
+        self.by_obfuscated
+            .get(obfuscated.as_fcq().as_str())
+            .map(|&index| &self.classes[index])
+    }
+
+    /// Gets the mapping of a class by its original name
+    pub fn class_by_original<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        original: &P,
+    ) -> Option<&ClassMapping> {
+        self.by_original
+            .get(original.as_fcq().as_str())
+            .map(|&index| &self.classes[index])
+    }
+
+    /// Gets the original name of a class, or the given name if the class wasn't renamed
+    pub fn original_class_name<'a>(&'a self, obfuscated: &'a FQName) -> &'a FQName {
+        self.class(obfuscated)
+            .map(ClassMapping::original_name)
+            .unwrap_or(obfuscated)
+    }
+
+    /// Finds the original methods and lines of a frame in an obfuscated stack trace. Frames of
+    /// classes that aren't in the mapping are returned unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// # use java_class_parser::mapping::Mapping;
+    /// let mapping = Mapping::parse(
+    ///     "com.example.Main -> a:\n    1:1:void helper():7:7 -> b\n    1:1:void run():3 -> b\n",
+    /// )
+    /// .unwrap();
+    /// let frames = mapping.retrace("a", "b", 1);
+    /// assert_eq!(frames[0].method(), "helper");
+    /// assert_eq!(frames[0].line(), 7);
+    /// assert_eq!(frames[1].method(), "run");
+    /// assert_eq!(frames[1].line(), 3);
+    /// ```
+    pub fn retrace<P: AsFullyQualifiedName + ?Sized>(
+        &self,
+        obfuscated_class: &P,
+        obfuscated_method: &str,
+        line: u32,
+    ) -> Vec<RetracedFrame> {
+        let obfuscated_class = obfuscated_class.as_fcq().to_internal_name();
+        match self.class(obfuscated_class.as_str()) {
+            Some(class) => class.retrace(obfuscated_method, line),
+            None => vec![RetracedFrame {
+                class: internal_name(&obfuscated_class),
+                method: obfuscated_method.to_string(),
+                line,
+            }],
+        }
+    }
+
+    /// Converts a descriptor to use original class names
+    pub fn deobfuscate_descriptor(&self, signature: &Signature) -> String {
+        match signature {
+            Signature::FullyQualifiedClass(name) => {
+                format!("L{};", self.original_class_name(FQName::new(name)))
+            }
+            Signature::Array(component) => format!("[{}", self.deobfuscate_descriptor(component)),
+            Signature::Method { args, ret_type } => format!(
+                "({}){}",
+                args.iter()
+                    .map(|arg| self.deobfuscate_descriptor(arg))
+                    .collect::<String>(),
+                self.deobfuscate_descriptor(ret_type)
+            ),
+            primitive => primitive.jni(),
+        }
+    }
+}
+
+/// A field or method shown with both its obfuscated and original name
+#[derive(Debug, Clone)]
+pub struct DeobfuscatedMember<'a> {
+    name: &'a str,
+    original_name: &'a str,
+    descriptor: String,
+    original_descriptor: String,
+    /// The mappings of a method for each range of its lines
+    line_mappings: Vec<&'a MethodMapping>,
+}
+
+impl<'a> DeobfuscatedMember<'a> {
+    /// The name of the member in the class file
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The original name of the member, which is the same as [`name`](Self::name) if it wasn't
+    /// renamed
+    pub fn original_name(&self) -> &'a str {
+        self.original_name
+    }
+
+    /// Checks if the member was renamed
+    pub fn is_renamed(&self) -> bool {
+        self.name != self.original_name
+    }
+
+    /// The descriptor of the member in the class file
+    pub fn descriptor(&self) -> &str {
+        &self.descriptor
+    }
+
+    /// The descriptor of the member using original class names
+    pub fn original_descriptor(&self) -> &str {
+        &self.original_descriptor
+    }
+
+    /// The signature of the member using original class names
+    pub fn original_signature(&self) -> Signature<'_> {
+        Signature::new(&self.original_descriptor).expect("descriptor was created from a signature")
+    }
+
+    /// Gets the original line of a line in a method, such as one from its `LineNumberTable`.
+    /// Lines of methods inlined into this method are given as the line of their outermost call.
+    /// Lines are unchanged if the mapping has no line numbers for the method.
+    pub fn original_line(&self, line: u32) -> u32 {
+        let at_line = self
+            .line_mappings
+            .iter()
+            .filter(|mapping| {
+                mapping
+                    .obfuscated_lines()
+                    .map_or(false, |lines| lines.contains(&line))
+            })
+            .collect::<Vec<_>>();
+        // overloads can share an obfuscated name, so prefer the frames of this method
+        at_line
+            .iter()
+            .rev()
+            .find(|mapping| mapping.original_name == self.original_name)
+            .or_else(|| at_line.last())
+            .and_then(|mapping| mapping.original_line(line))
+            .unwrap_or(line)
+    }
+}
+
+/// A class shown with the original names of itself and its members. Created by
+/// [`JavaClass::deobfuscate`](JavaClass::deobfuscate).
+#[derive(Debug, Clone)]
+pub struct DeobfuscatedClass<'a> {
+    class: &'a JavaClass,
+    mapping: &'a Mapping,
+    class_mapping: Option<&'a ClassMapping>,
+}
+
+impl<'a> DeobfuscatedClass<'a> {
+    /// The class
+    pub fn class(&self) -> &'a JavaClass {
+        self.class
+    }
+
+    /// The name of the class in the class file
+    pub fn this(&self) -> &'a FQName {
+        self.class.this()
+    }
+
+    /// The original name of the class
+    pub fn original_name(&self) -> &'a FQName {
+        self.mapping.original_class_name(self.class.this())
+    }
+
+    /// The original name of the super class, or `None` if the class has no
+    /// [super class](JavaClass::super_class)
+    pub fn original_super_name(&self) -> Option<&'a FQName> {
+        self.class
+            .super_class()
+            .map(|super_class| self.mapping.original_class_name(super_class))
+    }
+
+    /// The fields of the class
+    pub fn fields(&self) -> Vec<DeobfuscatedMember<'a>> {
+        self.class
+            .fields()
+            .iter()
+            .map(|field| {
+                let original_descriptor = self.mapping.deobfuscate_descriptor(field.signature());
+                let original_name = self
+                    .class_mapping
+                    .and_then(|mapping| {
+                        mapping.fields.iter().find(|mapping| {
+                            mapping.obfuscated_name == field.name()
+                                && mapping.original_descriptor == original_descriptor
+                        })
+                    })
+                    .map_or(field.name(), |mapping| mapping.original_name.as_str());
+                DeobfuscatedMember {
+                    name: field.name(),
+                    original_name,
+                    descriptor: field.signature().jni(),
+                    original_descriptor,
+                    line_mappings: vec![],
+                }
+            })
+            .collect()
+    }
+
+    /// The methods of the class
+    pub fn methods(&self) -> Vec<DeobfuscatedMember<'a>> {
+        self.class
+            .methods()
+            .iter()
+            .map(|method| {
+                let original_descriptor = self.mapping.deobfuscate_descriptor(method.signature());
+                let mapping = self.class_mapping.and_then(|mapping| {
+                    mapping.methods.iter().find(|mapping| {
+                        mapping.obfuscated_name == method.name()
+                            && mapping.original_descriptor == original_descriptor
+                    })
+                });
+                let original_name =
+                    mapping.map_or(method.name(), |mapping| mapping.original_name.as_str());
+                let line_mappings = self.class_mapping.map_or(vec![], |class_mapping| {
+                    class_mapping
+                        .methods
+                        .iter()
+                        .filter(|candidate| {
+                            candidate.obfuscated_name == method.name()
+                                && candidate.obfuscated_lines.is_some()
+                        })
+                        .collect()
+                });
+                DeobfuscatedMember {
+                    name: method.name(),
+                    original_name,
+                    descriptor: method.signature().jni(),
+                    original_descriptor,
+                    line_mappings,
+                }
+            })
+            .collect()
+    }
+}
+
+impl JavaClass {
+    /// Creates a view of this class using the original names recorded in a mapping
+    pub fn deobfuscate<'a>(&'a self, mapping: &'a Mapping) -> DeobfuscatedClass<'a> {
+        DeobfuscatedClass {
+            class: self,
+            mapping,
+            class_mapping: mapping.class(self.this()),
+        }
+    }
+}
+
+fn internal_name(java_name: &str) -> FQNameBuf {
+    FQName::new(&java_name.replace('.', "/")).to_fqname_buf()
+}
+
+type LineRange = Option<(u32, u32)>;
+
+/// Splits the line number ranges that may surround a method from it, as in
+/// `1:3:void run():10:12`. The first range is of the obfuscated lines, and the second is of the
+/// original lines, which may be a single line.
+fn split_line_numbers(member: &str) -> Result<(LineRange, &str, LineRange), &'static str> {
+    fn parse_lines(lines: &[&str]) -> Result<LineRange, &'static str> {
+        let lines = lines
+            .iter()
+            .map(|line| line.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "invalid line number")?;
+        Ok(match lines[..] {
+            [] => None,
+            [line] => Some((line, line)),
+            [start, end] => Some((start, end)),
+            _ => return Err("expected at most two line numbers"),
+        })
+    }
+
+    let mut member = member;
+    let mut obfuscated = vec![];
+    while let Some((prefix, rest)) = member.split_once(':') {
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+        obfuscated.push(prefix);
+        member = rest;
+    }
+    let (member, original) = match member.rfind(')') {
+        Some(end) => {
+            let original = member[end + 1..]
+                .split(':')
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>();
+            (&member[..=end], original)
+        }
+        None => (member, vec![]),
+    };
+    Ok((parse_lines(&obfuscated)?, member, parse_lines(&original)?))
+}
+
+/// Converts a type as written in java source, such as `java.lang.String[]`, to a descriptor
+fn java_type_descriptor(java_type: &str) -> String {
+    let component = java_type.trim_end_matches("[]");
+    let dimensions = (java_type.len() - component.len()) / 2;
+    let descriptor = match component {
+        "boolean" => "Z".to_string(),
+        "byte" => "B".to_string(),
+        "char" => "C".to_string(),
+        "short" => "S".to_string(),
+        "int" => "I".to_string(),
+        "long" => "J".to_string(),
+        "float" => "F".to_string(),
+        "double" => "D".to_string(),
+        "void" => "V".to_string(),
+        class => format!("L{};", class.replace('.', "/")),
+    };
+    format!("{}{}", "[".repeat(dimensions), descriptor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = "\
+# compiler: R8
+com.example.Main -> a:
+    java.lang.String[] names -> a
+    1:3:void run(com.example.Main,int[]):10:12 -> b
+    4:4:com.example.Main self():20 -> c
+com.example.Other -> b:
+";
+
+    #[test]
+    fn parse_mapping() {
+        let mapping = Mapping::parse(MAPPING).unwrap();
+        let main = mapping.class("a").unwrap();
+        assert_eq!(main.original_name(), "com/example/Main");
+        assert_eq!(
+            main.fields()[0].original_descriptor(),
+            "[Ljava/lang/String;"
+        );
+        let methods = main
+            .methods()
+            .iter()
+            .map(|method| (method.original_name(), method.original_descriptor()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            [
+                ("run", "(Lcom/example/Main;[I)V"),
+                ("self", "()Lcom/example/Main;")
+            ]
+        );
+        assert_eq!(
+            mapping
+                .class_by_original("com/example/Other")
+                .unwrap()
+                .obfuscated_name(),
+            "b"
+        );
+    }
+
+    #[test]
+    fn deobfuscate_descriptor() {
+        let mapping = Mapping::parse(MAPPING).unwrap();
+        let signature = Signature::new("(La;[Lb;I)Lc;").unwrap();
+        assert_eq!(
+            mapping.deobfuscate_descriptor(&signature),
+            "(Lcom/example/Main;[Lcom/example/Other;I)Lc;"
+        );
+    }
+
+    #[test]
+    fn parse_line_numbers() {
+        let mapping = Mapping::parse(MAPPING).unwrap();
+        let main = mapping.class("a").unwrap();
+        let run = &main.methods()[0];
+        assert_eq!(run.obfuscated_lines(), Some(1..=3));
+        assert_eq!(run.original_lines(), Some(10..=12));
+        assert_eq!(run.original_line(2), Some(11));
+        assert_eq!(run.original_line(4), None);
+        let this = &main.methods()[1];
+        assert_eq!(this.original_lines(), Some(20..=20));
+        assert_eq!(this.original_line(4), Some(20));
+    }
+
+    #[test]
+    fn retrace_inlined_frames() {
+        let mapping = Mapping::parse(
+            "\
+com.example.Main -> a:
+    1:3:void run():10:12 -> b
+    4:4:int com.example.Util.size(java.lang.Object):30:30 -> b
+    4:4:void run():13 -> b
+    5:6:void run():14:15 -> b
+    void unmapped() -> c
+",
+        )
+        .unwrap();
+        let frames = mapping
+            .retrace("a", "b", 4)
+            .into_iter()
+            .map(|frame| {
+                (
+                    frame.class().to_string(),
+                    frame.method().to_string(),
+                    frame.line(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            [
+                ("com/example/Util".to_string(), "size".to_string(), 30),
+                ("com/example/Main".to_string(), "run".to_string(), 13),
+            ]
+        );
+        let frames = mapping.retrace("a", "b", 6);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].line(), 15);
+        let frames = mapping.retrace("a", "c", 8);
+        assert_eq!(frames[0].method(), "unmapped");
+        assert_eq!(frames[0].line(), 8);
+        let frames = mapping.retrace("x.y", "z", 3);
+        assert_eq!(frames[0].class(), "x/y");
+        assert_eq!(frames[0].method(), "z");
+    }
+
+    #[test]
+    fn invalid_mapping() {
+        let error = Mapping::parse("a -> b:\n    int c\n").unwrap_err();
+        assert_eq!(error.line(), 2);
+        let error = Mapping::parse("a -> b:\n    99999999999:1:void c() -> d\n").unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+}
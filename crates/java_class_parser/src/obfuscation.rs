@@ -0,0 +1,282 @@
+//! Heuristics for detecting classes that have likely been run through an obfuscator such as
+//! ProGuard or R8, so tools can flag them before attempting further analysis.
+//!
+//! No single indicator is conclusive, as hand written code can have short names or lack debug
+//! information too. Indicators are instead combined into a score between `0.0` and `1.0`.
+//!
+//! | Indicator | Weight |
+//! |-----------|--------|
+//! | Fraction of class, field and method names that are at most 2 characters long | 0.4 |
+//! | Fraction of names that are valid in class files but not in java source | 0.2 |
+//! | Missing both a source file and line numbers | 0.25 |
+//! | Density of synthetic members, saturating at one synthetic member in two | 0.15 |
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::obfuscation::JarObfuscation;
+//! let report = JarObfuscation::from_jar("app.jar").unwrap();
+//! if report.is_likely_obfuscated() {
+//!     println!("app.jar looks obfuscated ({:.2})", report.score());
+//! }
+//! ```
+
+use crate::attributes::AttributeKind;
+use crate::error::Error;
+use crate::{entry_class_name, parse_bytes, FQNameBuf, HasAttributes, JavaClass};
+use std::fs::File;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Scores at or above this are considered likely obfuscated
+pub const LIKELY_OBFUSCATED: f64 = 0.5;
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Checks if a name could have been written in java source. The class file format allows nearly
+/// any name, which obfuscators use to make decompiled code fail to compile.
+pub fn is_java_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    (first.is_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        && !KEYWORDS.contains(&name)
+}
+
+/// The obfuscation indicators found in a single class
+#[derive(Debug, Clone)]
+pub struct ClassObfuscation {
+    name: FQNameBuf,
+    names: usize,
+    short_names: usize,
+    illegal_names: usize,
+    members: usize,
+    synthetic_members: usize,
+    has_source_file: bool,
+    has_line_numbers: bool,
+}
+
+impl ClassObfuscation {
+    /// Inspects a class for obfuscation indicators
+    pub fn of(class: &JavaClass) -> Self {
+        let this = class.this();
+        // only the innermost part of a nested class name is chosen by its author
+        let simple_name = this.as_str().rsplit(['/', '$']).next().unwrap_or_default();
+        let fields = class.fields();
+        let methods = class.methods();
+
+        let mut names = vec![simple_name];
+        names.extend(fields.iter().map(|field| field.name()));
+        names.extend(
+            methods
+                .iter()
+                .map(|method| method.name())
+                .filter(|name| !matches!(*name, "<init>" | "<clinit>")),
+        );
+
+        let synthetic_members = fields
+            .iter()
+            .filter(|field| field.access_flags().is_synthetic())
+            .count()
+            + methods
+                .iter()
+                .filter(|method| method.access_flags().is_synthetic())
+                .count();
+        let has_line_numbers = methods.iter().any(|method| {
+            method.attributes().any(|attribute| match attribute.kind() {
+                AttributeKind::Code(code) => code.get_attribute("LineNumberTable").is_some(),
+                _ => false,
+            })
+        });
+
+        Self {
+            name: this.to_fqname_buf(),
+            names: names.len(),
+            // anonymous classes are named with numbers, which are short but not obfuscated
+            short_names: names
+                .iter()
+                .filter(|name| name.chars().count() <= 2 && !name.chars().all(char::is_numeric))
+                .count(),
+            illegal_names: names
+                .iter()
+                .filter(|name| !name.chars().all(char::is_numeric))
+                .filter(|name| !is_java_identifier(name))
+                .count(),
+            members: fields.len() + methods.len(),
+            synthetic_members,
+            has_source_file: class.get_attribute("SourceFile").is_some(),
+            has_line_numbers,
+        }
+    }
+
+    /// The name of the class
+    pub fn name(&self) -> &FQNameBuf {
+        &self.name
+    }
+
+    /// The fraction of names that are at most 2 characters long
+    pub fn short_name_ratio(&self) -> f64 {
+        ratio(self.short_names, self.names)
+    }
+
+    /// The fraction of names that aren't valid java identifiers
+    pub fn illegal_name_ratio(&self) -> f64 {
+        ratio(self.illegal_names, self.names)
+    }
+
+    /// The fraction of fields and methods that are synthetic
+    pub fn synthetic_density(&self) -> f64 {
+        ratio(self.synthetic_members, self.members)
+    }
+
+    /// Whether the class is missing both its source file and line numbers
+    pub fn missing_debug_info(&self) -> bool {
+        !self.has_source_file && !self.has_line_numbers
+    }
+
+    /// The combined obfuscation score, between `0.0` and `1.0`
+    pub fn score(&self) -> f64 {
+        0.4 * self.short_name_ratio()
+            + 0.2 * self.illegal_name_ratio()
+            + 0.25 * if self.missing_debug_info() { 1.0 } else { 0.0 }
+            + 0.15 * (self.synthetic_density() * 2.0).min(1.0)
+    }
+
+    /// Whether the score is at least [`LIKELY_OBFUSCATED`]
+    pub fn is_likely_obfuscated(&self) -> bool {
+        self.score() >= LIKELY_OBFUSCATED
+    }
+}
+
+/// The obfuscation indicators of every class in a jar
+#[derive(Debug, Clone)]
+pub struct JarObfuscation {
+    classes: Vec<ClassObfuscation>,
+}
+
+impl JarObfuscation {
+    /// Inspects every class in a jar for obfuscation indicators
+    ///
+    /// # Error
+    /// Will return an error if the jar can't be read, or any class within it can't be parsed.
+    pub fn from_jar<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut archive = ZipArchive::new(File::open(path)?)?;
+        let mut classes = vec![];
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index)?;
+            if entry_class_name(entry.name()).is_some() {
+                classes.push(ClassObfuscation::of(&parse_bytes(entry)?));
+            }
+        }
+        Ok(Self { classes })
+    }
+
+    /// The indicators of each class, in the order they appear in the jar
+    pub fn classes(&self) -> &[ClassObfuscation] {
+        &self.classes[..]
+    }
+
+    /// The mean score of every class
+    pub fn score(&self) -> f64 {
+        if self.classes.is_empty() {
+            return 0.0;
+        }
+        self.classes
+            .iter()
+            .map(ClassObfuscation::score)
+            .sum::<f64>()
+            / self.classes.len() as f64
+    }
+
+    /// Classes that are likely obfuscated on their own
+    pub fn likely_obfuscated_classes(&self) -> impl Iterator<Item = &ClassObfuscation> {
+        self.classes
+            .iter()
+            .filter(|class| class.is_likely_obfuscated())
+    }
+
+    /// Whether the mean score is at least [`LIKELY_OBFUSCATED`]
+    pub fn is_likely_obfuscated(&self) -> bool {
+        self.score() >= LIKELY_OBFUSCATED
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_java_identifier;
+
+    #[test]
+    fn java_identifiers() {
+        assert!(is_java_identifier("getArea"));
+        assert!(is_java_identifier("$values"));
+        assert!(is_java_identifier("ñ"));
+        assert!(!is_java_identifier("do"));
+        assert!(!is_java_identifier("1a"));
+        assert!(!is_java_identifier("a-b"));
+        assert!(!is_java_identifier("\u{2002}"));
+        assert!(!is_java_identifier(""));
+    }
+}
@@ -3,7 +3,8 @@
 //! [class_file]: https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.1
 
 use crate::constant_pool::{parser, ConstantPool};
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
+use crate::Signature;
 use nom::combinator::eof;
 use nom::error::ParseError;
 use nom::number::complete::{be_u16, be_u32};
@@ -23,6 +24,8 @@ pub struct RawJavaClass {
     pub access_flags: u16,
     pub this_class: u16,
     pub super_class: u16,
+    // kept to mirror the class file, the count is written from the interfaces themselves
+    #[allow(dead_code)]
     pub interfaces_count: u16,
     pub interfaces: Box<[u16]>,
     pub fields_count: u16,
@@ -57,63 +60,199 @@ pub struct RawMethodInfo {
 #[derive(Debug, Default, Clone)]
 pub struct RawAttributeInfo {
     pub attribute_name_index: u16,
+    // kept to mirror the class file, the length is written from the info itself
+    #[allow(dead_code)]
     pub attribute_length: u32,
     pub info: Box<[u8]>,
 }
 
-/// Should parse the entire byte array to create a raw java class
-pub fn parse_class_file_bytes(bytes: &[u8]) -> Result<RawJavaClass, Error> {
-    fn inner<'a, E: ParseError<&'a [u8]>>(bytes: &'a [u8]) -> IResult<&'a [u8], RawJavaClass, E> {
-        let mut tuple_parser = tuple((be_u32, be_u16, be_u16, be_u16));
+impl RawJavaClass {
+    /// Writes this class file back into bytes. Counts are taken from the lengths of the constant
+    /// pool, interfaces, fields, methods and attributes rather than their count fields.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.magic.to_be_bytes());
+        out.extend(self.minor.to_be_bytes());
+        out.extend(self.major.to_be_bytes());
+        out.extend((self.constant_pool.len() as u16 + 1).to_be_bytes());
+        self.constant_pool.write(&mut out);
+        for value in [
+            self.access_flags,
+            self.this_class,
+            self.super_class,
+            self.interfaces.len() as u16,
+        ] {
+            out.extend(value.to_be_bytes());
+        }
+        for interface in self.interfaces.iter() {
+            out.extend(interface.to_be_bytes());
+        }
+        out.extend((self.fields.len() as u16).to_be_bytes());
+        for field in self.fields.iter() {
+            write_member(
+                field.access_flags,
+                field.name_index,
+                field.descriptor_index,
+                &field.attributes,
+                &mut out,
+            );
+        }
+        out.extend((self.methods.len() as u16).to_be_bytes());
+        for method in self.methods.iter() {
+            write_member(
+                method.access_flags,
+                method.name_index,
+                method.descriptor_index,
+                &method.attributes,
+                &mut out,
+            );
+        }
+        write_attributes(&self.attributes, &mut out);
+        out
+    }
+}
 
-        let (bytes, (magic, major, minor, constant_pool_count)) = tuple_parser(bytes)?;
+impl RawAttributeInfo {
+    /// Writes this attribute as it's stored in a class file. The length is taken from the length
+    /// of its info rather than its length field.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.extend(self.attribute_name_index.to_be_bytes());
+        out.extend((self.info.len() as u32).to_be_bytes());
+        out.extend(&*self.info);
+    }
+}
 
-        // for some reason, the constant pool contains n - 1 entries
-        let (bytes, constant_pool) = parser::parse_constant_pool(constant_pool_count - 1)(bytes)?;
+/// Writes the count of some attributes, followed by the attributes
+pub(crate) fn write_attributes(attributes: &[RawAttributeInfo], out: &mut Vec<u8>) {
+    out.extend((attributes.len() as u16).to_be_bytes());
+    for attribute in attributes {
+        attribute.write(out);
+    }
+}
 
-        let mut tuple_parser = tuple((be_u16, be_u16, be_u16, be_u16));
-        let (bytes, (access_flags, this_class, super_class, interfaces_count)) =
-            tuple_parser(bytes)?;
-        let (bytes, interfaces) = multi::count(be_u16, interfaces_count as usize)(bytes)?;
+fn write_member(
+    access_flags: u16,
+    name_index: u16,
+    descriptor_index: u16,
+    attributes: &[RawAttributeInfo],
+    out: &mut Vec<u8>,
+) {
+    for value in [access_flags, name_index, descriptor_index] {
+        out.extend(value.to_be_bytes());
+    }
+    write_attributes(attributes, out);
+}
 
-        let (bytes, fields_count) = be_u16(bytes)?;
-        let mut fields = vec![RawFieldInfo::default(); fields_count as usize];
-        let (bytes, _) = multi::fill(parser::parse_field_info, &mut fields)(bytes)?;
+/// Parses a class file from the start of some bytes, leaving any bytes after it unparsed
+pub(crate) fn parse_class_file<'a, E: ParseError<&'a [u8]>>(
+    bytes: &'a [u8],
+) -> IResult<&'a [u8], RawJavaClass, E> {
+    let (bytes, mut class) = parse_class_header(bytes)?;
 
-        let (bytes, methods_count) = be_u16(bytes)?;
-        let mut methods = vec![RawMethodInfo::default(); methods_count as usize];
-        let (bytes, _) = multi::fill(parser::parse_method_info, &mut methods)(bytes)?;
+    let (bytes, fields_count) = be_u16(bytes)?;
+    let mut fields = vec![RawFieldInfo::default(); fields_count as usize];
+    let (bytes, _) = multi::fill(parser::parse_field_info, &mut fields)(bytes)?;
 
-        let (bytes, attributes_count) = be_u16(bytes)?;
-        let mut attributes = vec![RawAttributeInfo::default(); attributes_count as usize];
-        let (bytes, _) = multi::fill(parser::parse_attribute_info, &mut attributes)(bytes)?;
+    let (bytes, methods_count) = be_u16(bytes)?;
+    let mut methods = vec![RawMethodInfo::default(); methods_count as usize];
+    let (bytes, _) = multi::fill(parser::parse_method_info, &mut methods)(bytes)?;
 
-        let (bytes, _) = eof(bytes)?;
+    let (bytes, attributes_count) = be_u16(bytes)?;
+    let mut attributes = vec![RawAttributeInfo::default(); attributes_count as usize];
+    let (bytes, _) = multi::fill(parser::parse_attribute_info, &mut attributes)(bytes)?;
+
+    class.fields_count = fields_count;
+    class.fields = fields.into_boxed_slice();
+    class.methods_count = methods_count;
+    class.methods = methods.into_boxed_slice();
+    class.attributes_count = attributes_count;
+    class.attributes = attributes.into_boxed_slice();
+    Ok((bytes, class))
+}
+
+/// Parses a class file up to and including its interfaces, leaving its fields, methods and
+/// attributes empty
+pub(crate) fn parse_class_header<'a, E: ParseError<&'a [u8]>>(
+    bytes: &'a [u8],
+) -> IResult<&'a [u8], RawJavaClass, E> {
+    let mut tuple_parser = tuple((be_u32, be_u16, be_u16, be_u16));
 
-        Ok((
-            bytes,
-            RawJavaClass {
-                magic,
-                major,
-                minor,
-                constant_pool_count,
-                constant_pool,
-                access_flags,
-                this_class,
-                super_class,
-                interfaces_count,
-                interfaces: interfaces.into_boxed_slice(),
-                fields_count,
-                fields: fields.into_boxed_slice(),
-                methods_count,
-                methods: methods.into_boxed_slice(),
-                attributes_count,
-                attributes: attributes.into_boxed_slice(),
-            },
-        ))
+    let (bytes, (magic, minor, major, constant_pool_count)) = tuple_parser(bytes)?;
+
+    // for some reason, the constant pool contains n - 1 entries
+    let (bytes, constant_pool) =
+        parser::parse_constant_pool(constant_pool_count.saturating_sub(1))(bytes)?;
+
+    let mut tuple_parser = tuple((be_u16, be_u16, be_u16, be_u16));
+    let (bytes, (access_flags, this_class, super_class, interfaces_count)) = tuple_parser(bytes)?;
+    let (bytes, interfaces) = multi::count(be_u16, interfaces_count as usize)(bytes)?;
+
+    Ok((
+        bytes,
+        RawJavaClass {
+            magic,
+            major,
+            minor,
+            constant_pool_count,
+            constant_pool,
+            access_flags,
+            this_class,
+            super_class,
+            interfaces_count,
+            interfaces: interfaces.into_boxed_slice(),
+            fields_count: 0,
+            fields: Box::new([]),
+            methods_count: 0,
+            methods: Box::new([]),
+            attributes_count: 0,
+            attributes: Box::new([]),
+        },
+    ))
+}
+
+/// Parses only the header of a class file, up to and including its interfaces
+pub fn parse_class_header_bytes(bytes: &[u8]) -> Result<RawJavaClass, Error> {
+    parse_class_header::<nom::error::Error<_>>(bytes)
+        .map(|(_, java)| java)
+        .map_err(Error::from)
+}
+
+/// Should parse the entire byte array to create a raw java class
+pub fn parse_class_file_bytes(bytes: &[u8]) -> Result<RawJavaClass, Error> {
+    fn inner<'a, E: ParseError<&'a [u8]>>(bytes: &'a [u8]) -> IResult<&'a [u8], RawJavaClass, E> {
+        let (bytes, class) = parse_class_file(bytes)?;
+        let (bytes, _) = eof(bytes)?;
+        Ok((bytes, class))
     }
 
-    inner::<nom::error::Error<_>>(bytes)
+    let class = inner::<nom::error::Error<_>>(bytes)
         .map(|(_, java)| java)
-        .map_err(|e| Error::from(e))
+        .map_err(Error::from)?;
+    check_members(&class)?;
+    Ok(class)
+}
+
+/// Checks that the name of every field and method is a utf8 constant, and that their descriptors
+/// can be parsed, so members can be viewed without failing.
+pub(crate) fn check_members(class: &RawJavaClass) -> Result<(), Error> {
+    let pool = &class.constant_pool;
+    let fields = class
+        .fields
+        .iter()
+        .map(|field| (field.name_index, field.descriptor_index));
+    let methods = class
+        .methods
+        .iter()
+        .map(|method| (method.name_index, method.descriptor_index));
+    for (name_index, descriptor_index) in fields.chain(methods) {
+        pool.get_utf8_lossy(name_index)
+            .ok_or(ErrorKind::InvalidConstantIndex(name_index))?;
+        let descriptor = pool
+            .get_utf8_lossy(descriptor_index)
+            .ok_or(ErrorKind::InvalidConstantIndex(descriptor_index))?;
+        if Signature::new(descriptor).is_err() {
+            return Err(ErrorKind::InvalidDescriptor(descriptor.to_string()).into());
+        }
+    }
+    Ok(())
 }
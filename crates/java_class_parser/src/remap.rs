@@ -0,0 +1,605 @@
+//! Renames classes, packages and members throughout a class file, producing the bytes of a new
+//! class file. This is the basis of shading, where the dependencies of a library are relocated
+//! into its own packages so that they can't conflict with other versions of the same dependency.
+//!
+//! Names are rewritten everywhere the JVM specification defines them to be used: class constants,
+//! field and method references, descriptors, generic signatures, annotations, inner classes,
+//! local variables and record components. String literals aren't rewritten, so classes loaded by
+//! reflection keep their old names.
+//!
+//! Members are renamed only where they're referred to through the class they're mapped for, so a
+//! method inherited by a subclass must be mapped for the subclass as well to rename calls made
+//! through the subclass.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::parse_file;
+//! # use java_class_parser::remap::Remapper;
+//! let remapper = Remapper::new()
+//!     .package("com/google/common", "shaded/com/google/common")
+//!     .class("com/example/Square", "com/example/Quadrilateral")
+//!     .method("com/example/Square", "getArea", "()D", "area");
+//! let class = parse_file("Square.class").expect("couldn't parse class");
+//! let remapped = remapper.remap(&class).expect("couldn't remap class");
+//! std::fs::write("Quadrilateral.class", remapped).expect("couldn't write class");
+//! ```
+
+use crate::constant_pool::references::{self, IndexUse};
+use crate::constant_pool::values::{
+    Class, Dynamic, FieldRef, InterfaceMethodRef, InvokeDynamic, MethodRef, MethodType, Package,
+};
+use crate::constant_pool::{ConstantPool, ConstantPoolInfo};
+use crate::error::Error;
+use crate::generics::{
+    ClassTypeSignature, FieldTypeSignature, GenericSignature, TypeArgument, TypeParameter,
+    TypeSignature,
+};
+use crate::mapping::Mapping;
+use crate::{AsFullyQualifiedName, ConstantPoolBuilder, FQName, FQNameBuf, JavaClass};
+use std::collections::HashMap;
+
+/// Maps old names of classes and members to new names, and rewrites class files to use the new
+/// names. Names are given in their old form, and classes can be named in either binary or
+/// internal form.
+///
+/// Classes mapped individually take precedence over packages, and the most specific package
+/// mapping is used when packages are nested.
+#[derive(Debug, Clone, Default)]
+pub struct Remapper {
+    classes: HashMap<String, String>,
+    /// Old and new package prefixes, both ending in `/`
+    packages: Vec<(String, String)>,
+    /// New field names, by their class and old name
+    fields: HashMap<(String, String), String>,
+    /// New method names, by their class, old name and old descriptor
+    methods: HashMap<(String, String, String), String>,
+}
+
+impl Remapper {
+    /// Creates a remapper that doesn't rename anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames a class
+    pub fn class<O, N>(mut self, old: &O, new: &N) -> Self
+    where
+        O: AsFullyQualifiedName + ?Sized,
+        N: AsFullyQualifiedName + ?Sized,
+    {
+        self.classes.insert(
+            old.as_fcq().to_internal_name(),
+            new.as_fcq().to_internal_name(),
+        );
+        self
+    }
+
+    /// Moves every class in a package and its subpackages into another package, such as
+    /// `com/google` into `shaded/com/google`
+    pub fn package<O, N>(mut self, old: &O, new: &N) -> Self
+    where
+        O: AsFullyQualifiedName + ?Sized,
+        N: AsFullyQualifiedName + ?Sized,
+    {
+        let prefix = |name: &FQName| format!("{}/", name.to_internal_name());
+        self.packages
+            .push((prefix(old.as_fcq()), prefix(new.as_fcq())));
+        // the longest prefix is the most specific
+        self.packages
+            .sort_by_key(|(old, _)| std::cmp::Reverse(old.len()));
+        self
+    }
+
+    /// Renames a field of a class
+    pub fn field<C: AsFullyQualifiedName + ?Sized>(
+        mut self,
+        class: &C,
+        name: &str,
+        new_name: &str,
+    ) -> Self {
+        self.fields.insert(
+            (class.as_fcq().to_internal_name(), name.to_string()),
+            new_name.to_string(),
+        );
+        self
+    }
+
+    /// Renames a method of a class. Methods are identified by their name and descriptor, as
+    /// overloads can be renamed separately.
+    pub fn method<C: AsFullyQualifiedName + ?Sized>(
+        mut self,
+        class: &C,
+        name: &str,
+        descriptor: &str,
+        new_name: &str,
+    ) -> Self {
+        self.methods.insert(
+            (
+                class.as_fcq().to_internal_name(),
+                name.to_string(),
+                descriptor.to_string(),
+            ),
+            new_name.to_string(),
+        );
+        self
+    }
+
+    /// Gets the new name of a class in internal form. Array classes, named by their descriptor,
+    /// have their component class renamed.
+    pub fn map_class<N: AsFullyQualifiedName + ?Sized>(&self, name: &N) -> FQNameBuf {
+        let name = name.as_fcq().to_internal_name();
+        let mapped = self.mapped_class(&name).unwrap_or(name);
+        FQName::new(&mapped).to_fqname_buf()
+    }
+
+    /// Gets a field or method descriptor with every class in it renamed
+    pub fn map_descriptor(&self, descriptor: &str) -> String {
+        let mut mapped = String::with_capacity(descriptor.len());
+        let mut rest = descriptor;
+        while let Some(start) = rest.find('L') {
+            let Some(end) = rest[start..].find(';') else {
+                break;
+            };
+            let name = &rest[start + 1..start + end];
+            mapped.push_str(&rest[..=start]);
+            match self.mapped_class(name) {
+                Some(new) => mapped.push_str(&new),
+                None => mapped.push_str(name),
+            }
+            mapped.push(';');
+            rest = &rest[start + end + 1..];
+        }
+        mapped.push_str(rest);
+        mapped
+    }
+
+    /// Gets a generic signature with every class in it renamed. Signatures that can't be parsed
+    /// are returned unchanged.
+    pub fn map_signature(&self, signature: &str) -> String {
+        let Ok(parsed) = GenericSignature::new(signature) else {
+            return signature.to_string();
+        };
+        let mut mapped = String::with_capacity(signature.len());
+        match &parsed {
+            GenericSignature::Class(class) => {
+                self.write_type_parameters(class.type_parameters(), &mut mapped);
+                self.write_class_type(class.superclass(), &mut mapped);
+                for interface in class.interfaces() {
+                    self.write_class_type(interface, &mut mapped);
+                }
+            }
+            GenericSignature::Method(method) => {
+                self.write_type_parameters(method.type_parameters(), &mut mapped);
+                mapped.push('(');
+                for parameter in method.parameters() {
+                    self.write_type(parameter, &mut mapped);
+                }
+                mapped.push(')');
+                self.write_type(method.return_type(), &mut mapped);
+                for throws in method.throws() {
+                    mapped.push('^');
+                    self.write_field_type(throws, &mut mapped);
+                }
+            }
+            GenericSignature::Field(field) => self.write_field_type(field, &mut mapped),
+        }
+        mapped
+    }
+
+    /// Gets the new name of a field
+    pub fn map_field_name<'a, C: AsFullyQualifiedName + ?Sized>(
+        &'a self,
+        class: &C,
+        name: &'a str,
+    ) -> &'a str {
+        self.fields
+            .get(&(class.as_fcq().to_internal_name(), name.to_string()))
+            .map_or(name, String::as_str)
+    }
+
+    /// Gets the new name of a method, given its old descriptor
+    pub fn map_method_name<'a, C: AsFullyQualifiedName + ?Sized>(
+        &'a self,
+        class: &C,
+        name: &'a str,
+        descriptor: &str,
+    ) -> &'a str {
+        self.methods
+            .get(&(
+                class.as_fcq().to_internal_name(),
+                name.to_string(),
+                descriptor.to_string(),
+            ))
+            .map_or(name, String::as_str)
+    }
+
+    /// Rewrites a class to use the new names. Constants that are no longer used are removed from
+    /// the constant pool.
+    ///
+    /// # Error
+    /// Will return an error if the class was parsed lazily and is invalid, refers to a constant
+    /// that doesn't exist, or has an attribute that is malformed or not defined by the JVM
+    /// specification, as the names used by unknown attributes can't be found.
+    pub fn remap(&self, class: &JavaClass) -> Result<Vec<u8>, Error> {
+        class.decode()?;
+        let pool = class.constant_pool();
+        let this = class.this().as_str();
+        let mut constants = ConstantPoolBuilder::from(pool);
+        for (index, info) in pool.iter() {
+            if let Some(info) = self.map_constant(pool, info, &mut constants)? {
+                constants.replace(index, info);
+            }
+        }
+
+        let mut raw = class.raw().clone();
+        references::map_indices(&mut raw, &mut |index, usage| {
+            self.map_index(pool, this, index, usage, &mut constants)
+        })?;
+        raw.constant_pool = constants.build();
+        references::compact(&mut raw)?;
+        Ok(raw.to_bytes())
+    }
+
+    /// Gets the new name of a class in internal form, or `None` if it isn't renamed
+    fn mapped_class(&self, name: &str) -> Option<String> {
+        if name.starts_with('[') {
+            let mapped = self.map_descriptor(name);
+            return (mapped != name).then_some(mapped);
+        }
+        if let Some(new) = self.classes.get(name) {
+            return Some(new.clone());
+        }
+        self.packages.iter().find_map(|(old, new)| {
+            name.strip_prefix(old.as_str())
+                .map(|rest| format!("{new}{rest}"))
+        })
+    }
+
+    /// Gets the new name of a package in internal form, or `None` if it isn't moved
+    fn mapped_package(&self, name: &str) -> Option<String> {
+        let name = format!("{name}/");
+        self.packages.iter().find_map(|(old, new)| {
+            let rest = name.strip_prefix(old.as_str())?;
+            let mapped = format!("{new}{rest}");
+            Some(mapped[..mapped.len() - 1].to_string())
+        })
+    }
+
+    /// Renames the names used by a constant, returning a replacement constant if any changed.
+    /// The utf8 constants of the old names are left in place, as they may be used elsewhere.
+    fn map_constant(
+        &self,
+        pool: &ConstantPool,
+        info: &ConstantPoolInfo,
+        constants: &mut ConstantPoolBuilder,
+    ) -> Result<Option<ConstantPoolInfo>, Error> {
+        let replacement = match info {
+            ConstantPoolInfo::Class(Class { name_index }) => {
+                let Some(new) = pool
+                    .get_utf8(*name_index)
+                    .and_then(|name| self.mapped_class(name))
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::Class(Class {
+                    name_index: constants.utf8(&new)?,
+                })
+            }
+            ConstantPoolInfo::FieldRef(FieldRef {
+                class_index,
+                name_and_type_index,
+            }) => {
+                let Some(name_and_type_index) =
+                    self.map_member(pool, *class_index, *name_and_type_index, false, constants)?
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::FieldRef(FieldRef {
+                    class_index: *class_index,
+                    name_and_type_index,
+                })
+            }
+            ConstantPoolInfo::MethodRef(MethodRef {
+                class_index,
+                name_and_type_index,
+            }) => {
+                let Some(name_and_type_index) =
+                    self.map_member(pool, *class_index, *name_and_type_index, true, constants)?
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::MethodRef(MethodRef {
+                    class_index: *class_index,
+                    name_and_type_index,
+                })
+            }
+            ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            }) => {
+                let Some(name_and_type_index) =
+                    self.map_member(pool, *class_index, *name_and_type_index, true, constants)?
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                    class_index: *class_index,
+                    name_and_type_index,
+                })
+            }
+            ConstantPoolInfo::Dynamic(Dynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            }) => {
+                let Some(name_and_type_index) =
+                    self.map_name_and_type(pool, *name_and_type_index, None, constants)?
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::Dynamic(Dynamic {
+                    bootstrap_method_attr_index: *bootstrap_method_attr_index,
+                    name_and_type_index,
+                })
+            }
+            ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            }) => {
+                let Some(name_and_type_index) =
+                    self.map_name_and_type(pool, *name_and_type_index, None, constants)?
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                    bootstrap_method_attr_index: *bootstrap_method_attr_index,
+                    name_and_type_index,
+                })
+            }
+            ConstantPoolInfo::MethodType(MethodType { descriptor_index }) => {
+                let Some(descriptor) = pool.get_utf8(*descriptor_index) else {
+                    return Ok(None);
+                };
+                let mapped = self.map_descriptor(descriptor);
+                if mapped == descriptor {
+                    return Ok(None);
+                }
+                ConstantPoolInfo::MethodType(MethodType {
+                    descriptor_index: constants.utf8(&mapped)?,
+                })
+            }
+            ConstantPoolInfo::Package(Package { name_index }) => {
+                let Some(new) = pool
+                    .get_utf8(*name_index)
+                    .and_then(|name| self.mapped_package(name))
+                else {
+                    return Ok(None);
+                };
+                ConstantPoolInfo::Package(Package {
+                    name_index: constants.utf8(&new)?,
+                })
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(replacement))
+    }
+
+    /// Renames the name and type of a field or method reference, returning the index of a new
+    /// name and type constant if it changed
+    fn map_member(
+        &self,
+        pool: &ConstantPool,
+        class_index: u16,
+        name_and_type_index: u16,
+        is_method: bool,
+        constants: &mut ConstantPoolBuilder,
+    ) -> Result<Option<u16>, Error> {
+        let Some(class) = pool.get_class_name(class_index) else {
+            return Ok(None);
+        };
+        self.map_name_and_type(
+            pool,
+            name_and_type_index,
+            Some((class, is_method)),
+            constants,
+        )
+    }
+
+    /// Renames a name and type constant, returning the index of a new constant if it changed.
+    /// The name is only renamed if the member's class is known.
+    fn map_name_and_type(
+        &self,
+        pool: &ConstantPool,
+        name_and_type_index: u16,
+        member_of: Option<(&FQName, bool)>,
+        constants: &mut ConstantPoolBuilder,
+    ) -> Result<Option<u16>, Error> {
+        let Some((name, descriptor)) = pool.get_name_and_type(name_and_type_index) else {
+            return Ok(None);
+        };
+        let new_name = match member_of {
+            Some((class, true)) => self.map_method_name(class, name, descriptor),
+            Some((class, false)) => self.map_field_name(class, name),
+            None => name,
+        };
+        let new_descriptor = self.map_descriptor(descriptor);
+        if new_name == name && new_descriptor == descriptor {
+            return Ok(None);
+        }
+        constants.name_and_type(new_name, &new_descriptor).map(Some)
+    }
+
+    /// Maps an index used by the class, adding the constants for new names
+    fn map_index(
+        &self,
+        pool: &ConstantPool,
+        this: &str,
+        index: u16,
+        usage: IndexUse,
+        constants: &mut ConstantPoolBuilder,
+    ) -> Result<u16, Error> {
+        if let IndexUse::MethodOf { class } = usage {
+            let Some(class) = pool.get_class_name(class) else {
+                return Ok(index);
+            };
+            return Ok(self
+                .map_name_and_type(pool, index, Some((class, true)), constants)?
+                .unwrap_or(index));
+        }
+        let Some(value) = pool.get_utf8(index) else {
+            return Ok(index);
+        };
+        let mapped = match usage {
+            IndexUse::Other | IndexUse::MethodOf { .. } => return Ok(index),
+            IndexUse::Descriptor => self.map_descriptor(value),
+            IndexUse::Signature => self.map_signature(value),
+            IndexUse::FieldName { .. } => self.map_field_name(this, value).to_string(),
+            IndexUse::MethodName { descriptor } => {
+                let descriptor = pool.get_utf8(descriptor).unwrap_or_default();
+                self.map_method_name(this, value, descriptor).to_string()
+            }
+            IndexUse::InnerName { inner_class } => {
+                let Some(new) = pool
+                    .get_class_name(inner_class)
+                    .and_then(|inner| self.mapped_class(inner.as_str()))
+                else {
+                    return Ok(index);
+                };
+                let start = new.rfind(['$', '/']).map_or(0, |index| index + 1);
+                new[start..].to_string()
+            }
+        };
+        if mapped == value {
+            Ok(index)
+        } else {
+            constants.utf8(&mapped)
+        }
+    }
+
+    fn write_type_parameters(&self, parameters: &[TypeParameter], out: &mut String) {
+        if parameters.is_empty() {
+            return;
+        }
+        out.push('<');
+        for parameter in parameters {
+            out.push_str(parameter.name());
+            out.push(':');
+            if let Some(class_bound) = parameter.class_bound() {
+                self.write_field_type(class_bound, out);
+            }
+            for interface_bound in parameter.interface_bounds() {
+                out.push(':');
+                self.write_field_type(interface_bound, out);
+            }
+        }
+        out.push('>');
+    }
+
+    fn write_type(&self, signature: &TypeSignature, out: &mut String) {
+        match signature {
+            TypeSignature::Primitive(primitive) => out.push_str(&primitive.jni()),
+            TypeSignature::Reference(reference) => self.write_field_type(reference, out),
+        }
+    }
+
+    fn write_field_type(&self, signature: &FieldTypeSignature, out: &mut String) {
+        match signature {
+            FieldTypeSignature::Class(class) => self.write_class_type(class, out),
+            FieldTypeSignature::TypeVariable(variable) => {
+                out.push('T');
+                out.push_str(variable);
+                out.push(';');
+            }
+            FieldTypeSignature::Array(component) => {
+                out.push('[');
+                self.write_type(component, out);
+            }
+        }
+    }
+
+    /// Writes a class type. Inner classes are renamed by their binary name, and keep their
+    /// simple name unless they're renamed into their renamed outer class.
+    fn write_class_type(&self, signature: &ClassTypeSignature, out: &mut String) {
+        out.push('L');
+        let mut old_name = String::new();
+        let mut new_name = String::new();
+        for (index, class) in signature.classes().iter().enumerate() {
+            if index == 0 {
+                old_name.push_str(class.name());
+                new_name = self
+                    .mapped_class(&old_name)
+                    .unwrap_or_else(|| old_name.clone());
+                out.push_str(&new_name);
+            } else {
+                old_name = format!("{old_name}${}", class.name());
+                let outer = format!("{new_name}$");
+                new_name = self
+                    .mapped_class(&old_name)
+                    .filter(|new| new.starts_with(&outer))
+                    .unwrap_or_else(|| format!("{outer}{}", class.name()));
+                out.push('.');
+                out.push_str(&new_name[outer.len()..]);
+            }
+            self.write_type_arguments(class.type_arguments(), out);
+        }
+        out.push(';');
+    }
+
+    fn write_type_arguments(&self, arguments: &[TypeArgument], out: &mut String) {
+        if arguments.is_empty() {
+            return;
+        }
+        out.push('<');
+        for argument in arguments {
+            match argument {
+                TypeArgument::Any => out.push('*'),
+                TypeArgument::Exact(exact) => self.write_field_type(exact, out),
+                TypeArgument::Extends(bound) => {
+                    out.push('+');
+                    self.write_field_type(bound, out);
+                }
+                TypeArgument::Super(bound) => {
+                    out.push('-');
+                    self.write_field_type(bound, out);
+                }
+            }
+        }
+        out.push('>');
+    }
+}
+
+/// Creates a remapper that renames obfuscated classes and members back to their original names
+impl From<&Mapping> for Remapper {
+    fn from(mapping: &Mapping) -> Self {
+        // the descriptors of methods in a mapping use original names
+        let obfuscate = mapping
+            .classes()
+            .iter()
+            .fold(Remapper::new(), |remapper, class| {
+                remapper.class(class.original_name(), class.obfuscated_name())
+            });
+        let mut remapper = Remapper::new();
+        for class in mapping.classes() {
+            let obfuscated = class.obfuscated_name();
+            if obfuscated != class.original_name() {
+                remapper = remapper.class(obfuscated, class.original_name());
+            }
+            for field in class.fields() {
+                if field.obfuscated_name() != field.original_name() {
+                    remapper =
+                        remapper.field(obfuscated, field.obfuscated_name(), field.original_name());
+                }
+            }
+            for method in class.methods() {
+                if method.obfuscated_name() != method.original_name() {
+                    remapper = remapper.method(
+                        obfuscated,
+                        method.obfuscated_name(),
+                        &obfuscate.map_descriptor(method.original_descriptor()),
+                        method.original_name(),
+                    );
+                }
+            }
+        }
+        remapper
+    }
+}
@@ -0,0 +1,629 @@
+//! Generates browsable reports of the public API of a classpath, for reviewing binary releases
+//! without their sources.
+//!
+//! A report has an index page listing every package, a page for each package listing its
+//! classes, and a page for each public class. Class pages show the inheritance of the class, the
+//! classes in the report that extend it, its annotations, and its public and protected fields,
+//! constructors and methods. Deprecated classes and members are marked as deprecated.
+//!
+//! Pages are named by the binary names of their packages and classes, such as
+//! `com.example.Square.html`, and link to each other by relative links, so a report can be
+//! written to any directory.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! # use java_class_parser::report::{api_report, ReportFormat};
+//! let parser = JavaClassParser::new("app.jar");
+//! let report = api_report(&parser, ReportFormat::Html).expect("couldn't create report");
+//! report.write_to("target/api").expect("couldn't write report");
+//! ```
+
+use crate::annotations::Annotation;
+use crate::error::Error;
+use crate::{FQName, FQNameBuf, JavaClass, JavaClassParser, Signature};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// The format of the pages of a report
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ReportFormat {
+    /// Standalone HTML pages
+    Html,
+    /// Markdown pages, as rendered by most code hosts
+    Markdown,
+}
+
+impl ReportFormat {
+    /// The extension of pages in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+/// The pages of a report, by their file names
+#[derive(Debug, Clone)]
+pub struct ApiReport {
+    format: ReportFormat,
+    pages: BTreeMap<String, String>,
+}
+
+impl ApiReport {
+    /// The format of the pages
+    pub fn format(&self) -> ReportFormat {
+        self.format
+    }
+
+    /// Every page, by file name
+    pub fn pages(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pages
+            .iter()
+            .map(|(name, page)| (name.as_str(), page.as_str()))
+    }
+
+    /// Gets a page by its file name, such as `index.html`
+    pub fn page(&self, name: &str) -> Option<&str> {
+        self.pages.get(name).map(String::as_str)
+    }
+
+    /// Writes every page to a directory, creating it if it doesn't exist
+    ///
+    /// # Error
+    /// Will return an error if the directory can't be created, or a page can't be written
+    pub fn write_to<P: AsRef<Path>>(&self, directory: P) -> Result<(), Error> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+        for (name, page) in &self.pages {
+            std::fs::write(directory.join(name), page)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generates a report of the public API of the classes on a parser's classpath. Ancestors of
+/// classes are found through the parser, so giving it the JDK or the dependencies of the classpath
+/// completes the inheritance of each class, but only classes on the classpath get pages.
+///
+/// # Error
+/// Will return an error if a class on the classpath can't be parsed
+pub fn api_report(parser: &JavaClassParser, format: ReportFormat) -> Result<ApiReport, Error> {
+    let mut classes = BTreeMap::new();
+    for class in parser.parse_all() {
+        let class = class?;
+        let flags = class.access_flags();
+        if flags.is_public() && !flags.is_module() && !flags.is_synthetic() {
+            classes.entry(class.this().to_fqname_buf()).or_insert(class);
+        }
+    }
+
+    let mut subtypes = BTreeMap::<FQNameBuf, BTreeSet<FQNameBuf>>::new();
+    for class in classes.values() {
+        let supertypes = super_name(class)
+            .into_iter()
+            .chain(class.interfaces().into_iter().map(FQName::to_fqname_buf));
+        for supertype in supertypes {
+            subtypes
+                .entry(supertype)
+                .or_default()
+                .insert(class.this().to_fqname_buf());
+        }
+    }
+
+    let mut packages = BTreeMap::<String, Vec<&JavaClass>>::new();
+    for class in classes.values() {
+        packages
+            .entry(package_of(class.this()))
+            .or_default()
+            .push(class);
+    }
+
+    let generator = Generator {
+        parser,
+        format,
+        classes: &classes,
+        subtypes: &subtypes,
+    };
+    let mut pages = BTreeMap::new();
+    pages.insert(
+        format!("index.{}", format.extension()),
+        generator.index(&packages),
+    );
+    for (package, classes) in &packages {
+        pages.insert(
+            generator.page_name(package),
+            generator.package(package, classes),
+        );
+        for class in classes {
+            pages.insert(
+                generator.page_name(&java_name(class.this().as_str())),
+                generator.class(class),
+            );
+        }
+    }
+    Ok(ApiReport { format, pages })
+}
+
+struct Generator<'a> {
+    parser: &'a JavaClassParser,
+    format: ReportFormat,
+    classes: &'a BTreeMap<FQNameBuf, JavaClass>,
+    subtypes: &'a BTreeMap<FQNameBuf, BTreeSet<FQNameBuf>>,
+}
+
+impl Generator<'_> {
+    fn page_name(&self, name: &str) -> String {
+        format!("{name}.{}", self.format.extension())
+    }
+
+    fn index(&self, packages: &BTreeMap<String, Vec<&JavaClass>>) -> String {
+        let mut page = Page::new(self.format, "API");
+        page.heading(1, "Packages");
+        let packages = packages
+            .iter()
+            .map(|(package, classes)| {
+                format!(
+                    "{} ({} classes)",
+                    page.link(display_package(package), &self.page_name(package)),
+                    classes.len()
+                )
+            })
+            .collect();
+        page.list(packages);
+        page.finish()
+    }
+
+    fn package(&self, package: &str, classes: &[&JavaClass]) -> String {
+        let mut page = Page::new(self.format, display_package(package));
+        page.navigation(&[("All packages", &self.page_name("index"))]);
+        page.heading(1, &format!("Package {}", display_package(package)));
+        let classes = classes.iter().map(|class| {
+            let name = java_name(class.this().as_str());
+            let mut item = format!(
+                "{} {}",
+                kind(class),
+                page.link(&simple_name(&name), &self.page_name(&name))
+            );
+            if class.is_deprecated() {
+                item.push(' ');
+                item.push_str(&page.strong("Deprecated."));
+            }
+            item
+        });
+        let classes = classes.collect();
+        page.list(classes);
+        page.finish()
+    }
+
+    fn class(&self, class: &JavaClass) -> String {
+        let name = java_name(class.this().as_str());
+        let package = package_of(class.this());
+        let mut page = Page::new(self.format, &name);
+        page.navigation(&[
+            ("All packages", &self.page_name("index")),
+            (display_package(&package), &self.page_name(&package)),
+        ]);
+        page.heading(1, &format!("{} {name}", capitalized(kind(class))));
+        if class.is_deprecated() {
+            let deprecated = page.strong("Deprecated.");
+            page.paragraph(&deprecated);
+        }
+        let mut declaration = class_modifiers(class);
+        declaration.push_str(&simple_name(&name));
+        page.code_block(&annotated(
+            &[class.annotations(), class.invisible_annotations()].concat(),
+            &declaration,
+        ));
+
+        let ancestors = self.ancestors(class);
+        if !ancestors.is_empty() {
+            page.heading(2, "Inheritance");
+            let ancestors = ancestors
+                .iter()
+                .rev()
+                .chain(std::iter::once(&class.this().to_fqname_buf()))
+                .map(|ancestor| self.type_link(&page, ancestor))
+                .collect();
+            page.list(ancestors);
+        }
+        let interfaces = class.interfaces();
+        if !interfaces.is_empty() {
+            page.heading(2, "Implemented interfaces");
+            let interfaces = interfaces
+                .into_iter()
+                .map(|interface| self.type_link(&page, interface))
+                .collect();
+            page.list(interfaces);
+        }
+        if let Some(subtypes) = self.subtypes.get(class.this()) {
+            page.heading(2, "Known subtypes");
+            let subtypes = subtypes
+                .iter()
+                .map(|subtype| self.type_link(&page, subtype))
+                .collect();
+            page.list(subtypes);
+        }
+
+        let fields = class
+            .fields()
+            .into_iter()
+            .filter(|field| {
+                let flags = field.access_flags();
+                (flags.is_public() || flags.is_protected()) && !flags.is_synthetic()
+            })
+            .map(|field| {
+                let mut declaration = member_modifiers(field.access_flags().bits());
+                let _ = write!(
+                    declaration,
+                    "{} {}",
+                    java_type(field.signature()),
+                    field.name()
+                );
+                let declaration = annotated(
+                    &[field.annotations(), field.invisible_annotations()].concat(),
+                    &declaration,
+                );
+                page.member(&declaration, field.is_deprecated())
+            })
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            page.heading(2, "Fields");
+            page.list(fields);
+        }
+
+        let (constructors, methods): (Vec<_>, Vec<_>) = class
+            .methods()
+            .into_iter()
+            .filter(|method| {
+                let flags = method.access_flags();
+                (flags.is_public() || flags.is_protected())
+                    && !flags.is_synthetic()
+                    && !flags.is_bridge()
+                    && method.name() != "<clinit>"
+            })
+            .partition(|method| method.name() == "<init>");
+        for (heading, methods) in [("Constructors", constructors), ("Methods", methods)] {
+            if methods.is_empty() {
+                continue;
+            }
+            let methods = methods
+                .iter()
+                .map(|method| {
+                    let signature = method.signature();
+                    let mut declaration = member_modifiers(method.access_flags().bits());
+                    if method.name() == "<init>" {
+                        declaration.push_str(&simple_name(&name));
+                    } else {
+                        let return_type = signature.return_type().unwrap_or(&Signature::Void);
+                        let _ = write!(declaration, "{} {}", java_type(return_type), method.name());
+                    }
+                    let parameters = signature
+                        .parameters()
+                        .iter()
+                        .map(java_type)
+                        .collect::<Vec<_>>();
+                    let _ = write!(declaration, "({})", parameters.join(", "));
+                    let exceptions = method.thrown_exceptions();
+                    if !exceptions.is_empty() {
+                        let exceptions = exceptions
+                            .iter()
+                            .map(|exception| java_name(exception.as_str()))
+                            .collect::<Vec<_>>();
+                        let _ = write!(declaration, " throws {}", exceptions.join(", "));
+                    }
+                    let declaration = annotated(
+                        &[method.annotations(), method.invisible_annotations()].concat(),
+                        &declaration,
+                    );
+                    page.member(&declaration, method.is_deprecated())
+                })
+                .collect::<Vec<_>>();
+            page.heading(2, heading);
+            page.list(methods);
+        }
+        page.finish()
+    }
+
+    /// The super classes of a class, starting with its direct super class. Super classes that
+    /// can't be found end the list.
+    fn ancestors(&self, class: &JavaClass) -> Vec<FQNameBuf> {
+        let mut ancestors = vec![];
+        let mut current = super_name(class);
+        while let Some(name) = current {
+            current = match self.classes.get(&name) {
+                Some(class) => super_name(class),
+                None => self
+                    .parser
+                    .find(&name)
+                    .ok()
+                    .and_then(|class| super_name(&class)),
+            };
+            ancestors.push(name);
+        }
+        ancestors
+    }
+
+    /// Links to the page of a class if it's in the report
+    fn type_link(&self, page: &Page, name: &FQName) -> String {
+        let java_name = java_name(name.as_str());
+        if self.classes.contains_key(name) {
+            page.link(&java_name, &self.page_name(&java_name))
+        } else {
+            page.code(&java_name)
+        }
+    }
+}
+
+/// Writes the contents of a page in either format
+struct Page {
+    format: ReportFormat,
+    out: String,
+}
+
+impl Page {
+    fn new(format: ReportFormat, title: &str) -> Self {
+        let mut out = String::new();
+        if format == ReportFormat::Html {
+            let _ = writeln!(out, "<!DOCTYPE html>");
+            let _ = writeln!(out, "<html>");
+            let _ = writeln!(out, "<head>");
+            let _ = writeln!(out, "<meta charset=\"utf-8\">");
+            let _ = writeln!(out, "<title>{}</title>", escape_html(title));
+            let _ = writeln!(out, "</head>");
+            let _ = writeln!(out, "<body>");
+        }
+        Self { format, out }
+    }
+
+    fn finish(mut self) -> String {
+        if self.format == ReportFormat::Html {
+            let _ = writeln!(self.out, "</body>");
+            let _ = writeln!(self.out, "</html>");
+        }
+        self.out
+    }
+
+    fn text(&self, text: &str) -> String {
+        match self.format {
+            ReportFormat::Html => escape_html(text),
+            ReportFormat::Markdown => escape_markdown(text),
+        }
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        match self.format {
+            ReportFormat::Html => format!("<a href=\"{target}\">{}</a>", escape_html(text)),
+            ReportFormat::Markdown => format!("[{}]({target})", escape_markdown(text)),
+        }
+    }
+
+    fn strong(&self, text: &str) -> String {
+        match self.format {
+            ReportFormat::Html => format!("<strong>{}</strong>", escape_html(text)),
+            ReportFormat::Markdown => format!("**{}**", escape_markdown(text)),
+        }
+    }
+
+    fn code(&self, text: &str) -> String {
+        match self.format {
+            ReportFormat::Html => format!("<code>{}</code>", escape_html(text)),
+            ReportFormat::Markdown => format!("`{text}`"),
+        }
+    }
+
+    /// A member declaration, marked if it's deprecated
+    fn member(&self, declaration: &str, deprecated: bool) -> String {
+        let mut member = self.code(declaration);
+        if deprecated {
+            member.push(' ');
+            member.push_str(&self.strong("Deprecated."));
+        }
+        member
+    }
+
+    fn navigation(&mut self, links: &[(&str, &str)]) {
+        let links = links
+            .iter()
+            .map(|(text, target)| self.link(text, target))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        self.paragraph(&links);
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        let text = self.text(text);
+        let _ = match self.format {
+            ReportFormat::Html => writeln!(self.out, "<h{level}>{text}</h{level}>"),
+            ReportFormat::Markdown => writeln!(self.out, "{} {text}\n", "#".repeat(level)),
+        };
+    }
+
+    fn paragraph(&mut self, contents: &str) {
+        let _ = match self.format {
+            ReportFormat::Html => writeln!(self.out, "<p>{contents}</p>"),
+            ReportFormat::Markdown => writeln!(self.out, "{contents}\n"),
+        };
+    }
+
+    fn code_block(&mut self, code: &str) {
+        let _ = match self.format {
+            ReportFormat::Html => writeln!(self.out, "<pre>{}</pre>", escape_html(code)),
+            ReportFormat::Markdown => writeln!(self.out, "```java\n{code}\n```\n"),
+        };
+    }
+
+    fn list(&mut self, items: Vec<String>) {
+        match self.format {
+            ReportFormat::Html => {
+                let _ = writeln!(self.out, "<ul>");
+                for item in items {
+                    let _ = writeln!(self.out, "<li>{item}</li>");
+                }
+                let _ = writeln!(self.out, "</ul>");
+            }
+            ReportFormat::Markdown => {
+                for item in items {
+                    let _ = writeln!(self.out, "- {item}");
+                }
+                let _ = writeln!(self.out);
+            }
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn super_name(class: &JavaClass) -> Option<FQNameBuf> {
+    class.super_class().map(FQName::to_fqname_buf)
+}
+
+/// Converts an internal name, such as `com/example/Square`, to a binary name
+fn java_name(name: &str) -> String {
+    name.replace('/', ".")
+}
+
+fn simple_name(name: &str) -> String {
+    name.rsplit('.').next().unwrap_or(name).to_string()
+}
+
+/// The package of a class as a binary name, which is empty for the unnamed package
+fn package_of(name: &FQName) -> String {
+    name.as_str()
+        .rsplit_once('/')
+        .map_or(String::new(), |(package, _)| java_name(package))
+}
+
+fn display_package(package: &str) -> &str {
+    if package.is_empty() {
+        "(unnamed package)"
+    } else {
+        package
+    }
+}
+
+fn kind(class: &JavaClass) -> &'static str {
+    let flags = class.access_flags();
+    if flags.is_annotation() {
+        "annotation"
+    } else if flags.is_interface() {
+        "interface"
+    } else if flags.is_enum() {
+        "enum"
+    } else if class.is_record() {
+        "record"
+    } else {
+        "class"
+    }
+}
+
+fn capitalized(kind: &str) -> String {
+    let mut chars = kind.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// The modifiers and keyword a class is declared with, such as `public final class `
+fn class_modifiers(class: &JavaClass) -> String {
+    let flags = class.access_flags();
+    let mut modifiers = String::from("public ");
+    let kind = kind(class);
+    if flags.is_abstract() && kind == "class" {
+        modifiers.push_str("abstract ");
+    }
+    if flags.is_final() && kind == "class" {
+        modifiers.push_str("final ");
+    }
+    if class.is_sealed() {
+        modifiers.push_str("sealed ");
+    }
+    modifiers.push_str(match kind {
+        "annotation" => "@interface",
+        kind => kind,
+    });
+    modifiers.push(' ');
+    modifiers
+}
+
+/// The modifiers a member is declared with, followed by a space if there are any
+fn member_modifiers(flags: u16) -> String {
+    [
+        (0x0001, "public "),
+        (0x0004, "protected "),
+        (0x0400, "abstract "),
+        (0x0008, "static "),
+        (0x0010, "final "),
+        (0x0100, "native "),
+    ]
+    .into_iter()
+    .filter(|&(flag, _)| flags & flag != 0)
+    .map(|(_, modifier)| modifier)
+    .collect()
+}
+
+/// Prefixes a declaration with its annotations
+fn annotated(annotations: &[Annotation], declaration: &str) -> String {
+    let mut annotated = String::new();
+    for annotation in annotations {
+        let _ = write!(
+            annotated,
+            "@{} ",
+            java_name(annotation.type_name().as_str())
+        );
+    }
+    annotated.push_str(declaration);
+    annotated
+}
+
+/// A type as written in java source, such as `java.lang.String[]`
+fn java_type(signature: &Signature) -> String {
+    match signature {
+        Signature::FullyQualifiedClass(class) => java_name(class),
+        Signature::Array(component) => format!("{}[]", java_type(component)),
+        primitive => primitive.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            escape_html("List<String> & \"more\""),
+            "List&lt;String&gt; &amp; &quot;more&quot;"
+        );
+        assert_eq!(escape_markdown("a_b*[c]"), "a\\_b\\*\\[c\\]");
+    }
+
+    #[test]
+    fn java_types() {
+        let signature =
+            Signature::Array(Box::new(Signature::FullyQualifiedClass("java/lang/String")));
+        assert_eq!(java_type(&signature), "java.lang.String[]");
+        assert_eq!(java_type(&Signature::Int), "int");
+        assert_eq!(member_modifiers(0x0019), "public static final ");
+        assert_eq!(simple_name("com.example.Square"), "Square");
+    }
+}
@@ -0,0 +1,284 @@
+//! An eagerly linked view of a class, where the types a class refers to are materialized as the
+//! classes themselves rather than just their names.
+//!
+//! The super class and interfaces of a [`ResolvedClass`] are resolved recursively, so the entire
+//! inheritance hierarchy is available through it. The declared types of fields and methods are
+//! resolved to the classes they name, without resolving those classes any further.
+//!
+//! Types that are not present on the classpath are represented as [`ClassRef::Missing`].
+
+use crate::error::{Error, ErrorKind};
+use crate::{FQName, FQNameBuf, JavaClass, JavaClassParser, Signature};
+use std::borrow::Borrow;
+
+/// A reference to a class, which may not have been present on the classpath
+#[derive(Debug, Clone)]
+pub enum ClassRef<T> {
+    /// The class was found on the classpath
+    Resolved(T),
+    /// No class with this name was found on the classpath
+    Missing(FQNameBuf),
+}
+
+impl<T> ClassRef<T> {
+    /// Gets the class, if it was found
+    pub fn resolved(&self) -> Option<&T> {
+        match self {
+            ClassRef::Resolved(class) => Some(class),
+            ClassRef::Missing(_) => None,
+        }
+    }
+
+    /// Checks if the class was missing from the classpath
+    pub fn is_missing(&self) -> bool {
+        matches!(self, ClassRef::Missing(_))
+    }
+}
+
+impl<T: Borrow<JavaClass>> ClassRef<T> {
+    /// The fully qualified name of the referenced class
+    pub fn name(&self) -> &FQName {
+        match self {
+            ClassRef::Resolved(class) => class.borrow().this(),
+            ClassRef::Missing(name) => name,
+        }
+    }
+}
+
+/// A type used in a declaration, with class types linked to their classes
+#[derive(Debug, Clone)]
+#[allow(missing_docs)]
+pub enum ResolvedType {
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Void,
+    /// A class type
+    Class(ClassRef<JavaClass>),
+    /// An array of some type
+    Array(Box<ResolvedType>),
+}
+
+impl ResolvedType {
+    /// Gets the class this type refers to, looking through arrays
+    pub fn class_ref(&self) -> Option<&ClassRef<JavaClass>> {
+        match self {
+            ResolvedType::Class(class) => Some(class),
+            ResolvedType::Array(component) => component.class_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// A field with its declared type resolved
+#[derive(Debug, Clone)]
+pub struct ResolvedField {
+    name: String,
+    declared_type: ResolvedType,
+}
+
+impl ResolvedField {
+    /// The name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The declared type of the field
+    pub fn declared_type(&self) -> &ResolvedType {
+        &self.declared_type
+    }
+}
+
+/// A method with its parameter and return types resolved
+#[derive(Debug, Clone)]
+pub struct ResolvedMethod {
+    name: String,
+    parameters: Vec<ResolvedType>,
+    return_type: ResolvedType,
+}
+
+impl ResolvedMethod {
+    /// The name of the method
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The types of the parameters of the method
+    pub fn parameters(&self) -> &[ResolvedType] {
+        &self.parameters[..]
+    }
+
+    /// The return type of the method
+    pub fn return_type(&self) -> &ResolvedType {
+        &self.return_type
+    }
+}
+
+/// A class with its super types and declared member types resolved. Created by
+/// [`JavaClassParser::resolve`](JavaClassParser::resolve).
+#[derive(Debug, Clone)]
+pub struct ResolvedClass {
+    class: JavaClass,
+    super_class: Option<Box<ClassRef<ResolvedClass>>>,
+    interfaces: Vec<ClassRef<ResolvedClass>>,
+    fields: Vec<ResolvedField>,
+    methods: Vec<ResolvedMethod>,
+}
+
+impl ResolvedClass {
+    /// The class itself
+    pub fn class(&self) -> &JavaClass {
+        &self.class
+    }
+
+    /// The fully qualified name of the class
+    pub fn this(&self) -> &FQName {
+        self.class.this()
+    }
+
+    /// The super class. Only `java/lang/Object` has no super class.
+    pub fn super_class(&self) -> Option<&ClassRef<ResolvedClass>> {
+        self.super_class.as_deref()
+    }
+
+    /// The interfaces directly implemented by the class
+    pub fn interfaces(&self) -> &[ClassRef<ResolvedClass>] {
+        &self.interfaces[..]
+    }
+
+    /// The fields declared in the class
+    pub fn fields(&self) -> &[ResolvedField] {
+        &self.fields[..]
+    }
+
+    /// The methods declared in the class
+    pub fn methods(&self) -> &[ResolvedMethod] {
+        &self.methods[..]
+    }
+
+    /// Iterates through the chain of super classes, starting from the direct super class and ending
+    /// at either `java/lang/Object` or the first class missing from the classpath.
+    pub fn super_classes(&self) -> impl Iterator<Item = &ClassRef<ResolvedClass>> {
+        std::iter::successors(self.super_class(), |super_class| {
+            super_class.resolved().and_then(ResolvedClass::super_class)
+        })
+    }
+}
+
+impl Borrow<JavaClass> for ResolvedClass {
+    fn borrow(&self) -> &JavaClass {
+        &self.class
+    }
+}
+
+/// Finds a class on the classpath, representing it as missing if it isn't there
+fn find(parser: &JavaClassParser, name: &FQName) -> Result<ClassRef<JavaClass>, Error> {
+    match parser.find(name) {
+        Ok(class) => Ok(ClassRef::Resolved(class)),
+        Err(e) => match e.kind() {
+            ErrorKind::NoClassFound(_) => Ok(ClassRef::Missing(name.to_fqname_buf())),
+            _ => Err(e),
+        },
+    }
+}
+
+fn resolve_type(parser: &JavaClassParser, signature: &Signature) -> Result<ResolvedType, Error> {
+    let resolved = match signature {
+        Signature::Boolean => ResolvedType::Boolean,
+        Signature::Byte => ResolvedType::Byte,
+        Signature::Char => ResolvedType::Char,
+        Signature::Short => ResolvedType::Short,
+        Signature::Int => ResolvedType::Int,
+        Signature::Long => ResolvedType::Long,
+        Signature::Float => ResolvedType::Float,
+        Signature::Double => ResolvedType::Double,
+        Signature::Void => ResolvedType::Void,
+        Signature::FullyQualifiedClass(name) => {
+            ResolvedType::Class(find(parser, FQName::new(name))?)
+        }
+        Signature::Array(component) => {
+            ResolvedType::Array(Box::new(resolve_type(parser, component)?))
+        }
+        Signature::Method { .. } => {
+            unreachable!("method signatures can't be the type of a declaration")
+        }
+    };
+    Ok(resolved)
+}
+
+/// Resolves a super type, which is itself resolved in full
+fn resolve_super(
+    parser: &JavaClassParser,
+    name: &FQName,
+    visiting: &mut Vec<FQNameBuf>,
+) -> Result<ClassRef<ResolvedClass>, Error> {
+    match find(parser, name)? {
+        ClassRef::Resolved(class) => Ok(ClassRef::Resolved(resolve(parser, class, visiting)?)),
+        ClassRef::Missing(name) => Ok(ClassRef::Missing(name)),
+    }
+}
+
+pub(crate) fn resolve(
+    parser: &JavaClassParser,
+    class: JavaClass,
+    visiting: &mut Vec<FQNameBuf>,
+) -> Result<ResolvedClass, Error> {
+    let name = class.this().to_fqname_buf();
+    if let Some(start) = visiting.iter().position(|visited| visited == &name) {
+        let mut cycle = visiting[start..].to_vec();
+        cycle.push(name);
+        return Err(Error::new(ErrorKind::InheritanceCycle(cycle)));
+    }
+    visiting.push(name);
+
+    let super_class = match class.super_class() {
+        Some(super_class) => Some(Box::new(resolve_super(parser, super_class, visiting)?)),
+        None => None,
+    };
+    let interfaces = class
+        .interfaces()
+        .into_iter()
+        .map(|interface| resolve_super(parser, interface, visiting))
+        .collect::<Result<Vec<_>, _>>()?;
+    let fields = class
+        .fields()
+        .iter()
+        .map(|field| {
+            Ok(ResolvedField {
+                name: field.name().to_string(),
+                declared_type: resolve_type(parser, field.signature())?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let methods = class
+        .methods()
+        .iter()
+        .map(|method| {
+            let Signature::Method { args, ret_type } = method.signature() else {
+                unreachable!("methods always have method descriptors")
+            };
+            Ok(ResolvedMethod {
+                name: method.name().to_string(),
+                parameters: args
+                    .iter()
+                    .map(|arg| resolve_type(parser, arg))
+                    .collect::<Result<Vec<_>, _>>()?,
+                return_type: resolve_type(parser, ret_type)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    visiting.pop();
+    Ok(ResolvedClass {
+        class,
+        super_class,
+        interfaces,
+        fields,
+        methods,
+    })
+}
@@ -0,0 +1,132 @@
+//! Finds class files embedded at arbitrary offsets within other binaries, such as self extracting
+//! installers or packed resources.
+//!
+//! Every occurrence of the `0xCAFEBABE` magic is treated as the possible start of a class file.
+//! Occurrences that don't parse as a class file are skipped.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::scan::scan;
+//! let installer = std::fs::File::open("setup.bin").unwrap();
+//! for embedded in scan(installer).unwrap() {
+//!     println!("{} at offset {}", embedded.class().this(), embedded.offset());
+//! }
+//! ```
+
+use crate::constant_pool::ConstantPoolInfo;
+use crate::error::Error;
+use crate::raw_java_class::{check_members, parse_class_file, RawJavaClass};
+use crate::JavaClass;
+use std::io::Read;
+
+const MAGIC: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+/// A class file found within a larger binary
+#[derive(Debug, Clone)]
+pub struct EmbeddedClass {
+    offset: usize,
+    len: usize,
+    class: JavaClass,
+}
+
+impl EmbeddedClass {
+    /// The offset of the start of the class file within the scanned bytes
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The length of the class file in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Class files are never empty, this always returns `false`
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The parsed class
+    pub fn class(&self) -> &JavaClass {
+        &self.class
+    }
+
+    /// Takes the parsed class
+    pub fn into_class(self) -> JavaClass {
+        self.class
+    }
+}
+
+/// An iterator over the class files embedded within some bytes. Created by [`scan_bytes`].
+#[derive(Debug, Clone)]
+pub struct EmbeddedClasses<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Iterator for EmbeddedClasses<'_> {
+    type Item = EmbeddedClass;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(found) = self.bytes[self.position..]
+            .windows(MAGIC.len())
+            .position(|window| window == MAGIC)
+        {
+            let offset = self.position + found;
+            match parse_embedded(&self.bytes[offset..]) {
+                Some((class, len)) => {
+                    // class files can't contain another class file, so skip over this one
+                    self.position = offset + len;
+                    return Some(EmbeddedClass { offset, len, class });
+                }
+                None => self.position = offset + 1,
+            }
+        }
+        self.position = self.bytes.len();
+        None
+    }
+}
+
+/// Scans bytes for embedded class files, in the order they appear
+pub fn scan_bytes(bytes: &[u8]) -> EmbeddedClasses<'_> {
+    EmbeddedClasses { bytes, position: 0 }
+}
+
+/// Reads an entire stream, then scans it for embedded class files
+///
+/// # Error
+/// Will return an error only if the stream can't be read
+pub fn scan<R: Read>(mut read: R) -> Result<Vec<EmbeddedClass>, Error> {
+    let mut buffer = vec![];
+    read.read_to_end(&mut buffer)?;
+    Ok(scan_bytes(&buffer).collect())
+}
+
+/// Tries to parse a class file at the start of the bytes, returning it and its length
+fn parse_embedded(bytes: &[u8]) -> Option<(JavaClass, usize)> {
+    let (rest, raw) = parse_class_file::<nom::error::Error<_>>(bytes).ok()?;
+    if !is_well_formed(&raw) {
+        return None;
+    }
+    Some((JavaClass::new(raw), bytes.len() - rest.len()))
+}
+
+/// Parsing only checks the structure of a class file. Arbitrary bytes can still parse, so make
+/// sure the class can actually be used without panicking.
+fn is_well_formed(raw: &RawJavaClass) -> bool {
+    let pool = &raw.constant_pool;
+    let all_utf8_valid = (1..=pool.len() as u16).all(|index| match pool.get(index) {
+        Some(ConstantPoolInfo::Utf8(utf8)) => utf8.error.is_none(),
+        _ => true,
+    });
+    let is_class_name = |index: u16| match pool.get(index) {
+        Some(ConstantPoolInfo::Class(class)) => {
+            matches!(pool.get(class.name_index), Some(ConstantPoolInfo::Utf8(_)))
+        }
+        _ => false,
+    };
+    all_utf8_valid
+        && check_members(raw).is_ok()
+        && is_class_name(raw.this_class)
+        && (raw.super_class == 0 || is_class_name(raw.super_class))
+        && raw.interfaces.iter().all(|&index| is_class_name(index))
+}
@@ -0,0 +1,178 @@
+//! Validates sealed class hierarchies, by checking that the classes a sealed class permits and the
+//! classes that directly extend or implement it are the same.
+//!
+//! The JVM only checks a sealed hierarchy when a subclass is loaded, so a published jar can contain
+//! a subclass a sealed class doesn't permit, or permit a class that doesn't exist, without any
+//! errors until the classes are used.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! # use java_class_parser::sealed;
+//! let parser = JavaClassParser::new("app.jar");
+//! for violation in sealed::check(&parser).unwrap() {
+//!     println!("{violation}");
+//! }
+//! ```
+
+use crate::error::Error;
+use crate::inheritance::InheritanceIndex;
+use crate::{FQName, FQNameBuf, JavaClassParser};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// A violation of a sealed hierarchy, found by [`check`]. The location is the classpath entry
+/// defining the class file that is wrong, which is `None` for classes that were
+/// [registered](JavaClassParser::register_class) instead of found on the classpath.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SealedViolation {
+    /// A class directly extends or implements a sealed class that doesn't permit it
+    NotPermitted {
+        /// The sealed class
+        sealed: FQNameBuf,
+        /// The class that isn't permitted
+        subclass: FQNameBuf,
+        /// Where the subclass is defined
+        location: Option<PathBuf>,
+    },
+    /// A sealed class permits a class that doesn't directly extend or implement it
+    NotASubclass {
+        /// The sealed class
+        sealed: FQNameBuf,
+        /// The permitted class
+        permitted: FQNameBuf,
+        /// Where the sealed class is defined
+        location: Option<PathBuf>,
+    },
+    /// A sealed class permits a class that isn't on the classpath
+    PermittedNotFound {
+        /// The sealed class
+        sealed: FQNameBuf,
+        /// The permitted class
+        permitted: FQNameBuf,
+        /// Where the sealed class is defined
+        location: Option<PathBuf>,
+    },
+}
+
+impl SealedViolation {
+    /// The sealed class whose hierarchy is violated
+    pub fn sealed(&self) -> &FQName {
+        match self {
+            SealedViolation::NotPermitted { sealed, .. }
+            | SealedViolation::NotASubclass { sealed, .. }
+            | SealedViolation::PermittedNotFound { sealed, .. } => sealed,
+        }
+    }
+
+    /// The classpath entry defining the class file that is wrong
+    pub fn location(&self) -> Option<&Path> {
+        match self {
+            SealedViolation::NotPermitted { location, .. }
+            | SealedViolation::NotASubclass { location, .. }
+            | SealedViolation::PermittedNotFound { location, .. } => location.as_deref(),
+        }
+    }
+}
+
+impl Display for SealedViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SealedViolation::NotPermitted {
+                sealed, subclass, ..
+            } => write!(
+                f,
+                "{subclass} extends sealed {sealed}, which doesn't permit it"
+            )?,
+            SealedViolation::NotASubclass {
+                sealed, permitted, ..
+            } => write!(
+                f,
+                "sealed {sealed} permits {permitted}, which doesn't extend it"
+            )?,
+            SealedViolation::PermittedNotFound {
+                sealed, permitted, ..
+            } => write!(
+                f,
+                "sealed {sealed} permits {permitted}, which can't be found"
+            )?,
+        }
+        if let Some(location) = self.location() {
+            write!(f, " (in {})", location.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks every sealed class on the classpath of a parser. Every class that directly extends or
+/// implements a sealed class must be permitted by it, and every permitted class must directly
+/// extend or implement it. Violations are sorted by the name of the sealed class, and then by the
+/// name of the subclass or in the order classes are permitted.
+///
+/// Only classes on the classpath are checked, so subclasses elsewhere aren't found.
+///
+/// # Error
+/// Will return an error if a class on the classpath can't be read or parsed
+pub fn check(parser: &JavaClassParser) -> Result<Vec<SealedViolation>, Error> {
+    let mut index = InheritanceIndex::default();
+    let mut sealed = vec![];
+    for class in parser.parse_all() {
+        let class = class?;
+        index.insert(&class);
+        if let Some(permitted) = class.permitted_subclasses() {
+            let permitted = permitted
+                .into_iter()
+                .map(|name| name.to_fqname_buf())
+                .collect::<Vec<_>>();
+            sealed.push((class.this().to_fqname_buf(), permitted));
+        }
+    }
+    sealed.sort();
+
+    let mut violations = vec![];
+    for (name, permitted) in sealed {
+        let mut subclasses = index
+            .direct_subtypes(&name)
+            .map(|(subclass, _)| subclass.to_fqname_buf())
+            .collect::<Vec<_>>();
+        subclasses.sort();
+        for subclass in &subclasses {
+            if !permitted.contains(subclass) {
+                violations.push(SealedViolation::NotPermitted {
+                    sealed: name.clone(),
+                    subclass: subclass.clone(),
+                    location: location(parser, subclass)?,
+                });
+            }
+        }
+        for permitted in permitted {
+            if subclasses.contains(&permitted) {
+                continue;
+            }
+            let location = location(parser, &name)?;
+            violations.push(if index.contains(&permitted) {
+                SealedViolation::NotASubclass {
+                    sealed: name.clone(),
+                    permitted,
+                    location,
+                }
+            } else {
+                SealedViolation::PermittedNotFound {
+                    sealed: name.clone(),
+                    permitted,
+                    location,
+                }
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// Finds the classpath entry that defines a class
+fn location(parser: &JavaClassParser, name: &FQName) -> Result<Option<PathBuf>, Error> {
+    Ok(parser
+        .find_all(name)?
+        .into_iter()
+        .next()
+        .map(|definition| definition.entry().to_path_buf()))
+}
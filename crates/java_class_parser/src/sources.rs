@@ -0,0 +1,141 @@
+//! An index from source files back to the classes compiled from them, built from the `SourceFile`
+//! attribute of every class on a classpath.
+//!
+//! A single source file usually produces several classes, such as its inner, local and anonymous
+//! classes. Crash logs and coverage reports only refer to a source file and a line, so the index can
+//! narrow these down to the classes whose line numbers cover that line.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! # use java_class_parser::sources::SourceIndex;
+//! let parser = JavaClassParser::new("app.jar");
+//! let index = SourceIndex::build(&parser).unwrap();
+//! for class in index.find_line("com/example/Foo.java", 123) {
+//!     println!("{}", class.name());
+//! }
+//! ```
+
+use crate::attributes::AttributeKind;
+use crate::error::Error;
+use crate::{FQName, FQNameBuf, HasAttributes, JavaClass, JavaClassParser};
+use std::collections::BTreeMap;
+
+/// A class compiled from a source file
+#[derive(Debug, Clone)]
+pub struct SourceClass {
+    name: FQNameBuf,
+    lines: Option<(u16, u16)>,
+}
+
+impl SourceClass {
+    /// The name of the class
+    pub fn name(&self) -> &FQName {
+        &self.name
+    }
+
+    /// The first and last line of the source file that the class has bytecode for, if the class
+    /// was compiled with line numbers
+    pub fn lines(&self) -> Option<(u16, u16)> {
+        self.lines
+    }
+
+    /// Checks if the class has bytecode for a line of the source file
+    pub fn contains_line(&self, line: u16) -> bool {
+        matches!(self.lines, Some((first, last)) if first <= line && line <= last)
+    }
+}
+
+/// Maps source files to the classes compiled from them.
+///
+/// Source files are identified by their path relative to the root of their package hierarchy,
+/// such as `com/example/Foo.java`. Classes compiled without a `SourceFile` attribute aren't
+/// indexed.
+#[derive(Debug, Clone, Default)]
+pub struct SourceIndex {
+    sources: BTreeMap<String, Vec<SourceClass>>,
+}
+
+impl SourceIndex {
+    /// Builds an index of every class on the classpath of a parser
+    ///
+    /// # Error
+    /// Will return an error if the classpath can't be read, or a class on it can't be parsed.
+    pub fn build(parser: &JavaClassParser) -> Result<Self, Error> {
+        let mut index = Self::default();
+        for name in parser.classes() {
+            index.insert(&parser.find(&name?)?);
+        }
+        Ok(index)
+    }
+
+    /// Adds a class to the index, returning the path of its source file. Nothing is added if the
+    /// class has no `SourceFile` attribute.
+    pub fn insert(&mut self, class: &JavaClass) -> Option<String> {
+        let source_file = class
+            .attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::SourceFile(path) => Some(path.to_string_lossy().into_owned()),
+                _ => None,
+            })?;
+        let source = match class.this().as_str().rsplit_once('/') {
+            Some((package, _)) => format!("{package}/{source_file}"),
+            None => source_file,
+        };
+        let classes = self.sources.entry(source.clone()).or_default();
+        classes.push(SourceClass {
+            name: class.this().to_fqname_buf(),
+            lines: line_range(class),
+        });
+        Some(source)
+    }
+
+    /// The paths of every indexed source file, sorted
+    pub fn sources(&self) -> impl Iterator<Item = &str> {
+        self.sources.keys().map(String::as_str)
+    }
+
+    /// Gets the classes compiled from a source file
+    pub fn classes(&self, source: &str) -> &[SourceClass] {
+        self.sources.get(source).map_or(&[], |classes| &classes[..])
+    }
+
+    /// Gets the paths of every source file with a given file name, such as `Foo.java`. Stack
+    /// traces only include the file name, so this may match files in several packages.
+    pub fn sources_named<'a>(&'a self, file_name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.sources()
+            .filter(move |source| source.rsplit('/').next() == Some(file_name))
+    }
+
+    /// Gets the classes compiled from a source file that have bytecode for a line
+    pub fn find_line(&self, source: &str, line: u16) -> Vec<&SourceClass> {
+        self.classes(source)
+            .iter()
+            .filter(|class| class.contains_line(line))
+            .collect()
+    }
+}
+
+/// The smallest and largest line numbers in any method of a class
+fn line_range(class: &JavaClass) -> Option<(u16, u16)> {
+    let mut range: Option<(u16, u16)> = None;
+    for method in class.methods() {
+        for attribute in method.attributes() {
+            let AttributeKind::Code(code) = attribute.kind() else {
+                continue;
+            };
+            for attribute in code.attributes() {
+                let AttributeKind::LineNumberTable(table) = attribute.kind() else {
+                    continue;
+                };
+                for &(_, line) in table.entries() {
+                    range = Some(match range {
+                        Some((first, last)) => (first.min(line), last.max(line)),
+                        None => (line, line),
+                    });
+                }
+            }
+        }
+    }
+    range
+}
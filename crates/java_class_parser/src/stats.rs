@@ -0,0 +1,647 @@
+//! Statistics about what makes up classes and jars, for investigating the size of an artifact.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::stats::JarStats;
+//! let stats = JarStats::from_jar("app.jar").expect("couldn't read jar");
+//! for (attribute, size) in stats.attribute_sizes() {
+//!     println!("{attribute}: {size} bytes");
+//! }
+//! ```
+
+use crate::bytecode::decode;
+use crate::constant_pool::values::{
+    Class, Dynamic, FieldRef, InterfaceMethodRef, InvokeDynamic, MethodHandle, MethodRef,
+    MethodType, Module, NameAndType, Package, StringValue,
+};
+use crate::error::Error;
+use crate::raw_java_class::RawAttributeInfo;
+use crate::utility::ByteReader;
+use crate::{entry_class_name, parse_bytes, ConstantPoolInfo, FQNameBuf, JavaClass};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// Which kinds of debug information a class contains
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct DebugInfo {
+    source_file: bool,
+    line_numbers: bool,
+    local_variables: bool,
+}
+
+impl DebugInfo {
+    /// Whether the class has a `SourceFile` attribute
+    pub fn source_file(&self) -> bool {
+        self.source_file
+    }
+
+    /// Whether any method has a `LineNumberTable` attribute
+    pub fn line_numbers(&self) -> bool {
+        self.line_numbers
+    }
+
+    /// Whether any method has a `LocalVariableTable` attribute
+    pub fn local_variables(&self) -> bool {
+        self.local_variables
+    }
+
+    /// Whether the class contains any debug information at all
+    pub fn any(&self) -> bool {
+        self.source_file || self.line_numbers || self.local_variables
+    }
+}
+
+/// Statistics of a single class
+#[derive(Debug, Clone)]
+pub struct ClassStats {
+    name: FQNameBuf,
+    size: usize,
+    methods: usize,
+    fields: usize,
+    bytecode_size: usize,
+    constant_pool_entries: usize,
+    constant_pool_referenced: usize,
+    attribute_sizes: BTreeMap<String, usize>,
+    debug_info: DebugInfo,
+}
+
+impl ClassStats {
+    /// Collects the statistics of a class
+    pub fn from_class(class: &JavaClass) -> Self {
+        let raw = class.raw();
+        let mut stats = Self {
+            name: class.this().to_fqname_buf(),
+            size: 0,
+            methods: raw.methods.len(),
+            fields: raw.fields.len(),
+            bytecode_size: 0,
+            // the unusable entries after long and double constants aren't counted
+            constant_pool_entries: (1..=raw.constant_pool.len() as u16)
+                .filter(|&index| raw.constant_pool.get(index).is_some())
+                .count(),
+            constant_pool_referenced: referenced_constants(class).len(),
+            attribute_sizes: BTreeMap::new(),
+            debug_info: DebugInfo::default(),
+        };
+
+        let members = raw
+            .fields
+            .iter()
+            .map(|field| &field.attributes)
+            .chain(raw.methods.iter().map(|method| &method.attributes));
+        let mut attributes_size = 0;
+        for attributes in members.chain([&raw.attributes]) {
+            attributes_size += 2 + stats.add_attributes(class, attributes_of(attributes));
+        }
+
+        let constant_pool_size = (1..=raw.constant_pool.len() as u16)
+            .filter_map(|index| raw.constant_pool.get(index))
+            .map(constant_size)
+            .sum::<usize>();
+        // the magic, versions, access flags, this and super class, and the lengths of each table
+        stats.size = 22
+            + constant_pool_size
+            + 2 * raw.interfaces.len()
+            + 6 * (raw.fields.len() + raw.methods.len())
+            + attributes_size;
+        stats
+    }
+
+    /// Records the sizes of attributes, returning the number of bytes they take up
+    fn add_attributes<'c>(
+        &mut self,
+        class: &'c JavaClass,
+        attributes: impl IntoIterator<Item = (u16, &'c [u8])>,
+    ) -> usize {
+        let mut total = 0;
+        for (name_index, info) in attributes {
+            let name = class.get_string(name_index).unwrap_or("<unknown>");
+            let size = 6 + info.len();
+            total += size;
+            match name {
+                "Code" => {
+                    let mut reader = ByteReader::new(info);
+                    if let Some(code_length) = reader.take(4).and_then(|_| reader.u32()) {
+                        self.bytecode_size += code_length as usize;
+                    }
+                }
+                "SourceFile" => self.debug_info.source_file = true,
+                "LineNumberTable" => self.debug_info.line_numbers = true,
+                "LocalVariableTable" => self.debug_info.local_variables = true,
+                _ => {}
+            }
+            // nested attributes are counted on their own, not as part of their parent
+            let nested = nested_attributes(name, info).unwrap_or_default();
+            let nested_size = self.add_attributes(class, nested);
+            *self.attribute_sizes.entry(name.to_string()).or_default() += size - nested_size;
+        }
+        total
+    }
+
+    /// The name of the class
+    pub fn name(&self) -> &FQNameBuf {
+        &self.name
+    }
+
+    /// The size of the class file in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of methods declared in the class
+    pub fn methods(&self) -> usize {
+        self.methods
+    }
+
+    /// The number of fields declared in the class
+    pub fn fields(&self) -> usize {
+        self.fields
+    }
+
+    /// The total length of the bytecode of every method
+    pub fn bytecode_size(&self) -> usize {
+        self.bytecode_size
+    }
+
+    /// The number of entries in the constant pool
+    pub fn constant_pool_entries(&self) -> usize {
+        self.constant_pool_entries
+    }
+
+    /// The number of constant pool entries that are referenced from the rest of the class.
+    ///
+    /// References are found from the class structure, bytecode, and the standard attributes that
+    /// refer to the constant pool. Type annotations, module attributes and unknown attributes are
+    /// not inspected, so anything only they reference is counted as unused.
+    pub fn constant_pool_referenced(&self) -> usize {
+        self.constant_pool_referenced
+    }
+
+    /// The fraction of constant pool entries that are referenced, between `0.0` and `1.0`
+    pub fn constant_pool_utilization(&self) -> f64 {
+        utilization(self.constant_pool_referenced, self.constant_pool_entries)
+    }
+
+    /// The number of bytes taken up by each kind of attribute, by attribute name. Attributes nested
+    /// within other attributes, like the `LineNumberTable` of a `Code` attribute, are not included
+    /// in the size of their parent.
+    pub fn attribute_sizes(&self) -> &BTreeMap<String, usize> {
+        &self.attribute_sizes
+    }
+
+    /// The debug information present in the class
+    pub fn debug_info(&self) -> DebugInfo {
+        self.debug_info
+    }
+}
+
+/// Statistics of every class within a jar
+#[derive(Debug, Clone)]
+pub struct JarStats {
+    path: PathBuf,
+    size: u64,
+    classes: Vec<ClassStats>,
+    resources: usize,
+    resources_size: u64,
+}
+
+impl JarStats {
+    /// Collects the statistics of every class in a jar
+    ///
+    /// # Error
+    /// Will return an error if the jar can't be read, or any class within it can't be parsed.
+    pub fn from_jar<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut archive = ZipArchive::new(File::open(path)?)?;
+        let mut stats = Self {
+            path: path.to_path_buf(),
+            size: std::fs::metadata(path)?.len(),
+            classes: vec![],
+            resources: 0,
+            resources_size: 0,
+        };
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index)?;
+            if entry.is_dir() {
+                continue;
+            }
+            if entry_class_name(entry.name()).is_some() {
+                let class = parse_bytes(entry)?;
+                stats.classes.push(ClassStats::from_class(&class));
+            } else {
+                stats.resources += 1;
+                stats.resources_size += entry.size();
+            }
+        }
+        Ok(stats)
+    }
+
+    /// The path of the jar
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The size of the jar file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The statistics of each class, in the order they appear in the jar
+    pub fn classes(&self) -> &[ClassStats] {
+        &self.classes[..]
+    }
+
+    /// The number of entries in the jar that aren't classes
+    pub fn resources(&self) -> usize {
+        self.resources
+    }
+
+    /// The uncompressed size of every entry that isn't a class
+    pub fn resources_size(&self) -> u64 {
+        self.resources_size
+    }
+
+    /// The total uncompressed size of every class
+    pub fn classes_size(&self) -> usize {
+        self.classes.iter().map(ClassStats::size).sum()
+    }
+
+    /// The total number of methods
+    pub fn methods(&self) -> usize {
+        self.classes.iter().map(ClassStats::methods).sum()
+    }
+
+    /// The total number of fields
+    pub fn fields(&self) -> usize {
+        self.classes.iter().map(ClassStats::fields).sum()
+    }
+
+    /// The total length of bytecode
+    pub fn bytecode_size(&self) -> usize {
+        self.classes.iter().map(ClassStats::bytecode_size).sum()
+    }
+
+    /// The total number of constant pool entries
+    pub fn constant_pool_entries(&self) -> usize {
+        self.classes
+            .iter()
+            .map(ClassStats::constant_pool_entries)
+            .sum()
+    }
+
+    /// The total number of referenced constant pool entries
+    pub fn constant_pool_referenced(&self) -> usize {
+        self.classes
+            .iter()
+            .map(ClassStats::constant_pool_referenced)
+            .sum()
+    }
+
+    /// The fraction of constant pool entries across every class that are referenced
+    pub fn constant_pool_utilization(&self) -> f64 {
+        utilization(
+            self.constant_pool_referenced(),
+            self.constant_pool_entries(),
+        )
+    }
+
+    /// The number of bytes taken up by each kind of attribute across every class
+    pub fn attribute_sizes(&self) -> BTreeMap<String, usize> {
+        let mut sizes = BTreeMap::new();
+        for (name, size) in self.classes.iter().flat_map(ClassStats::attribute_sizes) {
+            *sizes.entry(name.clone()).or_default() += size;
+        }
+        sizes
+    }
+
+    /// The number of classes that contain debug information matching a predicate
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::stats::{DebugInfo, JarStats};
+    /// let stats = JarStats::from_jar("app.jar").unwrap();
+    /// let with_lines = stats.classes_with_debug_info(DebugInfo::line_numbers);
+    /// ```
+    pub fn classes_with_debug_info<F: Fn(&DebugInfo) -> bool>(&self, predicate: F) -> usize {
+        self.classes
+            .iter()
+            .filter(|class| predicate(&class.debug_info))
+            .count()
+    }
+}
+
+fn utilization(referenced: usize, entries: usize) -> f64 {
+    if entries == 0 {
+        1.0
+    } else {
+        referenced as f64 / entries as f64
+    }
+}
+
+fn attributes_of(attributes: &[RawAttributeInfo]) -> impl Iterator<Item = (u16, &[u8])> {
+    attributes
+        .iter()
+        .map(|attribute| (attribute.attribute_name_index, &attribute.info[..]))
+}
+
+/// The size of a constant within the constant pool, including its tag
+fn constant_size(info: &ConstantPoolInfo) -> usize {
+    match info {
+        ConstantPoolInfo::Utf8(utf8) => 3 + utf8.bytes.len(),
+        ConstantPoolInfo::Class(_)
+        | ConstantPoolInfo::String(_)
+        | ConstantPoolInfo::MethodType(_)
+        | ConstantPoolInfo::Module(_)
+        | ConstantPoolInfo::Package(_) => 3,
+        ConstantPoolInfo::MethodHandle(_) => 4,
+        ConstantPoolInfo::Long(_) | ConstantPoolInfo::Double(_) => 9,
+        _ => 5,
+    }
+}
+
+/// Reads an `attributes` table, as found in the `Code` attribute and record components
+fn read_attributes<'a>(reader: &mut ByteReader<'a>) -> Option<Vec<(u16, &'a [u8])>> {
+    let count = reader.u16()?;
+    (0..count)
+        .map(|_| {
+            let name_index = reader.u16()?;
+            let length = reader.u32()?;
+            Some((name_index, reader.take(length as usize)?))
+        })
+        .collect()
+}
+
+/// Gets the attributes nested within an attribute
+fn nested_attributes<'a>(name: &str, info: &'a [u8]) -> Option<Vec<(u16, &'a [u8])>> {
+    let mut reader = ByteReader::new(info);
+    match name {
+        "Code" => {
+            reader.take(4)?;
+            let code_length = reader.u32()?;
+            reader.take(code_length as usize)?;
+            let exception_table_length = reader.u16()?;
+            reader.take(exception_table_length as usize * 8)?;
+            read_attributes(&mut reader)
+        }
+        "Record" => {
+            let mut nested = vec![];
+            for _ in 0..reader.u16()? {
+                reader.take(4)?;
+                nested.extend(read_attributes(&mut reader)?);
+            }
+            Some(nested)
+        }
+        _ => Some(vec![]),
+    }
+}
+
+/// Finds every constant pool index that is referenced from outside of the constant pool, along
+/// with every entry those reference in turn.
+fn referenced_constants(class: &JavaClass) -> BTreeSet<u16> {
+    let raw = class.raw();
+    let mut references = References {
+        class,
+        indices: BTreeSet::new(),
+    };
+    references.add(raw.this_class);
+    references.add(raw.super_class);
+    for &interface in &raw.interfaces[..] {
+        references.add(interface);
+    }
+    for field in &raw.fields[..] {
+        references.add(field.name_index);
+        references.add(field.descriptor_index);
+        references.attributes(attributes_of(&field.attributes));
+    }
+    for method in &raw.methods[..] {
+        references.add(method.name_index);
+        references.add(method.descriptor_index);
+        references.attributes(attributes_of(&method.attributes));
+    }
+    references.attributes(attributes_of(&raw.attributes));
+    references.indices
+}
+
+struct References<'c> {
+    class: &'c JavaClass,
+    indices: BTreeSet<u16>,
+}
+
+impl<'c> References<'c> {
+    fn add(&mut self, index: u16) {
+        if index == 0 || !self.indices.insert(index) {
+            return;
+        }
+        let Some(info) = self.class.get_at_index(index) else {
+            return;
+        };
+        match *info {
+            ConstantPoolInfo::Class(Class { name_index }) => self.add(name_index),
+            ConstantPoolInfo::String(StringValue { string_index }) => self.add(string_index),
+            ConstantPoolInfo::FieldRef(FieldRef {
+                class_index,
+                name_and_type_index,
+            })
+            | ConstantPoolInfo::MethodRef(MethodRef {
+                class_index,
+                name_and_type_index,
+            })
+            | ConstantPoolInfo::InterfaceMethodRef(InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            }) => {
+                self.add(class_index);
+                self.add(name_and_type_index);
+            }
+            ConstantPoolInfo::NameAndType(NameAndType {
+                name_index,
+                descriptor_index,
+            }) => {
+                self.add(name_index);
+                self.add(descriptor_index);
+            }
+            ConstantPoolInfo::MethodHandle(MethodHandle {
+                reference_index, ..
+            }) => self.add(reference_index),
+            ConstantPoolInfo::MethodType(MethodType { descriptor_index }) => {
+                self.add(descriptor_index)
+            }
+            ConstantPoolInfo::Dynamic(Dynamic {
+                name_and_type_index,
+                ..
+            })
+            | ConstantPoolInfo::InvokeDynamic(InvokeDynamic {
+                name_and_type_index,
+                ..
+            }) => self.add(name_and_type_index),
+            ConstantPoolInfo::Module(Module { name_index })
+            | ConstantPoolInfo::Package(Package { name_index }) => self.add(name_index),
+            _ => {}
+        }
+    }
+
+    fn attributes<'a>(&mut self, attributes: impl IntoIterator<Item = (u16, &'a [u8])>) {
+        for (name_index, info) in attributes {
+            self.add(name_index);
+            if let Some(name) = self.class.get_string(name_index) {
+                // malformed attributes contribute whatever was read before the problem
+                let _ = self.attribute(name, &mut ByteReader::new(info));
+            }
+        }
+    }
+
+    fn indices(&mut self, reader: &mut ByteReader, count: usize) -> Option<()> {
+        for _ in 0..count {
+            self.add(reader.u16()?);
+        }
+        Some(())
+    }
+
+    fn attribute(&mut self, name: &str, reader: &mut ByteReader) -> Option<()> {
+        match name {
+            "ConstantValue" | "SourceFile" | "Signature" | "NestHost" => self.indices(reader, 1)?,
+            "Exceptions" | "NestMembers" | "PermittedSubclasses" => {
+                let count = reader.u16()?;
+                self.indices(reader, count as usize)?;
+            }
+            "EnclosingMethod" => self.indices(reader, 2)?,
+            "InnerClasses" => {
+                for _ in 0..reader.u16()? {
+                    // inner class, outer class, inner name, then access flags
+                    self.indices(reader, 3)?;
+                    reader.u16()?;
+                }
+            }
+            "LocalVariableTable" | "LocalVariableTypeTable" => {
+                for _ in 0..reader.u16()? {
+                    reader.take(4)?;
+                    self.indices(reader, 2)?;
+                    reader.u16()?;
+                }
+            }
+            "MethodParameters" => {
+                for _ in 0..reader.u8()? {
+                    self.indices(reader, 1)?;
+                    reader.u16()?;
+                }
+            }
+            "BootstrapMethods" => {
+                for _ in 0..reader.u16()? {
+                    self.indices(reader, 1)?;
+                    let arguments = reader.u16()?;
+                    self.indices(reader, arguments as usize)?;
+                }
+            }
+            "RuntimeVisibleAnnotations" | "RuntimeInvisibleAnnotations" => {
+                self.annotations(reader)?
+            }
+            "RuntimeVisibleParameterAnnotations" | "RuntimeInvisibleParameterAnnotations" => {
+                for _ in 0..reader.u8()? {
+                    self.annotations(reader)?;
+                }
+            }
+            "AnnotationDefault" => self.element_value(reader)?,
+            "Record" => {
+                for _ in 0..reader.u16()? {
+                    self.indices(reader, 2)?;
+                    let nested = read_attributes(reader)?;
+                    self.attributes(nested);
+                }
+            }
+            "StackMapTable" => self.stack_map_table(reader)?,
+            "Code" => {
+                reader.take(4)?;
+                let code_length = reader.u32()?;
+                let code = reader.take(code_length as usize)?;
+                for instruction in decode(code) {
+                    self.add(instruction.ok()?.constant_index().unwrap_or(0));
+                }
+                for _ in 0..reader.u16()? {
+                    // start, end and handler pc, then the catch type
+                    reader.take(6)?;
+                    self.indices(reader, 1)?;
+                }
+                let nested = read_attributes(reader)?;
+                self.attributes(nested);
+            }
+            _ => {}
+        }
+        Some(())
+    }
+
+    fn annotations(&mut self, reader: &mut ByteReader) -> Option<()> {
+        for _ in 0..reader.u16()? {
+            self.annotation(reader)?;
+        }
+        Some(())
+    }
+
+    fn annotation(&mut self, reader: &mut ByteReader) -> Option<()> {
+        self.indices(reader, 1)?;
+        for _ in 0..reader.u16()? {
+            self.indices(reader, 1)?;
+            self.element_value(reader)?;
+        }
+        Some(())
+    }
+
+    fn element_value(&mut self, reader: &mut ByteReader) -> Option<()> {
+        match reader.u8()? {
+            b'e' => self.indices(reader, 2),
+            b'@' => self.annotation(reader),
+            b'[' => {
+                for _ in 0..reader.u16()? {
+                    self.element_value(reader)?;
+                }
+                Some(())
+            }
+            _ => self.indices(reader, 1),
+        }
+    }
+
+    fn stack_map_table(&mut self, reader: &mut ByteReader) -> Option<()> {
+        for _ in 0..reader.u16()? {
+            match reader.u8()? {
+                0..=63 => {}
+                64..=127 => self.verification_types(reader, 1)?,
+                247 => {
+                    reader.u16()?;
+                    self.verification_types(reader, 1)?;
+                }
+                248..=251 => {
+                    reader.u16()?;
+                }
+                frame_type @ 252..=254 => {
+                    reader.u16()?;
+                    self.verification_types(reader, frame_type as usize - 251)?;
+                }
+                255 => {
+                    reader.u16()?;
+                    let locals = reader.u16()?;
+                    self.verification_types(reader, locals as usize)?;
+                    let stack = reader.u16()?;
+                    self.verification_types(reader, stack as usize)?;
+                }
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+
+    fn verification_types(&mut self, reader: &mut ByteReader, count: usize) -> Option<()> {
+        for _ in 0..count {
+            match reader.u8()? {
+                // object, which refers to a class
+                7 => self.indices(reader, 1)?,
+                // uninitialized, which holds the offset of a new instruction
+                8 => {
+                    reader.u16()?;
+                }
+                _ => {}
+            }
+        }
+        Some(())
+    }
+}
@@ -1,16 +1,25 @@
 mod signatures;
 
-use crate::attributes::Attribute;
+use crate::attributes::{Attribute, AttributeType};
 pub use class::*;
 pub use class_entries::*;
 pub use signatures::*;
 
 pub use fully_qualified_name::*;
 
+pub mod annotations;
 pub mod attributes;
 mod class;
 mod class_entries;
+pub mod dynamic;
+pub mod enums;
 mod fully_qualified_name;
+pub mod generics;
+pub mod module;
+pub mod records;
+pub mod smap;
+pub mod stack_map;
+pub mod version;
 
 /// Objects which implement this trait can be queried for their attributes.
 pub trait HasAttributes {
@@ -23,8 +32,15 @@ pub trait HasAttributes {
     fn attributes<'a>(&'a self) -> Self::Iter<'a>;
 
     /// Attempts to get an attribute by attribute name
-    fn get_attribute(&self, name: &str) -> Option<Attribute> {
+    fn get_attribute(&self, name: &str) -> Option<Attribute<'_>> {
         self.attributes()
             .find(|att: &Attribute| att.attribute_name() == name)
     }
+
+    /// Gets an attribute by its type, such as [`Code`](attributes::Code) for the bytecode of a
+    /// method.
+    fn get<'a, T: AttributeType<'a>>(&'a self) -> Option<T> {
+        self.get_attribute(T::NAME)
+            .and_then(|attribute| T::from_kind(attribute.into_kind()))
+    }
 }
@@ -0,0 +1,743 @@
+//! Annotations, as defined in section [§4.7.16](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.16)
+//! of the JVM specification.
+//!
+//! Annotations on the parameters of a method are stored separately from the annotations on the
+//! method, as are type annotations
+//! ([§4.7.20](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20)), which
+//! annotate a use of a type and record where within the declaration that type appears.
+
+use crate::structures::fully_qualified_name::{FQName, FQNameBuf};
+use crate::{JavaClass, Signature};
+use nom::combinator::{flat_map, map};
+use nom::error::{make_error, ErrorKind};
+use nom::multi::count;
+use nom::number::complete::{be_u16, be_u8};
+use nom::sequence::tuple;
+use nom::IResult;
+use std::collections::BTreeMap;
+
+/// An annotation present on some element of a class file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation<'a> {
+    type_name: &'a FQName,
+    elements: Vec<(&'a str, ElementValue<'a>)>,
+}
+
+impl<'a> Annotation<'a> {
+    /// The fully qualified name of the annotation type
+    pub fn type_name(&self) -> &'a FQName {
+        self.type_name
+    }
+
+    /// The element value pairs explicitly present in this annotation. Elements that use their
+    /// default value are not included.
+    pub fn elements(&self) -> &[(&'a str, ElementValue<'a>)] {
+        &self.elements[..]
+    }
+
+    /// Gets the value of an element by name
+    pub fn get(&self, name: &str) -> Option<&ElementValue<'a>> {
+        self.elements
+            .iter()
+            .find(|(element, _)| *element == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Copies the element values of this annotation into an owned, typed map
+    pub fn values(&self) -> AnnotationValues {
+        self.elements
+            .iter()
+            .map(|(name, value)| (name.to_string(), AnnotationValue::from(value)))
+            .collect()
+    }
+}
+
+/// The value of an element within an annotation
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum ElementValue<'a> {
+    Byte(i8),
+    Char(char),
+    Double(f64),
+    Float(f32),
+    Int(i32),
+    Long(i64),
+    Short(i16),
+    Boolean(bool),
+    String(&'a str),
+    /// An enum constant
+    Enum {
+        /// The fully qualified name of the enum type
+        type_name: &'a FQName,
+        /// The name of the constant
+        const_name: &'a str,
+    },
+    /// A class literal, such as `String.class` or `void.class`
+    Class(Signature<'a>),
+    /// A nested annotation
+    Annotation(Annotation<'a>),
+    /// An array of element values
+    Array(Vec<ElementValue<'a>>),
+}
+
+impl<'a> ElementValue<'a> {
+    /// Gets this value as an enum constant, returning the enum type and constant name
+    pub fn as_enum(&self) -> Option<(&'a FQName, &'a str)> {
+        match self {
+            ElementValue::Enum {
+                type_name,
+                const_name,
+            } => Some((type_name, const_name)),
+            _ => None,
+        }
+    }
+
+    /// Gets this value as a slice of values. Non array values are treated as an array of length 1,
+    /// matching how java allows single values to be used for array typed elements.
+    pub fn as_slice(&self) -> &[ElementValue<'a>] {
+        match self {
+            ElementValue::Array(array) => &array[..],
+            other => std::slice::from_ref(other),
+        }
+    }
+}
+
+/// Serialized as its `type` and its `elements` by name
+#[cfg(feature = "serde")]
+impl serde::Serialize for Annotation<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Elements<'b, 'a>(&'b [(&'a str, ElementValue<'a>)]);
+        impl serde::Serialize for Elements<'_, '_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(self.0.iter().map(|(name, value)| (name, value)))
+            }
+        }
+
+        let mut annotation = serializer.serialize_struct("Annotation", 2)?;
+        annotation.serialize_field("type", self.type_name)?;
+        annotation.serialize_field("elements", &Elements(&self.elements))?;
+        annotation.end()
+    }
+}
+
+/// Serialized as an enum tagged by the kind of value. Class literals are serialized as their
+/// descriptor.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ElementValue<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStructVariant;
+
+        const NAME: &str = "ElementValue";
+        match self {
+            ElementValue::Byte(v) => serializer.serialize_newtype_variant(NAME, 0, "Byte", v),
+            ElementValue::Char(v) => serializer.serialize_newtype_variant(NAME, 1, "Char", v),
+            ElementValue::Double(v) => serializer.serialize_newtype_variant(NAME, 2, "Double", v),
+            ElementValue::Float(v) => serializer.serialize_newtype_variant(NAME, 3, "Float", v),
+            ElementValue::Int(v) => serializer.serialize_newtype_variant(NAME, 4, "Int", v),
+            ElementValue::Long(v) => serializer.serialize_newtype_variant(NAME, 5, "Long", v),
+            ElementValue::Short(v) => serializer.serialize_newtype_variant(NAME, 6, "Short", v),
+            ElementValue::Boolean(v) => serializer.serialize_newtype_variant(NAME, 7, "Boolean", v),
+            ElementValue::String(v) => serializer.serialize_newtype_variant(NAME, 8, "String", v),
+            ElementValue::Enum {
+                type_name,
+                const_name,
+            } => {
+                let mut value = serializer.serialize_struct_variant(NAME, 9, "Enum", 2)?;
+                value.serialize_field("type_name", type_name)?;
+                value.serialize_field("const_name", const_name)?;
+                value.end()
+            }
+            ElementValue::Class(v) => serializer.serialize_newtype_variant(NAME, 10, "Class", v),
+            ElementValue::Annotation(v) => {
+                serializer.serialize_newtype_variant(NAME, 11, "Annotation", v)
+            }
+            ElementValue::Array(v) => serializer.serialize_newtype_variant(NAME, 12, "Array", v),
+        }
+    }
+}
+
+/// The element values of an annotation, by element name
+pub type AnnotationValues = BTreeMap<String, AnnotationValue>;
+
+/// An owned copy of an [`ElementValue`], which can outlive the class it was read from
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum AnnotationValue {
+    Byte(i8),
+    Char(char),
+    Double(f64),
+    Float(f32),
+    Int(i32),
+    Long(i64),
+    Short(i16),
+    Boolean(bool),
+    String(String),
+    /// An enum constant
+    Enum {
+        /// The fully qualified name of the enum type
+        type_name: FQNameBuf,
+        /// The name of the constant
+        const_name: String,
+    },
+    /// A class literal, as a descriptor such as `Ljava/lang/String;` or `V`
+    Class(String),
+    /// A nested annotation
+    Annotation {
+        /// The fully qualified name of the annotation type
+        type_name: FQNameBuf,
+        /// The element values of the nested annotation
+        values: AnnotationValues,
+    },
+    /// An array of values
+    Array(Vec<AnnotationValue>),
+}
+
+impl From<&ElementValue<'_>> for AnnotationValue {
+    fn from(value: &ElementValue<'_>) -> Self {
+        match value {
+            ElementValue::Byte(v) => Self::Byte(*v),
+            ElementValue::Char(v) => Self::Char(*v),
+            ElementValue::Double(v) => Self::Double(*v),
+            ElementValue::Float(v) => Self::Float(*v),
+            ElementValue::Int(v) => Self::Int(*v),
+            ElementValue::Long(v) => Self::Long(*v),
+            ElementValue::Short(v) => Self::Short(*v),
+            ElementValue::Boolean(v) => Self::Boolean(*v),
+            ElementValue::String(v) => Self::String(v.to_string()),
+            ElementValue::Enum {
+                type_name,
+                const_name,
+            } => Self::Enum {
+                type_name: type_name.to_fqname_buf(),
+                const_name: const_name.to_string(),
+            },
+            ElementValue::Class(signature) => Self::Class(signature.jni()),
+            ElementValue::Annotation(annotation) => Self::Annotation {
+                type_name: annotation.type_name().to_fqname_buf(),
+                values: annotation.values(),
+            },
+            ElementValue::Array(values) => Self::Array(values.iter().map(Self::from).collect()),
+        }
+    }
+}
+
+/// Deserializes annotation values into rust types with serde.
+///
+/// Nested annotations are deserialized as maps, enum constants as unit variants named after the
+/// constant, and class literals as their descriptor. Like java, a single value can be deserialized
+/// as a sequence of length 1.
+#[cfg(feature = "serde")]
+mod de {
+    use super::{AnnotationValue, AnnotationValues};
+    use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+    use serde::de::{IntoDeserializer, Visitor};
+    use serde::{forward_to_deserialize_any, Deserializer};
+
+    impl<'de> IntoDeserializer<'de, Error> for AnnotationValue {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            self
+        }
+    }
+
+    pub(crate) fn values_deserializer(
+        values: AnnotationValues,
+    ) -> MapDeserializer<
+        'static,
+        std::collections::btree_map::IntoIter<String, AnnotationValue>,
+        Error,
+    > {
+        MapDeserializer::new(values.into_iter())
+    }
+
+    impl<'de> Deserializer<'de> for AnnotationValue {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self {
+                AnnotationValue::Byte(v) => visitor.visit_i8(v),
+                AnnotationValue::Char(v) => visitor.visit_char(v),
+                AnnotationValue::Double(v) => visitor.visit_f64(v),
+                AnnotationValue::Float(v) => visitor.visit_f32(v),
+                AnnotationValue::Int(v) => visitor.visit_i32(v),
+                AnnotationValue::Long(v) => visitor.visit_i64(v),
+                AnnotationValue::Short(v) => visitor.visit_i16(v),
+                AnnotationValue::Boolean(v) => visitor.visit_bool(v),
+                AnnotationValue::String(v) => visitor.visit_string(v),
+                AnnotationValue::Enum { const_name, .. } => visitor.visit_string(const_name),
+                AnnotationValue::Class(descriptor) => visitor.visit_string(descriptor),
+                AnnotationValue::Annotation { values, .. } => {
+                    visitor.visit_map(values_deserializer(values))
+                }
+                AnnotationValue::Array(values) => {
+                    visitor.visit_seq(SeqDeserializer::new(values.into_iter()))
+                }
+            }
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_some(self)
+        }
+
+        fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self {
+                AnnotationValue::Array(values) => {
+                    visitor.visit_seq(SeqDeserializer::new(values.into_iter()))
+                }
+                single => visitor.visit_seq(SeqDeserializer::new(std::iter::once(single))),
+            }
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            match self {
+                AnnotationValue::Enum { const_name, .. } => {
+                    visitor.visit_enum(const_name.into_deserializer())
+                }
+                other => other.deserialize_any(visitor),
+            }
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) use de::values_deserializer;
+
+/// The retention policy of an annotation type, declared with `@java.lang.annotation.Retention`
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum RetentionPolicy {
+    /// Discarded by the compiler
+    Source,
+    /// Recorded in the class file but not retained at runtime. This is the default.
+    Class,
+    /// Recorded in the class file and available reflectively at runtime
+    Runtime,
+}
+
+impl RetentionPolicy {
+    /// Gets a retention policy from the name of a `java.lang.annotation.RetentionPolicy` constant
+    pub fn from_const_name(name: &str) -> Option<Self> {
+        match name {
+            "SOURCE" => Some(Self::Source),
+            "CLASS" => Some(Self::Class),
+            "RUNTIME" => Some(Self::Runtime),
+            _ => None,
+        }
+    }
+}
+
+/// The contexts an annotation type is applicable in, declared with `@java.lang.annotation.Target`
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+#[allow(missing_docs)]
+pub enum ElementType {
+    Type,
+    Field,
+    Method,
+    Parameter,
+    Constructor,
+    LocalVariable,
+    AnnotationType,
+    Package,
+    TypeParameter,
+    TypeUse,
+    Module,
+    RecordComponent,
+}
+
+impl ElementType {
+    /// Gets an element type from the name of a `java.lang.annotation.ElementType` constant
+    pub fn from_const_name(name: &str) -> Option<Self> {
+        let element_type = match name {
+            "TYPE" => Self::Type,
+            "FIELD" => Self::Field,
+            "METHOD" => Self::Method,
+            "PARAMETER" => Self::Parameter,
+            "CONSTRUCTOR" => Self::Constructor,
+            "LOCAL_VARIABLE" => Self::LocalVariable,
+            "ANNOTATION_TYPE" => Self::AnnotationType,
+            "PACKAGE" => Self::Package,
+            "TYPE_PARAMETER" => Self::TypeParameter,
+            "TYPE_USE" => Self::TypeUse,
+            "MODULE" => Self::Module,
+            "RECORD_COMPONENT" => Self::RecordComponent,
+            _ => return None,
+        };
+        Some(element_type)
+    }
+}
+
+/// An annotation on a use of a type
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotation<'a> {
+    target_type: u8,
+    target: TypeAnnotationTarget,
+    type_path: Vec<TypePathEntry>,
+    annotation: Annotation<'a>,
+}
+
+impl<'a> TypeAnnotation<'a> {
+    /// The raw `target_type` of the annotation, which distinguishes targets that share the same
+    /// kind of [target](TypeAnnotationTarget), such as the return type and the type of a field
+    pub fn target_type(&self) -> u8 {
+        self.target_type
+    }
+
+    /// The type within the declaration that is annotated
+    pub fn target(&self) -> &TypeAnnotationTarget {
+        &self.target
+    }
+
+    /// The path to the annotated part of the target type, such as an element type of an array or
+    /// a type argument. Empty if the whole type is annotated.
+    pub fn type_path(&self) -> &[TypePathEntry] {
+        &self.type_path[..]
+    }
+
+    /// The annotation
+    pub fn annotation(&self) -> &Annotation<'a> {
+        &self.annotation
+    }
+}
+
+/// Serialized as its raw `target_type` and its `annotation`
+#[cfg(feature = "serde")]
+impl serde::Serialize for TypeAnnotation<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut annotation = serializer.serialize_struct("TypeAnnotation", 2)?;
+        annotation.serialize_field("target_type", &self.target_type)?;
+        annotation.serialize_field("annotation", &self.annotation)?;
+        annotation.end()
+    }
+}
+
+/// The type annotated by a type annotation, decoded from its `target_info`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeAnnotationTarget {
+    /// A type parameter of a generic class or method
+    TypeParameter {
+        /// The index of the type parameter
+        index: u8,
+    },
+    /// The superclass, when `index` is `65535`, or an interface of a class
+    Supertype {
+        /// The index of the interface in the interfaces of the class
+        index: u16,
+    },
+    /// A bound of a type parameter of a generic class or method
+    TypeParameterBound {
+        /// The index of the type parameter
+        type_parameter_index: u8,
+        /// The index of the bound of the type parameter
+        bound_index: u8,
+    },
+    /// The type of a field or record component, the return type of a method, or the receiver
+    /// type of a method
+    Empty,
+    /// The type of a parameter of a method
+    FormalParameter {
+        /// The index of the parameter, not counting implicit parameters
+        index: u8,
+    },
+    /// A type in the `throws` clause of a method
+    Throws {
+        /// The index of the type in the exceptions of the method
+        index: u16,
+    },
+    /// The type of a local variable, or a resource in a try-with-resources statement
+    LocalVariable(Vec<LocalVariableTarget>),
+    /// The type in a catch clause
+    Catch {
+        /// The index of the handler in the exception table of the code
+        exception_table_index: u16,
+    },
+    /// The type in an `instanceof` expression, a `new` expression or a method reference
+    Offset {
+        /// The bytecode offset of the instruction using the type
+        offset: u16,
+    },
+    /// A type argument of a cast, constructor invocation, method invocation or method reference
+    TypeArgument {
+        /// The bytecode offset of the instruction using the type
+        offset: u16,
+        /// The index of the type argument
+        type_argument_index: u8,
+    },
+}
+
+/// A range of bytecode in which a local variable has an annotated type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalVariableTarget {
+    /// The start of the range of bytecode
+    pub start_pc: u16,
+    /// The length of the range of bytecode
+    pub length: u16,
+    /// The index of the local variable
+    pub index: u16,
+}
+
+/// A step within a type, from the outermost type to the annotated part of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypePathEntry {
+    /// Into the element type of an array
+    Array,
+    /// Into a nested type
+    Nested,
+    /// Into the bound of a wildcard type argument
+    WildcardBound,
+    /// Into a type argument of a parameterized type
+    TypeArgument(u8),
+}
+
+/// Strips the `L` and `;` from a field descriptor, leaving the class name
+fn descriptor_class_name(descriptor: &str) -> Option<&FQName> {
+    descriptor
+        .strip_prefix('L')
+        .and_then(|s| s.strip_suffix(';'))
+        .map(FQName::new)
+}
+
+/// Fails parsing if a constant pool lookup couldn't be resolved
+fn resolve<T>(bytes: &[u8], value: Option<T>) -> IResult<&[u8], T> {
+    match value {
+        Some(value) => Ok((bytes, value)),
+        None => Err(nom::Err::Failure(make_error(bytes, ErrorKind::Verify))),
+    }
+}
+
+/// Parses the `annotations` table shared by the `RuntimeVisibleAnnotations` and
+/// `RuntimeInvisibleAnnotations` attributes.
+pub(crate) fn parse_annotations<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<Annotation<'a>>> {
+    flat_map(be_u16, |num_annotations| {
+        count(|b| parse_annotation(b, class), num_annotations as usize)
+    })(bytes)
+}
+
+/// Parses the tables shared by the `RuntimeVisibleParameterAnnotations` and
+/// `RuntimeInvisibleParameterAnnotations` attributes.
+pub(crate) fn parse_parameter_annotations<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<Vec<Annotation<'a>>>> {
+    flat_map(be_u8, |num_parameters| {
+        count(|b| parse_annotations(b, class), num_parameters as usize)
+    })(bytes)
+}
+
+/// Parses the `annotations` table shared by the `RuntimeVisibleTypeAnnotations` and
+/// `RuntimeInvisibleTypeAnnotations` attributes.
+pub(crate) fn parse_type_annotations<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<TypeAnnotation<'a>>> {
+    flat_map(be_u16, |num_annotations| {
+        count(
+            |b| parse_type_annotation(b, class),
+            num_annotations as usize,
+        )
+    })(bytes)
+}
+
+fn parse_type_annotation<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], TypeAnnotation<'a>> {
+    let (bytes, target_type) = be_u8(bytes)?;
+    let (bytes, target) = match target_type {
+        0x00 | 0x01 => map(be_u8, |index| TypeAnnotationTarget::TypeParameter { index })(bytes)?,
+        0x10 => map(be_u16, |index| TypeAnnotationTarget::Supertype { index })(bytes)?,
+        0x11 | 0x12 => map(
+            tuple((be_u8, be_u8)),
+            |(type_parameter_index, bound_index)| TypeAnnotationTarget::TypeParameterBound {
+                type_parameter_index,
+                bound_index,
+            },
+        )(bytes)?,
+        0x13..=0x15 => (bytes, TypeAnnotationTarget::Empty),
+        0x16 => map(be_u8, |index| TypeAnnotationTarget::FormalParameter {
+            index,
+        })(bytes)?,
+        0x17 => map(be_u16, |index| TypeAnnotationTarget::Throws { index })(bytes)?,
+        0x40 | 0x41 => map(
+            flat_map(be_u16, |table_length| {
+                count(
+                    map(
+                        tuple((be_u16, be_u16, be_u16)),
+                        |(start_pc, length, index)| LocalVariableTarget {
+                            start_pc,
+                            length,
+                            index,
+                        },
+                    ),
+                    table_length as usize,
+                )
+            }),
+            TypeAnnotationTarget::LocalVariable,
+        )(bytes)?,
+        0x42 => map(be_u16, |exception_table_index| {
+            TypeAnnotationTarget::Catch {
+                exception_table_index,
+            }
+        })(bytes)?,
+        0x43..=0x46 => map(be_u16, |offset| TypeAnnotationTarget::Offset { offset })(bytes)?,
+        0x47..=0x4B => map(tuple((be_u16, be_u8)), |(offset, type_argument_index)| {
+            TypeAnnotationTarget::TypeArgument {
+                offset,
+                type_argument_index,
+            }
+        })(bytes)?,
+        _ => return Err(nom::Err::Failure(make_error(bytes, ErrorKind::Tag))),
+    };
+    let (bytes, type_path) = flat_map(be_u8, |path_length| {
+        count(
+            |b| {
+                let (b, (kind, type_argument_index)) = tuple((be_u8, be_u8))(b)?;
+                let entry = match kind {
+                    0 => TypePathEntry::Array,
+                    1 => TypePathEntry::Nested,
+                    2 => TypePathEntry::WildcardBound,
+                    3 => TypePathEntry::TypeArgument(type_argument_index),
+                    _ => return Err(nom::Err::Failure(make_error(b, ErrorKind::Tag))),
+                };
+                Ok((b, entry))
+            },
+            path_length as usize,
+        )
+    })(bytes)?;
+    let (bytes, annotation) = parse_annotation(bytes, class)?;
+    Ok((
+        bytes,
+        TypeAnnotation {
+            target_type,
+            target,
+            type_path,
+            annotation,
+        },
+    ))
+}
+
+pub(crate) fn parse_annotation<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Annotation<'a>> {
+    let (bytes, type_index) = be_u16(bytes)?;
+    let (bytes, type_name) = resolve(
+        bytes,
+        class.get_string(type_index).and_then(descriptor_class_name),
+    )?;
+    let (bytes, elements) = flat_map(be_u16, |num_pairs| {
+        count(
+            |b| {
+                let (b, name_index) = be_u16(b)?;
+                let (b, name) = resolve(b, class.get_string(name_index))?;
+                let (b, value) = parse_element_value(b, class)?;
+                Ok((b, (name, value)))
+            },
+            num_pairs as usize,
+        )
+    })(bytes)?;
+    Ok((
+        bytes,
+        Annotation {
+            type_name,
+            elements,
+        },
+    ))
+}
+
+pub(crate) fn parse_element_value<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], ElementValue<'a>> {
+    let (bytes, tag) = be_u8(bytes)?;
+    match tag {
+        b'B' | b'C' | b'I' | b'S' | b'Z' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, int) = resolve(bytes, class.constant_pool().get_integer(index))?;
+            let value = match tag {
+                b'B' => ElementValue::Byte(int as i8),
+                b'C' => {
+                    let (bytes, c) = resolve(bytes, char::from_u32(int as u32))?;
+                    return Ok((bytes, ElementValue::Char(c)));
+                }
+                b'I' => ElementValue::Int(int),
+                b'S' => ElementValue::Short(int as i16),
+                _ => ElementValue::Boolean(int != 0),
+            };
+            Ok((bytes, value))
+        }
+        b'D' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, double) = resolve(bytes, class.constant_pool().get_double(index))?;
+            Ok((bytes, ElementValue::Double(double)))
+        }
+        b'F' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, float) = resolve(bytes, class.constant_pool().get_float(index))?;
+            Ok((bytes, ElementValue::Float(float)))
+        }
+        b'J' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, long) = resolve(bytes, class.constant_pool().get_long(index))?;
+            Ok((bytes, ElementValue::Long(long)))
+        }
+        b's' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, string) = resolve(bytes, class.get_string(index))?;
+            Ok((bytes, ElementValue::String(string)))
+        }
+        b'e' => {
+            let (bytes, (type_name_index, const_name_index)) = tuple((be_u16, be_u16))(bytes)?;
+            let (bytes, type_name) = resolve(
+                bytes,
+                class
+                    .get_string(type_name_index)
+                    .and_then(descriptor_class_name),
+            )?;
+            let (bytes, const_name) = resolve(bytes, class.get_string(const_name_index))?;
+            Ok((
+                bytes,
+                ElementValue::Enum {
+                    type_name,
+                    const_name,
+                },
+            ))
+        }
+        b'c' => {
+            let (bytes, index) = be_u16(bytes)?;
+            let (bytes, signature) = resolve(
+                bytes,
+                class.get_string(index).and_then(|s| Signature::new(s).ok()),
+            )?;
+            Ok((bytes, ElementValue::Class(signature)))
+        }
+        b'@' => {
+            let (bytes, annotation) = parse_annotation(bytes, class)?;
+            Ok((bytes, ElementValue::Annotation(annotation)))
+        }
+        b'[' => {
+            let (bytes, values) = flat_map(be_u16, |num_values| {
+                count(|b| parse_element_value(b, class), num_values as usize)
+            })(bytes)?;
+            Ok((bytes, ElementValue::Array(values)))
+        }
+        _ => Err(nom::Err::Failure(make_error(bytes, ErrorKind::Tag))),
+    }
+}
@@ -1,20 +1,32 @@
 //! Parsed attributes
 
+use crate::bytecode::{decode, Instructions, ResolvedInstructions};
 use crate::constant_pool::parser::parse_attribute_info;
+use crate::constant_pool::values::decode_modified_utf8;
 use crate::raw_java_class::RawAttributeInfo;
+use crate::structures::annotations::{
+    parse_annotations, parse_parameter_annotations, parse_type_annotations, Annotation,
+    TypeAnnotation,
+};
+use crate::structures::dynamic::{parse_bootstrap_methods, BootstrapMethod};
 use crate::structures::fully_qualified_name::FQName;
+use crate::structures::generics::GenericSignature;
+use crate::structures::module::{parse_module, parse_module_packages, JavaModule};
+use crate::structures::records::{parse_record, RecordComponent};
+use crate::structures::stack_map::{parse_stack_map_table, StackMapTable};
 use crate::utility::match_as;
-use crate::{ConstantPoolInfo, HasAttributes};
+use crate::HasAttributes;
 use crate::{JavaClass, Signature};
 use byteorder::ByteOrder;
 use nom::bytes::complete::take;
-use nom::combinator::{complete, flat_map, map};
+use nom::combinator::{complete, flat_map, map, map_opt};
 use nom::multi::count;
-use nom::number::complete::{be_u16, be_u32};
+use nom::number::complete::{be_u16, be_u32, be_u8};
 use nom::sequence::tuple;
 use nom::{Finish, IResult};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::ops::Range;
 use std::path::Path;
 
 /// An attribute info piece. Can be parsed into usable data
@@ -29,14 +41,55 @@ pub struct Attribute<'a> {
 pub enum AttributeKind<'a> {
     /// A source file
     SourceFile(&'a Path),
-    /// A signature
-    Signature(Signature<'a>),
+    /// The generic signature of a class, method or field
+    Signature(GenericSignature<'a>),
     /// Java bytecode
     Code(Code<'a>),
     /// A line number table helps map bytecode to original line numbers
     LineNumberTable(LineNumberTable),
     /// Deprecated
     Deprecated,
+    /// Annotations that are visible at runtime through reflection
+    RuntimeVisibleAnnotations(Vec<Annotation<'a>>),
+    /// Annotations that are recorded in the class file, but not visible through reflection
+    RuntimeInvisibleAnnotations(Vec<Annotation<'a>>),
+    /// Annotations on the parameters of a method that are visible at runtime through reflection
+    RuntimeVisibleParameterAnnotations(Vec<Vec<Annotation<'a>>>),
+    /// Annotations on the parameters of a method that aren't visible through reflection
+    RuntimeInvisibleParameterAnnotations(Vec<Vec<Annotation<'a>>>),
+    /// Annotations on types that are visible at runtime through reflection
+    RuntimeVisibleTypeAnnotations(Vec<TypeAnnotation<'a>>),
+    /// Annotations on types that aren't visible through reflection
+    RuntimeInvisibleTypeAnnotations(Vec<TypeAnnotation<'a>>),
+    /// The components of a record class
+    Record(Vec<RecordComponent<'a>>),
+    /// The checked exceptions a method declares it may throw
+    Exceptions(Vec<&'a FQName>),
+    /// The classes that are permitted to directly extend or implement a sealed class
+    PermittedSubclasses(Vec<&'a FQName>),
+    /// The value of a constant field
+    ConstantValue(ConstantValue<'a>),
+    /// The nested classes that are members of a class, or referenced by it
+    InnerClasses(Vec<InnerClass<'a>>),
+    /// The method or initializer that a local or anonymous class is declared in
+    EnclosingMethod(EnclosingMethod<'a>),
+    /// The bootstrap methods that link the call sites and dynamic constants of a class
+    BootstrapMethods(Vec<BootstrapMethod<'a>>),
+    /// The names and access flags of the parameters of a method
+    MethodParameters(Vec<MethodParameter<'a>>),
+    /// The local variables of a method, used by debuggers
+    LocalVariableTable(Vec<LocalVariable<'a>>),
+    /// The types of the locals and stack at the start of each basic block of a method
+    StackMapTable(StackMapTable<'a>),
+    /// The declaration of a module. Its packages and main class are found in separate
+    /// attributes, and are combined by [`JavaClass::as_module`].
+    Module(JavaModule<'a>),
+    /// Every package of a module, in internal form
+    ModulePackages(Vec<&'a str>),
+    /// The main class of a module
+    ModuleMainClass(&'a FQName),
+    /// Extended debugging information, usually a [source map](crate::smap)
+    SourceDebugExtension(String),
     /// An unknown attribute
     Unknown(&'a [u8]),
 }
@@ -51,18 +104,26 @@ impl<'a> Attribute<'a> {
 
         let kind: AttributeKind = match attribute_name {
             "SourceFile" => {
+                if bytes.len() != 2 {
+                    return Err(error());
+                }
                 let index = byteorder::BigEndian::read_u16(bytes);
                 let utf8 = class.get_string(index).ok_or(error())?;
                 AttributeKind::SourceFile(Path::new(utf8))
             }
             "Signature" => {
+                if bytes.len() != 2 {
+                    return Err(error());
+                }
                 let index = byteorder::BigEndian::read_u16(bytes);
                 let utf8 = class.get_string(index).ok_or(error())?;
-                let signature = Signature::new(utf8).map_err(|_| error())?;
+                let signature = GenericSignature::new(utf8).map_err(|_| error())?;
                 AttributeKind::Signature(signature)
             }
             "Code" => {
-                let (_, code) = parse_code_attr(bytes, class).finish().unwrap();
+                let (_, code) = parse_code_attr(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
                 AttributeKind::Code(code)
             }
             "LineNumberTable" => {
@@ -71,12 +132,160 @@ impl<'a> Attribute<'a> {
                         count(tuple((be_u16, be_u16)), length as usize)
                     })(bytes)
                 };
-                let (_, lines) = parser(bytes).finish().unwrap();
+                let (_, lines) = parser(bytes).finish().map_err(|_| error())?;
                 AttributeKind::LineNumberTable(LineNumberTable {
                     line_number_table: lines.into_boxed_slice(),
                 })
             }
             "Deprecated" => AttributeKind::Deprecated,
+            "RuntimeVisibleAnnotations" => {
+                let (_, annotations) = parse_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeVisibleAnnotations(annotations)
+            }
+            "RuntimeInvisibleAnnotations" => {
+                let (_, annotations) = parse_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeInvisibleAnnotations(annotations)
+            }
+            "RuntimeVisibleParameterAnnotations" => {
+                let (_, annotations) = parse_parameter_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeVisibleParameterAnnotations(annotations)
+            }
+            "RuntimeInvisibleParameterAnnotations" => {
+                let (_, annotations) = parse_parameter_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeInvisibleParameterAnnotations(annotations)
+            }
+            "RuntimeVisibleTypeAnnotations" => {
+                let (_, annotations) = parse_type_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeVisibleTypeAnnotations(annotations)
+            }
+            "RuntimeInvisibleTypeAnnotations" => {
+                let (_, annotations) = parse_type_annotations(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::RuntimeInvisibleTypeAnnotations(annotations)
+            }
+            "Record" => {
+                let (_, components) = parse_record(bytes, class).finish().map_err(|_| error())?;
+                AttributeKind::Record(components)
+            }
+            "Exceptions" => {
+                let (_, indices) = parse_u16_table(bytes).finish().map_err(|_| error())?;
+                let exceptions = indices
+                    .into_iter()
+                    .map(|index| class.constant_pool().get_class_name(index))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(error)?;
+                AttributeKind::Exceptions(exceptions)
+            }
+            "PermittedSubclasses" => {
+                let (_, indices) = parse_u16_table(bytes).finish().map_err(|_| error())?;
+                let permitted = indices
+                    .into_iter()
+                    .map(|index| class.constant_pool().get_class_name(index))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(error)?;
+                AttributeKind::PermittedSubclasses(permitted)
+            }
+            "ConstantValue" => {
+                if bytes.len() != 2 {
+                    return Err(error());
+                }
+                let index = byteorder::BigEndian::read_u16(bytes);
+                let pool = class.constant_pool();
+                let value = pool
+                    .get_integer(index)
+                    .map(JavaConstant::Integer)
+                    .or_else(|| pool.get_float(index).map(JavaConstant::Float))
+                    .or_else(|| pool.get_long(index).map(JavaConstant::Long))
+                    .or_else(|| pool.get_double(index).map(JavaConstant::Double))
+                    .or_else(|| pool.get_string_lossy(index).map(JavaConstant::String))
+                    .ok_or_else(error)?;
+                AttributeKind::ConstantValue(ConstantValue { index, value })
+            }
+            "InnerClasses" => {
+                let (_, classes) = parse_inner_classes(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::InnerClasses(classes)
+            }
+            "EnclosingMethod" => {
+                if bytes.len() != 4 {
+                    return Err(error());
+                }
+                let pool = class.constant_pool();
+                let class_index = byteorder::BigEndian::read_u16(bytes);
+                let method_index = byteorder::BigEndian::read_u16(&bytes[2..]);
+                let method = match method_index {
+                    0 => None,
+                    index => {
+                        let (name, descriptor) = pool.get_name_and_type(index).ok_or_else(error)?;
+                        Some((name, Signature::new(descriptor).map_err(|_| error())?))
+                    }
+                };
+                AttributeKind::EnclosingMethod(EnclosingMethod {
+                    class: pool.get_class_name(class_index).ok_or_else(error)?,
+                    method,
+                })
+            }
+            "BootstrapMethods" => {
+                let (_, methods) = parse_bootstrap_methods(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::BootstrapMethods(methods)
+            }
+            "MethodParameters" => {
+                let (_, parameters) = parse_method_parameters(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::MethodParameters(parameters)
+            }
+            "LocalVariableTable" => {
+                let (_, variables) = parse_local_variables(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::LocalVariableTable(variables)
+            }
+            "StackMapTable" => {
+                let (_, table) = parse_stack_map_table(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::StackMapTable(table)
+            }
+            "Module" => {
+                let (_, module) = parse_module(bytes, class).finish().map_err(|_| error())?;
+                AttributeKind::Module(module)
+            }
+            "ModulePackages" => {
+                let (_, packages) = parse_module_packages(bytes, class)
+                    .finish()
+                    .map_err(|_| error())?;
+                AttributeKind::ModulePackages(packages)
+            }
+            "ModuleMainClass" => {
+                if bytes.len() != 2 {
+                    return Err(error());
+                }
+                let index = byteorder::BigEndian::read_u16(bytes);
+                let main_class = class
+                    .constant_pool()
+                    .get_class_name(index)
+                    .ok_or_else(error)?;
+                AttributeKind::ModuleMainClass(main_class)
+            }
+            "SourceDebugExtension" => {
+                let extension = decode_modified_utf8(bytes).map_err(|_| error())?;
+                AttributeKind::SourceDebugExtension(extension)
+            }
             _ => AttributeKind::Unknown(bytes),
         };
         Ok(Self {
@@ -85,6 +294,14 @@ impl<'a> Attribute<'a> {
         })
     }
 
+    /// An attribute that's kept as its bytes, because it isn't known or couldn't be resolved
+    pub(crate) fn unknown(attribute_name: &'a str, bytes: &'a [u8]) -> Self {
+        Self {
+            attribute_name,
+            kind: AttributeKind::Unknown(bytes),
+        }
+    }
+
     /// Gets the name of the attribute
     pub fn attribute_name(&self) -> &'a str {
         self.attribute_name
@@ -100,6 +317,307 @@ impl<'a> Attribute<'a> {
     pub fn kind(&self) -> &AttributeKind<'a> {
         &self.kind
     }
+
+    /// Converts the attribute into its kind
+    pub fn into_kind(self) -> AttributeKind<'a> {
+        self.kind
+    }
+}
+
+/// A parsed attribute that can be looked up by its type, using [`HasAttributes::get`].
+///
+/// # Example
+/// ```no_run
+/// # use java_class_parser::attributes::{Code, SourceFile};
+/// # use java_class_parser::{HasAttributes, JavaClassParser};
+/// let parser = JavaClassParser::new("app.jar");
+/// let class = parser.find("com/example/Foo").unwrap();
+/// println!("{:?}", class.get::<SourceFile>().map(|file| file.path()));
+/// for method in class.methods() {
+///     if let Some(code) = method.get::<Code>() {
+///         println!("{}: {} bytes", method.name(), code.code().len());
+///     }
+/// }
+/// ```
+pub trait AttributeType<'a>: Sized {
+    /// The name of the attribute
+    const NAME: &'static str;
+
+    /// Gets the attribute from its kind, if it's the kind of this type
+    fn from_kind(kind: AttributeKind<'a>) -> Option<Self>;
+}
+
+// Implements `AttributeType` for the value of each variant, or for a wrapper around it that keeps
+// the value in `field`
+macro_rules! attribute_type {
+    ($($variant:ident($ty:ty) $({ $field:ident })?),* $(,)?) => {
+        $(
+            impl<'a> AttributeType<'a> for $ty {
+                const NAME: &'static str = stringify!($variant);
+
+                fn from_kind(kind: AttributeKind<'a>) -> Option<Self> {
+                    let value = match_as!(value; AttributeKind::$variant(value) = kind)?;
+                    Some(attribute_type!(@wrap value $($field)?))
+                }
+            }
+        )*
+    };
+    (@wrap $value:ident) => { $value };
+    (@wrap $value:ident $field:ident) => { Self { $field: $value } };
+}
+
+attribute_type! {
+    SourceFile(SourceFile<'a>) { path },
+    Signature(GenericSignature<'a>),
+    Code(Code<'a>),
+    LineNumberTable(LineNumberTable),
+    RuntimeVisibleAnnotations(RuntimeVisibleAnnotations<'a>) { annotations },
+    RuntimeInvisibleAnnotations(RuntimeInvisibleAnnotations<'a>) { annotations },
+    RuntimeVisibleParameterAnnotations(RuntimeVisibleParameterAnnotations<'a>) { annotations },
+    RuntimeInvisibleParameterAnnotations(RuntimeInvisibleParameterAnnotations<'a>) { annotations },
+    RuntimeVisibleTypeAnnotations(RuntimeVisibleTypeAnnotations<'a>) { annotations },
+    RuntimeInvisibleTypeAnnotations(RuntimeInvisibleTypeAnnotations<'a>) { annotations },
+    Record(Record<'a>) { components },
+    Exceptions(Exceptions<'a>) { exceptions },
+    PermittedSubclasses(PermittedSubclasses<'a>) { classes },
+    ConstantValue(ConstantValue<'a>),
+    InnerClasses(InnerClasses<'a>) { classes },
+    EnclosingMethod(EnclosingMethod<'a>),
+    BootstrapMethods(BootstrapMethods<'a>) { methods },
+    MethodParameters(MethodParameters<'a>) { parameters },
+    LocalVariableTable(LocalVariableTable<'a>) { variables },
+    StackMapTable(StackMapTable<'a>),
+    Module(JavaModule<'a>),
+    ModulePackages(ModulePackages<'a>) { packages },
+    ModuleMainClass(ModuleMainClass<'a>) { main_class },
+    SourceDebugExtension(SourceDebugExtension) { extension },
+}
+
+/// The `SourceFile` attribute of a class, naming the file it was compiled from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile<'a> {
+    path: &'a Path,
+}
+
+impl<'a> SourceFile<'a> {
+    /// The name of the source file, such as `Foo.java`
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+}
+
+/// The `RuntimeVisibleAnnotations` attribute, holding the annotations that are visible at runtime
+/// through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeVisibleAnnotations<'a> {
+    annotations: Vec<Annotation<'a>>,
+}
+
+impl<'a> RuntimeVisibleAnnotations<'a> {
+    /// The annotations
+    pub fn annotations(&self) -> &[Annotation<'a>] {
+        &self.annotations
+    }
+}
+
+/// The `RuntimeInvisibleAnnotations` attribute, holding the annotations that are recorded in the
+/// class file but aren't visible through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeInvisibleAnnotations<'a> {
+    annotations: Vec<Annotation<'a>>,
+}
+
+impl<'a> RuntimeInvisibleAnnotations<'a> {
+    /// The annotations
+    pub fn annotations(&self) -> &[Annotation<'a>] {
+        &self.annotations
+    }
+}
+
+/// The `RuntimeVisibleParameterAnnotations` attribute of a method, holding the annotations on its
+/// parameters that are visible at runtime through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeVisibleParameterAnnotations<'a> {
+    annotations: Vec<Vec<Annotation<'a>>>,
+}
+
+impl<'a> RuntimeVisibleParameterAnnotations<'a> {
+    /// The annotations of each parameter, in order
+    pub fn annotations(&self) -> &[Vec<Annotation<'a>>] {
+        &self.annotations
+    }
+}
+
+/// The `RuntimeInvisibleParameterAnnotations` attribute of a method, holding the annotations on its
+/// parameters that aren't visible through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeInvisibleParameterAnnotations<'a> {
+    annotations: Vec<Vec<Annotation<'a>>>,
+}
+
+impl<'a> RuntimeInvisibleParameterAnnotations<'a> {
+    /// The annotations of each parameter, in order
+    pub fn annotations(&self) -> &[Vec<Annotation<'a>>] {
+        &self.annotations
+    }
+}
+
+/// The `RuntimeVisibleTypeAnnotations` attribute, holding the annotations on types that are visible
+/// at runtime through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeVisibleTypeAnnotations<'a> {
+    annotations: Vec<TypeAnnotation<'a>>,
+}
+
+impl<'a> RuntimeVisibleTypeAnnotations<'a> {
+    /// The type annotations
+    pub fn annotations(&self) -> &[TypeAnnotation<'a>] {
+        &self.annotations
+    }
+}
+
+/// The `RuntimeInvisibleTypeAnnotations` attribute, holding the annotations on types that aren't
+/// visible through reflection
+#[derive(Debug, Clone)]
+pub struct RuntimeInvisibleTypeAnnotations<'a> {
+    annotations: Vec<TypeAnnotation<'a>>,
+}
+
+impl<'a> RuntimeInvisibleTypeAnnotations<'a> {
+    /// The type annotations
+    pub fn annotations(&self) -> &[TypeAnnotation<'a>] {
+        &self.annotations
+    }
+}
+
+/// The `Record` attribute of a record class
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    components: Vec<RecordComponent<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// The components of the record, in declaration order
+    pub fn components(&self) -> &[RecordComponent<'a>] {
+        &self.components
+    }
+}
+
+/// The `Exceptions` attribute of a method
+#[derive(Debug, Clone)]
+pub struct Exceptions<'a> {
+    exceptions: Vec<&'a FQName>,
+}
+
+impl<'a> Exceptions<'a> {
+    /// The checked exceptions the method declares it may throw
+    pub fn exceptions(&self) -> &[&'a FQName] {
+        &self.exceptions
+    }
+}
+
+/// The `PermittedSubclasses` attribute of a sealed class
+#[derive(Debug, Clone)]
+pub struct PermittedSubclasses<'a> {
+    classes: Vec<&'a FQName>,
+}
+
+impl<'a> PermittedSubclasses<'a> {
+    /// The classes that are permitted to directly extend or implement the class
+    pub fn classes(&self) -> &[&'a FQName] {
+        &self.classes
+    }
+}
+
+/// The `InnerClasses` attribute of a class
+#[derive(Debug, Clone)]
+pub struct InnerClasses<'a> {
+    classes: Vec<InnerClass<'a>>,
+}
+
+impl<'a> InnerClasses<'a> {
+    /// The nested classes that are members of the class, or referenced by it
+    pub fn classes(&self) -> &[InnerClass<'a>] {
+        &self.classes
+    }
+}
+
+/// The `BootstrapMethods` attribute of a class
+#[derive(Debug, Clone)]
+pub struct BootstrapMethods<'a> {
+    methods: Vec<BootstrapMethod<'a>>,
+}
+
+impl<'a> BootstrapMethods<'a> {
+    /// The bootstrap methods, indexed by the call sites and dynamic constants that use them
+    pub fn methods(&self) -> &[BootstrapMethod<'a>] {
+        &self.methods
+    }
+}
+
+/// The `MethodParameters` attribute of a method
+#[derive(Debug, Clone)]
+pub struct MethodParameters<'a> {
+    parameters: Vec<MethodParameter<'a>>,
+}
+
+impl<'a> MethodParameters<'a> {
+    /// The names and access flags of the parameters, in order
+    pub fn parameters(&self) -> &[MethodParameter<'a>] {
+        &self.parameters
+    }
+}
+
+/// The `LocalVariableTable` attribute of a [`Code`] attribute, used by debuggers
+#[derive(Debug, Clone)]
+pub struct LocalVariableTable<'a> {
+    variables: Vec<LocalVariable<'a>>,
+}
+
+impl<'a> LocalVariableTable<'a> {
+    /// The local variables
+    pub fn variables(&self) -> &[LocalVariable<'a>] {
+        &self.variables
+    }
+}
+
+/// The `ModulePackages` attribute of a module
+#[derive(Debug, Clone)]
+pub struct ModulePackages<'a> {
+    packages: Vec<&'a str>,
+}
+
+impl<'a> ModulePackages<'a> {
+    /// Every package of the module, in internal form
+    pub fn packages(&self) -> &[&'a str] {
+        &self.packages
+    }
+}
+
+/// The `ModuleMainClass` attribute of a module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleMainClass<'a> {
+    main_class: &'a FQName,
+}
+
+impl<'a> ModuleMainClass<'a> {
+    /// The main class of the module
+    pub fn main_class(&self) -> &'a FQName {
+        self.main_class
+    }
+}
+
+/// The `SourceDebugExtension` attribute of a class, holding extended debugging information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDebugExtension {
+    extension: String,
+}
+
+impl SourceDebugExtension {
+    /// The debugging information, usually a [source map](crate::smap)
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
 }
 
 /// An error occurred while resolving an attribute.
@@ -112,6 +630,246 @@ impl ResolveAttributeError {
     }
 }
 
+/// A compile time constant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JavaConstant<'a> {
+    /// An `int`, or a `boolean`, `byte`, `char` or `short` stored as an `int`
+    Integer(i32),
+    /// A `float`
+    Float(f32),
+    /// A `long`
+    Long(i64),
+    /// A `double`
+    Double(f64),
+    /// A `String`. Surrogates that aren't part of a pair can't be decoded, so they're replaced with
+    /// `U+FFFD`. The exact string can be found in the constant pool using
+    /// [`ConstantValue::constant_pool_index`].
+    String(&'a str),
+}
+
+/// The `ConstantValue` attribute, holding the value a constant field is initialized to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstantValue<'a> {
+    index: u16,
+    value: JavaConstant<'a>,
+}
+
+impl<'a> ConstantValue<'a> {
+    /// The index of the value in the constant pool
+    pub fn constant_pool_index(&self) -> u16 {
+        self.index
+    }
+
+    /// The value of the field
+    pub fn value(&self) -> JavaConstant<'a> {
+        self.value
+    }
+}
+
+bitflags::bitflags! {
+    /// The access flags of a nested class as declared in source, as described in table
+    /// [§4.7.6-A](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.6-300-D.1-D.1)
+    /// of the JVM specification.
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct InnerClassAccessFlags: u16 {
+        /// Declared `public`
+        const PUBLIC = 0x0001;
+        /// Declared `private`
+        const PRIVATE = 0x0002;
+        /// Declared `protected`
+        const PROTECTED = 0x0004;
+        /// Declared `static`
+        const STATIC = 0x0008;
+        /// Declared `final`
+        const FINAL = 0x0010;
+        /// Is an interface
+        const INTERFACE = 0x0200;
+        /// Declared `abstract`
+        const ABSTRACT = 0x0400;
+        /// Not present in the source code
+        const SYNTHETIC = 0x1000;
+        /// Declared as an annotation type
+        const ANNOTATION = 0x2000;
+        /// Declared as an enum
+        const ENUM = 0x4000;
+    }
+}
+
+impl InnerClassAccessFlags {
+    /// Checks if the class is declared `public`
+    pub fn is_public(&self) -> bool {
+        self.contains(Self::PUBLIC)
+    }
+
+    /// Checks if the class is declared `private`
+    pub fn is_private(&self) -> bool {
+        self.contains(Self::PRIVATE)
+    }
+
+    /// Checks if the class is declared `static`, or is implicitly static such as a nested
+    /// interface, enum or record
+    pub fn is_static(&self) -> bool {
+        self.contains(Self::STATIC)
+    }
+}
+
+/// An entry of the `InnerClasses` attribute, describing a nested class
+#[derive(Debug, Clone, PartialEq)]
+pub struct InnerClass<'a> {
+    inner_class: &'a FQName,
+    outer_class: Option<&'a FQName>,
+    inner_name: Option<&'a str>,
+    access_flags: InnerClassAccessFlags,
+}
+
+impl<'a> InnerClass<'a> {
+    /// The binary name of the nested class, such as `com/example/Outer$Inner`
+    pub fn inner_class(&self) -> &'a FQName {
+        self.inner_class
+    }
+
+    /// The class the nested class is a member of. This is `None` for local and anonymous
+    /// classes.
+    pub fn outer_class(&self) -> Option<&'a FQName> {
+        self.outer_class
+    }
+
+    /// The simple name of the nested class as declared in source. This is `None` for anonymous
+    /// classes.
+    pub fn inner_name(&self) -> Option<&'a str> {
+        self.inner_name
+    }
+
+    /// The access flags of the nested class as declared in source
+    pub fn access_flags(&self) -> InnerClassAccessFlags {
+        self.access_flags
+    }
+
+    /// Checks if the nested class is anonymous
+    pub fn is_anonymous(&self) -> bool {
+        self.inner_name.is_none()
+    }
+
+    /// Checks if the nested class is declared within a method or initializer
+    pub fn is_local(&self) -> bool {
+        self.outer_class.is_none() && self.inner_name.is_some()
+    }
+}
+
+/// The `EnclosingMethod` attribute of a local or anonymous class
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnclosingMethod<'a> {
+    class: &'a FQName,
+    method: Option<(&'a str, Signature<'a>)>,
+}
+
+impl<'a> EnclosingMethod<'a> {
+    /// The innermost class enclosing the declaration of the class
+    pub fn class(&self) -> &'a FQName {
+        self.class
+    }
+
+    /// The name of the method enclosing the declaration of the class. This is `None` if the
+    /// class is declared in an initializer.
+    pub fn method_name(&self) -> Option<&'a str> {
+        self.method.as_ref().map(|(name, _)| *name)
+    }
+
+    /// The descriptor of the method enclosing the declaration of the class
+    pub fn method_descriptor(&self) -> Option<&Signature<'a>> {
+        self.method.as_ref().map(|(_, descriptor)| descriptor)
+    }
+}
+
+bitflags::bitflags! {
+    /// The access flags of a parameter of a method
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct ParameterAccessFlags: u16 {
+        /// Declared `final`
+        const FINAL = 0x0010;
+        /// Not present in the source code, and not implied by the language
+        const SYNTHETIC = 0x1000;
+        /// Implicitly declared, as required by the language, such as the outer instance passed
+        /// to the constructor of an inner class
+        const MANDATED = 0x8000;
+    }
+}
+
+impl ParameterAccessFlags {
+    /// Checks if the parameter is declared `final`
+    pub fn is_final(&self) -> bool {
+        self.contains(Self::FINAL)
+    }
+
+    /// Checks if the parameter was generated by the compiler
+    pub fn is_synthetic(&self) -> bool {
+        self.contains(Self::SYNTHETIC)
+    }
+
+    /// Checks if the parameter was implicitly declared
+    pub fn is_mandated(&self) -> bool {
+        self.contains(Self::MANDATED)
+    }
+}
+
+/// An entry of the `MethodParameters` attribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodParameter<'a> {
+    name: Option<&'a str>,
+    access_flags: ParameterAccessFlags,
+}
+
+impl<'a> MethodParameter<'a> {
+    /// The name of the parameter, if the compiler recorded one
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// The access flags of the parameter
+    pub fn access_flags(&self) -> ParameterAccessFlags {
+        self.access_flags
+    }
+}
+
+/// An entry of the `LocalVariableTable` attribute, giving the name and type of a local variable
+/// over a range of bytecode
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVariable<'a> {
+    start_pc: u16,
+    length: u16,
+    name: &'a str,
+    descriptor: Signature<'a>,
+    index: u16,
+}
+
+impl<'a> LocalVariable<'a> {
+    /// The start of the range of bytecode the variable has a value in
+    pub fn start_pc(&self) -> u16 {
+        self.start_pc
+    }
+
+    /// The length of the range of bytecode the variable has a value in
+    pub fn length(&self) -> u16 {
+        self.length
+    }
+
+    /// The name of the variable
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The field descriptor of the type of the variable
+    pub fn descriptor(&self) -> &Signature<'a> {
+        &self.descriptor
+    }
+
+    /// The index of the variable in the local variables of the frame. `long` and `double`
+    /// variables use this index and the next.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+}
+
 /// The code attribute
 #[derive(Clone)]
 pub struct Code<'a> {
@@ -138,22 +896,67 @@ impl<'a> Code<'a> {
         self.code
     }
 
+    /// The class the code belongs to
+    pub(crate) fn class(&self) -> &'a JavaClass {
+        self.class
+    }
+
     /// The exceptions that can occur in the code
     pub fn exception_table(&self) -> &[Exception<'a>] {
         &self.exception_table[..]
     }
+
+    /// Decodes the instructions of the bytecode
+    pub fn instructions(&self) -> Instructions<'a> {
+        decode(self.code)
+    }
+
+    /// Decodes the instructions of the bytecode, resolving the constants they reference
+    pub fn resolved_instructions(&self) -> ResolvedInstructions<'a> {
+        ResolvedInstructions::new(self.instructions(), self.class)
+    }
+
+    /// The stack map frames from the `StackMapTable` attribute of the code. This is `None` for
+    /// code without branches, and for classes older than Java 6.
+    pub fn stack_map_table(&self) -> Option<StackMapTable<'a>> {
+        self.attributes
+            .iter()
+            .find(|raw| self.class.get_string(raw.attribute_name_index) == Some("StackMapTable"))
+            .and_then(|raw| {
+                let (_, table) = parse_stack_map_table(&raw.info, self.class).finish().ok()?;
+                Some(table)
+            })
+    }
+
+    /// The local variables from the `LocalVariableTable` attribute of the code. Empty if the
+    /// code was compiled without debug information.
+    pub fn local_variables(&self) -> Vec<LocalVariable<'a>> {
+        self.attributes
+            .iter()
+            .filter(|raw| {
+                self.class.get_string(raw.attribute_name_index) == Some("LocalVariableTable")
+            })
+            .filter_map(|raw| {
+                let (_, variables) = parse_local_variables(&raw.info, self.class).finish().ok()?;
+                Some(variables)
+            })
+            .flatten()
+            .collect()
+    }
 }
 
 impl HasAttributes for Code<'_> {
-    type Iter<'a> = <Vec<Attribute<'a>> as IntoIterator>::IntoIter where Self: 'a;
+    type Iter<'a>
+        = <Vec<Attribute<'a>> as IntoIterator>::IntoIter
+    where
+        Self: 'a;
 
     fn attributes<'a>(&'a self) -> Self::Iter<'a> {
         self.attributes
             .iter()
             .map(|raw| {
                 self.class
-                    .create_attribute(raw.attribute_name_index, &raw.info)
-                    .unwrap()
+                    .create_attribute_or_unknown(raw.attribute_name_index, &raw.info)
             })
             .collect::<Vec<_>>()
             .into_iter()
@@ -222,12 +1025,91 @@ fn parse_code_attr<'a>(info: &'a [u8], class: &'a JavaClass) -> IResult<&'a [u8]
             max_stack,
             max_locals,
             code,
-            exception_table: exception_table,
+            exception_table,
             attributes: attributes.into_boxed_slice(),
         },
     )(info)
 }
 
+fn parse_inner_classes<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<InnerClass<'a>>> {
+    let pool = class.constant_pool();
+    complete(flat_map(be_u16, |length: u16| {
+        count(
+            map_opt(
+                tuple((be_u16, be_u16, be_u16, be_u16)),
+                |(inner_index, outer_index, name_index, access_flags)| {
+                    Some(InnerClass {
+                        inner_class: pool.get_class_name(inner_index)?,
+                        outer_class: match outer_index {
+                            0 => None,
+                            index => Some(pool.get_class_name(index)?),
+                        },
+                        inner_name: match name_index {
+                            0 => None,
+                            index => Some(pool.get_utf8(index)?),
+                        },
+                        access_flags: InnerClassAccessFlags::from_bits_retain(access_flags),
+                    })
+                },
+            ),
+            length as usize,
+        )
+    }))(bytes)
+}
+
+fn parse_method_parameters<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<MethodParameter<'a>>> {
+    complete(flat_map(be_u8, |parameters_count: u8| {
+        count(
+            map_opt(tuple((be_u16, be_u16)), |(name_index, access_flags)| {
+                Some(MethodParameter {
+                    name: match name_index {
+                        0 => None,
+                        index => Some(class.get_string(index)?),
+                    },
+                    access_flags: ParameterAccessFlags::from_bits_retain(access_flags),
+                })
+            }),
+            parameters_count as usize,
+        )
+    }))(bytes)
+}
+
+fn parse_local_variables<'b, 'a>(
+    bytes: &'b [u8],
+    class: &'a JavaClass,
+) -> IResult<&'b [u8], Vec<LocalVariable<'a>>> {
+    complete(flat_map(be_u16, |length: u16| {
+        count(
+            map_opt(
+                tuple((be_u16, be_u16, be_u16, be_u16, be_u16)),
+                |(start_pc, length, name_index, descriptor_index, index)| {
+                    Some(LocalVariable {
+                        start_pc,
+                        length,
+                        name: class.get_string(name_index)?,
+                        descriptor: class.get_descriptor(descriptor_index)?,
+                        index,
+                    })
+                },
+            ),
+            length as usize,
+        )
+    }))(bytes)
+}
+
+/// Parses a table of constant pool indices, preceded by its length
+fn parse_u16_table(bytes: &[u8]) -> IResult<&[u8], Vec<u16>> {
+    complete(flat_map(be_u16, |length: u16| {
+        count(be_u16, length as usize)
+    }))(bytes)
+}
+
 fn parse_exception<'a>(bytes: &'a [u8], class: &'a JavaClass) -> IResult<&'a [u8], Exception<'a>> {
     map(
         tuple((be_u16, be_u16, be_u16, be_u16)),
@@ -238,10 +1120,7 @@ fn parse_exception<'a>(bytes: &'a [u8], class: &'a JavaClass) -> IResult<&'a [u8
             catch_type: if catch_type_index == 0 {
                 None
             } else {
-                class
-                    .get_at_index(catch_type_index)
-                    .and_then(|info| match_as!(utf; ConstantPoolInfo::Utf8(utf) = info))
-                    .map(|utf8| FQName::new(utf8))
+                class.constant_pool().get_class_name(catch_type_index)
             },
         },
     )(bytes)
@@ -253,17 +1132,47 @@ pub struct LineNumberTable {
 }
 
 impl LineNumberTable {
-    /// Converts a byte in the code to a line number
+    /// Converts a byte in the code to a line number. Returns `None` if the byte comes before
+    /// the first entry of the table.
     pub fn pc_to_line(&self, pc: u16) -> Option<u16> {
-        let mut output = None;
-        for &(start_pc, line_number) in &self.line_number_table[..] {
-            if pc > start_pc {
-                break;
-            } else {
-                output = Some(line_number);
-            }
-        }
-        output
+        self.ranges()
+            .find(|(range, _)| range.contains(&pc))
+            .map(|(_, line)| line)
+    }
+
+    /// Gets the start of every range of bytecode that came from a line, in ascending order. A
+    /// line can have several ranges, such as the condition of a loop.
+    pub fn line_to_pcs(&self, line: u16) -> Vec<u16> {
+        self.ranges()
+            .filter(|&(_, range_line)| range_line == line)
+            .map(|(range, _)| range.start)
+            .collect()
+    }
+
+    /// The ranges of bytecode in ascending order, paired with the line number they came from.
+    ///
+    /// The table doesn't record the length of the code, so the last range ends at `u16::MAX`,
+    /// past the end of any code.
+    pub fn ranges(&self) -> impl Iterator<Item = (Range<u16>, u16)> {
+        let mut entries = self.line_number_table.to_vec();
+        entries.sort_by_key(|&(start_pc, _)| start_pc);
+        entries.dedup_by_key(|&mut (start_pc, _)| start_pc);
+        let ends = entries
+            .iter()
+            .skip(1)
+            .map(|&(start_pc, _)| start_pc)
+            .chain([u16::MAX])
+            .collect::<Vec<_>>();
+        entries
+            .into_iter()
+            .zip(ends)
+            .map(|((start_pc, line), end_pc)| (start_pc..end_pc, line))
+    }
+
+    /// The entries of the table, as pairs of the start of a range of bytecode and the line
+    /// number it came from
+    pub fn entries(&self) -> &[(u16, u16)] {
+        &self.line_number_table[..]
     }
 }
 
@@ -276,3 +1185,244 @@ impl Debug for LineNumberTable {
             .fmt(f)
     }
 }
+
+/// Serializes attributes, and the values of known attributes.
+#[cfg(feature = "serde")]
+mod ser {
+    use super::*;
+    use crate::utility::{AttributeList, FlagNames};
+    use serde::ser::{SerializeMap, SerializeStruct};
+    use serde::{Serialize, Serializer};
+
+    /// Serialized as its `name` and the `value` of its kind
+    impl Serialize for Attribute<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut attribute = serializer.serialize_struct("Attribute", 2)?;
+            attribute.serialize_field("name", self.attribute_name)?;
+            attribute.serialize_field("value", &Value(&self.kind))?;
+            attribute.end()
+        }
+    }
+
+    /// Serialized as an enum tagged by the name of the attribute. The bytecode level
+    /// `StackMapTable` and `BootstrapMethods` attributes are only serialized as the number of
+    /// frames and methods they contain, and unknown attributes are serialized as their bytes.
+    impl Serialize for AttributeKind<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let (index, variant) = variant(self);
+            match self {
+                AttributeKind::Deprecated => {
+                    serializer.serialize_unit_variant("AttributeKind", index, variant)
+                }
+                _ => serializer.serialize_newtype_variant(
+                    "AttributeKind",
+                    index,
+                    variant,
+                    &Value(self),
+                ),
+            }
+        }
+    }
+
+    fn variant(kind: &AttributeKind) -> (u32, &'static str) {
+        match kind {
+            AttributeKind::SourceFile(_) => (0, "SourceFile"),
+            AttributeKind::Signature(_) => (1, "Signature"),
+            AttributeKind::Code(_) => (2, "Code"),
+            AttributeKind::LineNumberTable(_) => (3, "LineNumberTable"),
+            AttributeKind::Deprecated => (4, "Deprecated"),
+            AttributeKind::RuntimeVisibleAnnotations(_) => (5, "RuntimeVisibleAnnotations"),
+            AttributeKind::RuntimeInvisibleAnnotations(_) => (6, "RuntimeInvisibleAnnotations"),
+            AttributeKind::RuntimeVisibleParameterAnnotations(_) => {
+                (7, "RuntimeVisibleParameterAnnotations")
+            }
+            AttributeKind::RuntimeInvisibleParameterAnnotations(_) => {
+                (8, "RuntimeInvisibleParameterAnnotations")
+            }
+            AttributeKind::RuntimeVisibleTypeAnnotations(_) => (9, "RuntimeVisibleTypeAnnotations"),
+            AttributeKind::RuntimeInvisibleTypeAnnotations(_) => {
+                (10, "RuntimeInvisibleTypeAnnotations")
+            }
+            AttributeKind::Record(_) => (11, "Record"),
+            AttributeKind::Exceptions(_) => (12, "Exceptions"),
+            AttributeKind::PermittedSubclasses(_) => (13, "PermittedSubclasses"),
+            AttributeKind::ConstantValue(_) => (14, "ConstantValue"),
+            AttributeKind::InnerClasses(_) => (15, "InnerClasses"),
+            AttributeKind::EnclosingMethod(_) => (16, "EnclosingMethod"),
+            AttributeKind::BootstrapMethods(_) => (17, "BootstrapMethods"),
+            AttributeKind::MethodParameters(_) => (18, "MethodParameters"),
+            AttributeKind::LocalVariableTable(_) => (19, "LocalVariableTable"),
+            AttributeKind::StackMapTable(_) => (20, "StackMapTable"),
+            AttributeKind::Module(_) => (21, "Module"),
+            AttributeKind::ModulePackages(_) => (22, "ModulePackages"),
+            AttributeKind::ModuleMainClass(_) => (23, "ModuleMainClass"),
+            AttributeKind::SourceDebugExtension(_) => (24, "SourceDebugExtension"),
+            AttributeKind::Unknown(_) => (25, "Unknown"),
+        }
+    }
+
+    /// The value of an attribute, without the name of its kind
+    struct Value<'b, 'a>(&'b AttributeKind<'a>);
+
+    impl Serialize for Value<'_, '_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0 {
+                AttributeKind::SourceFile(path) => serializer.collect_str(&path.display()),
+                AttributeKind::Signature(signature) => signature.serialize(serializer),
+                AttributeKind::Code(code) => code.serialize(serializer),
+                AttributeKind::LineNumberTable(table) => table.serialize(serializer),
+                AttributeKind::Deprecated => serializer.serialize_unit(),
+                AttributeKind::RuntimeVisibleAnnotations(annotations)
+                | AttributeKind::RuntimeInvisibleAnnotations(annotations) => {
+                    annotations.serialize(serializer)
+                }
+                AttributeKind::RuntimeVisibleParameterAnnotations(annotations)
+                | AttributeKind::RuntimeInvisibleParameterAnnotations(annotations) => {
+                    annotations.serialize(serializer)
+                }
+                AttributeKind::RuntimeVisibleTypeAnnotations(annotations)
+                | AttributeKind::RuntimeInvisibleTypeAnnotations(annotations) => {
+                    annotations.serialize(serializer)
+                }
+                AttributeKind::Record(components) => components.serialize(serializer),
+                AttributeKind::Exceptions(classes)
+                | AttributeKind::PermittedSubclasses(classes) => classes.serialize(serializer),
+                AttributeKind::ConstantValue(constant) => constant.value.serialize(serializer),
+                AttributeKind::InnerClasses(classes) => classes.serialize(serializer),
+                AttributeKind::EnclosingMethod(method) => method.serialize(serializer),
+                AttributeKind::BootstrapMethods(methods) => {
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry("methods", &methods.len())?;
+                    map.end()
+                }
+                AttributeKind::MethodParameters(parameters) => parameters.serialize(serializer),
+                AttributeKind::LocalVariableTable(variables) => variables.serialize(serializer),
+                AttributeKind::StackMapTable(table) => {
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry("frames", &table.entries().len())?;
+                    map.end()
+                }
+                AttributeKind::Module(module) => module.serialize(serializer),
+                AttributeKind::ModulePackages(packages) => packages.serialize(serializer),
+                AttributeKind::ModuleMainClass(class) => class.serialize(serializer),
+                AttributeKind::SourceDebugExtension(extension) => extension.serialize(serializer),
+                AttributeKind::Unknown(bytes) => serializer.serialize_bytes(bytes),
+            }
+        }
+    }
+
+    /// Serialized as its `max_stack`, `max_locals`, the length of its bytecode as `code_length`,
+    /// its `exception_table` and its `attributes`
+    impl Serialize for Code<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut code = serializer.serialize_struct("Code", 5)?;
+            code.serialize_field("max_stack", &self.max_stack)?;
+            code.serialize_field("max_locals", &self.max_locals)?;
+            code.serialize_field("code_length", &self.code.len())?;
+            code.serialize_field("exception_table", &self.exception_table)?;
+            code.serialize_field("attributes", &AttributeList(self))?;
+            code.end()
+        }
+    }
+
+    /// Serialized as its `start_pc`, `end_pc`, `handler_pc` and `catch_type`, which is null for
+    /// handlers that catch every exception
+    impl Serialize for Exception<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut exception = serializer.serialize_struct("Exception", 4)?;
+            exception.serialize_field("start_pc", &self.start_pc)?;
+            exception.serialize_field("end_pc", &self.end_pc)?;
+            exception.serialize_field("handler_pc", &self.handler_pc)?;
+            exception.serialize_field("catch_type", &self.catch_type)?;
+            exception.end()
+        }
+    }
+
+    /// Serialized as a sequence of entries with their `start_pc` and `line`
+    impl Serialize for LineNumberTable {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            struct Entry(u16, u16);
+            impl Serialize for Entry {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut entry = serializer.serialize_struct("LineNumber", 2)?;
+                    entry.serialize_field("start_pc", &self.0)?;
+                    entry.serialize_field("line", &self.1)?;
+                    entry.end()
+                }
+            }
+
+            serializer.collect_seq(
+                self.line_number_table
+                    .iter()
+                    .map(|&(start_pc, line)| Entry(start_pc, line)),
+            )
+        }
+    }
+
+    /// Serialized as an enum tagged by the type of the constant
+    impl Serialize for JavaConstant<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            const NAME: &str = "JavaConstant";
+            match self {
+                JavaConstant::Integer(v) => {
+                    serializer.serialize_newtype_variant(NAME, 0, "Integer", v)
+                }
+                JavaConstant::Float(v) => serializer.serialize_newtype_variant(NAME, 1, "Float", v),
+                JavaConstant::Long(v) => serializer.serialize_newtype_variant(NAME, 2, "Long", v),
+                JavaConstant::Double(v) => {
+                    serializer.serialize_newtype_variant(NAME, 3, "Double", v)
+                }
+                JavaConstant::String(v) => {
+                    serializer.serialize_newtype_variant(NAME, 4, "String", v)
+                }
+            }
+        }
+    }
+
+    /// Serialized as its `inner_class`, `outer_class`, `inner_name` and `access_flags`
+    impl Serialize for InnerClass<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut class = serializer.serialize_struct("InnerClass", 4)?;
+            class.serialize_field("inner_class", self.inner_class)?;
+            class.serialize_field("outer_class", &self.outer_class)?;
+            class.serialize_field("inner_name", &self.inner_name)?;
+            class.serialize_field("access_flags", &FlagNames(self.access_flags))?;
+            class.end()
+        }
+    }
+
+    /// Serialized as its `class`, and the `method_name` and `method_descriptor` of the method,
+    /// which are null if the class isn't enclosed by a method
+    impl Serialize for EnclosingMethod<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut method = serializer.serialize_struct("EnclosingMethod", 3)?;
+            method.serialize_field("class", self.class)?;
+            method.serialize_field("method_name", &self.method_name())?;
+            method.serialize_field("method_descriptor", &self.method_descriptor())?;
+            method.end()
+        }
+    }
+
+    /// Serialized as its `name` and `access_flags`
+    impl Serialize for MethodParameter<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut parameter = serializer.serialize_struct("MethodParameter", 2)?;
+            parameter.serialize_field("name", &self.name)?;
+            parameter.serialize_field("access_flags", &FlagNames(self.access_flags))?;
+            parameter.end()
+        }
+    }
+
+    /// Serialized as its `start_pc`, `length`, `name`, `descriptor` and `index`
+    impl Serialize for LocalVariable<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut variable = serializer.serialize_struct("LocalVariable", 5)?;
+            variable.serialize_field("start_pc", &self.start_pc)?;
+            variable.serialize_field("length", &self.length)?;
+            variable.serialize_field("name", self.name)?;
+            variable.serialize_field("descriptor", &self.descriptor)?;
+            variable.serialize_field("index", &self.index)?;
+            variable.end()
+        }
+    }
+}
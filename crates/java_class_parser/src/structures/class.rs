@@ -1,41 +1,347 @@
-use crate::attributes::{Attribute, ResolveAttributeError};
-use crate::constant_pool::values::{Class, StringValue};
-use crate::constant_pool::{ConstantPool, ConstantPoolInfo};
-use crate::raw_java_class::RawJavaClass;
-use crate::utility::match_as;
+#[cfg(feature = "serde")]
+use crate::annotations::values_deserializer;
+use crate::annotations::{Annotation, AnnotationValues, ElementType, RetentionPolicy};
+use crate::attributes::{
+    Attribute, AttributeKind, EnclosingMethod, InnerClass, ResolveAttributeError,
+};
+use crate::constant_pool::values::Class;
+use crate::constant_pool::{ConstantPool, ConstantPoolInfo, ResolvedConstant};
+use crate::error::{Error, ErrorKind};
+use crate::generics::{ClassSignature, GenericSignature};
+use crate::raw_java_class::{parse_class_file_bytes, RawJavaClass};
+use crate::records::{RecordComponent, RecordMembers};
+use crate::utility::OnceCell;
 use crate::{Field, HasAttributes, Method, Signature};
 
 use crate::structures::fully_qualified_name::FQName;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
 
-/// A java class
+/// The annotation marking classes and members as deprecated
+pub(crate) const DEPRECATED: &str = "java/lang/Deprecated";
+
+bitflags::bitflags! {
+    /// The access flags of a class, as described in table
+    /// [§4.1-B](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.1-200-E.1)
+    /// of the JVM specification.
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct ClassAccessFlags: u16 {
+        /// Declared `public`
+        const PUBLIC = 0x0001;
+        /// Declared `final`
+        const FINAL = 0x0010;
+        /// Treat superclass methods specially when invoked by `invokespecial`
+        const SUPER = 0x0020;
+        /// Is an interface
+        const INTERFACE = 0x0200;
+        /// Declared `abstract`
+        const ABSTRACT = 0x0400;
+        /// Not present in the source code
+        const SYNTHETIC = 0x1000;
+        /// Declared as an annotation type
+        const ANNOTATION = 0x2000;
+        /// Declared as an enum
+        const ENUM = 0x4000;
+        /// Is a module, not a class or interface
+        const MODULE = 0x8000;
+    }
+}
+
+impl ClassAccessFlags {
+    /// Checks if the class is declared `public`
+    pub fn is_public(&self) -> bool {
+        self.contains(Self::PUBLIC)
+    }
+
+    /// Checks if the class is declared `final`
+    pub fn is_final(&self) -> bool {
+        self.contains(Self::FINAL)
+    }
+
+    /// Checks if the class is an interface, including annotation types
+    pub fn is_interface(&self) -> bool {
+        self.contains(Self::INTERFACE)
+    }
+
+    /// Checks if the class is declared `abstract`. Interfaces are always abstract.
+    pub fn is_abstract(&self) -> bool {
+        self.contains(Self::ABSTRACT)
+    }
+
+    /// Checks if the class was generated by the compiler
+    pub fn is_synthetic(&self) -> bool {
+        self.contains(Self::SYNTHETIC)
+    }
+
+    /// Checks if the class is an annotation type
+    pub fn is_annotation(&self) -> bool {
+        self.contains(Self::ANNOTATION)
+    }
+
+    /// Checks if the class is an enum
+    pub fn is_enum(&self) -> bool {
+        self.contains(Self::ENUM)
+    }
+
+    /// Checks if the class file describes a module
+    pub fn is_module(&self) -> bool {
+        self.contains(Self::MODULE)
+    }
+}
+
+/// A java class. Cloning a class is cheap, as clones share the parsed class file.
 #[derive(Clone)]
-pub struct JavaClass(RawJavaClass);
+pub struct JavaClass(Arc<ClassFile>);
+
+/// A parsed class file
+enum ClassFile {
+    /// A completely parsed class file
+    Parsed(RawJavaClass),
+    /// A class file where only the header was parsed. The rest of the class is parsed from its
+    /// bytes the first time it's needed.
+    Lazy {
+        header: RawJavaClass,
+        bytes: Box<[u8]>,
+        class: OnceCell<Result<RawJavaClass, Arc<Error>>>,
+    },
+}
 
 impl JavaClass {
     pub(crate) fn new(class: RawJavaClass) -> Self {
-        Self(class)
+        Self(Arc::new(ClassFile::Parsed(class)))
+    }
+
+    /// Creates a class where only the header is parsed, with the rest of the class parsed from
+    /// `bytes` when it's first needed.
+    pub(crate) fn lazy(header: RawJavaClass, bytes: Box<[u8]>) -> Self {
+        Self(Arc::new(ClassFile::Lazy {
+            header,
+            bytes,
+            class: OnceCell::new(),
+        }))
+    }
+
+    /// The complete class file, parsing it if this class is lazy. If the rest of a lazy class
+    /// can't be parsed, the class has no fields, methods or attributes, and
+    /// [`decode_error`](Self::decode_error) is the reason why.
+    pub(crate) fn raw(&self) -> &RawJavaClass {
+        match &*self.0 {
+            ClassFile::Parsed(class) => class,
+            ClassFile::Lazy { header, .. } => self.parsed().unwrap_or(header),
+        }
+    }
+
+    /// Parses the rest of this class if it's lazy and hasn't been parsed yet. The result is kept,
+    /// so a class is never parsed twice.
+    fn parsed(&self) -> Result<&RawJavaClass, &Arc<Error>> {
+        match &*self.0 {
+            ClassFile::Parsed(class) => Ok(class),
+            ClassFile::Lazy { bytes, class, .. } => class
+                .get_or_init(|| parse_class_file_bytes(bytes).map_err(Arc::new))
+                .as_ref(),
+        }
+    }
+
+    /// The class file up to and including its interfaces, which is never parsed lazily. Its
+    /// fields, methods and attributes may be empty.
+    pub(crate) fn header(&self) -> &RawJavaClass {
+        match &*self.0 {
+            ClassFile::Parsed(class) => class,
+            ClassFile::Lazy { header, .. } => header,
+        }
+    }
+
+    /// Checks if the fields, methods and attributes of this class have been parsed. Only classes
+    /// found by a [lazy parser](crate::JavaClassParser::with_lazy_parsing) are parsed later.
+    pub fn is_decoded(&self) -> bool {
+        match &*self.0 {
+            ClassFile::Parsed(_) => true,
+            ClassFile::Lazy { class, .. } => class.is_initialized(),
+        }
+    }
+
+    /// Parses the fields, methods and attributes of this class now if it was parsed lazily.
+    ///
+    /// # Error
+    /// Will return an error if the rest of the class is invalid, in which case the class has no
+    /// fields, methods or attributes. The error is the same every time this is called, and its
+    /// kind is [`ErrorKind::InvalidClassBody`](crate::error::ErrorKind::InvalidClassBody).
+    pub fn decode(&self) -> Result<(), Error> {
+        match self.parsed() {
+            Ok(_) => Ok(()),
+            Err(error) => Err(ErrorKind::InvalidClassBody(error.clone()).into()),
+        }
+    }
+
+    /// Why the fields, methods and attributes of this class couldn't be parsed, if it was parsed
+    /// lazily and they're invalid. This parses the rest of the class if it hasn't been yet.
+    ///
+    /// Lazy classes with an invalid body have no fields, methods or attributes, so analyses that
+    /// find nothing in a class can use this to tell an empty class from a corrupt one.
+    pub fn decode_error(&self) -> Option<&Error> {
+        self.parsed().err().map(|error| &**error)
+    }
+
+    /// Writes this class back into the bytes of a class file, parsing it first if it was parsed
+    /// lazily. Classes are written exactly as they were parsed.
+    ///
+    /// # Error
+    /// Will return an error if this class was parsed lazily and the rest of the class is invalid
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.decode()?;
+        Ok(self.raw().to_bytes())
+    }
+
+    /// Gets the access flags of the class. Flags not defined by the JVM specification are
+    /// retained.
+    pub fn access_flags(&self) -> ClassAccessFlags {
+        ClassAccessFlags::from_bits_retain(self.header().access_flags)
+    }
+
+    /// Gets the constant pool of the class
+    pub fn constant_pool(&self) -> &ConstantPool {
+        &self.header().constant_pool
+    }
+
+    /// Iterates over every constant in the constant pool with its index, resolving the indices
+    /// each constant refers to. The unusable entries following long and double constants are
+    /// skipped.
+    pub fn constants(&self) -> impl Iterator<Item = (u16, ResolvedConstant<'_>)> {
+        self.constant_pool().iter().map(move |(index, _)| {
+            (
+                index,
+                self.constant(index).unwrap_or(ResolvedConstant::Invalid),
+            )
+        })
+    }
+
+    /// Every `java.lang.String` literal in the constant pool, in the order they appear. This
+    /// includes strings only used by annotations or bootstrap methods, and the values of
+    /// constant fields.
+    pub fn string_constants(&self) -> Vec<&str> {
+        self.constants()
+            .filter_map(|(_, constant)| match constant {
+                ResolvedConstant::String(string) => Some(string),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Every class this class refers to, other than itself. This includes the classes named in
+    /// the constant pool, the classes in the descriptors of its members and of the members and
+    /// method types it uses, and the types of its annotations. Array classes are given as the
+    /// classes of their elements, and arrays of primitives are left out.
+    pub fn referenced_classes(&self) -> BTreeSet<&FQName> {
+        let mut classes = BTreeSet::new();
+        for (_, constant) in self.constants() {
+            match constant {
+                ResolvedConstant::Class(name) if name.as_str().starts_with('[') => {
+                    descriptor_classes(name.as_str(), &mut classes)
+                }
+                ResolvedConstant::Class(name) => {
+                    classes.insert(name);
+                }
+                ResolvedConstant::FieldRef(member)
+                | ResolvedConstant::MethodRef(member)
+                | ResolvedConstant::InterfaceMethodRef(member) => {
+                    descriptor_classes(member.descriptor(), &mut classes)
+                }
+                ResolvedConstant::NameAndType { descriptor, .. } => {
+                    descriptor_classes(descriptor, &mut classes)
+                }
+                ResolvedConstant::MethodType(signature) => {
+                    signature_classes(&signature, &mut classes)
+                }
+                ResolvedConstant::Dynamic(dynamic) | ResolvedConstant::InvokeDynamic(dynamic) => {
+                    signature_classes(dynamic.descriptor(), &mut classes)
+                }
+                _ => {}
+            }
+        }
+        for field in self.fields() {
+            signature_classes(field.signature(), &mut classes);
+            classes.extend(
+                [field.annotations(), field.invisible_annotations()]
+                    .concat()
+                    .iter()
+                    .map(|annotation| annotation.type_name()),
+            );
+        }
+        for method in self.methods() {
+            signature_classes(method.signature(), &mut classes);
+            classes.extend(
+                [method.annotations(), method.invisible_annotations()]
+                    .concat()
+                    .iter()
+                    .map(|annotation| annotation.type_name()),
+            );
+        }
+        classes.extend(
+            [self.annotations(), self.invisible_annotations()]
+                .concat()
+                .iter()
+                .map(|annotation| annotation.type_name()),
+        );
+        classes.remove(self.this());
+        classes
     }
 
-    pub(crate) fn raw_constant_pool(&self) -> &ConstantPool {
-        &self.0.constant_pool
+    /// Gets the constant at a constant pool index, resolving the indices it refers to. Returns
+    /// `None` if there's no usable constant at the index, or if the constant is invalid.
+    pub fn constant(&self, index: u16) -> Option<ResolvedConstant<'_>> {
+        let pool = self.constant_pool();
+        match pool.get(index)? {
+            ConstantPoolInfo::Utf8(_) => pool.get_utf8(index).map(ResolvedConstant::Utf8),
+            ConstantPoolInfo::Integer(_) => pool.get_integer(index).map(ResolvedConstant::Integer),
+            ConstantPoolInfo::Float(_) => pool.get_float(index).map(ResolvedConstant::Float),
+            ConstantPoolInfo::Long(_) => pool.get_long(index).map(ResolvedConstant::Long),
+            ConstantPoolInfo::Double(_) => pool.get_double(index).map(ResolvedConstant::Double),
+            ConstantPoolInfo::Class(_) => pool.get_class_name(index).map(ResolvedConstant::Class),
+            ConstantPoolInfo::String(_) => pool.get_string(index).map(ResolvedConstant::String),
+            ConstantPoolInfo::FieldRef(_) => {
+                pool.get_field_ref(index).map(ResolvedConstant::FieldRef)
+            }
+            ConstantPoolInfo::MethodRef(_) => {
+                pool.get_method_ref(index).map(ResolvedConstant::MethodRef)
+            }
+            ConstantPoolInfo::InterfaceMethodRef(_) => pool
+                .get_method_ref(index)
+                .map(ResolvedConstant::InterfaceMethodRef),
+            ConstantPoolInfo::NameAndType(_) => pool
+                .get_name_and_type(index)
+                .map(|(name, descriptor)| ResolvedConstant::NameAndType { name, descriptor }),
+            ConstantPoolInfo::MethodHandle(_) => self
+                .method_handle(index)
+                .map(ResolvedConstant::MethodHandle),
+            ConstantPoolInfo::MethodType(_) => {
+                self.method_type(index).map(ResolvedConstant::MethodType)
+            }
+            ConstantPoolInfo::Dynamic(_) => {
+                self.dynamic_constant(index).map(ResolvedConstant::Dynamic)
+            }
+            ConstantPoolInfo::InvokeDynamic(_) => self
+                .invoke_dynamic(index)
+                .map(ResolvedConstant::InvokeDynamic),
+            ConstantPoolInfo::Module(_) => {
+                pool.get_module_name(index).map(ResolvedConstant::Module)
+            }
+            ConstantPoolInfo::Package(_) => {
+                pool.get_package_name(index).map(ResolvedConstant::Package)
+            }
+        }
     }
 
     /// gets the info at a given constant pool location
     pub(crate) fn get_at_index(&self, index: u16) -> Option<&ConstantPoolInfo> {
-        self.raw_constant_pool().get(index)
+        self.constant_pool().get(index)
     }
 
     /// Gets a string at an index, or if possible follow indexes
     pub(crate) fn get_string(&self, index: u16) -> Option<&str> {
-        match self.raw_constant_pool().get(index)? {
-            ConstantPoolInfo::String(StringValue { string_index }) => {
-                self.get_string(*string_index)
-            }
-            ConstantPoolInfo::Utf8(s) => Some(s.as_ref()),
-            _ => None,
-        }
+        let pool = self.constant_pool();
+        pool.get_utf8_lossy(index)
+            .or_else(|| pool.get_string(index))
     }
 
     pub(crate) fn get_class_info(&self, index: u16) -> Option<&Class> {
@@ -46,14 +352,11 @@ impl JavaClass {
         }
     }
 
-    /// get a descriptor at an index
-    pub(crate) fn get_descriptor(&self, index: u16) -> Option<Signature> {
-        self.get_at_index(index)
-            .and_then(|info| match_as!(utf; ConstantPoolInfo::Utf8(utf) = info))
-            .map(|s| {
-                Signature::new(s.as_ref())
-                    .unwrap_or_else(|e| panic!("{} is invalid as signature: {}", s, e))
-            })
+    /// get a descriptor at an index. Returns `None` if the constant isn't a valid descriptor.
+    pub(crate) fn get_descriptor(&self, index: u16) -> Option<Signature<'_>> {
+        self.constant_pool()
+            .get_utf8_lossy(index)
+            .and_then(|s| Signature::new(s).ok())
     }
 
     pub(crate) fn create_attribute<'a>(
@@ -66,31 +369,56 @@ impl JavaClass {
             .and_then(|name| Attribute::new(self, name, info))
     }
 
+    /// Creates an attribute, keeping it as an [unknown](AttributeKind::Unknown) attribute if it
+    /// can't be resolved.
+    pub(crate) fn create_attribute_or_unknown<'a>(
+        &'a self,
+        name_index: u16,
+        info: &'a [u8],
+    ) -> Attribute<'a> {
+        self.create_attribute(name_index, info).unwrap_or_else(|_| {
+            Attribute::unknown(self.get_string(name_index).unwrap_or("<unknown>"), info)
+        })
+    }
+
     /// Gets this class's name
     pub fn this(&self) -> &FQName {
-        self.get_class_info(self.0.this_class)
+        self.get_class_info(self.header().this_class)
             .and_then(|Class { name_index }| self.get_string(*name_index))
-            .map(|s| FQName::new(s))
+            .map(FQName::new)
             .unwrap_or_else(|| {
-                let info = self.get_at_index(self.0.this_class);
+                let info = self.get_at_index(self.header().this_class);
                 panic!("{:?} could not be treated as a string", info);
             })
     }
 
     /// Gets the super class's name of this class
+    ///
+    /// # Panics
+    /// Will panic if this class has no super class, which is only the case for `java/lang/Object`
+    /// and modules. Use [`super_class`](Self::super_class) if the class may be either.
     pub fn super_name(&self) -> &FQName {
-        self.get_class_info(self.0.super_class)
+        self.get_class_info(self.header().super_class)
             .and_then(|Class { name_index }| self.get_string(*name_index))
-            .map(|s| FQName::new(s))
+            .map(FQName::new)
             .unwrap_or_else(|| {
-                let info = self.get_at_index(self.0.this_class);
+                let info = self.get_at_index(self.header().this_class);
                 panic!("{:?} could not be treated as a string", info);
             })
     }
 
+    /// Gets the super class's name of this class, or `None` for `java/lang/Object` and modules,
+    /// which have no super class
+    pub fn super_class(&self) -> Option<&FQName> {
+        match self.header().super_class {
+            0 => None,
+            _ => Some(self.super_name()),
+        }
+    }
+
     /// Gets the names of this interfaces that this class implements
     pub fn interfaces(&self) -> Vec<&FQName> {
-        self.0
+        self.header()
             .interfaces
             .iter()
             .map(|index| {
@@ -98,23 +426,216 @@ impl JavaClass {
                     self.get_class_info(*index).expect("no class info found");
                 self.get_string(*name_index).expect("couldn't get string")
             })
-            .map(|s| FQName::new(s))
+            .map(FQName::new)
             .collect()
     }
 
     /// Gets the fields declared in this class.
-    pub fn fields(&self) -> Vec<Field> {
-        self.0.fields.iter().map(|f| Field::new(f, &self)).collect()
+    pub fn fields(&self) -> Vec<Field<'_>> {
+        self.raw()
+            .fields
+            .iter()
+            .map(|f| Field::new(f, self))
+            .collect()
     }
 
     /// Gets the methods declared in this class.
-    pub fn methods(&self) -> Vec<Method> {
-        self.0
+    pub fn methods(&self) -> Vec<Method<'_>> {
+        self.raw()
             .methods
             .iter()
-            .map(|f| Method::new(f, &self))
+            .map(|f| Method::new(f, self))
+            .collect()
+    }
+
+    /// Gets the runtime visible annotations present on this class.
+    pub fn annotations(&self) -> Vec<Annotation<'_>> {
+        self.attributes()
+            .filter_map(|attribute| match attribute.kind() {
+                AttributeKind::RuntimeVisibleAnnotations(annotations) => Some(annotations.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Gets the annotations present on this class that have a retention policy of `CLASS`, and
+    /// aren't visible through reflection.
+    pub fn invisible_annotations(&self) -> Vec<Annotation<'_>> {
+        self.attributes()
+            .filter_map(|attribute| match attribute.kind() {
+                AttributeKind::RuntimeInvisibleAnnotations(annotations) => {
+                    Some(annotations.clone())
+                }
+                _ => None,
+            })
+            .flatten()
             .collect()
     }
+
+    /// Checks whether this class is deprecated, either by the `@Deprecated` annotation or the
+    /// `Deprecated` attribute
+    pub fn is_deprecated(&self) -> bool {
+        self.get_attribute("Deprecated").is_some()
+            || self
+                .annotations()
+                .iter()
+                .any(|annotation| annotation.type_name() == DEPRECATED)
+    }
+
+    /// Checks whether this class is a record
+    pub fn is_record(&self) -> bool {
+        self.get_attribute("Record").is_some()
+    }
+
+    /// Checks whether this class is sealed, which means only the classes it
+    /// [permits](Self::permitted_subclasses) may directly extend or implement it
+    pub fn is_sealed(&self) -> bool {
+        self.get_attribute("PermittedSubclasses").is_some()
+    }
+
+    /// Gets the classes that are permitted to directly extend or implement this class, from the
+    /// `PermittedSubclasses` attribute, or `None` if this class isn't sealed.
+    pub fn permitted_subclasses(&self) -> Option<Vec<&FQName>> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::PermittedSubclasses(permitted) => Some(permitted.clone()),
+                _ => None,
+            })
+    }
+
+    /// The generic signature of this class, if it declares type parameters or extends or
+    /// implements a parameterized type
+    pub fn generic_signature(&self) -> Option<ClassSignature<'_>> {
+        self.get::<GenericSignature>()
+            .and_then(GenericSignature::into_class)
+    }
+
+    /// Gets the components of this class if it's a record, in declaration order.
+    pub fn record_components(&self) -> Option<Vec<RecordComponent<'_>>> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::Record(components) => Some(components.clone()),
+                _ => None,
+            })
+    }
+
+    /// Gets the nested classes that are members of this class, or that this class refers to,
+    /// from the `InnerClasses` attribute. A nested class lists itself and the classes it's
+    /// nested in.
+    pub fn inner_classes(&self) -> Vec<InnerClass<'_>> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::InnerClasses(classes) => Some(classes.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the method enclosing this class if it's a local or anonymous class
+    pub fn enclosing_method(&self) -> Option<EnclosingMethod<'_>> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::EnclosingMethod(method) => Some(method.clone()),
+                _ => None,
+            })
+    }
+
+    /// Gets the canonical constructor and component accessors of this class if it's a record.
+    pub fn record_members(&self) -> Option<RecordMembers<'_>> {
+        self.record_components()
+            .map(|components| RecordMembers::new(self, components))
+    }
+
+    /// Gets an annotation present on this class by the annotation's type name
+    pub fn get_annotation(&self, type_name: &str) -> Option<Annotation<'_>> {
+        self.annotations()
+            .into_iter()
+            .find(|annotation| annotation.type_name() == type_name)
+    }
+
+    /// Gets the element values of an annotation present on this class by the annotation's type
+    /// name. Elements that use their default value are not included.
+    pub fn annotation_values(&self, type_name: &str) -> Option<AnnotationValues> {
+        self.get_annotation(type_name)
+            .map(|annotation| annotation.values())
+    }
+
+    /// Deserializes the element values of an annotation present on this class into a rust type.
+    /// Returns `None` if the annotation isn't present.
+    ///
+    /// Elements that use their default value are not included, so fields for them should use
+    /// `#[serde(default)]`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_class_parser::parse_file;
+    /// #[derive(serde::Deserialize)]
+    /// struct Entity {
+    ///     table: String,
+    /// }
+    ///
+    /// let class = parse_file("Account.class").unwrap();
+    /// let entity: Option<Entity> = class.extract_annotation("javax/persistence/Entity").unwrap();
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn extract_annotation<T: serde::de::DeserializeOwned>(
+        &self,
+        type_name: &str,
+    ) -> Result<Option<T>, Error> {
+        self.annotation_values(type_name)
+            .map(|values| T::deserialize(values_deserializer(values)))
+            .transpose()
+            .map_err(Error::from)
+    }
+}
+
+/// Annotation type methods
+impl JavaClass {
+    /// Checks whether this class is an annotation type
+    pub fn is_annotation(&self) -> bool {
+        self.access_flags().is_annotation()
+    }
+
+    /// Gets the retention policy of this annotation type, as declared by `@Retention`.
+    ///
+    /// Returns `None` if this class is not an annotation type. If the annotation type has no
+    /// `@Retention` meta-annotation, the default [`Class`](RetentionPolicy::Class) policy is returned.
+    pub fn retention(&self) -> Option<RetentionPolicy> {
+        if !self.is_annotation() {
+            return None;
+        }
+        let retention = self
+            .get_annotation("java/lang/annotation/Retention")
+            .and_then(|retention| {
+                retention
+                    .get("value")
+                    .and_then(|value| value.as_enum())
+                    .and_then(|(_, name)| RetentionPolicy::from_const_name(name))
+            })
+            .unwrap_or(RetentionPolicy::Class);
+        Some(retention)
+    }
+
+    /// Gets the contexts this annotation type is applicable in, as declared by `@Target`.
+    ///
+    /// Returns `None` if this class is not an annotation type or has no `@Target` meta-annotation,
+    /// in which case the annotation type is applicable in all declaration contexts.
+    pub fn targets(&self) -> Option<Vec<ElementType>> {
+        if !self.is_annotation() {
+            return None;
+        }
+        let target = self.get_annotation("java/lang/annotation/Target")?;
+        let targets = target
+            .get("value")
+            .map(|value| value.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|value| value.as_enum())
+            .filter_map(|(_, name)| ElementType::from_const_name(name))
+            .collect();
+        Some(targets)
+    }
 }
 
 impl Display for JavaClass {
@@ -123,12 +644,64 @@ impl Display for JavaClass {
     }
 }
 
+/// Serialized as its `name`, its class file `version` as `[major, minor]`, `access_flags`,
+/// `super_class`, which is null for `java/lang/Object` and modules, `interfaces`, `fields`,
+/// `methods` and `attributes`
+#[cfg(feature = "serde")]
+impl serde::Serialize for JavaClass {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::utility::{AttributeList, FlagNames};
+        use serde::ser::SerializeStruct;
+
+        let super_class = self.super_class();
+        let mut class = serializer.serialize_struct("JavaClass", 8)?;
+        class.serialize_field("name", self.this())?;
+        class.serialize_field("version", &self.class_file_version())?;
+        class.serialize_field("access_flags", &FlagNames(self.access_flags()))?;
+        class.serialize_field("super_class", &super_class)?;
+        class.serialize_field("interfaces", &self.interfaces())?;
+        class.serialize_field("fields", &self.fields())?;
+        class.serialize_field("methods", &self.methods())?;
+        class.serialize_field("attributes", &AttributeList(self))?;
+        class.end()
+    }
+}
+
+/// Adds the classes in a field or method descriptor
+fn descriptor_classes<'a>(descriptor: &'a str, classes: &mut BTreeSet<&'a FQName>) {
+    let mut rest = descriptor;
+    while let Some(start) = rest.find('L') {
+        let Some(end) = rest[start..].find(';') else {
+            break;
+        };
+        classes.insert(FQName::new(&rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+}
+
+/// Adds the classes in a signature
+fn signature_classes<'a>(signature: &Signature<'a>, classes: &mut BTreeSet<&'a FQName>) {
+    match signature {
+        Signature::FullyQualifiedClass(name) => {
+            classes.insert(FQName::new(*name));
+        }
+        Signature::Array(component) => signature_classes(component, classes),
+        Signature::Method { args, ret_type } => {
+            for arg in args {
+                signature_classes(arg, classes);
+            }
+            signature_classes(ret_type, classes);
+        }
+        _ => {}
+    }
+}
+
 impl Debug for JavaClass {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let attributes: Vec<_> = self.attributes().collect();
         f.debug_struct("JavaClass")
             .field("this", &self.this())
-            .field("super", &self.super_name())
+            .field("super", &self.super_class())
             .field("interfaces", &self.interfaces())
             .field(
                 "attributes",
@@ -142,11 +715,14 @@ impl Debug for JavaClass {
 }
 
 impl HasAttributes for JavaClass {
-    type Iter<'a>  = <Vec<Attribute<'a>> as IntoIterator>::IntoIter where Self: 'a;
+    type Iter<'a>
+        = <Vec<Attribute<'a>> as IntoIterator>::IntoIter
+    where
+        Self: 'a;
 
     fn attributes<'a>(&'a self) -> Self::Iter<'a> {
         let mut output = vec![];
-        for raw_info in self.0.attributes.iter() {
+        for raw_info in self.raw().attributes.iter() {
             let bytes = &*raw_info.info;
             output.extend(self.create_attribute(raw_info.attribute_name_index, bytes));
         }
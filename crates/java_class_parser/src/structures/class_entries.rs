@@ -1,8 +1,156 @@
-use crate::attributes::Attribute;
+use crate::annotations::{Annotation, TypeAnnotation};
+use crate::attributes::{Attribute, AttributeKind, JavaConstant, ParameterAccessFlags};
+use crate::generics::{FieldTypeSignature, GenericSignature, MethodSignature};
 use crate::raw_java_class::{RawAttributeInfo, RawFieldInfo, RawMethodInfo};
-use crate::structures::class::JavaClass;
+use crate::structures::class::{JavaClass, DEPRECATED};
 use crate::utility::match_as;
-use crate::{ConstantPoolInfo, HasAttributes, Signature};
+#[cfg(feature = "serde")]
+use crate::utility::FlagNames;
+use crate::{FQName, HasAttributes, ResolvedConstant, Signature};
+
+bitflags::bitflags! {
+    /// The access flags of a field, as described in table
+    /// [§4.5-A](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.5-200-A.1)
+    /// of the JVM specification.
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct FieldAccessFlags: u16 {
+        /// Declared `public`
+        const PUBLIC = 0x0001;
+        /// Declared `private`
+        const PRIVATE = 0x0002;
+        /// Declared `protected`
+        const PROTECTED = 0x0004;
+        /// Declared `static`
+        const STATIC = 0x0008;
+        /// Declared `final`
+        const FINAL = 0x0010;
+        /// Declared `volatile`
+        const VOLATILE = 0x0040;
+        /// Declared `transient`
+        const TRANSIENT = 0x0080;
+        /// Not present in the source code
+        const SYNTHETIC = 0x1000;
+        /// Declared as a constant of an enum
+        const ENUM = 0x4000;
+    }
+}
+
+impl FieldAccessFlags {
+    /// Checks if the field is declared `public`
+    pub fn is_public(&self) -> bool {
+        self.contains(Self::PUBLIC)
+    }
+
+    /// Checks if the field is declared `private`
+    pub fn is_private(&self) -> bool {
+        self.contains(Self::PRIVATE)
+    }
+
+    /// Checks if the field is declared `protected`
+    pub fn is_protected(&self) -> bool {
+        self.contains(Self::PROTECTED)
+    }
+
+    /// Checks if the field is declared `static`
+    pub fn is_static(&self) -> bool {
+        self.contains(Self::STATIC)
+    }
+
+    /// Checks if the field is declared `final`
+    pub fn is_final(&self) -> bool {
+        self.contains(Self::FINAL)
+    }
+
+    /// Checks if the field was generated by the compiler
+    pub fn is_synthetic(&self) -> bool {
+        self.contains(Self::SYNTHETIC)
+    }
+
+    /// Checks if the field is a constant of an enum
+    pub fn is_enum(&self) -> bool {
+        self.contains(Self::ENUM)
+    }
+}
+
+bitflags::bitflags! {
+    /// The access flags of a method, as described in table
+    /// [§4.6-A](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6-200-A.1)
+    /// of the JVM specification.
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct MethodAccessFlags: u16 {
+        /// Declared `public`
+        const PUBLIC = 0x0001;
+        /// Declared `private`
+        const PRIVATE = 0x0002;
+        /// Declared `protected`
+        const PROTECTED = 0x0004;
+        /// Declared `static`
+        const STATIC = 0x0008;
+        /// Declared `final`
+        const FINAL = 0x0010;
+        /// Declared `synchronized`
+        const SYNCHRONIZED = 0x0020;
+        /// A bridge method, generated by the compiler
+        const BRIDGE = 0x0040;
+        /// Declared with a variable number of arguments
+        const VARARGS = 0x0080;
+        /// Declared `native`
+        const NATIVE = 0x0100;
+        /// Declared `abstract`
+        const ABSTRACT = 0x0400;
+        /// Declared `strictfp`
+        const STRICT = 0x0800;
+        /// Not present in the source code
+        const SYNTHETIC = 0x1000;
+    }
+}
+
+impl MethodAccessFlags {
+    /// Checks if the method is declared `public`
+    pub fn is_public(&self) -> bool {
+        self.contains(Self::PUBLIC)
+    }
+
+    /// Checks if the method is declared `private`
+    pub fn is_private(&self) -> bool {
+        self.contains(Self::PRIVATE)
+    }
+
+    /// Checks if the method is declared `protected`
+    pub fn is_protected(&self) -> bool {
+        self.contains(Self::PROTECTED)
+    }
+
+    /// Checks if the method is declared `static`
+    pub fn is_static(&self) -> bool {
+        self.contains(Self::STATIC)
+    }
+
+    /// Checks if the method is declared `final`
+    pub fn is_final(&self) -> bool {
+        self.contains(Self::FINAL)
+    }
+
+    /// Checks if the method is declared `abstract`
+    pub fn is_abstract(&self) -> bool {
+        self.contains(Self::ABSTRACT)
+    }
+
+    /// Checks if the method is declared `native`
+    pub fn is_native(&self) -> bool {
+        self.contains(Self::NATIVE)
+    }
+
+    /// Checks if the method is a bridge method generated for generics or covariant return types
+    pub fn is_bridge(&self) -> bool {
+        self.contains(Self::BRIDGE)
+    }
+
+    /// Checks if the method was generated by the compiler
+    pub fn is_synthetic(&self) -> bool {
+        self.contains(Self::SYNTHETIC)
+    }
+}
 
 /// A field in a class
 #[derive(Debug)]
@@ -15,6 +163,7 @@ impl<'a> Field<'a> {
         Self {
             entry: Entry::new(
                 java_class,
+                field_info.access_flags,
                 field_info.name_index,
                 field_info.descriptor_index,
                 &field_info.attributes,
@@ -30,16 +179,72 @@ impl<'a> Field<'a> {
     pub fn signature(&self) -> &Signature<'a> {
         &self.entry.signature
     }
+
+    /// The generic signature of the field, if its type uses generics
+    pub fn generic_signature(&self) -> Option<FieldTypeSignature<'a>> {
+        self.entry
+            .generic_signature()
+            .and_then(GenericSignature::into_field)
+    }
+
+    /// The access flags of the field
+    pub fn access_flags(&self) -> FieldAccessFlags {
+        FieldAccessFlags::from_bits_retain(self.entry.access_flags)
+    }
+
+    /// Gets the runtime visible annotations present on this field
+    pub fn annotations(&self) -> Vec<Annotation<'a>> {
+        self.entry.annotations(true)
+    }
+
+    /// Gets the annotations present on this field that aren't visible through reflection
+    pub fn invisible_annotations(&self) -> Vec<Annotation<'a>> {
+        self.entry.annotations(false)
+    }
+
+    /// Checks whether this field is deprecated, either by the `@Deprecated` annotation or the
+    /// `Deprecated` attribute
+    pub fn is_deprecated(&self) -> bool {
+        self.entry.is_deprecated()
+    }
+
+    /// Gets the runtime visible annotations on the type of this field
+    pub fn type_annotations(&self) -> Vec<TypeAnnotation<'a>> {
+        self.entry.type_annotations()
+    }
+
+    /// The compile time constant the field is initialized to, if it's a constant field
+    pub fn constant_value(&self) -> Option<JavaConstant<'a>> {
+        self.entry
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::ConstantValue(constant) => Some(constant.value()),
+                _ => None,
+            })
+    }
 }
 
 impl HasAttributes for Field<'_> {
-    type Iter<'a> = <Vec<Attribute<'a>> as IntoIterator>::IntoIter where Self: 'a;
+    type Iter<'a>
+        = <Vec<Attribute<'a>> as IntoIterator>::IntoIter
+    where
+        Self: 'a;
 
     fn attributes<'a>(&'a self) -> Self::Iter<'a> {
         self.entry.attributes.clone().into_iter()
     }
 }
 
+/// Serialized as its `name`, `descriptor`, `access_flags` and `attributes`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Field<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entry
+            .serialize("Field", FlagNames(self.access_flags()), serializer)
+    }
+}
+
 /// A field in a class
 #[derive(Debug)]
 pub struct Method<'a> {
@@ -51,6 +256,7 @@ impl<'a> Method<'a> {
         Self {
             entry: Entry::new(
                 java_class,
+                method_info.access_flags,
                 method_info.name_index,
                 method_info.descriptor_index,
                 &method_info.attributes,
@@ -66,18 +272,164 @@ impl<'a> Method<'a> {
     pub fn signature(&self) -> &Signature<'a> {
         &self.entry.signature
     }
+
+    /// The generic signature of the method, if it declares type parameters or its parameters,
+    /// return type or exceptions use generics
+    pub fn generic_signature(&self) -> Option<MethodSignature<'a>> {
+        self.entry
+            .generic_signature()
+            .and_then(GenericSignature::into_method)
+    }
+
+    /// The access flags of the method
+    pub fn access_flags(&self) -> MethodAccessFlags {
+        MethodAccessFlags::from_bits_retain(self.entry.access_flags)
+    }
+
+    /// Gets the runtime visible annotations present on this method
+    pub fn annotations(&self) -> Vec<Annotation<'a>> {
+        self.entry.annotations(true)
+    }
+
+    /// Gets the annotations present on this method that aren't visible through reflection
+    pub fn invisible_annotations(&self) -> Vec<Annotation<'a>> {
+        self.entry.annotations(false)
+    }
+
+    /// Checks whether this method is deprecated, either by the `@Deprecated` annotation or the
+    /// `Deprecated` attribute
+    pub fn is_deprecated(&self) -> bool {
+        self.entry.is_deprecated()
+    }
+
+    /// Gets the runtime visible annotations on each parameter of this method, in declaration
+    /// order. Compilers may omit implicit parameters, such as the outer instance passed to the
+    /// constructor of an inner class, so there may be fewer lists than parameters in the
+    /// descriptor.
+    pub fn parameter_annotations(&self) -> Vec<Vec<Annotation<'a>>> {
+        self.entry.parameter_annotations(true)
+    }
+
+    /// Gets the annotations on each parameter of this method that aren't visible through
+    /// reflection
+    pub fn invisible_parameter_annotations(&self) -> Vec<Vec<Annotation<'a>>> {
+        self.entry.parameter_annotations(false)
+    }
+
+    /// Gets the runtime visible annotations on types in the signature of this method. Type
+    /// annotations within the body of the method are found in its `Code` attribute.
+    pub fn type_annotations(&self) -> Vec<TypeAnnotation<'a>> {
+        self.entry.type_annotations()
+    }
+
+    /// The parameters of the method, as their names, access flags and types.
+    ///
+    /// Names and access flags are taken from the `MethodParameters` attribute, which is only
+    /// present if the class was compiled with `-parameters`. Otherwise names are taken from the
+    /// `LocalVariableTable` of the method's code, if it was compiled with debug information, and
+    /// the access flags are empty.
+    pub fn parameters(&self) -> Vec<(Option<&'a str>, ParameterAccessFlags, Signature<'a>)> {
+        let args = self.signature().parameters();
+        let declared = self
+            .entry
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::MethodParameters(parameters) => Some(parameters),
+                _ => None,
+            })
+            .filter(|parameters| parameters.len() == args.len());
+        if let Some(parameters) = declared {
+            return parameters
+                .iter()
+                .zip(args.iter())
+                .map(|(parameter, arg)| (parameter.name(), parameter.access_flags(), arg.clone()))
+                .collect();
+        }
+
+        let variables = self
+            .entry
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::Code(code) => Some(code.local_variables()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        // parameters are the first local variables, after `this` for instance methods
+        let mut slot = if self.access_flags().is_static() {
+            0
+        } else {
+            1
+        };
+        args.iter()
+            .map(|arg| {
+                let name = variables
+                    .iter()
+                    .find(|variable| variable.start_pc() == 0 && variable.index() == slot)
+                    .map(|variable| variable.name());
+                slot += arg.slot_width() as u16;
+                (name, ParameterAccessFlags::empty(), arg.clone())
+            })
+            .collect()
+    }
+
+    /// The checked exceptions listed in the `throws` clause of the method
+    pub fn thrown_exceptions(&self) -> Vec<&'a FQName> {
+        self.entry
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::Exceptions(exceptions) => Some(exceptions.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// The `java.lang.String` literals loaded by `ldc` instructions in the bytecode of the
+    /// method, in the order they're loaded. Literals are repeated if they're loaded more than
+    /// once. Decoding stops at the first instruction that can't be decoded.
+    pub fn string_constants(&self) -> Vec<&'a str> {
+        let Some(code) =
+            self.entry.attributes.iter().find_map(
+                |attribute| match_as!(code; AttributeKind::Code(code) = attribute.kind()),
+            )
+        else {
+            return vec![];
+        };
+        code.resolved_instructions()
+            .map_while(Result::ok)
+            .filter_map(|instruction| match instruction.constant() {
+                Some(ResolvedConstant::String(string)) => Some(*string),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl HasAttributes for Method<'_> {
-    type Iter<'a> = <Vec<Attribute<'a>> as IntoIterator>::IntoIter where Self: 'a;
+    type Iter<'a>
+        = <Vec<Attribute<'a>> as IntoIterator>::IntoIter
+    where
+        Self: 'a;
 
     fn attributes<'a>(&'a self) -> Self::Iter<'a> {
         self.entry.attributes.clone().into_iter()
     }
 }
 
+/// Serialized as its `name`, `descriptor`, `access_flags` and `attributes`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Method<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entry
+            .serialize("Method", FlagNames(self.access_flags()), serializer)
+    }
+}
+
 #[derive(Debug)]
 struct Entry<'a> {
+    access_flags: u16,
     name: &'a str,
     signature: Signature<'a>,
     attributes: Vec<Attribute<'a>>,
@@ -86,28 +438,110 @@ struct Entry<'a> {
 impl<'a> Entry<'a> {
     fn new(
         java_class: &'a JavaClass,
+        access_flags: u16,
         name_index: u16,
         descriptor_index: u16,
         attributes: &'a [RawAttributeInfo],
     ) -> Self {
-        let name = match_as!(name; Some(ConstantPoolInfo::Utf8(name)) = java_class.get_at_index(name_index)).expect("invalid").as_ref();
+        // the names and descriptors of members are checked when the class is parsed
+        let name = java_class
+            .constant_pool()
+            .get_utf8_lossy(name_index)
+            .expect("member names are utf8 constants");
         let signature = java_class
             .get_descriptor(descriptor_index)
-            .expect("should be a valid descriptor");
+            .expect("member descriptors are valid");
 
         let attributes = attributes
             .iter()
-            .map(|s| {
-                java_class
-                    .create_attribute(s.attribute_name_index, &s.info)
-                    .expect("couldn't create attribute")
-            })
+            .map(|s| java_class.create_attribute_or_unknown(s.attribute_name_index, &s.info))
             .collect::<Vec<_>>();
 
         Self {
+            access_flags,
             name,
             signature,
             attributes,
         }
     }
+
+    fn annotations(&self, visible: bool) -> Vec<Annotation<'a>> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match (attribute.kind(), visible) {
+                (AttributeKind::RuntimeVisibleAnnotations(annotations), true)
+                | (AttributeKind::RuntimeInvisibleAnnotations(annotations), false) => {
+                    Some(annotations.clone())
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute.kind(), AttributeKind::Deprecated))
+            || self
+                .annotations(true)
+                .iter()
+                .any(|annotation| annotation.type_name() == DEPRECATED)
+    }
+
+    fn parameter_annotations(&self, visible: bool) -> Vec<Vec<Annotation<'a>>> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match (attribute.kind(), visible) {
+                (AttributeKind::RuntimeVisibleParameterAnnotations(annotations), true)
+                | (AttributeKind::RuntimeInvisibleParameterAnnotations(annotations), false) => {
+                    Some(annotations.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn generic_signature(&self) -> Option<GenericSignature<'a>> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::Signature(signature) => Some(signature.clone()),
+                _ => None,
+            })
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialize<F, S>(
+        &self,
+        name: &'static str,
+        access_flags: FlagNames<F>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        F: bitflags::Flags,
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut entry = serializer.serialize_struct(name, 4)?;
+        entry.serialize_field("name", self.name)?;
+        entry.serialize_field("descriptor", &self.signature)?;
+        entry.serialize_field("access_flags", &access_flags)?;
+        entry.serialize_field("attributes", &self.attributes)?;
+        entry.end()
+    }
+
+    fn type_annotations(&self) -> Vec<TypeAnnotation<'a>> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute.kind() {
+                AttributeKind::RuntimeVisibleTypeAnnotations(annotations) => {
+                    Some(annotations.clone())
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
 }
@@ -0,0 +1,277 @@
+//! Method handles, method types and dynamically computed call sites and constants, as described in
+//! sections [§4.4.8](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4.8)
+//! through [§4.4.10](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4.10) of the JVM
+//! specification.
+//!
+//! These constants are used by lambdas, method references, string concatenation and dynamically
+//! computed constants. They are looked up by constant pool index, as found in the operands of `ldc`
+//! and `invokedynamic` instructions.
+//!
+//! Call sites and dynamic constants are linked by a bootstrap method, listed in the
+//! `BootstrapMethods` attribute of the class.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Greeter").unwrap();
+//! for (_, constant) in class.constants() {
+//!     if let java_class_parser::ResolvedConstant::InvokeDynamic(call_site) = constant {
+//!         let bootstrap = class.bootstrap_method(&call_site).unwrap();
+//!         println!("{} linked by {}", call_site.name(), bootstrap.method().name());
+//!     }
+//! }
+//! ```
+
+use crate::attributes::AttributeKind;
+use crate::constant_pool::values;
+use crate::{ConstantPoolInfo, FQName, HasAttributes, JavaClass, ResolvedConstant, Signature};
+use nom::combinator::{complete, flat_map, map_opt};
+use nom::multi::count;
+use nom::number::complete::be_u16;
+use nom::sequence::tuple;
+use nom::IResult;
+
+/// The kind of a method handle, which determines the bytecode behavior it's equivalent to
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum ReferenceKind {
+    /// `getfield`
+    GetField,
+    /// `getstatic`
+    GetStatic,
+    /// `putfield`
+    PutField,
+    /// `putstatic`
+    PutStatic,
+    /// `invokevirtual`
+    InvokeVirtual,
+    /// `invokestatic`
+    InvokeStatic,
+    /// `invokespecial`
+    InvokeSpecial,
+    /// `new` followed by `invokespecial` of a constructor
+    NewInvokeSpecial,
+    /// `invokeinterface`
+    InvokeInterface,
+}
+
+impl ReferenceKind {
+    /// Gets a reference kind from its value in the class file
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        let kind = match kind {
+            1 => Self::GetField,
+            2 => Self::GetStatic,
+            3 => Self::PutField,
+            4 => Self::PutStatic,
+            5 => Self::InvokeVirtual,
+            6 => Self::InvokeStatic,
+            7 => Self::InvokeSpecial,
+            8 => Self::NewInvokeSpecial,
+            9 => Self::InvokeInterface,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Checks if the handle refers to a field rather than a method
+    pub fn is_field(&self) -> bool {
+        matches!(
+            self,
+            Self::GetField | Self::GetStatic | Self::PutField | Self::PutStatic
+        )
+    }
+}
+
+/// A method handle constant
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodHandle<'a> {
+    kind: ReferenceKind,
+    class: &'a FQName,
+    name: &'a str,
+    descriptor: Signature<'a>,
+}
+
+impl<'a> MethodHandle<'a> {
+    /// The kind of the handle
+    pub fn kind(&self) -> ReferenceKind {
+        self.kind
+    }
+
+    /// The class declaring the referenced field or method
+    pub fn class(&self) -> &'a FQName {
+        self.class
+    }
+
+    /// The name of the referenced field or method
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The descriptor of the referenced field or method
+    pub fn descriptor(&self) -> &Signature<'a> {
+        &self.descriptor
+    }
+}
+
+/// A call site or constant whose value is computed by a bootstrap method the first time it's used
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvokeDynamic<'a> {
+    bootstrap_method_attr_index: u16,
+    name: &'a str,
+    descriptor: Signature<'a>,
+}
+
+impl<'a> InvokeDynamic<'a> {
+    /// The index of the bootstrap method within the `BootstrapMethods` attribute of the class
+    pub fn bootstrap_method_attr_index(&self) -> u16 {
+        self.bootstrap_method_attr_index
+    }
+
+    /// The name of the call site, such as the name of the interface method a lambda implements
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The descriptor of the call site
+    pub fn descriptor(&self) -> &Signature<'a> {
+        &self.descriptor
+    }
+}
+
+/// An entry of the `BootstrapMethods` attribute, which links call sites and dynamic constants
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapMethod<'a> {
+    method: MethodHandle<'a>,
+    arguments: Vec<ResolvedConstant<'a>>,
+}
+
+impl<'a> BootstrapMethod<'a> {
+    /// The bootstrap method, such as `java/lang/invoke/LambdaMetafactory.metafactory`
+    pub fn method(&self) -> &MethodHandle<'a> {
+        &self.method
+    }
+
+    /// The static arguments passed to the bootstrap method
+    pub fn arguments(&self) -> &[ResolvedConstant<'a>] {
+        &self.arguments[..]
+    }
+}
+
+pub(crate) fn parse_bootstrap_methods<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<BootstrapMethod<'a>>> {
+    complete(flat_map(be_u16, |num_bootstrap_methods| {
+        count(
+            map_opt(
+                tuple((
+                    be_u16,
+                    flat_map(be_u16, |num_arguments| {
+                        count(be_u16, num_arguments as usize)
+                    }),
+                )),
+                |(method_ref, arguments)| {
+                    Some(BootstrapMethod {
+                        method: class.method_handle(method_ref)?,
+                        arguments: arguments
+                            .into_iter()
+                            .map(|index| class.constant(index))
+                            .collect::<Option<_>>()?,
+                    })
+                },
+            ),
+            num_bootstrap_methods as usize,
+        )
+    }))(bytes)
+}
+
+/// Dynamic constant lookups
+impl JavaClass {
+    /// Gets the method handle constant at a constant pool index
+    pub fn method_handle(&self, index: u16) -> Option<MethodHandle<'_>> {
+        let ConstantPoolInfo::MethodHandle(values::MethodHandle {
+            reference_kind,
+            reference_index,
+        }) = self.get_at_index(index)?
+        else {
+            return None;
+        };
+        let pool = self.constant_pool();
+        let member = pool
+            .get_field_ref(*reference_index)
+            .or_else(|| pool.get_method_ref(*reference_index))?;
+        Some(MethodHandle {
+            kind: ReferenceKind::from_u8(*reference_kind)?,
+            class: member.class(),
+            name: member.name(),
+            descriptor: Signature::new(member.descriptor()).ok()?,
+        })
+    }
+
+    /// Gets the method descriptor of the method type constant at a constant pool index
+    pub fn method_type(&self, index: u16) -> Option<Signature<'_>> {
+        let ConstantPoolInfo::MethodType(values::MethodType { descriptor_index }) =
+            self.get_at_index(index)?
+        else {
+            return None;
+        };
+        Signature::new(self.get_string(*descriptor_index)?).ok()
+    }
+
+    /// Gets the dynamically computed constant at a constant pool index. Its descriptor is the
+    /// field descriptor of the computed value.
+    pub fn dynamic_constant(&self, index: u16) -> Option<InvokeDynamic<'_>> {
+        let ConstantPoolInfo::Dynamic(values::Dynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        }) = self.get_at_index(index)?
+        else {
+            return None;
+        };
+        let (name, descriptor) = self.name_and_type(*name_and_type_index)?;
+        Some(InvokeDynamic {
+            bootstrap_method_attr_index: *bootstrap_method_attr_index,
+            name,
+            descriptor,
+        })
+    }
+
+    /// Gets the dynamically computed call site at a constant pool index
+    pub fn invoke_dynamic(&self, index: u16) -> Option<InvokeDynamic<'_>> {
+        let ConstantPoolInfo::InvokeDynamic(values::InvokeDynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        }) = self.get_at_index(index)?
+        else {
+            return None;
+        };
+        let (name, descriptor) = self.name_and_type(*name_and_type_index)?;
+        Some(InvokeDynamic {
+            bootstrap_method_attr_index: *bootstrap_method_attr_index,
+            name,
+            descriptor,
+        })
+    }
+
+    /// Gets the bootstrap methods of the class, from the `BootstrapMethods` attribute
+    pub fn bootstrap_methods(&self) -> Vec<BootstrapMethod<'_>> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::BootstrapMethods(methods) => Some(methods.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the bootstrap method that links a call site or dynamic constant
+    pub fn bootstrap_method(&self, call_site: &InvokeDynamic) -> Option<BootstrapMethod<'_>> {
+        self.bootstrap_methods()
+            .into_iter()
+            .nth(call_site.bootstrap_method_attr_index() as usize)
+    }
+
+    fn name_and_type(&self, index: u16) -> Option<(&str, Signature<'_>)> {
+        let (name, descriptor) = self.constant_pool().get_name_and_type(index)?;
+        Some((name, Signature::new(descriptor).ok()?))
+    }
+}
@@ -0,0 +1,109 @@
+//! Recognizes the members a compiler generates to support enums, so they can be told apart from
+//! members declared in source.
+
+use crate::{FQName, Field, FieldAccessFlags, JavaClass, Method, Signature};
+
+/// A member generated by the compiler to support an enum, or a switch over an enum
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum EnumSupport {
+    /// The `public static E[] values()` method of an enum
+    Values,
+    /// The `public static E valueOf(String)` method of an enum
+    ValueOf,
+    /// The `$VALUES` field of an enum, which backs the `values()` method
+    ValuesField,
+    /// The `$values()` method of an enum, which creates the `$VALUES` array
+    ValuesInitializer,
+    /// A `$SwitchMap$` field, mapping the ordinals of an enum to the cases of a switch
+    SwitchMap,
+}
+
+/// Enum support methods
+impl JavaClass {
+    /// Checks whether this class is an enum
+    pub fn is_enum(&self) -> bool {
+        self.access_flags().is_enum()
+    }
+
+    /// Checks whether this class is a compiler generated class holding `$SwitchMap$` fields, such
+    /// as the `Foo$1` class generated for a switch over an enum within `Foo`.
+    pub fn is_switch_map_class(&self) -> bool {
+        let fields = self.fields();
+        self.access_flags().is_synthetic()
+            && !fields.is_empty()
+            && fields
+                .iter()
+                .all(|field| self.field_enum_support(field) == Some(EnumSupport::SwitchMap))
+    }
+
+    /// Checks if a method of this class was generated to support this class being an enum
+    pub fn method_enum_support(&self, method: &Method) -> Option<EnumSupport> {
+        if !self.is_enum() || !method.access_flags().is_static() {
+            return None;
+        }
+        let Signature::Method { args, ret_type } = method.signature() else {
+            return None;
+        };
+        let returns_this = is_class(ret_type, self.this());
+        let returns_array = is_array_of(ret_type, self.this());
+        match (method.name(), &args[..]) {
+            ("values", []) if returns_array => Some(EnumSupport::Values),
+            ("valueOf", [Signature::FullyQualifiedClass("java/lang/String")]) if returns_this => {
+                Some(EnumSupport::ValueOf)
+            }
+            ("$values", []) if returns_array && method.access_flags().is_synthetic() => {
+                Some(EnumSupport::ValuesInitializer)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks if a field of this class was generated to support this class being an enum, or to
+    /// support a switch over an enum
+    pub fn field_enum_support(&self, field: &Field) -> Option<EnumSupport> {
+        if !field
+            .access_flags()
+            .contains(FieldAccessFlags::SYNTHETIC | FieldAccessFlags::STATIC)
+        {
+            return None;
+        }
+        let name = field.name();
+        if name.starts_with("$SwitchMap$")
+            && *field.signature() == Signature::Array(Box::new(Signature::Int))
+        {
+            return Some(EnumSupport::SwitchMap);
+        }
+        // ENUM$VALUES is used by the eclipse compiler
+        if self.is_enum()
+            && (name == "$VALUES" || name == "ENUM$VALUES")
+            && is_array_of(field.signature(), self.this())
+        {
+            return Some(EnumSupport::ValuesField);
+        }
+        None
+    }
+
+    /// Gets the methods declared in this class, excluding those generated to support enums.
+    pub fn methods_without_enum_support(&self) -> Vec<Method<'_>> {
+        self.methods()
+            .into_iter()
+            .filter(|method| self.method_enum_support(method).is_none())
+            .collect()
+    }
+
+    /// Gets the fields declared in this class, excluding those generated to support enums.
+    pub fn fields_without_enum_support(&self) -> Vec<Field<'_>> {
+        self.fields()
+            .into_iter()
+            .filter(|field| self.field_enum_support(field).is_none())
+            .collect()
+    }
+}
+
+fn is_class(signature: &Signature, class: &FQName) -> bool {
+    matches!(signature, Signature::FullyQualifiedClass(name) if class == *name)
+}
+
+fn is_array_of(signature: &Signature, class: &FQName) -> bool {
+    matches!(signature, Signature::Array(component) if is_class(component, class))
+}
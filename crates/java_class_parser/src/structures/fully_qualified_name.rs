@@ -1,10 +1,12 @@
 //! similar to paths
 
-use std::borrow::Borrow;
-use std::ffi::OsStr;
+use crate::error::{Error, ErrorKind};
+use crate::Signature;
+use std::borrow::{Borrow, Cow};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::str::FromStr;
 
 /// Gets an object as a fully qualified path
 pub trait AsFullyQualifiedName {
@@ -26,12 +28,39 @@ impl AsFullyQualifiedName for &str {
 
 impl AsFullyQualifiedName for String {
     fn as_fcq(&self) -> &FQName {
-        FQName::new(&*self)
+        FQName::new(self)
     }
 }
 
+/// The characters separating the components of a fully qualified name
+const SEPARATORS: [char; 2] = ['/', '.'];
+
+/// The type a fully qualified name refers to
+#[derive(Debug, Clone, PartialEq)]
+pub enum NamedType<'a> {
+    /// A class, interface or other type with a class file
+    Class(&'a FQName),
+    /// An array, named by its descriptor
+    Array(Signature<'a>),
+    /// A primitive type or `void`, named by its keyword
+    Primitive(Signature<'a>),
+}
+
+/// The kind of a class, as far as can be told from its name
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NestedKind<'a> {
+    /// A class that isn't nested in another class
+    TopLevel,
+    /// A class declared as a member of another class, with its simple name
+    Member(&'a str),
+    /// A class declared in a block of code, with its simple name
+    Local(&'a str),
+    /// An anonymous class
+    Anonymous,
+}
+
 /// A fully qualified name is a set of identifiers seperated by either `/` or `.`
-#[derive(Eq, PartialEq, Hash)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct FQName {
     fcq: str,
@@ -52,11 +81,151 @@ impl FQName {
         ptr as *const FQName
     }
 
+    /// Gets the fully qualified name as a string
+    pub fn as_str(&self) -> &str {
+        &self.fcq
+    }
+
     /// Gets the fully qualified name as a path
     pub fn as_path(&self) -> &Path {
         Path::new(&self.fcq)
     }
 
+    /// Classifies the type this name refers to. Class constants and the catch types of exception
+    /// handlers can name array types by their descriptor, such as `[Ljava/lang/String;`, which
+    /// have no class file and mustn't be treated as paths.
+    pub fn as_type(&self) -> NamedType<'_> {
+        if self.fcq.starts_with('[') {
+            if let Ok(signature @ Signature::Array(_)) = Signature::new(&self.fcq) {
+                return NamedType::Array(signature);
+            }
+        }
+        let primitive = match &self.fcq {
+            "boolean" => Signature::Boolean,
+            "byte" => Signature::Byte,
+            "char" => Signature::Char,
+            "short" => Signature::Short,
+            "int" => Signature::Int,
+            "long" => Signature::Long,
+            "float" => Signature::Float,
+            "double" => Signature::Double,
+            "void" => Signature::Void,
+            _ => return NamedType::Class(self),
+        };
+        NamedType::Primitive(primitive)
+    }
+
+    /// Gets the package of this name, or `None` if it's in the default package
+    pub fn package(&self) -> Option<&FQName> {
+        self.fcq
+            .rfind(SEPARATORS)
+            .map(|index| FQName::new(&self.fcq[..index]))
+    }
+
+    /// Gets the last component of this name, such as `Foo` for `com/example/Foo`. Nested classes
+    /// keep the name of their outer class, such as `Outer$Inner`.
+    pub fn simple_name(&self) -> &str {
+        self.components().last().unwrap_or_default()
+    }
+
+    /// Gets the components of this name, in order
+    pub fn components(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.fcq.split(SEPARATORS)
+    }
+
+    /// Gets this name in binary form, separated by dots like `com.example.Foo`
+    pub fn to_binary_name(&self) -> String {
+        self.fcq.replace('/', ".")
+    }
+
+    /// Gets this name in internal form, separated by slashes like `com/example/Foo`
+    pub fn to_internal_name(&self) -> String {
+        self.fcq.replace('.', "/")
+    }
+
+    /// Gets the class this class is nested in, based on the last `$` in its simple name. The
+    /// compiler emits nested classes as `Outer$Inner`, but `$` is also legal in top level class
+    /// names, so the [`InnerClasses`](crate::attributes::InnerClass) of a class should be
+    /// consulted when this must be exact.
+    pub fn outer_class(&self) -> Option<&FQName> {
+        let simple_name = self.simple_name();
+        let index = simple_name.rfind('$')?;
+        if index == 0 || index == simple_name.len() - 1 {
+            return None;
+        }
+        let end = self.fcq.len() - simple_name.len() + index;
+        Some(FQName::new(&self.fcq[..end]))
+    }
+
+    /// Checks if this is the name of a nested class
+    pub fn is_nested(&self) -> bool {
+        self.outer_class().is_some()
+    }
+
+    /// Gets the chain of classes this class is nested in, starting with the top level class and
+    /// ending with this class
+    pub fn nesting_chain(&self) -> Vec<&FQName> {
+        let mut chain = vec![self];
+        while let Some(outer) = chain.last().and_then(|name| name.outer_class()) {
+            chain.push(outer);
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Gets what kind of class this is, based on the name the compiler gives nested classes.
+    /// Anonymous classes are numbered like `Outer$1`, and local classes are numbered and named
+    /// like `Outer$1Local`.
+    pub fn nested_kind(&self) -> NestedKind<'_> {
+        let Some(outer) = self.outer_class() else {
+            return NestedKind::TopLevel;
+        };
+        let name = &self.fcq[outer.fcq.len() + 1..];
+        let digits = name.len() - name.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        match (digits, &name[digits..]) {
+            (0, name) => NestedKind::Member(name),
+            (_, "") => NestedKind::Anonymous,
+            (_, name) => NestedKind::Local(name),
+        }
+    }
+
+    /// Gets this name in its canonical internal form, so that `com.example.Foo` and
+    /// `com/example/Foo` refer to the same class. Only allocates if the name contains dots.
+    pub fn canonicalize(&self) -> Cow<'_, FQName> {
+        if self.fcq.contains('.') {
+            Cow::Owned(FQNameBuf {
+                buf: self.to_internal_name(),
+            })
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    /// Checks that every component of this name is a valid java identifier. The special
+    /// `module-info` and `package-info` classes are also valid.
+    pub fn is_valid(&self) -> bool {
+        let mut components = self.components().peekable();
+        while let Some(component) = components.next() {
+            let is_last = components.peek().is_none();
+            if !(is_java_identifier(component)
+                || is_last && matches!(component, "module-info" | "package-info"))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Checks that this is a legal binary name in the JVM, which is less strict than
+    /// [`is_valid`](Self::is_valid). As described in section
+    /// [§4.2.1](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.2.1) of
+    /// the JVM specification, components can't be empty or contain `;` or `[`, but can be names
+    /// that aren't java identifiers, such as `my-lib` or `1Foo`.
+    pub fn is_binary_name(&self) -> bool {
+        self.components()
+            .all(|component| !component.is_empty() && !component.contains([';', '[']))
+    }
+
     /// Turns this FQName into an owned version.
     pub fn to_fqname_buf(&self) -> FQNameBuf {
         FQNameBuf {
@@ -121,10 +290,25 @@ impl AsFullyQualifiedName for FQName {
 }
 
 /// An owned version of a fully qualified name
-#[derive(Eq, PartialEq, Hash, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 pub struct FQNameBuf {
     buf: String,
 }
+impl FQNameBuf {
+    /// Parses a fully qualified name in either binary or internal form, validating it against the
+    /// rules for java identifiers. The name is stored in its canonical internal form.
+    ///
+    /// # Error
+    /// Will return an [`InvalidName`](ErrorKind::InvalidName) error if the name isn't valid
+    pub fn parse(name: &str) -> Result<Self, Error> {
+        let fcq = FQName::new(name);
+        if !fcq.is_valid() {
+            return Err(ErrorKind::InvalidName(name.to_string()).into());
+        }
+        Ok(fcq.canonicalize().into_owned())
+    }
+}
+
 impl Debug for FQNameBuf {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&self.buf, f)
@@ -171,9 +355,52 @@ impl AsFullyQualifiedName for FQNameBuf {
     }
 }
 
+impl FromStr for FQNameBuf {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FQName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.fcq)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FQNameBuf {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.buf)
+    }
+}
+
+/// Deserializes from a string in either binary or internal form, which is validated like
+/// [`FQNameBuf::parse`].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FQNameBuf {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        Self::parse(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Checks if a string is a valid java identifier
+fn is_java_identifier(string: &str) -> bool {
+    let mut chars = string.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::structures::FQName;
+    use crate::error::ErrorKind;
+    use crate::structures::{FQName, FQNameBuf, NamedType, NestedKind};
+    use crate::Signature;
     use std::path::PathBuf;
 
     #[test]
@@ -211,11 +438,141 @@ mod tests {
         assert_eq!(fcq, "Test");
     }
 
+    #[test]
+    fn name_parts() {
+        let fcq = FQName::new("com/example/Outer$Inner");
+        assert_eq!(fcq.package().unwrap(), "com/example");
+        assert_eq!(fcq.simple_name(), "Outer$Inner");
+        assert_eq!(
+            fcq.components().collect::<Vec<_>>(),
+            ["com", "example", "Outer$Inner"]
+        );
+        assert_eq!(fcq.to_binary_name(), "com.example.Outer$Inner");
+        assert_eq!(
+            FQName::new("com.example.Foo").to_internal_name(),
+            "com/example/Foo"
+        );
+
+        let fcq = FQName::new("Foo");
+        assert_eq!(fcq.package(), None);
+        assert_eq!(fcq.simple_name(), "Foo");
+    }
+
+    #[test]
+    fn named_types() {
+        let fcq = FQName::new("java/lang/String");
+        assert_eq!(fcq.as_type(), NamedType::Class(fcq));
+        assert_eq!(
+            FQName::new("[[Ljava/lang/String;").as_type(),
+            NamedType::Array(Signature::array_of(Signature::array_of(Signature::object(
+                "java/lang/String"
+            ))))
+        );
+        assert_eq!(
+            FQName::new("int").as_type(),
+            NamedType::Primitive(Signature::Int)
+        );
+    }
+
+    #[test]
+    fn nested_names() {
+        let fcq = FQName::new("com/example/Outer$Inner$1Local$2");
+        assert_eq!(fcq.outer_class().unwrap(), "com/example/Outer$Inner$1Local");
+        assert_eq!(
+            fcq.nesting_chain(),
+            [
+                "com/example/Outer",
+                "com/example/Outer$Inner",
+                "com/example/Outer$Inner$1Local",
+                "com/example/Outer$Inner$1Local$2",
+            ]
+            .map(FQName::new)
+        );
+        assert_eq!(fcq.nested_kind(), NestedKind::Anonymous);
+        assert_eq!(
+            FQName::new("com/example/Outer$Inner$1Local").nested_kind(),
+            NestedKind::Local("Local")
+        );
+        assert_eq!(
+            FQName::new("com/example/Outer$Inner").nested_kind(),
+            NestedKind::Member("Inner")
+        );
+
+        for top_level in [
+            "com/example/Outer",
+            "com/ex$ample/Outer",
+            "$Proxy",
+            "Object$",
+        ] {
+            let fcq = FQName::new(top_level);
+            assert!(!fcq.is_nested());
+            assert_eq!(fcq.nested_kind(), NestedKind::TopLevel);
+            assert_eq!(fcq.nesting_chain(), [fcq]);
+        }
+    }
+
+    #[test]
+    fn canonical_names() {
+        assert_eq!(
+            FQName::new("com.example.Foo").canonicalize().as_ref(),
+            FQName::new("com/example/Foo")
+        );
+        assert_eq!(
+            FQNameBuf::parse("com.example.Outer$1").unwrap(),
+            "com/example/Outer$1"
+        );
+        assert_eq!(FQNameBuf::parse("module-info").unwrap(), "module-info");
+        for invalid in ["", "com//Foo", "com/1Foo", "com/Foo;", "my-package/Foo"] {
+            let error = FQNameBuf::parse(invalid).unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::InvalidName(name) if name == invalid));
+        }
+    }
+
+    #[test]
+    fn binary_names() {
+        for legal in [
+            "com/1Foo",
+            "my-package/Foo",
+            "com.example.Foo",
+            "module-info",
+        ] {
+            assert!(FQName::new(legal).is_binary_name(), "{legal}");
+        }
+        for illegal in ["", "com//Foo", "com/Foo;", "com/[Foo", "com..Foo", "/Foo"] {
+            assert!(!FQName::new(illegal).is_binary_name(), "{illegal}");
+        }
+    }
+
+    #[test]
+    fn ordered_names() {
+        let mut names = ["java/lang/String", "com/example/Foo", "java/lang/Object"]
+            .map(|name| name.parse::<FQNameBuf>().unwrap());
+        names.sort();
+        assert_eq!(
+            names,
+            ["com/example/Foo", "java/lang/Object", "java/lang/String"]
+        );
+        assert!(FQName::new("a/B") < FQName::new("a/C"));
+        assert!("com/1Foo".parse::<FQNameBuf>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_name() {
+        use serde::de::value::{Error, StrDeserializer};
+        use serde::Deserialize;
+
+        let name =
+            FQNameBuf::deserialize(StrDeserializer::<Error>::new("com.example.Foo")).unwrap();
+        assert_eq!(name, "com/example/Foo");
+        assert!(FQNameBuf::deserialize(StrDeserializer::<Error>::new("com/1Foo")).is_err());
+    }
+
     #[test]
     fn safe_usage() {
         let string = "java/lang/Object";
         let fcq = FQName::new(string);
-        assert_eq!(&*fcq, string);
+        assert_eq!(fcq, string);
         assert_eq!(
             (*fcq).as_path(),
             PathBuf::from_iter(["java", "lang", "Object"])
@@ -224,13 +581,13 @@ mod tests {
         let mut string = string.to_string();
         let cloned = string.clone();
         let fcq = FQName::new(&cloned);
-        assert_eq!(&*fcq, &string);
+        assert_eq!(fcq, &string);
         assert_eq!(
             (*fcq).as_path(),
             PathBuf::from_iter(["java", "lang", "Object"])
         );
 
         string.push('j');
-        assert_ne!(&*fcq, &string); // should no longer be equal
+        assert_ne!(fcq, &string); // should no longer be equal
     }
 }
@@ -0,0 +1,647 @@
+//! Generic signatures, as described in section [§4.7.9.1](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1)
+//! of the JVM specification.
+//!
+//! Descriptors only record the erasure of a type. When a class, method or field uses generics, the
+//! full type is recorded in a `Signature` attribute using a richer grammar that includes type
+//! parameters, type arguments, wildcards and type variables.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Container").unwrap();
+//! if let Some(signature) = class.generic_signature() {
+//!     for parameter in signature.type_parameters() {
+//!         println!("type parameter {}", parameter.name());
+//!     }
+//! }
+//! ```
+
+use crate::Signature;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_till1};
+use nom::combinator::{eof, map, opt};
+use nom::multi::{many0, many1};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+use std::fmt::{Display, Formatter};
+
+/// The contents of a `Signature` attribute.
+///
+/// The grammar used depends on whether the attribute is attached to a class, a method or a field.
+/// A class signature without type parameters or interfaces can't be told apart from a field
+/// signature, so [`into_class`](GenericSignature::into_class) accepts both.
+#[derive(Debug, PartialEq, Clone)]
+pub enum GenericSignature<'a> {
+    /// The signature of a class
+    Class(ClassSignature<'a>),
+    /// The signature of a method
+    Method(MethodSignature<'a>),
+    /// The signature of a field, record component or local variable
+    Field(FieldTypeSignature<'a>),
+}
+
+impl<'a> GenericSignature<'a> {
+    /// Parses the contents of a signature attribute, detecting which grammar it uses
+    pub fn new(str: &'a str) -> Result<Self, nom::Err<nom::error::Error<String>>> {
+        complete(parse_generic_signature)(str)
+    }
+
+    /// Gets this as a class signature
+    pub fn into_class(self) -> Option<ClassSignature<'a>> {
+        match self {
+            GenericSignature::Class(class) => Some(class),
+            GenericSignature::Field(FieldTypeSignature::Class(superclass)) => {
+                Some(ClassSignature {
+                    type_parameters: vec![],
+                    superclass,
+                    interfaces: vec![],
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets this as a method signature
+    pub fn into_method(self) -> Option<MethodSignature<'a>> {
+        match self {
+            GenericSignature::Method(method) => Some(method),
+            _ => None,
+        }
+    }
+
+    /// Gets this as a field signature
+    pub fn into_field(self) -> Option<FieldTypeSignature<'a>> {
+        match self {
+            GenericSignature::Field(field) => Some(field),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GenericSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenericSignature::Class(class) => write!(f, "{class}"),
+            GenericSignature::Method(method) => write!(f, "{method}"),
+            GenericSignature::Field(field) => write!(f, "{field}"),
+        }
+    }
+}
+
+/// Serialized as it's written in the class file, such as `<T:Ljava/lang/Object;>Ljava/lang/Object;`
+#[cfg(feature = "serde")]
+impl serde::Serialize for GenericSignature<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The generic signature of a class, such as `<T:Ljava/lang/Object;>Ljava/lang/Object;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassSignature<'a> {
+    type_parameters: Vec<TypeParameter<'a>>,
+    superclass: ClassTypeSignature<'a>,
+    interfaces: Vec<ClassTypeSignature<'a>>,
+}
+
+impl<'a> ClassSignature<'a> {
+    /// Parses a class signature
+    pub fn new(str: &'a str) -> Result<Self, nom::Err<nom::error::Error<String>>> {
+        complete(parse_class_signature)(str)
+    }
+
+    /// The type parameters declared by the class
+    pub fn type_parameters(&self) -> &[TypeParameter<'a>] {
+        &self.type_parameters
+    }
+
+    /// The superclass of the class
+    pub fn superclass(&self) -> &ClassTypeSignature<'a> {
+        &self.superclass
+    }
+
+    /// The interfaces directly implemented by the class
+    pub fn interfaces(&self) -> &[ClassTypeSignature<'a>] {
+        &self.interfaces
+    }
+}
+
+impl Display for ClassSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_type_parameters(f, &self.type_parameters)?;
+        write!(f, "{}", self.superclass)?;
+        for interface in &self.interfaces {
+            write!(f, "{interface}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The generic signature of a method, such as `<T:Ljava/lang/Object;>(TT;)Ljava/util/List<TT;>;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct MethodSignature<'a> {
+    type_parameters: Vec<TypeParameter<'a>>,
+    parameters: Vec<TypeSignature<'a>>,
+    return_type: TypeSignature<'a>,
+    throws: Vec<FieldTypeSignature<'a>>,
+}
+
+impl<'a> MethodSignature<'a> {
+    /// Parses a method signature
+    pub fn new(str: &'a str) -> Result<Self, nom::Err<nom::error::Error<String>>> {
+        complete(parse_method_signature)(str)
+    }
+
+    /// The type parameters declared by the method
+    pub fn type_parameters(&self) -> &[TypeParameter<'a>] {
+        &self.type_parameters
+    }
+
+    /// The types of the parameters of the method
+    pub fn parameters(&self) -> &[TypeSignature<'a>] {
+        &self.parameters
+    }
+
+    /// The return type of the method, which is [`Signature::Void`] wrapped in
+    /// [`TypeSignature::Primitive`] for `void` methods
+    pub fn return_type(&self) -> &TypeSignature<'a> {
+        &self.return_type
+    }
+
+    /// The exceptions the method declares it may throw, which are either classes or type
+    /// variables. This is empty when the method only throws non-generic exceptions, which are
+    /// recorded in the `Exceptions` attribute instead.
+    pub fn throws(&self) -> &[FieldTypeSignature<'a>] {
+        &self.throws
+    }
+}
+
+impl Display for MethodSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_type_parameters(f, &self.type_parameters)?;
+        write!(f, "(")?;
+        for parameter in &self.parameters {
+            write!(f, "{parameter}")?;
+        }
+        write!(f, "){}", self.return_type)?;
+        for throws in &self.throws {
+            write!(f, "^{throws}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A type parameter declared by a generic class or method, such as `T extends Comparable<T>`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeParameter<'a> {
+    name: &'a str,
+    class_bound: Option<FieldTypeSignature<'a>>,
+    interface_bounds: Vec<FieldTypeSignature<'a>>,
+}
+
+impl<'a> TypeParameter<'a> {
+    /// The name of the type parameter
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The class or type variable bounding the parameter. This is `None` when the parameter is
+    /// only bound by interfaces.
+    pub fn class_bound(&self) -> Option<&FieldTypeSignature<'a>> {
+        self.class_bound.as_ref()
+    }
+
+    /// The interfaces bounding the parameter
+    pub fn interface_bounds(&self) -> &[FieldTypeSignature<'a>] {
+        &self.interface_bounds
+    }
+}
+
+impl Display for TypeParameter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.name)?;
+        if let Some(class_bound) = &self.class_bound {
+            write!(f, "{class_bound}")?;
+        }
+        for interface_bound in &self.interface_bounds {
+            write!(f, ":{interface_bound}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Any type, either a primitive or a reference type
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeSignature<'a> {
+    /// A primitive type or `void`. This is never a class, array or method [`Signature`].
+    Primitive(Signature<'a>),
+    /// A reference type
+    Reference(FieldTypeSignature<'a>),
+}
+
+impl Display for TypeSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeSignature::Primitive(primitive) => write!(f, "{}", primitive.jni()),
+            TypeSignature::Reference(reference) => write!(f, "{reference}"),
+        }
+    }
+}
+
+/// A reference type
+#[derive(Debug, PartialEq, Clone)]
+pub enum FieldTypeSignature<'a> {
+    /// A class, such as `Ljava/util/List<Ljava/lang/String;>;`
+    Class(ClassTypeSignature<'a>),
+    /// A type variable, such as `TT;`
+    TypeVariable(&'a str),
+    /// An array of some type
+    Array(Box<TypeSignature<'a>>),
+}
+
+impl<'a> FieldTypeSignature<'a> {
+    /// Parses a field signature
+    pub fn new(str: &'a str) -> Result<Self, nom::Err<nom::error::Error<String>>> {
+        complete(parse_field_type_signature)(str)
+    }
+}
+
+impl Display for FieldTypeSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldTypeSignature::Class(class) => write!(f, "{class}"),
+            FieldTypeSignature::TypeVariable(variable) => write!(f, "T{variable};"),
+            FieldTypeSignature::Array(component) => write!(f, "[{component}"),
+        }
+    }
+}
+
+/// A possibly parameterized class type. Inner classes of parameterized classes are recorded as a
+/// chain of classes, such as `Lcom/example/Outer<TT;>.Inner;`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassTypeSignature<'a> {
+    classes: Vec<SimpleClassTypeSignature<'a>>,
+}
+
+impl<'a> ClassTypeSignature<'a> {
+    /// The binary name of the class in internal form, such as `com/example/Outer$Inner`
+    pub fn name(&self) -> String {
+        self.classes
+            .iter()
+            .map(SimpleClassTypeSignature::name)
+            .collect::<Vec<_>>()
+            .join("$")
+    }
+
+    /// The chain of classes, starting with the outermost class. The name of the outermost class
+    /// includes its package.
+    pub fn classes(&self) -> &[SimpleClassTypeSignature<'a>] {
+        &self.classes
+    }
+
+    /// The type arguments of the innermost class
+    pub fn type_arguments(&self) -> &[TypeArgument<'a>] {
+        self.classes
+            .last()
+            .map(SimpleClassTypeSignature::type_arguments)
+            .unwrap_or_default()
+    }
+}
+
+impl Display for ClassTypeSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "L")?;
+        for (index, class) in self.classes.iter().enumerate() {
+            if index > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{class}")?;
+        }
+        write!(f, ";")
+    }
+}
+
+/// A single class within a [`ClassTypeSignature`] and its type arguments
+#[derive(Debug, PartialEq, Clone)]
+pub struct SimpleClassTypeSignature<'a> {
+    name: &'a str,
+    type_arguments: Vec<TypeArgument<'a>>,
+}
+
+impl<'a> SimpleClassTypeSignature<'a> {
+    /// The name of the class. For inner classes this is only the simple name.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The type arguments of the class
+    pub fn type_arguments(&self) -> &[TypeArgument<'a>] {
+        &self.type_arguments
+    }
+}
+
+impl Display for SimpleClassTypeSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.type_arguments.is_empty() {
+            write!(f, "<")?;
+            for argument in &self.type_arguments {
+                write!(f, "{argument}")?;
+            }
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+/// A type argument of a parameterized class
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeArgument<'a> {
+    /// The unbounded wildcard `?`
+    Any,
+    /// A concrete type
+    Exact(FieldTypeSignature<'a>),
+    /// A wildcard with an upper bound, `? extends T`
+    Extends(FieldTypeSignature<'a>),
+    /// A wildcard with a lower bound, `? super T`
+    Super(FieldTypeSignature<'a>),
+}
+
+impl Display for TypeArgument<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeArgument::Any => write!(f, "*"),
+            TypeArgument::Exact(exact) => write!(f, "{exact}"),
+            TypeArgument::Extends(bound) => write!(f, "+{bound}"),
+            TypeArgument::Super(bound) => write!(f, "-{bound}"),
+        }
+    }
+}
+
+fn write_type_parameters(f: &mut Formatter<'_>, parameters: &[TypeParameter]) -> std::fmt::Result {
+    if !parameters.is_empty() {
+        write!(f, "<")?;
+        for parameter in parameters {
+            write!(f, "{parameter}")?;
+        }
+        write!(f, ">")?;
+    }
+    Ok(())
+}
+
+/// Runs a parser over an entire string
+fn complete<'a, T>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> Result<T, nom::Err<nom::error::Error<String>>> {
+    move |str| {
+        let (rest, parsed) =
+            parser(str).map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())?;
+        eof(rest).map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())?;
+        Ok(parsed)
+    }
+}
+
+fn parse_generic_signature(string: &str) -> IResult<&str, GenericSignature<'_>> {
+    alt((
+        map(
+            terminated(parse_field_type_signature, eof),
+            GenericSignature::Field,
+        ),
+        map(
+            terminated(parse_method_signature, eof),
+            GenericSignature::Method,
+        ),
+        map(parse_class_signature, GenericSignature::Class),
+    ))(string)
+}
+
+fn parse_class_signature(string: &str) -> IResult<&str, ClassSignature<'_>> {
+    map(
+        tuple((
+            parse_type_parameters,
+            parse_class_type_signature,
+            many0(parse_class_type_signature),
+        )),
+        |(type_parameters, superclass, interfaces)| ClassSignature {
+            type_parameters,
+            superclass,
+            interfaces,
+        },
+    )(string)
+}
+
+fn parse_method_signature(string: &str) -> IResult<&str, MethodSignature<'_>> {
+    map(
+        tuple((
+            parse_type_parameters,
+            delimited(tag("("), many0(parse_type_signature), tag(")")),
+            alt((
+                map(tag("V"), |_| TypeSignature::Primitive(Signature::Void)),
+                parse_type_signature,
+            )),
+            many0(preceded(
+                tag("^"),
+                alt((
+                    map(parse_class_type_signature, FieldTypeSignature::Class),
+                    parse_type_variable,
+                )),
+            )),
+        )),
+        |(type_parameters, parameters, return_type, throws)| MethodSignature {
+            type_parameters,
+            parameters,
+            return_type,
+            throws,
+        },
+    )(string)
+}
+
+fn parse_type_parameters(string: &str) -> IResult<&str, Vec<TypeParameter<'_>>> {
+    map(
+        opt(delimited(tag("<"), many1(parse_type_parameter), tag(">"))),
+        Option::unwrap_or_default,
+    )(string)
+}
+
+fn parse_type_parameter(string: &str) -> IResult<&str, TypeParameter<'_>> {
+    map(
+        tuple((
+            parse_identifier,
+            preceded(tag(":"), opt(parse_field_type_signature)),
+            many0(preceded(tag(":"), parse_field_type_signature)),
+        )),
+        |(name, class_bound, interface_bounds)| TypeParameter {
+            name,
+            class_bound,
+            interface_bounds,
+        },
+    )(string)
+}
+
+fn parse_type_signature(string: &str) -> IResult<&str, TypeSignature<'_>> {
+    alt((
+        map(parse_field_type_signature, TypeSignature::Reference),
+        map(
+            alt((
+                map(tag("Z"), |_| Signature::Boolean),
+                map(tag("B"), |_| Signature::Byte),
+                map(tag("C"), |_| Signature::Char),
+                map(tag("S"), |_| Signature::Short),
+                map(tag("I"), |_| Signature::Int),
+                map(tag("J"), |_| Signature::Long),
+                map(tag("F"), |_| Signature::Float),
+                map(tag("D"), |_| Signature::Double),
+            )),
+            TypeSignature::Primitive,
+        ),
+    ))(string)
+}
+
+fn parse_field_type_signature(string: &str) -> IResult<&str, FieldTypeSignature<'_>> {
+    alt((
+        map(parse_class_type_signature, FieldTypeSignature::Class),
+        parse_type_variable,
+        map(preceded(tag("["), parse_type_signature), |component| {
+            FieldTypeSignature::Array(Box::new(component))
+        }),
+    ))(string)
+}
+
+fn parse_type_variable(string: &str) -> IResult<&str, FieldTypeSignature<'_>> {
+    map(
+        delimited(tag("T"), parse_identifier, tag(";")),
+        FieldTypeSignature::TypeVariable,
+    )(string)
+}
+
+fn parse_class_type_signature(string: &str) -> IResult<&str, ClassTypeSignature<'_>> {
+    map(
+        delimited(
+            tag("L"),
+            pair(
+                // the outermost class includes its package, so slashes are allowed
+                parse_simple_class_type_signature(|c| matches!(c, '.' | ';' | '<' | '>' | ':')),
+                many0(preceded(
+                    tag("."),
+                    parse_simple_class_type_signature(|c| {
+                        matches!(c, '.' | ';' | '<' | '>' | ':' | '/')
+                    }),
+                )),
+            ),
+            tag(";"),
+        ),
+        |(outer, inner)| ClassTypeSignature {
+            classes: std::iter::once(outer).chain(inner).collect(),
+        },
+    )(string)
+}
+
+fn parse_simple_class_type_signature<'a>(
+    terminator: impl Fn(char) -> bool + Copy,
+) -> impl FnMut(&'a str) -> IResult<&'a str, SimpleClassTypeSignature<'a>> {
+    move |string| {
+        map(
+            pair(
+                take_till1(terminator),
+                map(
+                    opt(delimited(tag("<"), many1(parse_type_argument), tag(">"))),
+                    Option::unwrap_or_default,
+                ),
+            ),
+            |(name, type_arguments)| SimpleClassTypeSignature {
+                name,
+                type_arguments,
+            },
+        )(string)
+    }
+}
+
+fn parse_type_argument(string: &str) -> IResult<&str, TypeArgument<'_>> {
+    alt((
+        map(tag("*"), |_| TypeArgument::Any),
+        map(
+            preceded(tag("+"), parse_field_type_signature),
+            TypeArgument::Extends,
+        ),
+        map(
+            preceded(tag("-"), parse_field_type_signature),
+            TypeArgument::Super,
+        ),
+        map(parse_field_type_signature, TypeArgument::Exact),
+    ))(string)
+}
+
+fn parse_identifier(string: &str) -> IResult<&str, &str> {
+    take_till1(|c| matches!(c, '.' | ';' | '[' | '/' | '<' | '>' | ':'))(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_class_signature() {
+        let string = "<T::Ljava/lang/Comparable<-TT;>;>Ljava/util/AbstractList<TT;>;Ljava/util/RandomAccess;";
+        let parsed = GenericSignature::new(string)
+            .expect("couldn't parse")
+            .into_class()
+            .unwrap();
+        let [parameter] = parsed.type_parameters() else {
+            panic!("expected one type parameter")
+        };
+        assert_eq!(parameter.name(), "T");
+        assert_eq!(parameter.class_bound(), None);
+        let [FieldTypeSignature::Class(bound)] = parameter.interface_bounds() else {
+            panic!("expected one interface bound")
+        };
+        assert_eq!(bound.name(), "java/lang/Comparable");
+        assert_eq!(
+            bound.type_arguments(),
+            [TypeArgument::Super(FieldTypeSignature::TypeVariable("T"))]
+        );
+        assert_eq!(parsed.superclass().name(), "java/util/AbstractList");
+        assert_eq!(parsed.interfaces().len(), 1);
+        assert_eq!(parsed.to_string(), string);
+    }
+
+    #[test]
+    fn parse_method_signature() {
+        let string = "<E:Ljava/lang/Exception;>([ILjava/util/List<*>;)V^TE;";
+        let parsed = GenericSignature::new(string)
+            .expect("couldn't parse")
+            .into_method()
+            .unwrap();
+        assert_eq!(parsed.type_parameters()[0].name(), "E");
+        assert_eq!(
+            parsed.parameters()[0],
+            TypeSignature::Reference(FieldTypeSignature::Array(Box::new(
+                TypeSignature::Primitive(Signature::Int)
+            )))
+        );
+        assert_eq!(
+            parsed.return_type(),
+            &TypeSignature::Primitive(Signature::Void)
+        );
+        assert_eq!(parsed.throws(), [FieldTypeSignature::TypeVariable("E")]);
+        assert_eq!(parsed.to_string(), string);
+    }
+
+    #[test]
+    fn parse_inner_class_signature() {
+        let string = "Lcom/example/Outer<TT;>.Inner<+Ljava/lang/Number;>;";
+        let parsed = GenericSignature::new(string).expect("couldn't parse");
+        let Some(FieldTypeSignature::Class(class)) = parsed.clone().into_field() else {
+            panic!("expected a class type")
+        };
+        assert_eq!(class.name(), "com/example/Outer$Inner");
+        assert_eq!(class.classes()[1].name(), "Inner");
+        assert_eq!(parsed.to_string(), string);
+    }
+
+    #[test]
+    fn reject_descriptors_with_missing_terminator() {
+        assert!(GenericSignature::new("Ljava/util/List<TT;>").is_err());
+        assert!(FieldTypeSignature::new("I").is_err());
+    }
+}
@@ -0,0 +1,432 @@
+//! Modules, as described by the `Module`, `ModulePackages` and `ModuleMainClass` attributes in
+//! sections [§4.7.25](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.25)
+//! through [§4.7.27](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.27)
+//! of the JVM specification.
+//!
+//! A module is declared in `module-info.java`, which compiles to a `module-info.class` with no
+//! fields or methods. Module names use dots, such as `java.base`, while package names use the
+//! internal form, such as `java/lang`.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let module = parser.find("module-info").unwrap();
+//! let module = module.as_module().expect("should be a module");
+//! for requires in module.requires() {
+//!     println!("{} requires {}", module.name(), requires.module());
+//! }
+//! ```
+
+use crate::attributes::AttributeKind;
+#[cfg(feature = "serde")]
+use crate::utility::FlagNames;
+use crate::{FQName, HasAttributes, JavaClass};
+use nom::combinator::{complete, flat_map, map_opt};
+use nom::error::{make_error, ErrorKind};
+use nom::multi::count;
+use nom::number::complete::be_u16;
+use nom::sequence::tuple;
+use nom::IResult;
+
+bitflags::bitflags! {
+    /// The flags of a module
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct ModuleFlags: u16 {
+        /// Declared `open`, opening every package for deep reflection
+        const OPEN = 0x0020;
+        /// Not declared in source
+        const SYNTHETIC = 0x1000;
+        /// Implicitly declared
+        const MANDATED = 0x8000;
+    }
+}
+
+bitflags::bitflags! {
+    /// The flags of a module dependency
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct RequiresFlags: u16 {
+        /// Declared `transitive`, so modules depending on this module also depend on the
+        /// required module
+        const TRANSITIVE = 0x0020;
+        /// Declared `static`, so the dependency is mandatory at compile time but optional at
+        /// runtime
+        const STATIC_PHASE = 0x0040;
+        /// Not declared in source
+        const SYNTHETIC = 0x1000;
+        /// Implicitly declared, such as the dependency on `java.base`
+        const MANDATED = 0x8000;
+    }
+}
+
+bitflags::bitflags! {
+    /// The flags of an exported or opened package
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub struct ExportsFlags: u16 {
+        /// Not declared in source
+        const SYNTHETIC = 0x1000;
+        /// Implicitly declared
+        const MANDATED = 0x8000;
+    }
+}
+
+/// A module that a module depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requires<'a> {
+    module: &'a str,
+    flags: RequiresFlags,
+    version: Option<&'a str>,
+}
+
+impl<'a> Requires<'a> {
+    /// The name of the required module
+    pub fn module(&self) -> &'a str {
+        self.module
+    }
+
+    /// The flags of the dependency
+    pub fn flags(&self) -> RequiresFlags {
+        self.flags
+    }
+
+    /// The version of the required module when this module was compiled, if recorded
+    pub fn version(&self) -> Option<&'a str> {
+        self.version
+    }
+
+    /// Checks if the dependency is declared `transitive`
+    pub fn is_transitive(&self) -> bool {
+        self.flags.contains(RequiresFlags::TRANSITIVE)
+    }
+
+    /// Checks if the dependency is declared `static`
+    pub fn is_static(&self) -> bool {
+        self.flags.contains(RequiresFlags::STATIC_PHASE)
+    }
+}
+
+/// A package that a module exports or opens, to every module or only to some
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exports<'a> {
+    package: &'a str,
+    flags: ExportsFlags,
+    to: Vec<&'a str>,
+}
+
+impl<'a> Exports<'a> {
+    /// The name of the package, in internal form
+    pub fn package(&self) -> &'a str {
+        self.package
+    }
+
+    /// The flags of the export
+    pub fn flags(&self) -> ExportsFlags {
+        self.flags
+    }
+
+    /// The modules the package is exported or opened to. Empty if it's exported or opened to
+    /// every module.
+    pub fn to(&self) -> &[&'a str] {
+        &self.to[..]
+    }
+
+    /// Checks if the package is only exported or opened to some modules
+    pub fn is_qualified(&self) -> bool {
+        !self.to.is_empty()
+    }
+}
+
+/// A service that a module provides implementations of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provides<'a> {
+    service: &'a FQName,
+    with: Vec<&'a FQName>,
+}
+
+impl<'a> Provides<'a> {
+    /// The service interface or class
+    pub fn service(&self) -> &'a FQName {
+        self.service
+    }
+
+    /// The implementations of the service
+    pub fn with(&self) -> &[&'a FQName] {
+        &self.with[..]
+    }
+}
+
+/// A module declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaModule<'a> {
+    name: &'a str,
+    flags: ModuleFlags,
+    version: Option<&'a str>,
+    requires: Vec<Requires<'a>>,
+    exports: Vec<Exports<'a>>,
+    opens: Vec<Exports<'a>>,
+    uses: Vec<&'a FQName>,
+    provides: Vec<Provides<'a>>,
+    packages: Vec<&'a str>,
+    main_class: Option<&'a FQName>,
+}
+
+impl<'a> JavaModule<'a> {
+    /// The name of the module
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The flags of the module
+    pub fn flags(&self) -> ModuleFlags {
+        self.flags
+    }
+
+    /// Checks if the module is declared `open`
+    pub fn is_open(&self) -> bool {
+        self.flags.contains(ModuleFlags::OPEN)
+    }
+
+    /// The version of the module, if recorded
+    pub fn version(&self) -> Option<&'a str> {
+        self.version
+    }
+
+    /// The modules this module depends on
+    pub fn requires(&self) -> &[Requires<'a>] {
+        &self.requires[..]
+    }
+
+    /// The packages this module exports
+    pub fn exports(&self) -> &[Exports<'a>] {
+        &self.exports[..]
+    }
+
+    /// The packages this module opens for deep reflection
+    pub fn opens(&self) -> &[Exports<'a>] {
+        &self.opens[..]
+    }
+
+    /// The services this module uses
+    pub fn uses(&self) -> &[&'a FQName] {
+        &self.uses[..]
+    }
+
+    /// The services this module provides implementations of
+    pub fn provides(&self) -> &[Provides<'a>] {
+        &self.provides[..]
+    }
+
+    /// Every package in the module, from the `ModulePackages` attribute. This is usually added
+    /// by the `jar` tool, so it's empty for modules compiled by `javac` alone.
+    pub fn packages(&self) -> &[&'a str] {
+        &self.packages[..]
+    }
+
+    /// The main class of the module, from the `ModuleMainClass` attribute
+    pub fn main_class(&self) -> Option<&'a FQName> {
+        self.main_class
+    }
+}
+
+/// Serialized as the `module` it requires, its `flags` and the `version` it was compiled against
+#[cfg(feature = "serde")]
+impl serde::Serialize for Requires<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut requires = serializer.serialize_struct("Requires", 3)?;
+        requires.serialize_field("module", self.module)?;
+        requires.serialize_field("flags", &FlagNames(self.flags))?;
+        requires.serialize_field("version", &self.version)?;
+        requires.end()
+    }
+}
+
+/// Serialized as the `package`, its `flags` and the modules it's exported or opened `to`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Exports<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut exports = serializer.serialize_struct("Exports", 3)?;
+        exports.serialize_field("package", self.package)?;
+        exports.serialize_field("flags", &FlagNames(self.flags))?;
+        exports.serialize_field("to", &self.to)?;
+        exports.end()
+    }
+}
+
+/// Serialized as the `service` and the implementations it's provided `with`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Provides<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut provides = serializer.serialize_struct("Provides", 2)?;
+        provides.serialize_field("service", self.service)?;
+        provides.serialize_field("with", &self.with)?;
+        provides.end()
+    }
+}
+
+/// Serialized as its `name`, `flags` and `version`, what it `requires`, `exports`, `opens`, `uses`
+/// and `provides`, and its `packages` and `main_class`
+#[cfg(feature = "serde")]
+impl serde::Serialize for JavaModule<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut module = serializer.serialize_struct("JavaModule", 10)?;
+        module.serialize_field("name", self.name)?;
+        module.serialize_field("flags", &FlagNames(self.flags))?;
+        module.serialize_field("version", &self.version)?;
+        module.serialize_field("requires", &self.requires)?;
+        module.serialize_field("exports", &self.exports)?;
+        module.serialize_field("opens", &self.opens)?;
+        module.serialize_field("uses", &self.uses)?;
+        module.serialize_field("provides", &self.provides)?;
+        module.serialize_field("packages", &self.packages)?;
+        module.serialize_field("main_class", &self.main_class)?;
+        module.end()
+    }
+}
+
+/// Module declarations
+impl JavaClass {
+    /// Gets the module declared by this class if it's a `module-info.class`
+    pub fn as_module(&self) -> Option<JavaModule<'_>> {
+        let mut module = None;
+        let mut packages = vec![];
+        let mut main_class = None;
+        for attribute in self.attributes() {
+            match attribute.kind() {
+                AttributeKind::Module(declared) => module = Some(declared.clone()),
+                AttributeKind::ModulePackages(declared) => packages = declared.clone(),
+                AttributeKind::ModuleMainClass(declared) => main_class = Some(*declared),
+                _ => {}
+            }
+        }
+        module.map(|module| JavaModule {
+            packages,
+            main_class,
+            ..module
+        })
+    }
+}
+
+pub(crate) fn parse_module<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], JavaModule<'a>> {
+    let pool = class.constant_pool();
+    let optional_utf8 = |index: u16| match index {
+        0 => Some(None),
+        index => pool.get_utf8(index).map(Some),
+    };
+    let exports = || {
+        flat_map(be_u16, move |exports_count| {
+            count(
+                map_opt(
+                    tuple((
+                        be_u16,
+                        be_u16,
+                        flat_map(be_u16, |to_count| count(be_u16, to_count as usize)),
+                    )),
+                    move |(package_index, flags, to)| {
+                        Some(Exports {
+                            package: pool.get_package_name(package_index)?,
+                            flags: ExportsFlags::from_bits_retain(flags),
+                            to: to
+                                .into_iter()
+                                .map(|index| pool.get_module_name(index))
+                                .collect::<Option<_>>()?,
+                        })
+                    },
+                ),
+                exports_count as usize,
+            )
+        })
+    };
+
+    let (bytes, (name_index, flags, version_index)) = tuple((be_u16, be_u16, be_u16))(bytes)?;
+    let (bytes, requires) = flat_map(be_u16, |requires_count| {
+        count(
+            map_opt(
+                tuple((be_u16, be_u16, be_u16)),
+                |(module_index, flags, version_index)| {
+                    Some(Requires {
+                        module: pool.get_module_name(module_index)?,
+                        flags: RequiresFlags::from_bits_retain(flags),
+                        version: optional_utf8(version_index)?,
+                    })
+                },
+            ),
+            requires_count as usize,
+        )
+    })(bytes)?;
+    let (bytes, exports_table) = exports()(bytes)?;
+    let (bytes, opens) = exports()(bytes)?;
+    let (bytes, uses) = parse_class_table(bytes, class)?;
+    let (bytes, provides) = complete(flat_map(be_u16, |provides_count| {
+        count(
+            map_opt(
+                tuple((be_u16, |b| parse_class_table(b, class))),
+                |(service_index, with)| {
+                    Some(Provides {
+                        service: pool.get_class_name(service_index)?,
+                        with,
+                    })
+                },
+            ),
+            provides_count as usize,
+        )
+    }))(bytes)?;
+    let (Some(name), Some(version)) = (
+        pool.get_module_name(name_index),
+        optional_utf8(version_index),
+    ) else {
+        return Err(nom::Err::Failure(make_error(bytes, ErrorKind::Verify)));
+    };
+    Ok((
+        bytes,
+        JavaModule {
+            name,
+            flags: ModuleFlags::from_bits_retain(flags),
+            version,
+            requires,
+            exports: exports_table,
+            opens,
+            uses,
+            provides,
+            packages: vec![],
+            main_class: None,
+        },
+    ))
+}
+
+pub(crate) fn parse_module_packages<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<&'a str>> {
+    let pool = class.constant_pool();
+    complete(flat_map(be_u16, |package_count| {
+        count(
+            map_opt(be_u16, |index| pool.get_package_name(index)),
+            package_count as usize,
+        )
+    }))(bytes)
+}
+
+/// Parses a table of class constants, preceded by its length
+pub(crate) fn parse_class_table<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<&'a FQName>> {
+    let pool = class.constant_pool();
+    flat_map(be_u16, |length| {
+        count(
+            map_opt(be_u16, |index| pool.get_class_name(index)),
+            length as usize,
+        )
+    })(bytes)
+}
@@ -0,0 +1,392 @@
+//! Records, as described by the `Record` attribute in section [§4.7.30](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.30)
+//! of the JVM specification.
+
+use crate::annotations::Annotation;
+use crate::attributes::{Attribute, AttributeKind};
+use crate::constant_pool::values::{FieldRef, NameAndType};
+use crate::generics::FieldTypeSignature;
+use crate::utility::match_as;
+use crate::{ConstantPoolInfo, HasAttributes, JavaClass, Method, Signature};
+use nom::bytes::complete::take;
+use nom::combinator::{flat_map, map_opt};
+use nom::multi::count;
+use nom::number::complete::{be_u16, be_u32};
+use nom::sequence::tuple;
+use nom::IResult;
+use std::fmt::{Debug, Formatter};
+
+/// A component of a record class
+#[derive(Clone)]
+pub struct RecordComponent<'a> {
+    class: &'a JavaClass,
+    name: &'a str,
+    descriptor: Signature<'a>,
+    attributes: Vec<(u16, &'a [u8])>,
+}
+
+impl<'a> RecordComponent<'a> {
+    /// The name of the component
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The field descriptor of the component
+    pub fn descriptor(&self) -> &Signature<'a> {
+        &self.descriptor
+    }
+
+    /// The generic signature of the component, if its type uses generics
+    pub fn signature(&self) -> Option<FieldTypeSignature<'a>> {
+        self.component_attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::Signature(signature) => signature.clone().into_field(),
+                _ => None,
+            })
+    }
+
+    /// The runtime visible annotations present on the component
+    pub fn annotations(&self) -> Vec<Annotation<'a>> {
+        self.component_attributes()
+            .filter_map(|attribute| match attribute.kind() {
+                AttributeKind::RuntimeVisibleAnnotations(annotations) => Some(annotations.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn component_attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.attributes
+            .iter()
+            .map(|&(name_index, info)| self.class.create_attribute_or_unknown(name_index, info))
+    }
+}
+
+impl HasAttributes for RecordComponent<'_> {
+    type Iter<'a>
+        = <Vec<Attribute<'a>> as IntoIterator>::IntoIter
+    where
+        Self: 'a;
+
+    fn attributes<'a>(&'a self) -> Self::Iter<'a> {
+        self.component_attributes().collect::<Vec<_>>().into_iter()
+    }
+}
+
+/// Serialized as its `name`, `descriptor` and `attributes`
+#[cfg(feature = "serde")]
+impl serde::Serialize for RecordComponent<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut component = serializer.serialize_struct("RecordComponent", 3)?;
+        component.serialize_field("name", self.name)?;
+        component.serialize_field("descriptor", &self.descriptor)?;
+        component.serialize_field("attributes", &crate::utility::AttributeList(self))?;
+        component.end()
+    }
+}
+
+impl PartialEq for RecordComponent<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.descriptor == other.descriptor
+            && self.attributes == other.attributes
+    }
+}
+
+impl Debug for RecordComponent<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordComponent")
+            .field("name", &self.name)
+            .field("descriptor", &self.descriptor)
+            .field("attributes", &self.attributes().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+pub(crate) fn parse_record<'a>(
+    bytes: &'a [u8],
+    class: &'a JavaClass,
+) -> IResult<&'a [u8], Vec<RecordComponent<'a>>> {
+    flat_map(be_u16, |components_count| {
+        count(
+            map_opt(
+                tuple((
+                    be_u16,
+                    be_u16,
+                    flat_map(be_u16, |attributes_count| {
+                        count(
+                            tuple((be_u16, flat_map(be_u32, take))),
+                            attributes_count as usize,
+                        )
+                    }),
+                )),
+                |(name_index, descriptor_index, attributes)| {
+                    Some(RecordComponent {
+                        class,
+                        name: class.get_string(name_index)?,
+                        descriptor: class.get_descriptor(descriptor_index)?,
+                        attributes,
+                    })
+                },
+            ),
+            components_count as usize,
+        )
+    })(bytes)
+}
+
+/// How the canonical constructor of a record was declared
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CanonicalConstructorKind {
+    /// Generated by the compiler
+    Implicit,
+    /// Declared in the compact form, without a parameter list. The component fields are still
+    /// assigned by the compiler at the end of the constructor.
+    Compact,
+    /// Declared with an explicit parameter list
+    Explicit,
+}
+
+/// A place where a record deviates from the members the compiler would have generated for it
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum RecordDeviation<'a> {
+    /// No constructor matches the order and types of the record components
+    MissingCanonicalConstructor,
+    /// The canonical constructor was declared by the user
+    DeclaredCanonicalConstructor(CanonicalConstructorKind),
+    /// No accessor method exists for the named component
+    MissingAccessor(&'a str),
+    /// The accessor for the named component was declared by the user, and does more than
+    /// return the component field
+    DeclaredAccessor(&'a str),
+}
+
+/// The members a record class has because it is a record: its canonical constructor and
+/// component accessors.
+///
+/// Whether a member was declared by the user is determined from its bytecode, as the class file
+/// does not record it. A user declared member whose body is identical to what the compiler would
+/// have generated is indistinguishable from a generated one.
+#[derive(Debug)]
+pub struct RecordMembers<'a> {
+    components: Vec<RecordComponent<'a>>,
+    canonical_constructor: Option<(Method<'a>, CanonicalConstructorKind)>,
+    accessors: Vec<Option<(Method<'a>, bool)>>,
+}
+
+impl<'a> RecordMembers<'a> {
+    pub(crate) fn new(class: &'a JavaClass, components: Vec<RecordComponent<'a>>) -> Self {
+        let mut canonical_constructor = None;
+        let mut accessors: Vec<Option<(Method<'a>, bool)>> =
+            components.iter().map(|_| None).collect();
+
+        let canonical_args = components
+            .iter()
+            .map(|component| component.descriptor.clone())
+            .collect::<Vec<_>>();
+        for method in class.methods() {
+            match (method.name(), method.signature()) {
+                ("<init>", Signature::Method { args, ret_type })
+                    if **ret_type == Signature::Void && args[..] == canonical_args[..] =>
+                {
+                    let kind = method_code(&method)
+                        .map(|code| constructor_kind(class, &components, code))
+                        .unwrap_or(CanonicalConstructorKind::Explicit);
+                    canonical_constructor = Some((method, kind));
+                }
+                (name, Signature::Method { args, ret_type }) if args.is_empty() => {
+                    let Some(index) = components.iter().position(|component| {
+                        component.name == name && component.descriptor == **ret_type
+                    }) else {
+                        continue;
+                    };
+                    let generated = method_code(&method)
+                        .map(|code| is_generated_accessor(class, &components[index], code))
+                        .unwrap_or(false);
+                    accessors[index] = Some((method, generated));
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            components,
+            canonical_constructor,
+            accessors,
+        }
+    }
+
+    /// The components of the record, in declaration order
+    pub fn components(&self) -> &[RecordComponent<'a>] {
+        &self.components[..]
+    }
+
+    /// The canonical constructor, whose parameters match the components of the record
+    pub fn canonical_constructor(&self) -> Option<&Method<'a>> {
+        self.canonical_constructor
+            .as_ref()
+            .map(|(method, _)| method)
+    }
+
+    /// How the canonical constructor was declared
+    pub fn canonical_constructor_kind(&self) -> Option<CanonicalConstructorKind> {
+        self.canonical_constructor.as_ref().map(|&(_, kind)| kind)
+    }
+
+    /// Checks if the record declares a compact canonical constructor
+    pub fn has_compact_constructor(&self) -> bool {
+        self.canonical_constructor_kind() == Some(CanonicalConstructorKind::Compact)
+    }
+
+    /// Gets the accessor method of a component by the component's name
+    pub fn accessor(&self, component: &str) -> Option<&Method<'a>> {
+        self.components
+            .iter()
+            .position(|c| c.name == component)
+            .and_then(|index| self.accessors[index].as_ref())
+            .map(|(method, _)| method)
+    }
+
+    /// Gets every component alongside its accessor method
+    pub fn accessors(&self) -> impl Iterator<Item = (&RecordComponent<'a>, Option<&Method<'a>>)> {
+        self.components.iter().zip(
+            self.accessors
+                .iter()
+                .map(|accessor| accessor.as_ref().map(|(method, _)| method)),
+        )
+    }
+
+    /// Gets every place the record deviates from the members the compiler would have generated
+    pub fn deviations(&self) -> Vec<RecordDeviation<'a>> {
+        let mut deviations = vec![];
+        match self.canonical_constructor_kind() {
+            None => deviations.push(RecordDeviation::MissingCanonicalConstructor),
+            Some(CanonicalConstructorKind::Implicit) => {}
+            Some(kind) => deviations.push(RecordDeviation::DeclaredCanonicalConstructor(kind)),
+        }
+        for (component, accessor) in self.components.iter().zip(&self.accessors) {
+            match accessor {
+                None => deviations.push(RecordDeviation::MissingAccessor(component.name)),
+                Some((_, false)) => {
+                    deviations.push(RecordDeviation::DeclaredAccessor(component.name))
+                }
+                Some((_, true)) => {}
+            }
+        }
+        deviations
+    }
+}
+
+const ALOAD_0: u8 = 0x2a;
+const INVOKESPECIAL: u8 = 0xb7;
+const GETFIELD: u8 = 0xb4;
+const PUTFIELD: u8 = 0xb5;
+const RETURN: u8 = 0xb1;
+
+fn method_code<'m>(method: &'m Method) -> Option<&'m [u8]> {
+    method
+        .attributes()
+        .find_map(|attribute| match attribute.kind() {
+            AttributeKind::Code(code) => Some(code.code()),
+            _ => None,
+        })
+}
+
+/// The offset of the typed variant of an instruction (such as `iload` or `ireturn`) for a
+/// descriptor, relative to the `int` variant.
+fn type_offset(descriptor: &Signature) -> u8 {
+    match descriptor {
+        Signature::Long => 1,
+        Signature::Float => 2,
+        Signature::Double => 3,
+        Signature::FullyQualifiedClass(_) | Signature::Array(_) => 4,
+        _ => 0,
+    }
+}
+
+/// The instruction that loads a local variable of the given type from the given slot
+fn load_instruction(descriptor: &Signature, slot: u16) -> Vec<u8> {
+    let offset = type_offset(descriptor);
+    match slot {
+        // iload_0 and friends
+        0..=3 => vec![0x1a + offset * 4 + slot as u8],
+        // iload and friends
+        _ => vec![0x15 + offset, slot as u8],
+    }
+}
+
+/// Resolves the name of the field referenced by a field ref in the constant pool
+fn field_ref_name(class: &JavaClass, high: u8, low: u8) -> Option<&str> {
+    let index = u16::from_be_bytes([high, low]);
+    let FieldRef {
+        name_and_type_index,
+        ..
+    } = class
+        .get_at_index(index)
+        .and_then(|info| match_as!(field; ConstantPoolInfo::FieldRef(field) = info))?;
+    let NameAndType { name_index, .. } = class
+        .get_at_index(*name_and_type_index)
+        .and_then(|info| match_as!(nt; ConstantPoolInfo::NameAndType(nt) = info))?;
+    class.get_string(*name_index)
+}
+
+/// Checks that an accessor only returns its component field, like a generated accessor does
+fn is_generated_accessor(class: &JavaClass, component: &RecordComponent, code: &[u8]) -> bool {
+    match code {
+        &[ALOAD_0, GETFIELD, high, low, ret] => {
+            ret == 0xac + type_offset(&component.descriptor)
+                && field_ref_name(class, high, low) == Some(component.name)
+        }
+        _ => false,
+    }
+}
+
+/// Generated and compact canonical constructors end by assigning every component field from
+/// its parameter, in order. Returns the length of that sequence if the code ends with it.
+fn component_assignments_len(
+    class: &JavaClass,
+    components: &[RecordComponent],
+    code: &[u8],
+) -> Option<usize> {
+    let mut slot = 1;
+    let loads = components
+        .iter()
+        .map(|component| {
+            let load = load_instruction(&component.descriptor, slot);
+            slot += component.descriptor.slot_width() as u16;
+            load
+        })
+        .collect::<Vec<_>>();
+    // aload_0, the load, and putfield with its index, followed by a single return
+    let len = loads.iter().map(|load| load.len() + 4).sum::<usize>() + 1;
+
+    let mut tail = code.get(code.len().checked_sub(len)?..)?;
+    for (component, load) in components.iter().zip(&loads) {
+        tail = tail.strip_prefix(&[ALOAD_0])?.strip_prefix(&load[..])?;
+        match tail {
+            [PUTFIELD, high, low, rest @ ..]
+                if field_ref_name(class, *high, *low) == Some(component.name) =>
+            {
+                tail = rest
+            }
+            _ => return None,
+        }
+    }
+    (tail == [RETURN]).then_some(len)
+}
+
+fn constructor_kind(
+    class: &JavaClass,
+    components: &[RecordComponent],
+    code: &[u8],
+) -> CanonicalConstructorKind {
+    match component_assignments_len(class, components, code) {
+        // aload_0, invokespecial java/lang/Record.<init>
+        Some(len) if code.len() == len + 4 && code[..2] == [ALOAD_0, INVOKESPECIAL] => {
+            CanonicalConstructorKind::Implicit
+        }
+        Some(_) => CanonicalConstructorKind::Compact,
+        None => CanonicalConstructorKind::Explicit,
+    }
+}
@@ -5,7 +5,8 @@ use nom::combinator::{eof, map};
 use nom::multi::many0;
 use nom::sequence::{delimited, preceded, tuple};
 use nom::IResult;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 
 /// A signature
 #[derive(Debug, PartialEq, Clone)]
@@ -34,6 +35,28 @@ pub enum Signature<'a> {
 }
 
 impl<'a> Signature<'a> {
+    /// Creates the signature of a class, such as `Signature::object("java/lang/Object")`. The name
+    /// must be in internal form.
+    pub fn object(name: &'a str) -> Self {
+        Signature::FullyQualifiedClass(name)
+    }
+
+    /// Creates the signature of an array of some type
+    pub fn array_of(component: Signature<'a>) -> Self {
+        Signature::Array(Box::new(component))
+    }
+
+    /// Creates the signature of a method from its parameter types and return type
+    pub fn method<I: IntoIterator<Item = Signature<'a>>>(
+        parameters: I,
+        ret_type: Signature<'a>,
+    ) -> Self {
+        Signature::Method {
+            args: parameters.into_iter().collect(),
+            ret_type: Box::new(ret_type),
+        }
+    }
+
     /// emits this signature as JNI
     pub fn jni(&self) -> String {
         match self {
@@ -62,6 +85,72 @@ impl<'a> Signature<'a> {
         }
     }
 
+    /// The parameter types of a method signature. Empty if this isn't a method signature.
+    pub fn parameters(&self) -> &[Signature<'a>] {
+        match self {
+            Signature::Method { args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    /// The return type of a method signature, or `None` if this isn't a method signature
+    pub fn return_type(&self) -> Option<&Signature<'a>> {
+        match self {
+            Signature::Method { ret_type, .. } => Some(ret_type),
+            _ => None,
+        }
+    }
+
+    /// The number of parameters of a method signature
+    pub fn parameter_count(&self) -> usize {
+        self.parameters().len()
+    }
+
+    /// The number of local variable slots taken by the parameters of a method signature, not
+    /// including `this`. Longs and doubles take two slots.
+    pub fn argument_slot_count(&self) -> usize {
+        self.parameters().iter().map(Signature::slot_width).sum()
+    }
+
+    /// The number of local variable or operand stack slots a value of this type takes. Longs and
+    /// doubles take two slots, `void` and methods take none, and every other type takes one.
+    pub fn slot_width(&self) -> usize {
+        match self {
+            Signature::Long | Signature::Double => 2,
+            Signature::Void | Signature::Method { .. } => 0,
+            _ => 1,
+        }
+    }
+
+    /// Renders this signature in java source syntax, such as `java.lang.Object` or `int[]`. Method
+    /// signatures are rendered as a declaration without a name, such as
+    /// `java.lang.String (int, boolean)`.
+    pub fn to_java(&self) -> String {
+        match self {
+            Signature::FullyQualifiedClass(fqc) => fqc.replace('/', "."),
+            Signature::Array(array) => format!("{}[]", array.to_java()),
+            Signature::Method { .. } => self.to_java_method("").unwrap_or_default(),
+            primitive => primitive.to_string(),
+        }
+    }
+
+    /// Renders a method signature as a java declaration with the given name, such as
+    /// `java.lang.String foo(int, boolean)`. Returns `None` if this isn't a method signature.
+    pub fn to_java_method(&self, name: &str) -> Option<String> {
+        let Signature::Method { args, ret_type } = self else {
+            return None;
+        };
+        Some(format!(
+            "{} {}({})",
+            ret_type.to_java(),
+            name,
+            args.iter()
+                .map(Signature::to_java)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+
     /// Creates a new signature by parsing a string.
     pub fn new(str: &'a str) -> Result<Self, nom::Err<nom::error::Error<String>>> {
         let (bytes, parsed) =
@@ -69,6 +158,11 @@ impl<'a> Signature<'a> {
         eof(bytes).map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())?;
         Ok(parsed)
     }
+
+    /// Turns this signature into an owned version.
+    pub fn to_signature_buf(&self) -> SignatureBuf {
+        SignatureBuf { buf: self.jni() }
+    }
 }
 
 impl Display for Signature<'_> {
@@ -122,7 +216,90 @@ impl Display for Signature<'_> {
     }
 }
 
-fn parse_signature(string: &str) -> IResult<&str, Signature> {
+impl PartialEq<SignatureBuf> for Signature<'_> {
+    fn eq(&self, other: &SignatureBuf) -> bool {
+        *self == other.as_signature()
+    }
+}
+
+impl From<&Signature<'_>> for SignatureBuf {
+    fn from(value: &Signature<'_>) -> Self {
+        value.to_signature_buf()
+    }
+}
+
+impl From<Signature<'_>> for SignatureBuf {
+    fn from(value: Signature<'_>) -> Self {
+        value.to_signature_buf()
+    }
+}
+
+/// An owned version of a signature, stored as its descriptor
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub struct SignatureBuf {
+    buf: String,
+}
+
+impl SignatureBuf {
+    /// Creates a new owned signature by parsing a string.
+    pub fn new<S: Into<String>>(
+        descriptor: S,
+    ) -> Result<Self, nom::Err<nom::error::Error<String>>> {
+        let buf = descriptor.into();
+        Signature::new(&buf)?;
+        Ok(Self { buf })
+    }
+
+    /// Gets a signature borrowing from this one
+    pub fn as_signature(&self) -> Signature<'_> {
+        Signature::new(&self.buf).expect("descriptor was validated on creation")
+    }
+
+    /// Gets the descriptor of the signature
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl FromStr for SignatureBuf {
+    type Err = nom::Err<nom::error::Error<String>>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl<'a> From<&'a SignatureBuf> for Signature<'a> {
+    fn from(value: &'a SignatureBuf) -> Self {
+        value.as_signature()
+    }
+}
+
+impl PartialEq<Signature<'_>> for SignatureBuf {
+    fn eq(&self, other: &Signature<'_>) -> bool {
+        self.as_signature() == *other
+    }
+}
+
+impl AsRef<str> for SignatureBuf {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl Debug for SignatureBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.buf, f)
+    }
+}
+
+impl Display for SignatureBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.as_signature(), f)
+    }
+}
+
+fn parse_signature(string: &str) -> IResult<&str, Signature<'_>> {
     nom::branch::alt((
         map(tag("Z"), |_| Signature::Boolean),
         map(tag("B"), |_| Signature::Byte),
@@ -135,7 +312,7 @@ fn parse_signature(string: &str) -> IResult<&str, Signature> {
         map(tag("V"), |_| Signature::Void),
         map(
             delimited(tag("L"), take_till(|c| c == ';'), tag(";")),
-            |chars| Signature::FullyQualifiedClass(chars),
+            Signature::FullyQualifiedClass,
         ),
         map(preceded(tag("["), parse_signature), |s| {
             Signature::Array(Box::new(s))
@@ -153,6 +330,22 @@ fn parse_signature(string: &str) -> IResult<&str, Signature> {
     ))(string)
 }
 
+/// Serialized as its descriptor, such as `[Ljava/lang/String;`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signature<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.jni())
+    }
+}
+
+/// Serialized as its descriptor, such as `[Ljava/lang/String;`
+#[cfg(feature = "serde")]
+impl serde::Serialize for SignatureBuf {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +363,78 @@ mod tests {
         assert_eq!(parsed.jni(), jni);
         assert_eq!(parsed.to_string(), "java/lang/Object (boolean, int)")
     }
+
+    #[test]
+    fn build_signature() {
+        let built = Signature::method(
+            [
+                Signature::array_of(Signature::object("java/lang/String")),
+                Signature::Long,
+            ],
+            Signature::Void,
+        );
+        assert_eq!(built.jni(), "([Ljava/lang/String;J)V");
+        assert_eq!(Signature::new("([Ljava/lang/String;J)V").unwrap(), built);
+        assert_eq!(Signature::method([], Signature::Int).jni(), "()I");
+    }
+
+    #[test]
+    fn method_descriptor_helpers() {
+        let method = Signature::new("(IJLjava/lang/String;D)Z").unwrap();
+        assert_eq!(method.parameter_count(), 4);
+        assert_eq!(method.argument_slot_count(), 6);
+        assert_eq!(method.parameters()[1], Signature::Long);
+        assert_eq!(method.return_type(), Some(&Signature::Boolean));
+
+        assert!(Signature::Int.parameters().is_empty());
+        assert_eq!(Signature::Int.return_type(), None);
+        assert_eq!(Signature::Void.slot_width(), 0);
+    }
+
+    #[test]
+    fn java_syntax() {
+        let parsed = Signature::new("(I[[ZLjava/lang/String;)Ljava/lang/Object;").unwrap();
+        assert_eq!(
+            parsed.to_java_method("foo").unwrap(),
+            "java.lang.Object foo(int, boolean[][], java.lang.String)"
+        );
+        assert_eq!(
+            parsed.to_java(),
+            "java.lang.Object (int, boolean[][], java.lang.String)"
+        );
+        assert_eq!(Signature::new("[I").unwrap().to_java(), "int[]");
+        assert_eq!(
+            Signature::new("()V")
+                .unwrap()
+                .to_java_method("run")
+                .unwrap(),
+            "void run()"
+        );
+        assert_eq!(Signature::Int.to_java_method("run"), None);
+    }
+
+    #[test]
+    fn owned_signature() {
+        let owned = {
+            let jni = String::from("([Ljava/lang/String;)V");
+            Signature::new(&jni).unwrap().to_signature_buf()
+        };
+        assert_eq!(owned.as_str(), "([Ljava/lang/String;)V");
+        assert_eq!(
+            owned.as_signature(),
+            Signature::Method {
+                args: vec![Signature::Array(Box::new(Signature::FullyQualifiedClass(
+                    "java/lang/String"
+                )))]
+                .into_boxed_slice(),
+                ret_type: Box::new(Signature::Void)
+            }
+        );
+        assert_eq!(
+            "([Ljava/lang/String;)V".parse::<SignatureBuf>().unwrap(),
+            owned
+        );
+        assert_eq!(Signature::from(&owned), owned);
+        assert!("Ljava/lang/String".parse::<SignatureBuf>().is_err());
+    }
 }
@@ -0,0 +1,434 @@
+//! Source maps, as defined by [JSR-45](https://jcp.org/en/jsr/detail?id=45), which map the lines of
+//! a class back to the sources of languages other than java.
+//!
+//! Compilers for Kotlin, Groovy and JSP embed a source map in the `SourceDebugExtension` attribute
+//! of a class. It's made of strata, each describing the class in terms of a source language.
+//!
+//! ```text
+//! SMAP
+//! Greeter.kt
+//! Kotlin
+//! *S Kotlin
+//! *F
+//! + 1 Greeter.kt
+//! com/example/Greeter.kt
+//! *L
+//! 1#1,10:1
+//! *E
+//! ```
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::JavaClassParser;
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/GreeterKt").unwrap();
+//! if let Some(smap) = class.smap().unwrap() {
+//!     if let Some((file, line)) = smap.map_line(12) {
+//!         println!("line 12 is {}:{}", file.name(), line);
+//!     }
+//! }
+//! ```
+
+use crate::attributes::AttributeKind;
+use crate::{HasAttributes, JavaClass};
+
+/// An error that occurred while parsing a source map
+#[derive(Debug, thiserror::Error)]
+#[error("invalid source map on line {line}: {reason}")]
+pub struct SmapError {
+    line: usize,
+    reason: String,
+}
+
+impl SmapError {
+    fn new<S: AsRef<str>>(line: usize, reason: S) -> Self {
+        Self {
+            line,
+            reason: reason.as_ref().to_string(),
+        }
+    }
+
+    /// The line number the error occurred on, starting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// A source file of a stratum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmapFile {
+    id: u32,
+    name: String,
+    path: Option<String>,
+}
+
+impl SmapFile {
+    /// The id of the file, used by line mappings
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The name of the file
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path of the file relative to a source root, if given
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+}
+
+/// Maps a range of input source lines to a range of output lines in the class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMapping {
+    input_start: u32,
+    file_id: u32,
+    repeat_count: u32,
+    output_start: u32,
+    output_increment: u32,
+}
+
+impl LineMapping {
+    /// The first input line
+    pub fn input_start(&self) -> u32 {
+        self.input_start
+    }
+
+    /// The id of the file containing the input lines
+    pub fn file_id(&self) -> u32 {
+        self.file_id
+    }
+
+    /// The number of input lines mapped
+    pub fn repeat_count(&self) -> u32 {
+        self.repeat_count
+    }
+
+    /// The first output line
+    pub fn output_start(&self) -> u32 {
+        self.output_start
+    }
+
+    /// The number of output lines each input line maps to
+    pub fn output_increment(&self) -> u32 {
+        self.output_increment
+    }
+
+    /// Maps an output line back to its input line, if it's within this mapping
+    pub fn input_line(&self, output_line: u32) -> Option<u32> {
+        let offset = output_line.checked_sub(self.output_start)?;
+        if self.output_increment == 0 {
+            return (offset == 0 && self.repeat_count > 0).then_some(self.input_start);
+        }
+        let input_offset = offset / self.output_increment;
+        (input_offset < self.repeat_count).then_some(self.input_start + input_offset)
+    }
+}
+
+/// A description of the class in terms of a single source language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stratum {
+    id: String,
+    files: Vec<SmapFile>,
+    lines: Vec<LineMapping>,
+}
+
+impl Stratum {
+    /// The id of the stratum, usually the name of the language such as `Kotlin`
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The source files of the stratum
+    pub fn files(&self) -> &[SmapFile] {
+        &self.files[..]
+    }
+
+    /// Gets a source file by its id
+    pub fn file(&self, id: u32) -> Option<&SmapFile> {
+        self.files.iter().find(|file| file.id == id)
+    }
+
+    /// The line mappings of the stratum
+    pub fn lines(&self) -> &[LineMapping] {
+        &self.lines[..]
+    }
+
+    /// Maps a line of the class, as found in its `LineNumberTable`, back to a source file and
+    /// line
+    pub fn map_line(&self, output_line: u32) -> Option<(&SmapFile, u32)> {
+        self.lines.iter().find_map(|mapping| {
+            let line = mapping.input_line(output_line)?;
+            Some((self.file(mapping.file_id)?, line))
+        })
+    }
+}
+
+/// A source map, decoded from the `SourceDebugExtension` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Smap {
+    output_file: String,
+    default_stratum: String,
+    strata: Vec<Stratum>,
+}
+
+impl Smap {
+    /// Parses a source map
+    pub fn parse(smap: &str) -> Result<Self, SmapError> {
+        let mut lines = smap
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line));
+        let mut next = |expected: &str| {
+            lines
+                .next()
+                .ok_or_else(|| SmapError::new(0, format!("expected {expected}")))
+        };
+        let (line_number, header) = next("header")?;
+        if header.trim() != "SMAP" {
+            return Err(SmapError::new(line_number, "expected `SMAP` header"));
+        }
+        let (_, output_file) = next("output file name")?;
+        let (_, default_stratum) = next("default stratum")?;
+
+        let mut strata: Vec<Stratum> = vec![];
+        let mut section = Section::None;
+        let mut file_id = 0;
+        let mut pending_file: Option<(u32, String)> = None;
+        for (line_number, line) in lines {
+            let error = |reason: &str| SmapError::new(line_number, reason);
+            let line = line.trim_end();
+            if let Some((id, name)) = pending_file.take() {
+                let stratum = strata.last_mut().expect("files are within a stratum");
+                stratum.files.push(SmapFile {
+                    id,
+                    name,
+                    path: Some(line.to_string()),
+                });
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix('*') {
+                section = match rest.split_whitespace().collect::<Vec<_>>()[..] {
+                    ["S", id] => {
+                        strata.push(Stratum {
+                            id: id.to_string(),
+                            files: vec![],
+                            lines: vec![],
+                        });
+                        file_id = 0;
+                        Section::None
+                    }
+                    ["F"] | ["L"] if strata.is_empty() => {
+                        return Err(error("section outside of a stratum"));
+                    }
+                    ["F"] => Section::Files,
+                    ["L"] => Section::Lines,
+                    // kotlin ends each stratum, rather than the whole map
+                    ["E"] => Section::None,
+                    // vendor and future sections are ignored
+                    _ => Section::Ignored,
+                };
+                continue;
+            }
+            match section {
+                Section::Files => {
+                    let (has_path, info) = match line.strip_prefix('+') {
+                        Some(info) => (true, info),
+                        None => (false, line),
+                    };
+                    let (id, name) = info
+                        .trim()
+                        .split_once(' ')
+                        .ok_or_else(|| error("expected a file id and name"))?;
+                    let id = id.parse().map_err(|_| error("invalid file id"))?;
+                    if has_path {
+                        pending_file = Some((id, name.trim().to_string()));
+                    } else {
+                        strata.last_mut().unwrap().files.push(SmapFile {
+                            id,
+                            name: name.trim().to_string(),
+                            path: None,
+                        });
+                    }
+                }
+                Section::Lines => {
+                    let mapping = parse_line_mapping(line, &mut file_id)
+                        .ok_or_else(|| error("invalid line mapping"))?;
+                    strata.last_mut().unwrap().lines.push(mapping);
+                }
+                Section::None if !line.is_empty() => {
+                    return Err(error("expected a section"));
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            output_file: output_file.trim().to_string(),
+            default_stratum: default_stratum.trim().to_string(),
+            strata,
+        })
+    }
+
+    /// The name of the file the class was compiled to, such as `Greeter.kt`
+    pub fn output_file(&self) -> &str {
+        &self.output_file
+    }
+
+    /// The id of the stratum debuggers should use by default
+    pub fn default_stratum_id(&self) -> &str {
+        &self.default_stratum
+    }
+
+    /// Every stratum in the source map
+    pub fn strata(&self) -> &[Stratum] {
+        &self.strata[..]
+    }
+
+    /// Gets a stratum by its id
+    pub fn stratum(&self, id: &str) -> Option<&Stratum> {
+        self.strata.iter().find(|stratum| stratum.id == id)
+    }
+
+    /// The stratum debuggers should use by default
+    pub fn default_stratum(&self) -> Option<&Stratum> {
+        self.stratum(&self.default_stratum)
+    }
+
+    /// Maps a line of the class back to a source file and line, using the default stratum
+    pub fn map_line(&self, output_line: u32) -> Option<(&SmapFile, u32)> {
+        self.default_stratum()?.map_line(output_line)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    None,
+    Files,
+    Lines,
+    Ignored,
+}
+
+/// Parses `InputStartLine[#LineFileID][,RepeatCount]:OutputStartLine[,OutputLineIncrement]`. The
+/// file id carries over from the previous mapping when omitted.
+fn parse_line_mapping(line: &str, file_id: &mut u32) -> Option<LineMapping> {
+    let (input, output) = line.trim().split_once(':')?;
+    let (input, repeat_count) = match input.split_once(',') {
+        Some((input, repeat_count)) => (input, repeat_count.parse().ok()?),
+        None => (input, 1),
+    };
+    let input_start = match input.split_once('#') {
+        Some((input, id)) => {
+            *file_id = id.parse().ok()?;
+            input.parse().ok()?
+        }
+        None => input.parse().ok()?,
+    };
+    let (output_start, output_increment) = match output.split_once(',') {
+        Some((start, increment)) => (start.parse().ok()?, increment.parse().ok()?),
+        None => (output.parse().ok()?, 1),
+    };
+    Some(LineMapping {
+        input_start,
+        file_id: *file_id,
+        repeat_count,
+        output_start,
+        output_increment,
+    })
+}
+
+/// Source maps
+impl JavaClass {
+    /// The contents of the `SourceDebugExtension` attribute
+    pub fn source_debug_extension(&self) -> Option<String> {
+        self.attributes()
+            .find_map(|attribute| match attribute.kind() {
+                AttributeKind::SourceDebugExtension(extension) => Some(extension.clone()),
+                _ => None,
+            })
+    }
+
+    /// Decodes the source map in the `SourceDebugExtension` attribute. Returns `None` if the
+    /// class has no such attribute.
+    ///
+    /// # Error
+    /// Will return an error if the attribute isn't a valid source map
+    pub fn smap(&self) -> Result<Option<Smap>, SmapError> {
+        self.source_debug_extension()
+            .map(|extension| Smap::parse(&extension))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smap;
+
+    const KOTLIN: &str = "SMAP
+Greeter.kt
+Kotlin
+*S Kotlin
+*F
++ 1 Greeter.kt
+com/example/Greeter.kt
++ 2 Strings.kt
+kotlin/text/StringsKt__StringsKt.kt
+*L
+1#1,12:1
+40#2,3:13
+*E
+*S KotlinDebug
+*F
++ 1 Greeter.kt
+com/example/Greeter.kt
+*L
+5#1:13,3
+*E
+";
+
+    #[test]
+    fn parse_kotlin() {
+        let smap = Smap::parse(KOTLIN).unwrap();
+        assert_eq!(smap.output_file(), "Greeter.kt");
+        assert_eq!(smap.default_stratum_id(), "Kotlin");
+        assert_eq!(smap.strata().len(), 2);
+
+        let kotlin = smap.default_stratum().unwrap();
+        assert_eq!(kotlin.files().len(), 2);
+        assert_eq!(
+            kotlin.file(2).unwrap().path(),
+            Some("kotlin/text/StringsKt__StringsKt.kt")
+        );
+
+        let (file, line) = smap.map_line(7).unwrap();
+        assert_eq!((file.name(), line), ("Greeter.kt", 7));
+        let (file, line) = smap.map_line(14).unwrap();
+        assert_eq!((file.name(), line), ("Strings.kt", 41));
+        assert!(smap.map_line(16).is_none());
+
+        // each input line maps to 3 output lines
+        let debug = smap.stratum("KotlinDebug").unwrap();
+        assert_eq!(debug.map_line(15).map(|(_, line)| line), Some(5));
+        assert!(debug.map_line(16).is_none());
+    }
+
+    #[test]
+    fn file_id_carries_over() {
+        let smap =
+            Smap::parse("SMAP\nA.jsp\nJSP\n*S JSP\n*F\n0 A.jsp\n1 b.jsp\n*L\n1#1:5\n3:6\n*E\n")
+                .unwrap();
+        let lines = smap.default_stratum().unwrap().lines();
+        assert_eq!(lines[1].file_id(), 1);
+        assert_eq!(
+            smap.map_line(6).map(|(file, line)| (file.name(), line)),
+            Some(("b.jsp", 3))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Smap::parse("not a source map").is_err());
+        let error = Smap::parse("SMAP\nA.kt\nKotlin\n*S Kotlin\n*L\nnope\n*E\n").unwrap_err();
+        assert_eq!(error.line(), 6);
+    }
+}
@@ -0,0 +1,263 @@
+//! Stack map frames, as described by the `StackMapTable` attribute in section
+//! [§4.7.4](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.4) of the JVM
+//! specification.
+//!
+//! A stack map frame gives the types of the local variables and operand stack at the start of a
+//! basic block, so the verifier can check a method in a single pass. Frames are delta encoded, each
+//! one relative to the frame before it.
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::attributes::Code;
+//! # use java_class_parser::{HasAttributes, JavaClassParser};
+//! let parser = JavaClassParser::new("app.jar");
+//! let class = parser.find("com/example/Foo").unwrap();
+//! for method in class.methods() {
+//!     if let Some(code) = method.get::<Code>() {
+//!         for (offset, frame) in code.stack_map_table().unwrap_or_default().frames() {
+//!             println!("{}: {:?}", offset, frame);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{FQName, JavaClass};
+use nom::combinator::{complete, flat_map, map, map_opt};
+use nom::error::{make_error, ErrorKind};
+use nom::multi::count;
+use nom::number::complete::{be_u16, be_u8};
+use nom::IResult;
+
+/// The type of a local variable or operand stack entry, as tracked by the verifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationType<'a> {
+    /// An unusable value, such as the second slot of a `long` or `double`
+    Top,
+    /// An `int`, or a `boolean`, `byte`, `char` or `short`
+    Integer,
+    /// A `float`
+    Float,
+    /// A `double`, which also takes up the next slot
+    Double,
+    /// A `long`, which also takes up the next slot
+    Long,
+    /// `null`
+    Null,
+    /// `this` in a constructor, before the super constructor is called
+    UninitializedThis,
+    /// An instance of a class, or an array when the name is an array descriptor
+    Object(&'a FQName),
+    /// An object created by the `new` instruction at an offset, whose constructor hasn't been
+    /// called yet
+    Uninitialized {
+        /// The bytecode offset of the `new` instruction
+        offset: u16,
+    },
+}
+
+/// A stack map frame, giving the types at the start of a basic block relative to the previous
+/// frame.
+///
+/// The first frame applies at offset `offset_delta`, and each later frame at
+/// `previous_offset + offset_delta + 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackMapFrame<'a> {
+    /// The same locals as the previous frame, and an empty stack
+    Same {
+        /// The offset from the previous frame
+        offset_delta: u16,
+    },
+    /// The same locals as the previous frame, and a single value on the stack
+    SameLocals1StackItem {
+        /// The offset from the previous frame
+        offset_delta: u16,
+        /// The value on the stack
+        stack: VerificationType<'a>,
+    },
+    /// The locals of the previous frame without the last few, and an empty stack
+    Chop {
+        /// The offset from the previous frame
+        offset_delta: u16,
+        /// The number of locals removed, between 1 and 3
+        chopped: u8,
+    },
+    /// The locals of the previous frame with a few more, and an empty stack
+    Append {
+        /// The offset from the previous frame
+        offset_delta: u16,
+        /// The locals added, between 1 and 3
+        locals: Vec<VerificationType<'a>>,
+    },
+    /// Every local and stack entry, independent of the previous frame
+    Full {
+        /// The offset from the previous frame
+        offset_delta: u16,
+        /// The locals
+        locals: Vec<VerificationType<'a>>,
+        /// The stack, from bottom to top
+        stack: Vec<VerificationType<'a>>,
+    },
+}
+
+impl StackMapFrame<'_> {
+    /// The offset of this frame from the previous frame
+    pub fn offset_delta(&self) -> u16 {
+        match self {
+            StackMapFrame::Same { offset_delta }
+            | StackMapFrame::SameLocals1StackItem { offset_delta, .. }
+            | StackMapFrame::Chop { offset_delta, .. }
+            | StackMapFrame::Append { offset_delta, .. }
+            | StackMapFrame::Full { offset_delta, .. } => *offset_delta,
+        }
+    }
+}
+
+/// The stack map frames of a method's code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StackMapTable<'a> {
+    frames: Vec<StackMapFrame<'a>>,
+}
+
+impl<'a> StackMapTable<'a> {
+    /// The frames, paired with the bytecode offsets they apply at
+    pub fn frames(&self) -> impl Iterator<Item = (u16, &StackMapFrame<'a>)> {
+        let mut offset: Option<u16> = None;
+        self.frames.iter().map(move |frame| {
+            let next = match offset {
+                None => frame.offset_delta(),
+                Some(previous) => previous + frame.offset_delta() + 1,
+            };
+            offset = Some(next);
+            (next, frame)
+        })
+    }
+
+    /// The frames as encoded, without resolving their offsets
+    pub fn entries(&self) -> &[StackMapFrame<'a>] {
+        &self.frames[..]
+    }
+}
+
+pub(crate) fn parse_stack_map_table<'b, 'a>(
+    bytes: &'b [u8],
+    class: &'a JavaClass,
+) -> IResult<&'b [u8], StackMapTable<'a>> {
+    map(
+        complete(flat_map(be_u16, |number_of_entries| {
+            count(|b| parse_frame(b, class), number_of_entries as usize)
+        })),
+        |frames| StackMapTable { frames },
+    )(bytes)
+}
+
+fn parse_frame<'b, 'a>(
+    bytes: &'b [u8],
+    class: &'a JavaClass,
+) -> IResult<&'b [u8], StackMapFrame<'a>> {
+    let (bytes, frame_type) = be_u8(bytes)?;
+    match frame_type {
+        0..=63 => Ok((
+            bytes,
+            StackMapFrame::Same {
+                offset_delta: frame_type as u16,
+            },
+        )),
+        64..=127 => {
+            let (bytes, stack) = parse_verification_type(bytes, class)?;
+            Ok((
+                bytes,
+                StackMapFrame::SameLocals1StackItem {
+                    offset_delta: frame_type as u16 - 64,
+                    stack,
+                },
+            ))
+        }
+        247 => {
+            let (bytes, offset_delta) = be_u16(bytes)?;
+            let (bytes, stack) = parse_verification_type(bytes, class)?;
+            Ok((
+                bytes,
+                StackMapFrame::SameLocals1StackItem {
+                    offset_delta,
+                    stack,
+                },
+            ))
+        }
+        248..=250 => {
+            let (bytes, offset_delta) = be_u16(bytes)?;
+            Ok((
+                bytes,
+                StackMapFrame::Chop {
+                    offset_delta,
+                    chopped: 251 - frame_type,
+                },
+            ))
+        }
+        251 => {
+            let (bytes, offset_delta) = be_u16(bytes)?;
+            Ok((bytes, StackMapFrame::Same { offset_delta }))
+        }
+        252..=254 => {
+            let (bytes, offset_delta) = be_u16(bytes)?;
+            let (bytes, locals) = count(
+                |b| parse_verification_type(b, class),
+                frame_type as usize - 251,
+            )(bytes)?;
+            Ok((
+                bytes,
+                StackMapFrame::Append {
+                    offset_delta,
+                    locals,
+                },
+            ))
+        }
+        255 => {
+            let (bytes, offset_delta) = be_u16(bytes)?;
+            let (bytes, locals) = flat_map(be_u16, |number_of_locals| {
+                count(
+                    |b| parse_verification_type(b, class),
+                    number_of_locals as usize,
+                )
+            })(bytes)?;
+            let (bytes, stack) = flat_map(be_u16, |number_of_stack_items| {
+                count(
+                    |b| parse_verification_type(b, class),
+                    number_of_stack_items as usize,
+                )
+            })(bytes)?;
+            Ok((
+                bytes,
+                StackMapFrame::Full {
+                    offset_delta,
+                    locals,
+                    stack,
+                },
+            ))
+        }
+        _ => Err(nom::Err::Failure(make_error(bytes, ErrorKind::Tag))),
+    }
+}
+
+fn parse_verification_type<'b, 'a>(
+    bytes: &'b [u8],
+    class: &'a JavaClass,
+) -> IResult<&'b [u8], VerificationType<'a>> {
+    let (bytes, tag) = be_u8(bytes)?;
+    match tag {
+        0 => Ok((bytes, VerificationType::Top)),
+        1 => Ok((bytes, VerificationType::Integer)),
+        2 => Ok((bytes, VerificationType::Float)),
+        3 => Ok((bytes, VerificationType::Double)),
+        4 => Ok((bytes, VerificationType::Long)),
+        5 => Ok((bytes, VerificationType::Null)),
+        6 => Ok((bytes, VerificationType::UninitializedThis)),
+        7 => map_opt(be_u16, |index| {
+            class
+                .constant_pool()
+                .get_class_name(index)
+                .map(VerificationType::Object)
+        })(bytes),
+        8 => map(be_u16, |offset| VerificationType::Uninitialized { offset })(bytes),
+        _ => Err(nom::Err::Failure(make_error(bytes, ErrorKind::Tag))),
+    }
+}
@@ -0,0 +1,175 @@
+//! Maps class file versions to the Java releases that introduced them.
+//!
+//! Each release of Java increments the major version of the class files it produces. Classes using
+//! preview features of a release are marked with a minor version of `0xFFFF`, and can only be
+//! loaded by that exact release with preview features enabled.
+
+use crate::JavaClass;
+use std::fmt::{Display, Formatter};
+
+/// The minor version of classes that depend on the preview features of their release
+pub const PREVIEW_MINOR_VERSION: u16 = 0xFFFF;
+
+/// A Java release, identified by the major version of the class files it produces
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub enum JavaVersion {
+    /// JDK 1.1, major version 45
+    Java1_1,
+    /// J2SE 1.2, major version 46
+    Java1_2,
+    /// J2SE 1.3, major version 47
+    Java1_3,
+    /// J2SE 1.4, major version 48
+    Java1_4,
+    /// J2SE 5.0, major version 49
+    Java5,
+    /// Java SE 6, major version 50
+    Java6,
+    /// Java SE 7, major version 51
+    Java7,
+    /// Java SE 8, major version 52
+    Java8,
+    /// Java SE 9, major version 53
+    Java9,
+    /// Java SE 10, major version 54
+    Java10,
+    /// Java SE 11, major version 55
+    Java11,
+    /// Java SE 12, major version 56
+    Java12,
+    /// Java SE 13, major version 57
+    Java13,
+    /// Java SE 14, major version 58
+    Java14,
+    /// Java SE 15, major version 59
+    Java15,
+    /// Java SE 16, major version 60
+    Java16,
+    /// Java SE 17, major version 61
+    Java17,
+    /// Java SE 18, major version 62
+    Java18,
+    /// Java SE 19, major version 63
+    Java19,
+    /// Java SE 20, major version 64
+    Java20,
+    /// Java SE 21, major version 65
+    Java21,
+    /// A release newer than Java SE 21, with its feature release number
+    Later(u16),
+}
+
+impl JavaVersion {
+    const RELEASES: [JavaVersion; 21] = [
+        Self::Java1_1,
+        Self::Java1_2,
+        Self::Java1_3,
+        Self::Java1_4,
+        Self::Java5,
+        Self::Java6,
+        Self::Java7,
+        Self::Java8,
+        Self::Java9,
+        Self::Java10,
+        Self::Java11,
+        Self::Java12,
+        Self::Java13,
+        Self::Java14,
+        Self::Java15,
+        Self::Java16,
+        Self::Java17,
+        Self::Java18,
+        Self::Java19,
+        Self::Java20,
+        Self::Java21,
+    ];
+
+    /// Gets the release that produces class files with a major version. Versions before 45 were
+    /// only used by pre-release versions of Java, and return `None`.
+    pub fn from_major(major: u16) -> Option<Self> {
+        let index = usize::from(major.checked_sub(45)?);
+        Some(
+            Self::RELEASES
+                .get(index)
+                .copied()
+                .unwrap_or(Self::Later(major - 44)),
+        )
+    }
+
+    /// The major version of class files produced by this release
+    pub fn major(&self) -> u16 {
+        match self {
+            Self::Later(release) => release + 44,
+            _ => {
+                let index = Self::RELEASES
+                    .iter()
+                    .position(|release| release == self)
+                    .expect("every other release is listed");
+                index as u16 + 45
+            }
+        }
+    }
+
+    /// The feature release number, such as `8` for Java SE 8. Releases before Java 5 were
+    /// numbered `1.x`, and return `1`.
+    pub fn release(&self) -> u16 {
+        match self.major() {
+            major @ 49.. => major - 44,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for JavaVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Java1_1 | Self::Java1_2 | Self::Java1_3 | Self::Java1_4 => {
+                write!(f, "Java 1.{}", self.major() - 44)
+            }
+            _ => write!(f, "Java {}", self.release()),
+        }
+    }
+}
+
+/// Class file versions
+impl JavaClass {
+    /// The `(major, minor)` version of the class file
+    pub fn class_file_version(&self) -> (u16, u16) {
+        (self.header().major, self.header().minor)
+    }
+
+    /// The Java release that produced this class, if its major version is known
+    pub fn java_version(&self) -> Option<JavaVersion> {
+        JavaVersion::from_major(self.header().major)
+    }
+
+    /// Checks whether the class depends on the preview features of its release
+    pub fn is_preview(&self) -> bool {
+        self.header().major >= 56 && self.header().minor == PREVIEW_MINOR_VERSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JavaVersion;
+
+    #[test]
+    fn major_versions() {
+        assert_eq!(JavaVersion::from_major(44), None);
+        assert_eq!(JavaVersion::from_major(45), Some(JavaVersion::Java1_1));
+        assert_eq!(JavaVersion::from_major(52), Some(JavaVersion::Java8));
+        assert_eq!(JavaVersion::from_major(61), Some(JavaVersion::Java17));
+        assert_eq!(JavaVersion::from_major(66), Some(JavaVersion::Later(22)));
+        for major in 45..70 {
+            assert_eq!(JavaVersion::from_major(major).unwrap().major(), major);
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(JavaVersion::Java1_4.to_string(), "Java 1.4");
+        assert_eq!(JavaVersion::Java1_4.release(), 1);
+        assert_eq!(JavaVersion::Java5.to_string(), "Java 5");
+        assert_eq!(JavaVersion::Later(23).to_string(), "Java 23");
+    }
+}
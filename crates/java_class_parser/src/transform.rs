@@ -0,0 +1,248 @@
+//! Transforms class files by removing attributes, producing the bytes of a new class file. This
+//! is mostly useful for shrinking jars, by removing debug info and annotations that aren't needed
+//! at runtime.
+//!
+//! Attributes are removed from the class, its fields and methods, the `Code` attributes of its
+//! methods, and the components of records. The constant pool isn't changed, so the names and
+//! values of removed attributes are still in the class file until the constant pool is
+//! [compacted](compact_constant_pool).
+//!
+//! # Example
+//! ```no_run
+//! # use java_class_parser::{parse_bytes, parse_file, transform};
+//! let class = parse_file("Square.class").expect("couldn't parse class");
+//! let stripped = transform::strip_debug_info(&class).expect("couldn't transform class");
+//! let stripped = parse_bytes(&stripped[..]).expect("couldn't parse class");
+//! let compacted = transform::compact_constant_pool(&stripped).expect("couldn't transform class");
+//! std::fs::write("Square.class", compacted).expect("couldn't write class");
+//! ```
+
+use crate::constant_pool::parser::parse_attribute_info;
+use crate::constant_pool::references;
+use crate::error::Error;
+use crate::raw_java_class::{write_attributes, RawAttributeInfo};
+use crate::{ConstantPoolBuilder, JavaClass};
+use nom::bytes::complete::take;
+use nom::combinator::eof;
+use nom::multi::count;
+use nom::number::complete::{be_u16, be_u32};
+use nom::IResult;
+
+/// The attributes removed by [`strip_debug_info`]
+pub const DEBUG_ATTRIBUTES: [&str; 4] = [
+    "LineNumberTable",
+    "LocalVariableTable",
+    "LocalVariableTypeTable",
+    "SourceFile",
+];
+
+/// The attributes removed by [`strip_annotations`] when stripping visible annotations
+pub const VISIBLE_ANNOTATION_ATTRIBUTES: [&str; 3] = [
+    "RuntimeVisibleAnnotations",
+    "RuntimeVisibleParameterAnnotations",
+    "RuntimeVisibleTypeAnnotations",
+];
+
+/// The attributes removed by [`strip_annotations`] when stripping invisible annotations
+pub const INVISIBLE_ANNOTATION_ATTRIBUTES: [&str; 3] = [
+    "RuntimeInvisibleAnnotations",
+    "RuntimeInvisibleParameterAnnotations",
+    "RuntimeInvisibleTypeAnnotations",
+];
+
+/// Removes line numbers, local variable names and the source file name from a class, like
+/// compiling it with `javac -g:none`. See [`DEBUG_ATTRIBUTES`].
+///
+/// # Error
+/// Will return an error if the class was parsed lazily and is invalid, or it has an invalid
+/// `Code` or `Record` attribute
+pub fn strip_debug_info(class: &JavaClass) -> Result<Vec<u8>, Error> {
+    retain_attributes(class, |name| !DEBUG_ATTRIBUTES.contains(&name))
+}
+
+/// Removes either the visible or invisible annotations from a class, including annotations on
+/// parameters and types. Visible annotations are the ones retained at runtime.
+///
+/// # Error
+/// Will return an error if the class was parsed lazily and is invalid, or it has an invalid
+/// `Code` or `Record` attribute
+pub fn strip_annotations(class: &JavaClass, visible: bool) -> Result<Vec<u8>, Error> {
+    let stripped = if visible {
+        VISIBLE_ANNOTATION_ATTRIBUTES
+    } else {
+        INVISIBLE_ANNOTATION_ATTRIBUTES
+    };
+    retain_attributes(class, |name| !stripped.contains(&name))
+}
+
+/// Keeps only the attributes whose names match a predicate, removing every other attribute.
+/// Attributes whose names can't be found in the constant pool are always kept.
+///
+/// Removing attributes the JVM needs, such as `Code` or `StackMapTable`, produces a class file
+/// that won't load.
+///
+/// # Error
+/// Will return an error if the class was parsed lazily and is invalid, or it has an invalid
+/// `Code` or `Record` attribute
+pub fn retain_attributes<F: FnMut(&str) -> bool>(
+    class: &JavaClass,
+    mut predicate: F,
+) -> Result<Vec<u8>, Error> {
+    class.decode()?;
+    let mut raw = class.raw().clone();
+    let mut retainer = Retainer {
+        class,
+        predicate: &mut predicate,
+    };
+    for field in raw.fields.iter_mut() {
+        field.attributes = retainer.retain(&field.attributes)?;
+        field.attributes_count = field.attributes.len() as u16;
+    }
+    for method in raw.methods.iter_mut() {
+        method.attributes = retainer.retain(&method.attributes)?;
+        method.attributes_count = method.attributes.len() as u16;
+    }
+    raw.attributes = retainer.retain(&raw.attributes)?;
+    raw.attributes_count = raw.attributes.len() as u16;
+    Ok(raw.to_bytes())
+}
+
+/// Removes every constant that isn't used by a class, directly or through other constants. The
+/// remaining constants keep their order.
+///
+/// # Error
+/// Will return an error if the class was parsed lazily and is invalid, refers to a constant that
+/// doesn't exist, or has an attribute that is malformed or not defined by the JVM specification,
+/// as the constants used by unknown attributes can't be found.
+pub fn compact_constant_pool(class: &JavaClass) -> Result<Vec<u8>, Error> {
+    class.decode()?;
+    let mut raw = class.raw().clone();
+    references::compact(&mut raw)?;
+    Ok(raw.to_bytes())
+}
+
+/// Adds an attribute to a class. The contents of the attribute are created by `info`, which can
+/// add any constants the attribute refers to. Existing constants keep their indices.
+///
+/// # Example
+/// ```no_run
+/// # use java_class_parser::{parse_file, transform};
+/// let class = parse_file("Square.class").expect("couldn't parse class");
+/// let bytes = transform::add_attribute(&class, "SourceFile", |constants| {
+///     Ok(constants.utf8("Square.java")?.to_be_bytes().to_vec())
+/// })
+/// .expect("couldn't transform class");
+/// ```
+///
+/// # Error
+/// Will return an error if the class was parsed lazily and is invalid, the constant pool is full,
+/// or `info` fails
+pub fn add_attribute<F>(class: &JavaClass, name: &str, info: F) -> Result<Vec<u8>, Error>
+where
+    F: FnOnce(&mut ConstantPoolBuilder) -> Result<Vec<u8>, Error>,
+{
+    class.decode()?;
+    let mut raw = class.raw().clone();
+    let mut constants = ConstantPoolBuilder::from(&raw.constant_pool);
+    let attribute_name_index = constants.utf8(name)?;
+    let info = info(&mut constants)?;
+    let mut attributes = raw.attributes.into_vec();
+    attributes.push(RawAttributeInfo {
+        attribute_name_index,
+        attribute_length: info.len() as u32,
+        info: info.into_boxed_slice(),
+    });
+    raw.attributes = attributes.into_boxed_slice();
+    raw.attributes_count = raw.attributes.len() as u16;
+    raw.constant_pool = constants.build();
+    raw.constant_pool_count = raw.constant_pool.len() as u16 + 1;
+    Ok(raw.to_bytes())
+}
+
+struct Retainer<'a, F> {
+    class: &'a JavaClass,
+    predicate: &'a mut F,
+}
+
+impl<F: FnMut(&str) -> bool> Retainer<'_, F> {
+    /// Retains attributes, and the attributes nested within them
+    fn retain(
+        &mut self,
+        attributes: &[RawAttributeInfo],
+    ) -> Result<Box<[RawAttributeInfo]>, Error> {
+        let mut retained = vec![];
+        for attribute in attributes {
+            let name = self
+                .class
+                .constant_pool()
+                .get_utf8(attribute.attribute_name_index);
+            if !name.map_or(true, |name| (self.predicate)(name)) {
+                continue;
+            }
+            let info = match name {
+                Some("Code") => self.retain_code(&attribute.info)?,
+                Some("Record") => self.retain_record(&attribute.info)?,
+                _ => {
+                    retained.push(attribute.clone());
+                    continue;
+                }
+            };
+            retained.push(RawAttributeInfo {
+                attribute_name_index: attribute.attribute_name_index,
+                attribute_length: info.len() as u32,
+                info: info.into_boxed_slice(),
+            });
+        }
+        Ok(retained.into_boxed_slice())
+    }
+
+    /// Retains the attributes of a `Code` attribute, keeping its bytecode and exception table
+    fn retain_code(&mut self, info: &[u8]) -> Result<Vec<u8>, Error> {
+        fn parse(bytes: &[u8]) -> IResult<&[u8], (&[u8], Vec<RawAttributeInfo>)> {
+            let (rest, _max_stack_and_locals) = take(4_usize)(bytes)?;
+            let (rest, code_length) = be_u32(rest)?;
+            let (rest, _code) = take(code_length)(rest)?;
+            let (rest, exception_table_length) = be_u16(rest)?;
+            let (rest, _exception_table) = take(exception_table_length as usize * 8)(rest)?;
+            let header = &bytes[..bytes.len() - rest.len()];
+            let (rest, attributes_count) = be_u16(rest)?;
+            let (rest, attributes) = count(parse_attribute_info, attributes_count as usize)(rest)?;
+            let (rest, _) = eof(rest)?;
+            Ok((rest, (header, attributes)))
+        }
+
+        let (_, (header, attributes)) = parse(info)?;
+        let mut out = header.to_vec();
+        write_attributes(&self.retain(&attributes)?, &mut out);
+        Ok(out)
+    }
+
+    /// Retains the attributes of every component in a `Record` attribute
+    fn retain_record(&mut self, info: &[u8]) -> Result<Vec<u8>, Error> {
+        type Component = (u16, u16, Vec<RawAttributeInfo>);
+
+        fn parse_component(bytes: &[u8]) -> IResult<&[u8], Component> {
+            let (rest, name_index) = be_u16(bytes)?;
+            let (rest, descriptor_index) = be_u16(rest)?;
+            let (rest, attributes_count) = be_u16(rest)?;
+            let (rest, attributes) = count(parse_attribute_info, attributes_count as usize)(rest)?;
+            Ok((rest, (name_index, descriptor_index, attributes)))
+        }
+
+        fn parse(bytes: &[u8]) -> IResult<&[u8], Vec<Component>> {
+            let (rest, components_count) = be_u16(bytes)?;
+            let (rest, components) = count(parse_component, components_count as usize)(rest)?;
+            let (rest, _) = eof(rest)?;
+            Ok((rest, components))
+        }
+
+        let (_, components) = parse(info)?;
+        let mut out = (components.len() as u16).to_be_bytes().to_vec();
+        for (name_index, descriptor_index, attributes) in components {
+            out.extend(name_index.to_be_bytes());
+            out.extend(descriptor_index.to_be_bytes());
+            write_attributes(&self.retain(&attributes)?, &mut out);
+        }
+        Ok(out)
+    }
+}
@@ -1,5 +1,9 @@
 //! utility stuff
 
+use std::cell::UnsafeCell;
+use std::fmt::{Debug, Formatter};
+use std::sync::Once;
+
 /// Matches a thing to a thing
 macro_rules! match_as {
     (
@@ -15,3 +19,128 @@ macro_rules! match_as {
     };
 }
 pub(crate) use match_as;
+
+/// A cursor over big endian bytes, for walking structures where using nom would be overkill.
+#[derive(Debug, Clone)]
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// The number of bytes read so far
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let taken = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(taken)
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Option<u16> {
+        self.take(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn i8(&mut self) -> Option<i8> {
+        self.u8().map(|byte| byte as i8)
+    }
+
+    pub(crate) fn i16(&mut self) -> Option<i16> {
+        self.u16().map(|short| short as i16)
+    }
+
+    pub(crate) fn i32(&mut self) -> Option<i32> {
+        self.u32().map(|int| int as i32)
+    }
+}
+
+/// Quotes a string as a graphviz dot identifier
+pub(crate) fn dot_quoted(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Serializes bitflags as the names of the flags that are set, such as `["PUBLIC", "FINAL"]`
+#[cfg(feature = "serde")]
+pub(crate) struct FlagNames<F>(pub(crate) F);
+
+#[cfg(feature = "serde")]
+impl<F: bitflags::Flags> serde::Serialize for FlagNames<F> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter_names().map(|(name, _)| name))
+    }
+}
+
+/// Serializes the attributes of something as a sequence
+#[cfg(feature = "serde")]
+pub(crate) struct AttributeList<'a, T>(pub(crate) &'a T);
+
+#[cfg(feature = "serde")]
+impl<T: crate::HasAttributes> serde::Serialize for AttributeList<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.attributes())
+    }
+}
+
+/// A cell written at most once, which can be shared between threads. `std::sync::OnceLock` isn't
+/// available on the minimum supported rust version.
+pub(crate) struct OnceCell<T> {
+    once: Once,
+    value: UnsafeCell<Option<T>>,
+}
+
+// Safety: the value is only written once, by the closure passed to `call_once`, and is only read
+// after `call_once` has completed
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            once: Once::new(),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Gets the value, initializing it if it hasn't been yet
+    pub(crate) fn get_or_init<F: FnOnce() -> T>(&self, init: F) -> &T {
+        self.once.call_once(|| {
+            // Safety: no references to the value exist before `call_once` completes
+            unsafe { *self.value.get() = Some(init()) }
+        });
+        // Safety: the value was written by `call_once`, and is never written again
+        unsafe { (*self.value.get()).as_ref() }.expect("once cell wasn't initialized")
+    }
+
+    /// Checks if the value has been initialized
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.once.is_completed()
+    }
+}
+
+impl<T> Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnceCell")
+            .field("initialized", &self.is_initialized())
+            .finish()
+    }
+}
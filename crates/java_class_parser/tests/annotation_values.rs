@@ -0,0 +1,66 @@
+use java_class_parser::JavaClassParser;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Entity {
+    table: String,
+    #[serde(default)]
+    schema: Option<String>,
+    kind: Kind,
+    version: i32,
+    listeners: Vec<String>,
+    indexes: Vec<Index>,
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize)]
+enum Kind {
+    #[serde(rename = "TABLE")]
+    Table,
+    #[serde(rename = "VIEW")]
+    View,
+}
+
+#[derive(Debug, Deserialize)]
+struct Index {
+    columns: Vec<String>,
+}
+
+#[test]
+fn extract_annotation() {
+    let parser = JavaClassParser::from(itest_common::classes());
+    let account = parser.find("com/example/entities/Account").unwrap();
+    let entity: Entity = account
+        .extract_annotation("com/example/annotations/Entity")
+        .unwrap()
+        .expect("account is an entity");
+
+    assert_eq!(entity.table, "accounts");
+    assert_eq!(entity.schema, None);
+    assert_eq!(entity.kind, Kind::View);
+    assert_eq!(entity.version, 100000);
+    assert_eq!(entity.listeners, ["Ljava/lang/Object;"]);
+    assert_eq!(entity.indexes[0].columns, ["id"]);
+    assert_eq!(entity.indexes[1].columns, ["name", "email"]);
+
+    let missing: Option<Entity> = parser
+        .find("com/example/Rectangle")
+        .unwrap()
+        .extract_annotation("com/example/annotations/Entity")
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[test]
+fn mismatched_type() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Wrong {
+        table: i32,
+    }
+
+    let parser = JavaClassParser::from(itest_common::classes());
+    let account = parser.find("com/example/entities/Account").unwrap();
+    assert!(account
+        .extract_annotation::<Wrong>("com/example/annotations/Entity")
+        .is_err());
+}
@@ -0,0 +1,234 @@
+use java_class_parser::annotations::{
+    Annotation, AnnotationValue, ElementType, RetentionPolicy, TypeAnnotationTarget, TypePathEntry,
+};
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn meta_annotations() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+
+    let marker = parser
+        .find("com/example/annotations/Marker")
+        .expect("couldn't find marker");
+    assert!(marker.is_annotation());
+    assert_eq!(marker.retention(), Some(RetentionPolicy::Runtime));
+    assert_eq!(
+        marker.targets(),
+        Some(vec![ElementType::Type, ElementType::Method])
+    );
+
+    let unconfigured = parser
+        .find("com/example/annotations/Unconfigured")
+        .expect("couldn't find unconfigured");
+    assert_eq!(unconfigured.retention(), Some(RetentionPolicy::Class));
+    assert_eq!(unconfigured.targets(), None);
+
+    let shape = parser
+        .find("com/example/Shape")
+        .expect("couldn't find shape");
+    assert!(!shape.is_annotation());
+    assert_eq!(shape.retention(), None);
+}
+
+#[test]
+fn find_runtime_annotations() {
+    let parser = JavaClassParser::from(itest_common::classes());
+    let runtime = parser
+        .find_runtime_annotations()
+        .expect("couldn't scan classpath")
+        .into_iter()
+        .map(|class| class.this().to_fqname_buf())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        runtime,
+        [
+            "com/example/annotations/Entity",
+            "com/example/annotations/Marker",
+            "com/example/annotations/Named",
+            "com/example/annotations/NonNull"
+        ]
+    );
+}
+
+#[test]
+fn find_runtime_annotations_skips_invalid_classes() {
+    let directory =
+        std::env::temp_dir().join(format!("runtime-annotations-{}", std::process::id()));
+    let annotations = directory.join("com/example/annotations");
+    std::fs::create_dir_all(&annotations).unwrap();
+    std::fs::copy(
+        itest_common::classes().join("com/example/annotations/Marker.class"),
+        annotations.join("Marker.class"),
+    )
+    .unwrap();
+    std::fs::write(
+        directory.join("com/example/Broken.class"),
+        [0xCA, 0xFE, 0xBA, 0xBE, 0, 0],
+    )
+    .unwrap();
+
+    let parser = JavaClassParser::from(directory.clone());
+    let runtime = parser
+        .find_runtime_annotations()
+        .expect("invalid classes should be skipped");
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(runtime.len(), 1);
+    assert_eq!(runtime[0].this(), "com/example/annotations/Marker");
+}
+
+#[test]
+fn annotation_values() {
+    let parser = JavaClassParser::from(itest_common::classes());
+    let account = parser.find("com/example/entities/Account").unwrap();
+    let values = account
+        .annotation_values("com/example/annotations/Entity")
+        .expect("account is an entity");
+
+    assert_eq!(
+        values["table"],
+        AnnotationValue::String("accounts".to_string())
+    );
+    assert!(!values.contains_key("schema"));
+    assert_eq!(values["version"], AnnotationValue::Int(100000));
+    assert!(matches!(
+        &values["kind"],
+        AnnotationValue::Enum { type_name, const_name }
+            if type_name == "com/example/annotations/Entity$Kind" && const_name == "VIEW"
+    ));
+    assert_eq!(
+        values["listeners"],
+        AnnotationValue::Array(vec![AnnotationValue::Class(
+            "Ljava/lang/Object;".to_string()
+        )])
+    );
+    let AnnotationValue::Array(indexes) = &values["indexes"] else {
+        panic!("indexes should be an array");
+    };
+    assert!(matches!(
+        &indexes[1],
+        AnnotationValue::Annotation { type_name, values }
+            if type_name == "com/example/annotations/Index" && values["columns"]
+                == AnnotationValue::Array(vec![
+                    AnnotationValue::String("name".to_string()),
+                    AnnotationValue::String("email".to_string())
+                ])
+    ));
+    assert!(account
+        .annotation_values("com/example/annotations/Marker")
+        .is_none());
+}
+
+#[test]
+fn member_annotations() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let annotated = parser
+        .find("com/example/annotations/Annotated")
+        .expect("couldn't find annotated");
+    let names = |annotations: Vec<Annotation>| {
+        annotations
+            .iter()
+            .map(|annotation| annotation.type_name().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names(annotated.annotations()), ["java/lang/Deprecated"]);
+    assert_eq!(
+        names(annotated.invisible_annotations()),
+        ["com/example/annotations/Unconfigured"]
+    );
+
+    let fields = annotated.fields();
+    assert_eq!(names(fields[0].annotations()), ["java/lang/Deprecated"]);
+    assert!(fields[0].invisible_annotations().is_empty());
+    assert!(fields[1].annotations().is_empty());
+    assert_eq!(
+        names(fields[1].invisible_annotations()),
+        ["com/example/annotations/Unconfigured"]
+    );
+
+    let mark = annotated
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "mark")
+        .unwrap();
+    assert_eq!(
+        names(mark.annotations()),
+        ["com/example/annotations/Marker"]
+    );
+    assert_eq!(
+        names(mark.invisible_annotations()),
+        ["com/example/annotations/Unconfigured"]
+    );
+}
+
+#[test]
+fn parameter_annotations() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let annotated = parser
+        .find("com/example/annotations/Annotated")
+        .expect("couldn't find annotated");
+    let greet = annotated
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "greet")
+        .unwrap();
+
+    let visible = greet.parameter_annotations();
+    assert_eq!(visible.len(), 3);
+    assert_eq!(visible[0][0].type_name(), "com/example/annotations/Named");
+    assert_eq!(
+        visible[0][0].values()["value"],
+        AnnotationValue::String("who".to_string())
+    );
+    assert!(visible[1].is_empty() && visible[2].is_empty());
+
+    let invisible = greet.invisible_parameter_annotations();
+    assert_eq!(
+        invisible[1][0].type_name(),
+        "com/example/annotations/Unconfigured"
+    );
+}
+
+#[test]
+fn type_annotations() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let annotated = parser
+        .find("com/example/annotations/Annotated")
+        .expect("couldn't find annotated");
+    let greet = annotated
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "greet")
+        .unwrap();
+
+    let targets = greet
+        .type_annotations()
+        .iter()
+        .inspect(|annotation| {
+            assert_eq!(
+                annotation.annotation().type_name(),
+                "com/example/annotations/NonNull"
+            )
+        })
+        .map(|annotation| (annotation.target().clone(), annotation.type_path().to_vec()))
+        .collect::<Vec<_>>();
+    assert!(targets.contains(&(TypeAnnotationTarget::Empty, vec![])));
+    assert!(targets.contains(&(TypeAnnotationTarget::FormalParameter { index: 2 }, vec![])));
+    assert!(targets.contains(&(
+        TypeAnnotationTarget::FormalParameter { index: 2 },
+        vec![TypePathEntry::Array]
+    )));
+
+    let tags = annotated
+        .fields()
+        .into_iter()
+        .find(|field| field.name() == "tags")
+        .unwrap();
+    let paths = tags
+        .type_annotations()
+        .iter()
+        .map(|annotation| annotation.type_path().to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(paths.len(), 2);
+    assert!(paths.contains(&vec![]) && paths.contains(&vec![TypePathEntry::Array]));
+}
@@ -0,0 +1,87 @@
+use java_class_parser::attributes::Code;
+use java_class_parser::bytecode::{Opcode, ResolvedInstruction};
+use java_class_parser::{HasAttributes, JavaClass, JavaClassParser, ResolvedConstant};
+
+/// Calls `f` with the resolved instructions of a method
+fn with_instructions<R>(
+    class: &JavaClass,
+    method: &str,
+    f: impl FnOnce(Vec<ResolvedInstruction>) -> R,
+) -> R {
+    let method = class
+        .methods()
+        .into_iter()
+        .find(|m| m.name() == method)
+        .expect("method should exist");
+    let code = method.get::<Code>().expect("method should have code");
+    f(code
+        .resolved_instructions()
+        .collect::<Result<_, _>>()
+        .unwrap())
+}
+
+#[test]
+fn resolved_members() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let calls = with_instructions(&square, "compareTo", |instructions| {
+        instructions
+            .into_iter()
+            .filter(|instruction| instruction.opcode() == Opcode::Invokevirtual)
+            .map(|instruction| {
+                let member = instruction.member().unwrap();
+                (
+                    member.class().to_string(),
+                    member.name().to_string(),
+                    member.descriptor().to_string(),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        calls,
+        [
+            ("com/example/Square".into(), "getArea".into(), "()D".into()),
+            (
+                "com/example/Rectangle".into(),
+                "getArea".into(),
+                "()D".into()
+            ),
+        ]
+    );
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let fields = with_instructions(&rectangle, "getArea", |instructions| {
+        instructions
+            .into_iter()
+            .filter(|instruction| instruction.opcode() == Opcode::Getfield)
+            .map(|instruction| instruction.member().unwrap().name().to_string())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(fields, ["width", "length"]);
+}
+
+#[test]
+fn resolved_constants() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    with_instructions(&constants, "total", |instructions| {
+        let loaded = instructions
+            .iter()
+            .filter(|instruction| {
+                matches!(
+                    instruction.opcode(),
+                    Opcode::Ldc | Opcode::LdcW | Opcode::Ldc2W
+                )
+            })
+            .map(|instruction| instruction.constant().unwrap())
+            .collect::<Vec<_>>();
+        assert!(loaded.contains(&&ResolvedConstant::Integer(100000)));
+        assert!(loaded.contains(&&ResolvedConstant::Long(1 << 40)));
+        assert!(loaded.contains(&&ResolvedConstant::String("constants")));
+
+        // instructions without a constant operand aren't resolved
+        assert_eq!(instructions[0].opcode(), Opcode::Iload0);
+        assert!(instructions[0].constant().is_none());
+    });
+}
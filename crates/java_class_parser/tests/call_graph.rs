@@ -0,0 +1,81 @@
+use java_class_parser::call_graph::{CallGraph, InvokeKind, MethodId};
+use java_class_parser::{FQName, JavaClassParser};
+
+#[test]
+fn extract_calls() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let graph = CallGraph::of(&square).unwrap();
+
+    let constructor = MethodId::new(square.this(), "<init>", "(D)V");
+    assert!(graph.is_internal(&constructor));
+    assert_eq!(
+        graph.callees(&constructor),
+        [(
+            &MethodId::new(
+                parser.find("com/example/Rectangle").unwrap().this(),
+                "<init>",
+                "(DD)V"
+            ),
+            InvokeKind::Special
+        )]
+    );
+
+    // the bridge method generated for Comparable calls the real implementation
+    let bridge = MethodId::new(square.this(), "compareTo", "(Ljava/lang/Object;)I");
+    let compare_to = MethodId::new(square.this(), "compareTo", "(Lcom/example/Rectangle;)I");
+    assert_eq!(graph.callees(&bridge), [(&compare_to, InvokeKind::Virtual)]);
+    assert!(graph
+        .calls()
+        .all(|(caller, _, _)| graph.is_internal(caller)));
+}
+
+#[test]
+fn dot_output() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let graph = CallGraph::of(&parser.find("com/example/Square").unwrap()).unwrap();
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph {"));
+    assert!(dot.contains("label=\"com/example/Square\""));
+    assert!(dot.contains("[label=\"com/example/Rectangle.<init>(DD)V\", style=dashed]"));
+    assert!(dot.contains("[label=\"invokespecial\"]"));
+    assert_eq!(dot.matches("->").count(), graph.calls().count());
+}
+
+#[test]
+fn invoked_methods() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+    let invocations = |name: &str| {
+        greeter
+            .methods()
+            .iter()
+            .find(|method| method.name() == name)
+            .unwrap()
+            .invocations()
+            .unwrap()
+    };
+
+    // lambdas and method references are linked to their implementation
+    let greeting = invocations("greeting");
+    assert_eq!(greeting.len(), 1);
+    assert_eq!(greeting[0].class(), Some(greeter.this()));
+    assert!(greeting[0].name().starts_with("lambda$greeting$"));
+    assert!(invocations("notifier").contains(&MethodId::new(
+        FQName::new("java/lang/Object"),
+        "notify",
+        "()V"
+    )));
+
+    let invoked = greeter.invoked_methods().unwrap();
+    assert!(invoked.contains(&greeting[0]));
+    assert!(invoked.contains(&MethodId::new(
+        FQName::new("java/io/PrintStream"),
+        "println",
+        "(Ljava/lang/String;)V"
+    )));
+    // string concatenation has no implementation method to link to
+    assert!(invoked
+        .iter()
+        .any(|method| method.class().is_none() && method.name() == "makeConcatWithConstants"));
+}
@@ -0,0 +1,166 @@
+use java_class_parser::JavaClassParser;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+#[test]
+fn list_classes() {
+    let jar = JavaClassParser::from(itest_common::jar_file());
+    let names = jar.classes().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(names.iter().any(|name| name == "com/example/Square"));
+    assert!(names.iter().all(|name| name != "module-info"));
+
+    let classes = JavaClassParser::from(itest_common::classes());
+    let mut dir_names = classes.classes().collect::<Result<Vec<_>, _>>().unwrap();
+    let mut names = names;
+    names.sort();
+    dir_names.sort();
+    assert_eq!(dir_names, names);
+
+    // classes shadowed by a later entry are only listed once
+    let both = JavaClassParser::from_iter([itest_common::classes(), itest_common::jar_file()]);
+    assert_eq!(both.classes().count(), names.len());
+}
+
+#[test]
+fn parse_all_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let classes = parser.parse_all().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(classes.len(), parser.classes().count());
+    assert!(classes
+        .iter()
+        .any(|class| class.this() == "com/example/records/Point"));
+}
+
+#[test]
+fn package_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let names = |package: &str, recursive: bool| {
+        parser
+            .classes_in_package(package, recursive)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+
+    let mut records = names("com.example.records", false);
+    records.sort();
+    assert_eq!(
+        records,
+        [
+            "com/example/records/Point",
+            "com/example/records/Range",
+            "com/example/records/Tagged"
+        ]
+    );
+
+    let direct = names("com/example", false);
+    assert!(direct.iter().any(|name| name == "com/example/Square"));
+    assert!(direct
+        .iter()
+        .all(|name| name.package().unwrap() == "com/example"));
+
+    let recursive = names("com/example", true);
+    assert!(recursive.len() > direct.len());
+    assert!(recursive
+        .iter()
+        .any(|name| name == "com/example/records/Point"));
+    assert!(names("com/exam", true).is_empty());
+
+    let parsed = parser
+        .parse_package("com/example/enums", false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut parsed_names = parsed
+        .iter()
+        .map(|class| class.this().to_fqname_buf())
+        .collect::<Vec<_>>();
+    parsed_names.sort();
+    assert_eq!(
+        parsed_names,
+        [
+            "com/example/enums/Color",
+            "com/example/enums/Palette",
+            "com/example/enums/Palette$1"
+        ]
+    );
+    assert!(parsed.iter().any(|class| class.access_flags().is_enum()));
+}
+
+#[test]
+fn search_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let mut matching = parser
+        .find_matching("com/example/**/P*")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    matching.sort();
+    assert_eq!(
+        matching,
+        [
+            "com/example/enums/Palette",
+            "com/example/enums/Palette$1",
+            "com/example/records/Point"
+        ]
+    );
+
+    let nested = parser
+        .find_where(|name| name.is_nested())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(nested
+        .iter()
+        .any(|name| name == "com/example/enums/Palette$1"));
+    assert!(nested.iter().all(|name| name.is_nested()));
+}
+
+#[test]
+fn nested_jar_classes() {
+    let fat_jar = Path::new(env!("CARGO_TARGET_TMPDIR")).join("parser-fat.jar");
+    let mut writer = ZipWriter::new(File::create(&fat_jar).unwrap());
+    writer
+        .start_file("BOOT-INF/lib/inner.jar", FileOptions::default())
+        .unwrap();
+    writer
+        .write_all(&std::fs::read(itest_common::jar_file()).unwrap())
+        .unwrap();
+    writer.finish().unwrap();
+
+    let parser = JavaClassParser::from(format!("{}!/BOOT-INF/lib/inner.jar", fat_jar.display()));
+    let square = parser.find("com/example/Square").unwrap();
+    assert_eq!(square.this(), "com/example/Square");
+    let names = parser.classes().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        names.len(),
+        JavaClassParser::from(itest_common::jar_file())
+            .classes()
+            .count()
+    );
+}
+
+#[test]
+fn duplicate_classes() {
+    let parser = JavaClassParser::from_iter([itest_common::classes(), itest_common::jar_file()]);
+    let definitions = parser.find_all("com.example.Square").unwrap();
+    let entries = definitions
+        .iter()
+        .map(|definition| definition.entry().to_path_buf())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, [itest_common::classes(), itest_common::jar_file()]);
+    assert!(definitions
+        .iter()
+        .all(|definition| definition.class().this() == "com/example/Square"));
+    assert!(parser.find_all("com/example/Missing").unwrap().is_empty());
+
+    let duplicates = parser.duplicates().unwrap();
+    assert_eq!(duplicates.len(), parser.classes().count());
+    let square = duplicates
+        .iter()
+        .find(|duplicate| duplicate.name() == "com/example/Square")
+        .unwrap();
+    assert_eq!(square.entries(), entries);
+
+    let single = JavaClassParser::from(itest_common::jar_file());
+    assert!(single.duplicates().unwrap().is_empty());
+}
@@ -0,0 +1,108 @@
+use java_class_parser::compare::{compare_jars, ClassChange, ClassDiff, ManifestChange, Member};
+use java_class_parser::{FQName, JavaClassParser};
+
+#[test]
+fn compare_jar_versions() {
+    let mut square = itest_common::class_bytes("com/example/Square");
+    // bump the major version
+    square[7] += 1;
+
+    let old = itest_common::write_jar(
+        "old",
+        &[
+            (
+                "META-INF/MANIFEST.MF",
+                b"Manifest-Version: 1.0\r\nImplementation-Version: 1.0\r\n".to_vec(),
+            ),
+            (
+                "com/example/Rectangle.class",
+                itest_common::class_bytes("com/example/Rectangle"),
+            ),
+            (
+                "com/example/Square.class",
+                itest_common::class_bytes("com/example/Square"),
+            ),
+            (
+                "com/example/enums/Color.class",
+                itest_common::class_bytes("com/example/enums/Color"),
+            ),
+            ("a.txt", b"a".to_vec()),
+            ("b.txt", b"b".to_vec()),
+        ],
+    );
+    let new = itest_common::write_jar(
+        "new",
+        &[
+            (
+                "META-INF/MANIFEST.MF",
+                b"Manifest-Version: 1.0\r\nImplementation-Version: 1.1\r\nMain-Class: com.ex\r\n ample.Main\r\n"
+                    .to_vec(),
+            ),
+            ("com/example/Rectangle.class", itest_common::class_bytes("com/example/Rectangle")),
+            ("com/example/Square.class", square),
+            ("com/example/enums/Palette.class", itest_common::class_bytes("com/example/enums/Palette")),
+            ("a.txt", b"changed".to_vec()),
+            ("c.txt", b"c".to_vec()),
+        ],
+    );
+
+    let comparison = compare_jars(&old, &new).expect("couldn't compare jars");
+    std::fs::remove_file(old).unwrap();
+    std::fs::remove_file(new).unwrap();
+
+    assert_eq!(comparison.added_classes(), ["com/example/enums/Palette"]);
+    assert_eq!(comparison.removed_classes(), ["com/example/enums/Color"]);
+    let [square] = comparison.changed_classes() else {
+        panic!("expected only square to change: {comparison}");
+    };
+    assert_eq!(square.name(), "com/example/Square");
+    assert!(matches!(
+        square.changes(),
+        [ClassChange::VersionChanged { from, to }] if to.0 == from.0 + 1
+    ));
+
+    assert_eq!(comparison.added_resources(), ["c.txt"]);
+    assert_eq!(comparison.removed_resources(), ["b.txt"]);
+    assert_eq!(comparison.changed_resources(), ["a.txt"]);
+    assert_eq!(
+        comparison.manifest_changes(),
+        [
+            ManifestChange::Changed {
+                key: "Implementation-Version".to_string(),
+                from: "1.0".to_string(),
+                to: "1.1".to_string()
+            },
+            ManifestChange::Added {
+                key: "Main-Class".to_string(),
+                value: "com.example.Main".to_string()
+            }
+        ]
+    );
+}
+
+#[test]
+fn diff_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let square = parser.find("com/example/Square").unwrap();
+
+    let diff = ClassDiff::between(&rectangle, &square);
+    let changes = diff.changes();
+    assert!(changes.contains(&ClassChange::SuperClassChanged {
+        from: Some(FQName::new("java/lang/Object").to_fqname_buf()),
+        to: Some(FQName::new("com/example/Rectangle").to_fqname_buf()),
+    }));
+    assert!(changes.contains(&ClassChange::InterfaceRemoved(
+        FQName::new("com/example/Shape").to_fqname_buf()
+    )));
+    assert!(changes.contains(&ClassChange::FieldRemoved(Member::new("width", "D"))));
+    assert!(changes.contains(&ClassChange::MethodRemoved(Member::new("getArea", "()D"))));
+    assert!(changes.contains(&ClassChange::MethodAdded(Member::new(
+        "compareTo",
+        "(Lcom/example/Rectangle;)I"
+    ))));
+    assert!(changes.contains(&ClassChange::MethodRemoved(Member::new("<init>", "(DD)V"))));
+    assert!(changes.contains(&ClassChange::MethodAdded(Member::new("<init>", "(D)V"))));
+
+    assert!(ClassDiff::between(&square, &square).is_empty());
+}
@@ -0,0 +1,123 @@
+use java_class_parser::compare::Member;
+use java_class_parser::compat::{diff_classes, diff_jars, ApiChangeKind};
+use java_class_parser::remap::Remapper;
+use java_class_parser::{FQName, JavaClassParser};
+
+#[test]
+fn diff_class_apis() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let square = parser.find("com/example/Square").unwrap();
+
+    let report = diff_classes(&rectangle, &square);
+    let kinds = report
+        .changes()
+        .iter()
+        .map(|change| change.kind().clone())
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&ApiChangeKind::SuperClassChanged {
+        from: Some(FQName::new("java/lang/Object").to_fqname_buf()),
+        to: Some(FQName::new("com/example/Rectangle").to_fqname_buf()),
+    }));
+    assert!(kinds.contains(&ApiChangeKind::InterfaceRemoved(
+        FQName::new("com/example/Shape").to_fqname_buf()
+    )));
+    assert!(kinds.contains(&ApiChangeKind::MethodRemoved(Member::new("getArea", "()D"))));
+    assert!(kinds.contains(&ApiChangeKind::MethodAdded {
+        method: Member::new("compareTo", "(Lcom/example/Rectangle;)I"),
+        is_abstract: false,
+    }));
+    // private fields aren't part of the api
+    assert!(!kinds
+        .iter()
+        .any(|kind| matches!(kind, ApiChangeKind::FieldRemoved(_))));
+    assert!(!report.is_binary_compatible());
+
+    let added = report
+        .changes()
+        .iter()
+        .find(|change| matches!(change.kind(), ApiChangeKind::MethodAdded { .. }))
+        .unwrap();
+    assert!(added.is_compatible());
+    assert!(diff_classes(&square, &square).is_empty());
+}
+
+#[test]
+fn abstract_method_added_to_interface() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let shape = parser.find("com/example/Shape").unwrap();
+    let renamed = Remapper::new()
+        .method("com/example/Shape", "getArea", "()D", "area")
+        .remap(&shape)
+        .unwrap();
+    let renamed = java_class_parser::parse_bytes(&renamed[..]).unwrap();
+
+    let report = diff_classes(&shape, &renamed);
+    let added = report
+        .changes()
+        .iter()
+        .find(|change| {
+            change.kind()
+                == &ApiChangeKind::MethodAdded {
+                    method: Member::new("area", "()D"),
+                    is_abstract: true,
+                }
+        })
+        .expect("area should be added");
+    assert!(added.is_binary_compatible());
+    assert!(!added.is_source_compatible());
+    assert!(!report.is_source_compatible());
+}
+
+#[test]
+fn diff_jar_apis() {
+    let old = itest_common::write_jar(
+        "old",
+        &[
+            (
+                "com/example/Shape.class",
+                itest_common::class_bytes("com/example/Shape"),
+            ),
+            (
+                "com/example/Rectangle.class",
+                itest_common::class_bytes("com/example/Rectangle"),
+            ),
+        ],
+    );
+    let new = itest_common::write_jar(
+        "new",
+        &[
+            (
+                "com/example/Rectangle.class",
+                itest_common::class_bytes("com/example/Rectangle"),
+            ),
+            (
+                "com/example/Square.class",
+                itest_common::class_bytes("com/example/Square"),
+            ),
+        ],
+    );
+
+    let report = diff_jars(old.to_str().unwrap(), new.to_str().unwrap()).unwrap();
+    let changes = report
+        .changes()
+        .iter()
+        .map(|change| (change.class().to_string(), change.kind().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            ("com/example/Shape".to_string(), ApiChangeKind::ClassRemoved),
+            ("com/example/Square".to_string(), ApiChangeKind::ClassAdded),
+        ]
+    );
+    assert!(!report.is_binary_compatible());
+    assert_eq!(report.binary_breaking().count(), 1);
+    assert_eq!(
+        report.to_string(),
+        "[breaking] com/example/Shape: removed\n[compatible] com/example/Square: added\n"
+    );
+
+    std::fs::remove_file(old).unwrap();
+    std::fs::remove_file(new).unwrap();
+}
@@ -0,0 +1,206 @@
+use java_class_parser::{ConstantPoolBuilder, FQName, JavaClassParser, ResolvedConstant};
+
+#[test]
+fn typed_accessors() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let pool = square.constant_pool();
+    let indices = || 1..=pool.len() as u16;
+
+    let super_constructor = indices()
+        .filter_map(|index| pool.get_method_ref(index))
+        .find(|method| method.name() == "<init>")
+        .expect("the super constructor should be referenced");
+    assert_eq!(super_constructor.class(), "com/example/Rectangle");
+    assert_eq!(super_constructor.descriptor(), "(DD)V");
+
+    let classes = indices()
+        .filter_map(|index| pool.get_class_name(index))
+        .collect::<Vec<_>>();
+    assert!(classes.contains(&square.this()));
+    assert!(indices()
+        .filter_map(|index| pool.get_name_and_type(index))
+        .any(|name_and_type| name_and_type == ("getArea", "()D")));
+    assert!(indices().any(|index| pool.get_utf8(index) == Some("Code")));
+
+    // accessors don't match constants of a different kind
+    assert!(indices()
+        .filter_map(|index| pool.get_class_name(index).and(pool.get_utf8(index)))
+        .next()
+        .is_none());
+}
+
+#[test]
+fn numeric_accessors() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let pool = constants.constant_pool();
+    let indices = || 1..=pool.len() as u16;
+
+    assert!(indices().any(|index| pool.get_integer(index) == Some(42)));
+    assert!(indices().any(|index| pool.get_long(index) == Some(1 << 40)));
+    assert!(indices().any(|index| pool.get_float(index) == Some(1.5)));
+    assert!(indices().any(|index| pool.get_double(index) == Some(2.25)));
+    assert!(indices().any(|index| pool.get_string(index) == Some("constants")));
+    assert!(indices().any(|index| pool.get_string(index) == Some("\u{1F600}\0")));
+}
+
+#[test]
+fn field_refs() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+    let pool = greeter.constant_pool();
+    let out = (1..=pool.len() as u16)
+        .find_map(|index| pool.get_field_ref(index))
+        .expect("System.out should be referenced");
+    assert_eq!(out.class(), "java/lang/System");
+    assert_eq!(out.name(), "out");
+    assert_eq!(out.descriptor(), "Ljava/io/PrintStream;");
+}
+
+#[test]
+fn resolved_constants() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let resolved = constants.constants().collect::<Vec<_>>();
+
+    assert!(resolved
+        .iter()
+        .all(|(_, constant)| *constant != ResolvedConstant::Invalid));
+    assert!(resolved
+        .iter()
+        .any(|(_, constant)| *constant == ResolvedConstant::Long(1 << 40)));
+    assert!(resolved
+        .iter()
+        .any(|(_, constant)| *constant == ResolvedConstant::String("constants")));
+    assert!(resolved
+        .iter()
+        .any(|(_, constant)| *constant == ResolvedConstant::Class(constants.this())));
+    // the slot after a long or double is skipped
+    for window in resolved.windows(2) {
+        let (index, constant) = &window[0];
+        let step = match constant {
+            ResolvedConstant::Long(_) | ResolvedConstant::Double(_) => 2,
+            _ => 1,
+        };
+        assert_eq!(window[1].0, index + step);
+    }
+
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+    let call_site = greeter
+        .constants()
+        .find_map(|(_, constant)| match constant {
+            ResolvedConstant::InvokeDynamic(call_site) => Some(call_site),
+            _ => None,
+        })
+        .expect("lambdas should be linked with invokedynamic");
+    assert_eq!(call_site.name(), "run");
+    assert!(greeter.constants().any(|(_, constant)| matches!(
+        constant,
+        ResolvedConstant::MethodRef(method) if method.name() == "println"
+    )));
+}
+
+#[test]
+fn string_constants() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let strings = constants.string_constants();
+    assert!(strings.contains(&"constants"));
+    assert!(strings.contains(&"\u{1F600}\0"));
+
+    let methods = constants.methods();
+    let total = methods
+        .iter()
+        .find(|method| method.name() == "total")
+        .unwrap();
+    assert_eq!(total.string_constants(), ["constants"]);
+
+    // the recipe of a string concatenation is a bootstrap argument, not loaded by ldc
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+    assert!(greeter
+        .string_constants()
+        .iter()
+        .any(|string| string.starts_with("hello ")));
+    assert!(greeter
+        .methods()
+        .iter()
+        .all(|method| method.string_constants().is_empty()));
+}
+
+#[test]
+fn build_constant_pool() {
+    let mut builder = ConstantPoolBuilder::new();
+    let method = builder
+        .method_ref("com.example.Rectangle", "<init>", "(DD)V")
+        .unwrap();
+    let long = builder.long(1 << 40).unwrap();
+    let string = builder.string("é\0").unwrap();
+    // the utf8 name comes before the class constant
+    assert_eq!(builder.class("com/example/Rectangle").unwrap(), 2);
+    assert_eq!(builder.utf8("é\0").unwrap(), string - 1);
+    assert_eq!(builder.long(1 << 40).unwrap(), long);
+    assert_eq!(builder.integer(3).unwrap(), string + 1);
+
+    let pool = builder.build();
+    let method = pool.get_method_ref(method).unwrap();
+    assert_eq!(method.class(), "com/example/Rectangle");
+    assert_eq!((method.name(), method.descriptor()), ("<init>", "(DD)V"));
+    assert_eq!(pool.get_long(long), Some(1 << 40));
+    assert_eq!(pool.get_string(string), Some("é\0"));
+    assert_eq!(pool.get_integer(string + 1), Some(3));
+    assert_eq!(pool.get_utf8(long + 2), Some("é\0"));
+}
+
+#[test]
+fn extend_existing_constant_pool() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let pool = square.constant_pool();
+    let mut builder = ConstantPoolBuilder::from(pool);
+    let code = (1..=pool.len() as u16)
+        .find(|&index| pool.get_utf8(index) == Some("Code"))
+        .unwrap();
+    assert_eq!(builder.utf8("Code").unwrap(), code);
+    let added = builder.class("com/example/Generated").unwrap();
+    assert!(added as usize > pool.len());
+
+    let built = builder.build();
+    assert_eq!(
+        built.get_class_name(added).unwrap(),
+        "com/example/Generated"
+    );
+    for index in 1..=pool.len() as u16 {
+        assert_eq!(built.get_utf8(index), pool.get_utf8(index));
+    }
+}
+
+#[test]
+fn referenced_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let referenced = square
+        .referenced_classes()
+        .into_iter()
+        .map(|class| class.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        referenced,
+        [
+            "com/example/Rectangle",
+            "java/lang/Comparable",
+            "java/lang/Object"
+        ]
+    );
+
+    // classes only named in descriptors and annotations are included, arrays by their elements
+    let natives = parser.find("com/example/natives/Natives").unwrap();
+    assert!(natives
+        .referenced_classes()
+        .contains(FQName::new("java/lang/String")));
+    let annotated = parser.find("com/example/annotations/Annotated").unwrap();
+    assert!(annotated
+        .referenced_classes()
+        .contains(FQName::new("java/lang/Deprecated")));
+    assert!(!annotated.referenced_classes().contains(annotated.this()));
+}
@@ -0,0 +1,116 @@
+use java_class_parser::attributes::AttributeKind;
+use java_class_parser::bytecode::Opcode;
+use java_class_parser::dynamic::ReferenceKind;
+use java_class_parser::{HasAttributes, JavaClass, JavaClassParser, ResolvedConstant};
+
+/// The constant pool indices used by the invokedynamic instructions of a method
+fn invokedynamic_indices(class: &JavaClass, method: &str) -> Vec<u16> {
+    let method = class
+        .methods()
+        .into_iter()
+        .find(|m| m.name() == method)
+        .expect("method should exist");
+    let attribute = method
+        .get_attribute("Code")
+        .expect("method should have code");
+    let AttributeKind::Code(code) = attribute.kind() else {
+        unreachable!()
+    };
+    code.instructions()
+        .map(|instruction| instruction.unwrap())
+        .filter(|instruction| instruction.opcode() == Opcode::Invokedynamic)
+        .filter_map(|instruction| instruction.constant_index())
+        .collect()
+}
+
+#[test]
+fn invoke_dynamic() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+
+    let call_sites = invokedynamic_indices(&greeter, "greeting")
+        .into_iter()
+        .map(|index| greeter.invoke_dynamic(index).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(call_sites.len(), 1);
+    assert_eq!(call_sites[0].name(), "run");
+    assert_eq!(
+        call_sites[0].descriptor().jni(),
+        "(Ljava/lang/String;)Ljava/lang/Runnable;"
+    );
+
+    // string concatenation within the lambda is also linked dynamically
+    let concat_index = invokedynamic_indices(&greeter, "lambda$greeting$0")[0];
+    let concat = greeter.invoke_dynamic(concat_index).unwrap();
+    assert_eq!(concat.name(), "makeConcatWithConstants");
+    assert_ne!(
+        concat.bootstrap_method_attr_index(),
+        call_sites[0].bootstrap_method_attr_index()
+    );
+    assert!(greeter.method_handle(concat_index).is_none());
+}
+
+#[test]
+fn method_handles_and_types() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+
+    let handles = (1..u16::MAX)
+        .filter_map(|index| greeter.method_handle(index))
+        .collect::<Vec<_>>();
+    let notify = handles
+        .iter()
+        .find(|handle| handle.name() == "notify")
+        .expect("Object::notify should be referenced by a method handle");
+    assert_eq!(notify.kind(), ReferenceKind::InvokeVirtual);
+    assert_eq!(notify.class(), "java/lang/Object");
+    assert!(!notify.kind().is_field());
+    assert!(handles.iter().any(
+        |handle| handle.kind() == ReferenceKind::InvokeStatic && handle.name() == "metafactory"
+    ));
+
+    let types = (1..u16::MAX)
+        .filter_map(|index| greeter.method_type(index))
+        .map(|signature| signature.jni())
+        .collect::<Vec<_>>();
+    assert!(types.contains(&"()V".to_string()));
+}
+
+#[test]
+fn bootstrap_methods() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let greeter = parser.find("com/example/lambdas/Greeter").unwrap();
+    let index = invokedynamic_indices(&greeter, "greeting")[0];
+    let call_site = greeter.invoke_dynamic(index).unwrap();
+    let bootstrap = greeter
+        .bootstrap_method(&call_site)
+        .expect("call sites should have a bootstrap method");
+
+    let method = bootstrap.method();
+    assert_eq!(method.kind(), ReferenceKind::InvokeStatic);
+    assert_eq!(method.class(), "java/lang/invoke/LambdaMetafactory");
+    assert_eq!(method.name(), "metafactory");
+
+    let arguments = bootstrap.arguments();
+    assert_eq!(arguments.len(), 3);
+    assert!(matches!(
+        &arguments[0],
+        ResolvedConstant::MethodType(descriptor) if descriptor.jni() == "()V"
+    ));
+    let ResolvedConstant::MethodHandle(implementation) = &arguments[1] else {
+        panic!("the implementation of a lambda should be a method handle")
+    };
+    assert_eq!(implementation.class(), greeter.this());
+    assert!(implementation.name().starts_with("lambda$greeting$"));
+
+    // string concatenation is linked with a recipe
+    let concat = greeter
+        .bootstrap_methods()
+        .into_iter()
+        .find(|bootstrap| bootstrap.method().name() == "makeConcatWithConstants")
+        .expect("string concatenation should be linked dynamically");
+    assert_eq!(
+        concat.arguments(),
+        [ResolvedConstant::String("hello \u{1}")]
+    );
+}
@@ -0,0 +1,61 @@
+use java_class_parser::enums::EnumSupport;
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn enum_support_members() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let color = parser
+        .find("com/example/enums/Color")
+        .expect("couldn't find color");
+    assert!(color.is_enum());
+
+    let mut support = color
+        .methods()
+        .iter()
+        .filter_map(|method| color.method_enum_support(method))
+        .collect::<Vec<_>>();
+    support.extend(
+        color
+            .fields()
+            .iter()
+            .filter_map(|field| color.field_enum_support(field)),
+    );
+    assert_eq!(
+        support,
+        [
+            EnumSupport::Values,
+            EnumSupport::ValueOf,
+            EnumSupport::ValuesInitializer,
+            EnumSupport::ValuesField
+        ]
+    );
+
+    let methods = color
+        .methods_without_enum_support()
+        .iter()
+        .map(|method| method.name())
+        .collect::<Vec<_>>();
+    assert_eq!(methods, ["<init>", "next", "<clinit>"]);
+    let fields = color
+        .fields_without_enum_support()
+        .iter()
+        .map(|field| field.name())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["RED", "GREEN", "BLUE"]);
+}
+
+#[test]
+fn switch_map_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let switch_map = parser
+        .find("com/example/enums/Palette$1")
+        .expect("couldn't find switch map class");
+    assert!(switch_map.is_switch_map_class());
+    assert!(switch_map.fields_without_enum_support().is_empty());
+
+    let palette = parser
+        .find("com/example/enums/Palette")
+        .expect("couldn't find palette");
+    assert!(!palette.is_switch_map_class());
+    assert!(!palette.is_enum());
+}
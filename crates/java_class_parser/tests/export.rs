@@ -0,0 +1,32 @@
+use java_class_parser::export::{export_jar, ExportFormat};
+
+#[test]
+fn export_ndjson() {
+    let mut output = vec![];
+    let exported = export_jar(itest_common::jar_file(), &mut output, ExportFormat::Ndjson)
+        .expect("couldn't export jar");
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), exported);
+    assert!(lines
+        .iter()
+        .all(|line| line.starts_with("{\"name\":") && line.ends_with('}')));
+
+    let rectangle = lines
+        .iter()
+        .find(|line| line.starts_with(r#"{"name":"com/example/Rectangle""#))
+        .expect("rectangle wasn't exported");
+    assert!(rectangle.contains(r#""super":"java/lang/Object","interfaces":["com/example/Shape"]"#));
+    assert!(rectangle.contains(r#"{"name":"width","descriptor":"D","access_flags":18}"#));
+    assert!(rectangle.contains(r#""references":["com/example/Shape","java/lang/Object"]"#));
+}
+
+#[test]
+fn export_json_array() {
+    let mut output = vec![];
+    let exported = export_jar(itest_common::jar_file(), &mut output, ExportFormat::Json)
+        .expect("couldn't export jar");
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("[{") && output.ends_with("}]\n"));
+    assert_eq!(output.matches("]},{\"name\"").count(), exported - 1);
+}
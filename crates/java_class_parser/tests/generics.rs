@@ -0,0 +1,98 @@
+use java_class_parser::generics::{FieldTypeSignature, TypeArgument, TypeSignature};
+use java_class_parser::{JavaClassParser, Signature};
+
+#[test]
+fn class_signature() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let container = parser.find("com/example/generics/Container").unwrap();
+    let signature = container.generic_signature().expect("no signature");
+
+    let [parameter] = signature.type_parameters() else {
+        panic!("expected one type parameter")
+    };
+    assert_eq!(parameter.name(), "T");
+    let [FieldTypeSignature::Class(bound)] = parameter.interface_bounds() else {
+        panic!("expected one interface bound")
+    };
+    assert_eq!(bound.name(), "java/lang/Comparable");
+    assert_eq!(
+        bound.type_arguments(),
+        [TypeArgument::Super(FieldTypeSignature::TypeVariable("T"))]
+    );
+    assert_eq!(signature.superclass().name(), "java/util/AbstractList");
+    assert_eq!(
+        signature.superclass().type_arguments(),
+        [TypeArgument::Exact(FieldTypeSignature::TypeVariable("T"))]
+    );
+    assert_eq!(signature.interfaces()[0].name(), "java/util/RandomAccess");
+
+    let square = parser.find("com/example/Square").unwrap();
+    let signature = square.generic_signature().expect("no signature");
+    assert!(signature.type_parameters().is_empty());
+    assert_eq!(signature.superclass().name(), "com/example/Rectangle");
+    assert_eq!(
+        signature.interfaces()[0].to_string(),
+        "Ljava/lang/Comparable<Lcom/example/Rectangle;>;"
+    );
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    assert!(rectangle.generic_signature().is_none());
+}
+
+#[test]
+fn field_signatures() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let container = parser.find("com/example/generics/Container").unwrap();
+    let fields = container.fields();
+    let signature = |name: &str| {
+        fields
+            .iter()
+            .find(|field| field.name() == name)
+            .unwrap()
+            .generic_signature()
+    };
+
+    assert_eq!(
+        signature("counts").unwrap().to_string(),
+        "Ljava/util/Map<Ljava/lang/String;+Ljava/lang/Number;>;"
+    );
+    let Some(FieldTypeSignature::Class(first)) = signature("first") else {
+        panic!("expected a class type")
+    };
+    assert_eq!(first.name(), "com/example/generics/Container$Inner");
+    assert_eq!(first.classes()[0].name(), "com/example/generics/Container");
+    assert_eq!(signature("size"), None);
+}
+
+#[test]
+fn method_signatures() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let container = parser.find("com/example/generics/Container").unwrap();
+    let methods = container.methods();
+    let drain_to = methods
+        .iter()
+        .find(|method| method.name() == "drainTo")
+        .unwrap()
+        .generic_signature()
+        .unwrap();
+
+    assert_eq!(drain_to.type_parameters()[0].name(), "E");
+    assert_eq!(drain_to.parameters().len(), 2);
+    assert_eq!(
+        drain_to.parameters()[1],
+        TypeSignature::Reference(FieldTypeSignature::Array(Box::new(
+            TypeSignature::Primitive(Signature::Int)
+        )))
+    );
+    assert_eq!(
+        drain_to.return_type(),
+        &TypeSignature::Primitive(Signature::Void)
+    );
+    assert_eq!(drain_to.throws(), [FieldTypeSignature::TypeVariable("E")]);
+
+    let size = methods
+        .iter()
+        .find(|method| method.name() == "size")
+        .unwrap();
+    assert!(size.generic_signature().is_none());
+}
@@ -0,0 +1,139 @@
+use java_class_parser::javap::JavapOptions;
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn disassemble_class() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let javap = square.to_javap_string();
+    let lines = javap.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], "  Compiled from \"Square.java\"");
+    assert_eq!(
+        lines[1],
+        "public class com.example.Square extends com.example.Rectangle implements java.lang.Comparable"
+    );
+    assert!(lines.contains(&"  major version: 61"));
+    assert!(lines.contains(&"  flags: (0x0021) ACC_PUBLIC, ACC_SUPER"));
+    assert!(lines.contains(&"Constant pool:"));
+    assert!(lines.contains(
+        &"   #1 = Methodref          #2.#3          // com/example/Rectangle.\"<init>\":(DD)V"
+    ));
+    assert!(lines.contains(&"   #4 = Utf8               com/example/Rectangle"));
+
+    let constructor = lines
+        .iter()
+        .position(|line| *line == "  public com.example.Square(double);")
+        .expect("constructor should be disassembled");
+    assert_eq!(
+        lines[constructor + 1..constructor + 16],
+        [
+            "    descriptor: (D)V",
+            "    flags: (0x0001) ACC_PUBLIC",
+            "    Code:",
+            "      stack=5, locals=3, args_size=2",
+            "         0: aload_0",
+            "         1: dload_1",
+            "         2: dload_1",
+            "         3: invokespecial #1                  // Method com/example/Rectangle.\"<init>\":(DD)V",
+            "         6: return",
+            "      LineNumberTable:",
+            "        line 6: 0",
+            "        line 7: 6",
+            "      LocalVariableTable:",
+            "        Start  Length  Slot  Name   Signature",
+            "            0       7     0  this   Lcom/example/Square;",
+        ]
+    );
+    // members of the class itself aren't qualified
+    assert!(javap.contains("invokevirtual #7                  // Method getArea:()D"));
+    assert!(lines.contains(&"  public int compareTo(java.lang.Object);"));
+    assert!(lines.contains(&"    flags: (0x1041) ACC_PUBLIC, ACC_BRIDGE, ACC_SYNTHETIC"));
+    assert_eq!(lines.last(), Some(&"SourceFile: \"Square.java\""));
+}
+
+#[test]
+fn disassemble_control_flow() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let branches = parser.find("com/example/flow/Branches").unwrap();
+    let javap = branches.to_javap_string();
+    assert!(javap.contains("public class com.example.flow.Branches\n"));
+    assert!(javap.contains(
+        "      Exception table:\n         from    to  target type\n            0     4     5   Class java/lang/NumberFormatException\n"
+    ));
+    assert!(javap.contains(
+        "         1: tableswitch   { // 0 to 2
+                       0: 28
+                       1: 31
+                       2: 34
+                 default: 37
+            }
+        28: ldc           #15                 // String zero
+"
+    ));
+    assert!(javap.contains("        16: iinc          2, 1\n"));
+}
+
+#[test]
+fn disassemble_constants() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let javap = constants.to_javap_string();
+    assert!(javap.contains("  public static final long BIG;\n    descriptor: J\n"));
+    assert!(javap.contains("    ConstantValue: long 1099511627776l\n"));
+    assert!(javap.contains("    ConstantValue: float 1.5f\n"));
+    assert!(javap.contains("    ConstantValue: String \u{1F600}\\u0000\n"));
+    assert!(javap.contains("Long               1099511627776l\n"));
+}
+
+#[test]
+fn javap_options() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    assert_eq!(
+        square.javap_with(JavapOptions::new()).to_string(),
+        "Compiled from \"Square.java\"\n\
+         public class com.example.Square extends com.example.Rectangle implements java.lang.Comparable {\n  \
+         public com.example.Square(double);\n  \
+         public int compareTo(com.example.Rectangle);\n  \
+         public int compareTo(java.lang.Object);\n\
+         }\n"
+    );
+
+    let code = square
+        .javap_with(JavapOptions::new().code(true))
+        .to_string();
+    assert!(code.contains("  public com.example.Square(double);\n    Code:\n         0: aload_0\n"));
+    assert!(code.contains("         6: return\n\n  public int compareTo"));
+    assert!(!code.contains("Constant pool:"));
+    assert!(!code.contains("descriptor:"));
+    assert!(!code.contains("stack="));
+    assert!(!code.contains("LineNumberTable:"));
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let private = rectangle
+        .fields()
+        .into_iter()
+        .find(|field| field.access_flags().is_private())
+        .expect("rectangle should have a private field");
+    let declaration = format!("{} {};", private.signature().to_java(), private.name());
+    assert!(!rectangle
+        .javap_with(JavapOptions::new())
+        .to_string()
+        .contains(&declaration));
+    assert!(rectangle
+        .javap_with(JavapOptions::new().private(true))
+        .to_string()
+        .contains(&declaration));
+
+    let verbose = square
+        .javap_with(JavapOptions::new().verbose(true))
+        .to_string();
+    assert!(verbose.contains("Constant pool:"));
+    assert!(verbose.contains("      stack=5, locals=3, args_size=2\n"));
+    assert!(verbose.contains("SourceFile: \"Square.java\"\n"));
+    assert_eq!(
+        square.javap_with(JavapOptions::all()).to_string(),
+        square.to_javap_string()
+    );
+}
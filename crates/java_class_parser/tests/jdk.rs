@@ -0,0 +1,75 @@
+use java_class_parser::inheritance::{inspect, inspect_complete};
+use java_class_parser::jimage::JImage;
+use java_class_parser::{FQName, JavaClassParser};
+
+#[test]
+fn find_jdk_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file())
+        .with_jdk()
+        .expect("couldn't read the jdk");
+    let object = parser.find("java/lang/Object").unwrap();
+    assert_eq!(object.this(), "java/lang/Object");
+    assert!(parser.find_super(&object).unwrap().is_none());
+    assert!(parser.find("java.util.ArrayList").is_ok());
+    assert!(parser.find("java/lang/DoesNotExist").is_err());
+    assert!(parser
+        .is_assignable("java/util/ArrayList", "java/util/Collection")
+        .unwrap());
+    assert!(!parser
+        .is_assignable("com/example/Square", "java/io/Serializable")
+        .unwrap());
+
+    let square = parser.find("com/example/Square").unwrap();
+    let inheritance = inspect(&square, &parser).unwrap();
+    let mut parents = inheritance
+        .inherits(square.this())
+        .unwrap()
+        .into_iter()
+        .map(|(class, _)| class.this().to_fqname_buf())
+        .collect::<Vec<_>>();
+    parents.sort();
+    parents.dedup();
+    assert_eq!(
+        parents,
+        [
+            "com/example/Rectangle",
+            "com/example/Shape",
+            "java/lang/Comparable",
+            "java/lang/Object"
+        ]
+    );
+    assert!(inheritance.is_complete());
+
+    // the jdk is found even if the parser doesn't use it
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let inheritance = inspect_complete(&square, &parser).unwrap();
+    assert!(inheritance.is_complete());
+    assert!(inheritance
+        .inherits(square.this())
+        .unwrap()
+        .iter()
+        .any(|(class, _)| class.this() == "java/lang/Object"));
+}
+
+#[test]
+fn read_runtime_image() {
+    let image = JImage::locate().unwrap();
+    assert_eq!(image.module_of("java/lang"), Some("java.base"));
+    assert_eq!(image.module_of("java/sql"), Some("java.sql"));
+    let string = image
+        .find_class(FQName::new("java/lang/String"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(string.super_name(), "java/lang/Object");
+    assert_eq!(string.super_class().unwrap(), "java/lang/Object");
+    let object = image
+        .find_class(FQName::new("java/lang/Object"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(object.super_class(), None);
+    assert!(image.get("java.base/java/lang/String.class").is_some());
+    assert!(image
+        .find_class(FQName::new("com/example/Square"))
+        .is_none());
+    assert!(image.class_names().any(|name| name == "java/util/HashMap"));
+}
@@ -0,0 +1,69 @@
+use java_class_parser::{jni, JavaClassParser};
+
+#[test]
+fn jni_symbol_names() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let natives = parser.find("com/example/natives/Natives").unwrap();
+    let symbols = natives
+        .methods()
+        .iter()
+        .filter(|method| method.access_flags().is_native())
+        .map(|method| method.jni_symbol_name(&natives))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        symbols,
+        [
+            "Java_com_example_natives_Natives_add__II",
+            "Java_com_example_natives_Natives_add__JLjava_lang_String_2",
+            "Java_com_example_natives_Natives_init_1lib",
+        ]
+    );
+
+    let methods = natives.methods();
+    let init = methods
+        .iter()
+        .find(|method| method.name() == "init_lib")
+        .unwrap();
+    assert_eq!(
+        init.jni_long_symbol_name(&natives),
+        "Java_com_example_natives_Natives_init_1lib___3Ljava_lang_String_2"
+    );
+}
+
+#[test]
+fn jni_header() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let natives = parser.find("com/example/natives/Natives").unwrap();
+    let header = jni::header(&natives);
+    assert!(header.starts_with("/* DO NOT EDIT THIS FILE - it is machine generated */\n"));
+    assert!(header.contains("#ifndef _Included_com_example_natives_Natives\n"));
+    assert!(header.contains(
+        "\
+/*
+ * Class:     com_example_natives_Natives
+ * Method:    add
+ * Signature: (JLjava/lang/String;)J
+ */
+JNIEXPORT jlong JNICALL Java_com_example_natives_Natives_add__JLjava_lang_String_2
+  (JNIEnv *, jobject, jlong, jstring);
+"
+    ));
+    assert!(header.contains(
+        "\
+ * Method:    init_1lib
+ * Signature: ([Ljava/lang/String;)V
+ */
+JNIEXPORT void JNICALL Java_com_example_natives_Natives_init_1lib
+  (JNIEnv *, jclass, jobjectArray);
+"
+    ));
+    assert!(!header.contains("twice"));
+
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let header = jni::header(&constants);
+    assert!(header.contains("#define com_example_constants_Constants_ANSWER 42L\n"));
+    assert!(header.contains("#define com_example_constants_Constants_BIG 1099511627776LL\n"));
+    assert!(header.contains("#define com_example_constants_Constants_RATIO 1.5f\n"));
+    assert!(header.contains("#define com_example_constants_Constants_PRECISE 2.25\n"));
+    assert!(!header.contains("NAME"));
+}
@@ -0,0 +1,51 @@
+use java_class_parser::{jni, JavaClassParser};
+
+#[test]
+fn rust_bindings() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let natives = parser.find("com/example/natives/Natives").unwrap();
+    let bindings = jni::bindings::generate(&natives);
+    assert!(
+        bindings.contains("use jni::objects::{JClass, JObject, JObjectArray, JString, JValue};\n")
+    );
+    assert!(bindings.contains("use jni::sys::{jint, jlong};\n"));
+    assert!(bindings.contains(
+        "\
+#[no_mangle]
+#[allow(non_snake_case, unused_variables)]
+pub extern \"system\" fn Java_com_example_natives_Natives_add__JLjava_lang_String_2<'local>(
+    env: JNIEnv<'local>,
+    this: JObject<'local>,
+    arg0: jlong,
+    arg1: JString<'local>,
+) -> jlong {
+    todo!()
+}
+"
+    ));
+    assert!(bindings.contains(
+        "\
+pub extern \"system\" fn Java_com_example_natives_Natives_init_1lib<'local>(
+    env: JNIEnv<'local>,
+    class: JClass<'local>,
+    arg0: JObjectArray<'local>,
+) {
+"
+    ));
+    assert!(bindings.contains("pub struct Natives<'local>(pub JObject<'local>);\n"));
+    assert!(bindings.contains("    pub const CLASS: &str = \"com/example/natives/Natives\";\n"));
+    assert!(bindings.contains(
+        "    pub fn add__II(&self, env: &mut JNIEnv<'local>, arg0: jint, arg1: jint) -> jni::errors::Result<jint> {\n"
+    ));
+    assert!(bindings.contains(
+        "        env.call_static_method(Self::CLASS, \"init_lib\", \"([Ljava/lang/String;)V\", &[JValue::Object(arg0)])?.v()\n"
+    ));
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let bindings = jni::bindings::generate(&rectangle);
+    assert!(!bindings.contains("extern \"system\""));
+    assert!(bindings.contains(
+        "pub fn get_area(&self, env: &mut JNIEnv<'local>) -> jni::errors::Result<jdouble> {\n"
+    ));
+    assert!(bindings.contains("env.new_object(Self::CLASS, \"(DD)V\", &[JValue::Double(width), JValue::Double(length)]).map(Self)\n"));
+}
@@ -0,0 +1,62 @@
+use java_class_parser::mapping::Mapping;
+use java_class_parser::JavaClassParser;
+
+const MAPPING: &str = "\
+com.example.obfuscated.Holder -> com.example.obfuscated.a:
+    java.lang.Object value -> b
+    1:1:java.lang.Object get():7:7 -> c
+    1:2:void set(java.lang.Object):11:12 -> c
+";
+
+#[test]
+fn deobfuscated_view() {
+    let mapping = Mapping::parse(MAPPING).unwrap();
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser.find("com/example/obfuscated/a").unwrap();
+    let view = class.deobfuscate(&mapping);
+    assert_eq!(view.original_name(), "com/example/obfuscated/Holder");
+    assert_eq!(view.this(), "com/example/obfuscated/a");
+    assert_eq!(view.original_super_name().unwrap(), "java/lang/Object");
+
+    let fields = view.fields();
+    assert_eq!(fields[0].name(), "b");
+    assert_eq!(fields[0].original_name(), "value");
+
+    let methods = view
+        .methods()
+        .into_iter()
+        .filter(|method| method.is_renamed())
+        .map(|method| (method.original_name(), method.descriptor().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        methods,
+        [
+            ("get", "()Ljava/lang/Object;".to_string()),
+            ("set", "(Ljava/lang/Object;)V".to_string())
+        ]
+    );
+
+    let get = view
+        .methods()
+        .into_iter()
+        .find(|method| method.original_name() == "get")
+        .unwrap();
+    assert_eq!(get.original_line(1), 7);
+    assert_eq!(fields[0].original_line(3), 3);
+}
+
+#[test]
+fn unmapped_class_is_unchanged() {
+    let mapping = Mapping::parse(MAPPING).unwrap();
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser.find("com/example/Rectangle").unwrap();
+    let view = class.deobfuscate(&mapping);
+    assert_eq!(view.original_name(), "com/example/Rectangle");
+    assert!(view.methods().iter().all(|method| !method.is_renamed()));
+
+    let module_info = parser.find("module-info").unwrap();
+    assert_eq!(
+        module_info.deobfuscate(&mapping).original_super_name(),
+        None
+    );
+}
@@ -0,0 +1,43 @@
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn method_metrics() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let branches = parser.find("com/example/flow/Branches").unwrap();
+    let metrics = |name: &str| {
+        branches
+            .methods()
+            .iter()
+            .find(|method| method.name() == name)
+            .unwrap()
+            .metrics()
+            .unwrap()
+            .unwrap()
+    };
+
+    let constructor = metrics("<init>");
+    assert_eq!(constructor.instructions(), 3);
+    assert_eq!(constructor.code_size(), 5);
+    assert_eq!(constructor.complexity(), 1);
+    assert_eq!(constructor.call_sites(), 1);
+    assert_eq!((constructor.max_stack(), constructor.max_locals()), (1, 1));
+
+    let sum = metrics("sum");
+    assert_eq!(sum.code_size(), 24);
+    assert_eq!(sum.basic_blocks(), 4);
+    assert_eq!(sum.complexity(), 2);
+    assert_eq!(sum.call_sites(), 0);
+
+    assert_eq!(metrics("choose").complexity(), 2);
+    // the catch clause is a second path through the method
+    assert_eq!(metrics("parse").complexity(), 2);
+    assert_eq!(metrics("parse").call_sites(), 1);
+    // three cases and the default
+    assert_eq!(metrics("name").complexity(), 4);
+
+    let shape = parser.find("com/example/Shape").unwrap();
+    assert!(shape
+        .methods()
+        .iter()
+        .all(|method| method.metrics().unwrap().is_none()));
+}
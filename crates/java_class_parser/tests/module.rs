@@ -0,0 +1,58 @@
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn module_declaration() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let module_info = parser
+        .find("module-info")
+        .expect("module-info.class should parse");
+    assert!(module_info.access_flags().is_module());
+    assert_eq!(module_info.super_class(), None);
+    let module = module_info.as_module().expect("should declare a module");
+    assert_eq!(module.name(), "com.example");
+    assert!(!module.is_open());
+
+    let requires = module
+        .requires()
+        .iter()
+        .map(|requires| {
+            (
+                requires.module(),
+                requires.is_transitive(),
+                requires.is_static(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(requires.contains(&("java.base", false, false)));
+    assert!(requires.contains(&("java.logging", true, false)));
+    assert!(requires.contains(&("java.sql", false, true)));
+
+    let exports = module
+        .exports()
+        .iter()
+        .map(|exports| (exports.package(), exports.to().to_vec()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        [
+            ("com/example", vec![]),
+            ("com/example/annotations", vec![]),
+            ("com/example/records", vec!["java.logging"]),
+        ]
+    );
+    assert_eq!(module.opens().len(), 1);
+    assert_eq!(module.opens()[0].package(), "com/example/entities");
+    assert!(!module.opens()[0].is_qualified());
+
+    assert_eq!(module.uses(), ["java/lang/Runnable"]);
+    let provides = &module.provides()[0];
+    assert_eq!(provides.service(), "java/lang/Runnable");
+    assert_eq!(provides.with(), ["com/example/lambdas/Task"]);
+
+    // the jar tool records every package of the module
+    assert!(module.packages().contains(&"com/example/constants"));
+    assert_eq!(module.main_class(), None);
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    assert!(rectangle.as_module().is_none());
+}
@@ -0,0 +1,65 @@
+use java_class_parser::{JavaClassParser, Signature};
+
+#[test]
+fn inner_classes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let outer = parser.find("com/example/nested/Outer").unwrap();
+    let inner_classes = outer.inner_classes();
+
+    let nested = inner_classes
+        .iter()
+        .find(|class| class.inner_name() == Some("Nested"))
+        .expect("member classes should be listed");
+    assert_eq!(nested.inner_class(), "com/example/nested/Outer$Nested");
+    assert_eq!(nested.outer_class(), Some(outer.this()));
+    assert!(nested.access_flags().is_public() && nested.access_flags().is_static());
+    assert!(!nested.is_anonymous() && !nested.is_local());
+
+    let inner = inner_classes
+        .iter()
+        .find(|class| class.inner_name() == Some("Inner"))
+        .unwrap();
+    assert!(inner.access_flags().is_private() && !inner.access_flags().is_static());
+
+    let anonymous = inner_classes
+        .iter()
+        .find(|class| class.inner_class() == "com/example/nested/Outer$1")
+        .unwrap();
+    assert!(anonymous.is_anonymous());
+    assert_eq!(anonymous.outer_class(), None);
+
+    let local = inner_classes
+        .iter()
+        .find(|class| class.inner_name() == Some("Local"))
+        .unwrap();
+    assert!(local.is_local());
+    assert!(outer.enclosing_method().is_none());
+}
+
+#[test]
+fn enclosing_method() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let anonymous = parser.find("com/example/nested/Outer$1").unwrap();
+    let enclosing = anonymous
+        .enclosing_method()
+        .expect("anonymous classes have an enclosing method");
+    assert_eq!(enclosing.class(), "com/example/nested/Outer");
+    assert_eq!(enclosing.method_name(), Some("anonymous"));
+    assert_eq!(
+        enclosing.method_descriptor(),
+        Some(&Signature::new("()Ljava/lang/Runnable;").unwrap())
+    );
+
+    let local = parser.find("com/example/nested/Outer$1Local").unwrap();
+    assert_eq!(
+        local.enclosing_method().unwrap().method_name(),
+        Some("local")
+    );
+
+    let nested = parser.find("com/example/nested/Outer$Nested").unwrap();
+    assert!(nested.enclosing_method().is_none());
+    assert!(nested
+        .inner_classes()
+        .iter()
+        .any(|class| class.inner_class() == nested.this()));
+}
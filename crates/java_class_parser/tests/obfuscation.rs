@@ -0,0 +1,29 @@
+use java_class_parser::obfuscation::{ClassObfuscation, JarObfuscation};
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn obfuscated_names() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+
+    let obfuscated = ClassObfuscation::of(&parser.find("com/example/obfuscated/a").unwrap());
+    assert_eq!(obfuscated.short_name_ratio(), 1.0);
+    assert_eq!(obfuscated.illegal_name_ratio(), 0.0);
+    assert!(!obfuscated.missing_debug_info());
+    assert!(obfuscated.score() >= 0.4);
+
+    let rectangle = ClassObfuscation::of(&parser.find("com/example/Rectangle").unwrap());
+    assert_eq!(rectangle.score(), 0.0);
+    assert!(!rectangle.is_likely_obfuscated());
+
+    // the switch map of palette is synthetic, and named with a number
+    let switch_map = ClassObfuscation::of(&parser.find("com/example/enums/Palette$1").unwrap());
+    assert_eq!(switch_map.short_name_ratio(), 0.0);
+    assert!(switch_map.synthetic_density() > 0.0);
+}
+
+#[test]
+fn jar_obfuscation() {
+    let report = JarObfuscation::from_jar(itest_common::jar_file()).unwrap();
+    assert!(!report.is_likely_obfuscated());
+    assert_eq!(report.likely_obfuscated_classes().count(), 0);
+}
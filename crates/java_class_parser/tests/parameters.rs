@@ -0,0 +1,82 @@
+use java_class_parser::attributes::{AttributeKind, ParameterAccessFlags};
+use java_class_parser::{HasAttributes, JavaClass, JavaClassParser, Method, Signature};
+
+fn method<'a>(class: &'a JavaClass, name: &str) -> Method<'a> {
+    class
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == name)
+        .expect("method should exist")
+}
+
+#[test]
+fn method_parameters() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let range = parser.find("com/example/records/Range").unwrap();
+    // javac always records the parameters of the canonical constructor of a record
+    let constructor = method(&range, "<init>");
+    assert!(constructor.get_attribute("MethodParameters").is_some());
+    assert_eq!(
+        constructor.parameters(),
+        [
+            (Some("low"), ParameterAccessFlags::empty(), Signature::Int),
+            (Some("high"), ParameterAccessFlags::empty(), Signature::Long),
+            (
+                Some("tag"),
+                ParameterAccessFlags::empty(),
+                Signature::FullyQualifiedClass("java/lang/Object")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn local_variable_parameter_names() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let constructor = method(&rectangle, "<init>");
+    assert!(constructor.get_attribute("MethodParameters").is_none());
+    // doubles take two local variable slots
+    let names = constructor
+        .parameters()
+        .into_iter()
+        .map(|(name, _, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("width"), Some("length")]);
+
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    assert_eq!(
+        method(&constants, "total").parameters(),
+        [(Some("count"), ParameterAccessFlags::empty(), Signature::Int)]
+    );
+}
+
+#[test]
+fn local_variable_table() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let constructor = method(&rectangle, "<init>");
+    let AttributeKind::Code(code) = constructor.get_attribute("Code").unwrap().kind().clone()
+    else {
+        unreachable!()
+    };
+    let variables = code
+        .local_variables()
+        .into_iter()
+        .map(|variable| {
+            (
+                variable.name(),
+                variable.index(),
+                variable.descriptor().jni(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        variables,
+        [
+            ("this", 0, "Lcom/example/Rectangle;".to_string()),
+            ("width", 1, "D".to_string()),
+            ("length", 3, "D".to_string()),
+        ]
+    );
+}
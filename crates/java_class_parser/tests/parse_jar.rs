@@ -1,6 +1,15 @@
-use java_class_parser::inheritance::inspect;
-use java_class_parser::JavaClassParser;
+use java_class_parser::attributes::{
+    Code, ConstantValue, Exceptions, InnerClasses, JavaConstant, LineNumberTable, ModulePackages,
+    PermittedSubclasses, Record, RuntimeVisibleAnnotations, SourceFile,
+};
+use java_class_parser::cache::CachePolicy;
+use java_class_parser::error::ErrorKind;
+use java_class_parser::inheritance::{inspect, GraphBuilder, InheritKind, InheritanceIndex};
+use java_class_parser::scan::scan_bytes;
+use java_class_parser::version::JavaVersion;
+use java_class_parser::{parse_bytes, parse_bytes_lazy, FQName, HasAttributes, JavaClassParser};
 use std::path::Path;
+use std::rc::Rc;
 
 #[test]
 fn parse_jar() {
@@ -10,10 +19,11 @@ fn parse_jar() {
         .expect("couldn't get square");
     let super_class = parser
         .find_super(&class)
-        .expect("Square should have a super class that's on the classpath");
+        .expect("Square should have a super class that's on the classpath")
+        .expect("Square should have a super class");
     assert_eq!(super_class.this(), "com/example/Rectangle");
     assert!(
-        matches!(parser.find_super(&super_class), Err(_)),
+        parser.find_super(&super_class).is_err(),
         "Rectangle should have no available super class"
     );
 
@@ -25,6 +35,333 @@ fn parse_jar() {
         .map(|(class, _)| class.this().to_fqname_buf())
         .collect::<Vec<_>>();
     assert_eq!(parents, ["com/example/Rectangle", "com/example/Shape"]);
+
+    // classes outside the classpath are external
+    assert!(!inheritance.is_complete());
+    let mut external = inheritance.external_types().collect::<Vec<_>>();
+    external.sort();
+    assert_eq!(external, ["java/lang/Comparable", "java/lang/Object"]);
+    let ancestors = inheritance.ancestors(class.this()).unwrap();
+    // super classes first, then interfaces
+    let ancestors = ancestors
+        .iter()
+        .map(|(class, kind)| (class.name().as_str(), class.is_missing(), *kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ancestors,
+        [
+            ("com/example/Rectangle", false, InheritKind::Extends),
+            ("java/lang/Object", true, InheritKind::Extends),
+            ("java/lang/Comparable", true, InheritKind::Implements),
+            ("com/example/Shape", false, InheritKind::Implements),
+        ]
+    );
+
+    let common = |a: &str, b: &str| {
+        inheritance
+            .least_common_supertype(FQName::new(a), FQName::new(b))
+            .unwrap()
+            .map(|name| name.as_str().to_string())
+    };
+    assert_eq!(
+        common("com/example/Square", "com/example/Rectangle").as_deref(),
+        Some("com/example/Rectangle")
+    );
+    assert_eq!(
+        common("com/example/Square", "com/example/Shape").as_deref(),
+        Some("com/example/Shape")
+    );
+    assert_eq!(
+        common("java/lang/Comparable", "com/example/Shape").as_deref(),
+        None
+    );
+    assert!(inheritance
+        .least_common_supertype(class.this(), FQName::new("java/util/List"))
+        .is_err());
+}
+
+#[test]
+fn inheritance_index() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let index = InheritanceIndex::build(&parser).unwrap();
+    assert!(index.contains("com/example/Square"));
+    assert!(!index.contains("java/lang/Object"));
+
+    assert_eq!(
+        index.subclasses_of("com/example/Rectangle"),
+        ["com/example/Square"]
+    );
+    assert_eq!(
+        index.implementors_of("com/example/Shape"),
+        ["com/example/Rectangle", "com/example/Square"]
+    );
+    assert_eq!(
+        index
+            .direct_subtypes("com/example/Shape")
+            .collect::<Vec<_>>(),
+        [(
+            FQName::new("com/example/Rectangle"),
+            InheritKind::Implements
+        )]
+    );
+    assert!(index
+        .subclasses_of("java/lang/Object")
+        .contains(&FQName::new("com/example/Rectangle")));
+    assert!(index
+        .implementors_of("java/lang/Runnable")
+        .contains(&FQName::new("com/example/lambdas/Task")));
+
+    assert_eq!(
+        index.subtypes_of("com/example/Shape"),
+        ["com/example/Rectangle", "com/example/Square"]
+    );
+    let annotation = FQName::new("com/example/annotations/Marker");
+    assert!(index
+        .subtypes_of("java/lang/annotation/Annotation")
+        .contains(&annotation));
+    assert!(!index
+        .implementors_of("java/lang/annotation/Annotation")
+        .contains(&annotation));
+}
+
+#[test]
+fn assignability() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    assert!(parser
+        .is_assignable("com/example/Square", "com.example.Shape")
+        .unwrap());
+    assert!(parser
+        .is_assignable("com/example/Shape", "java/lang/Object")
+        .unwrap());
+    assert!(parser
+        .is_assignable("[Lcom/example/Square;", "[Lcom/example/Rectangle;")
+        .unwrap());
+    assert!(parser
+        .is_assignable("[[I", "[Ljava/lang/Cloneable;")
+        .unwrap());
+    assert!(!parser.is_assignable("[I", "[J").unwrap());
+    assert!(parser.is_assignable("char", "int").unwrap());
+    assert!(!parser.is_assignable("int", "char").unwrap());
+    assert!(!parser.is_assignable("int", "java/lang/Integer").unwrap());
+    assert!(!parser
+        .is_assignable("com/example/Rectangle", "[Lcom/example/Rectangle;")
+        .unwrap());
+    // Square implements Comparable, which can't be found without the jdk
+    let error = parser
+        .is_assignable("com/example/Square", "java/io/Serializable")
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NoClassFound(_)));
+
+    let square = parser.find("com/example/Square").unwrap();
+    let inheritance = inspect(&square, &parser).unwrap();
+    assert!(inheritance
+        .is_subtype_of(square.this(), FQName::new("java/lang/Comparable"))
+        .unwrap());
+    assert!(!inheritance
+        .is_subtype_of(FQName::new("com/example/Rectangle"), square.this())
+        .unwrap());
+
+    let dot = inheritance.to_dot();
+    assert!(dot.starts_with("digraph {"));
+    assert!(dot.contains("[label=\"com/example/Square\", style=bold]"));
+    assert!(dot.contains("[label=\"java/lang/Object\", style=dashed]"));
+    assert_eq!(dot.matches("style=solid").count(), 3);
+    assert_eq!(dot.matches("-> ").count(), inheritance.edges().count());
+}
+
+#[test]
+fn method_resolution() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let inheritance = inspect(&square, &parser).unwrap();
+
+    let (class, method) = inheritance
+        .resolve_method(square.this(), "getArea", "()D")
+        .unwrap()
+        .expect("getArea should be inherited from Rectangle");
+    assert_eq!(class.this(), "com/example/Rectangle");
+    assert_eq!(method.name(), "getArea");
+    let (class, method) = inheritance
+        .resolve_method(FQName::new("com/example/Shape"), "getArea", "()D")
+        .unwrap()
+        .unwrap();
+    assert_eq!(class.this(), "com/example/Shape");
+    assert!(method.access_flags().is_abstract());
+    // declared by Object, which isn't on the classpath
+    assert!(inheritance
+        .resolve_method(square.this(), "hashCode", "()I")
+        .unwrap()
+        .is_none());
+    assert!(inheritance
+        .resolve_method(FQName::new("java/util/List"), "size", "()I")
+        .is_err());
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let get_area = rectangle
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "getArea")
+        .unwrap();
+    let overridden = inheritance
+        .overrides_of(rectangle.this(), &get_area)
+        .unwrap()
+        .into_iter()
+        .map(|(class, method)| (class.this().as_str(), method.name()))
+        .collect::<Vec<_>>();
+    assert_eq!(overridden, [("com/example/Shape", "getArea")]);
+    let constructor = square
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "<init>")
+        .unwrap();
+    assert!(inheritance
+        .overrides_of(square.this(), &constructor)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn all_members() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let methods = parser.all_methods(&square).unwrap();
+    let inherited = methods
+        .iter()
+        .filter(|member| member.is_inherited())
+        .map(|member| {
+            (
+                member.declaring_class().this().to_fqname_buf(),
+                member.method().name().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    // Shape's getArea is overridden by Rectangle, and constructors aren't inherited
+    assert_eq!(
+        inherited,
+        [(
+            FQName::new("com/example/Rectangle").to_fqname_buf(),
+            "getArea".to_string()
+        )]
+    );
+    assert_eq!(
+        methods
+            .iter()
+            .filter(|member| !member.is_inherited())
+            .count(),
+        square.methods().len()
+    );
+
+    // Rectangle's fields are private
+    assert!(parser.all_fields(&square).unwrap().is_empty());
+    let rectangle = parser.find_super(&square).unwrap().unwrap();
+    let fields = parser.all_fields(&rectangle).unwrap();
+    let names = fields
+        .iter()
+        .map(|member| member.field().name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["width", "length"]);
+    assert!(fields.iter().all(|member| !member.is_inherited()));
+}
+
+#[test]
+fn multiple_roots() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let task = parser.find("com/example/lambdas/Task").unwrap();
+    assert!(GraphBuilder::new(&parser).build().unwrap().is_none());
+    let graph = GraphBuilder::new(&parser)
+        .root(square.clone())
+        .roots([task.clone(), square.clone()])
+        .build()
+        .unwrap()
+        .unwrap();
+    assert_eq!(graph.root(), square.this());
+    assert_eq!(
+        graph.roots().collect::<Vec<_>>(),
+        [square.this(), task.this()]
+    );
+    // java/lang/Object is shared by both hierarchies
+    assert_eq!(
+        graph
+            .types()
+            .filter(|class| class.name() == "java/lang/Object")
+            .count(),
+        1
+    );
+    assert!(graph
+        .is_subtype_of(task.this(), FQName::new("java/lang/Runnable"))
+        .unwrap());
+    assert!(graph
+        .is_subtype_of(square.this(), FQName::new("com/example/Shape"))
+        .unwrap());
+
+    let mut extended = inspect(&square, &parser).unwrap();
+    let edges = extended.edges().count();
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    extended.extend(&rectangle, &parser).unwrap();
+    assert_eq!(extended.edges().count(), edges);
+    extended.extend(&task, &parser).unwrap();
+    assert_eq!(extended.edges().count(), graph.edges().count());
+    assert_eq!(extended.roots().count(), 3);
+}
+
+#[test]
+fn inheritance_cycles() {
+    let read = |name: &str| std::fs::read(itest_common::classes().join(name)).unwrap();
+    let mut parser = JavaClassParser::default();
+    // Rectangle extends Square, which extends Rectangle
+    let rectangle = itest_common::replace_utf8(
+        &read("com/example/Rectangle.class"),
+        "java/lang/Object",
+        "com/example/Square",
+    );
+    parser
+        .register_bytes("com/example/Rectangle", rectangle)
+        .unwrap();
+    let square = parser
+        .register_bytes("com/example/Square", read("com/example/Square.class"))
+        .unwrap();
+
+    let error = inspect(&square, &parser).unwrap_err();
+    let ErrorKind::InheritanceCycle(cycle) = error.kind() else {
+        panic!("expected a cycle, but got {error}");
+    };
+    assert_eq!(
+        cycle,
+        &[
+            FQName::new("com/example/Rectangle").to_fqname_buf(),
+            FQName::new("com/example/Square").to_fqname_buf(),
+            FQName::new("com/example/Rectangle").to_fqname_buf(),
+        ]
+    );
+    assert_eq!(
+        error.to_string(),
+        "inheritance cycle: com/example/Rectangle -> com/example/Square -> com/example/Rectangle"
+    );
+    let error = parser.resolve("com/example/Square").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InheritanceCycle(cycle) if cycle.len() == 3));
+    // checking assignability still terminates
+    assert!(parser
+        .is_assignable("com/example/Square", "com/example/Shape")
+        .unwrap());
+}
+
+#[test]
+fn find_by_binary_name() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser
+        .find("com.example.Square")
+        .expect("couldn't get square");
+    assert_eq!(class.this(), "com/example/Square");
+    assert!(parser.find("com/example/Square").is_ok());
+
+    // names that aren't java identifiers are still legal in the JVM
+    let error = parser.find("com/example/1Square").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NoClassFound(_)));
+    let error = parser.find("com/example//Square").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidName(_)));
+    let error = parser.find("[Lcom/example/Square;").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NotAClass(_)));
 }
 
 #[test]
@@ -32,3 +369,411 @@ fn get_java_install() {
     let java_home = java_locator::locate_java_home().unwrap();
     println!("java_home: {:?}", Path::new(&java_home));
 }
+
+#[test]
+fn parse_numeric_constants() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser
+        .find("com/example/constants/Constants")
+        .expect("classes with numeric constants should parse");
+    let fields = class
+        .fields()
+        .iter()
+        .map(|field| field.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        ["ANSWER", "BIG", "RATIO", "PRECISE", "NAME", "SMILE"]
+    );
+    // long and double constants take up two slots, so entries after them must not be shifted
+    let descriptors = class
+        .fields()
+        .iter()
+        .map(|field| field.signature().jni())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        descriptors,
+        [
+            "I",
+            "J",
+            "F",
+            "D",
+            "Ljava/lang/String;",
+            "Ljava/lang/String;"
+        ]
+    );
+    assert_eq!(class.methods().len(), 2);
+}
+
+#[test]
+fn parse_modified_utf8() {
+    // the string constants of the class use modified utf8 for a supplementary character and null
+    let bytes =
+        std::fs::read(itest_common::classes().join("com/example/constants/Constants.class"))
+            .unwrap();
+    let embedded = scan_bytes(&bytes).collect::<Vec<_>>();
+    assert_eq!(embedded.len(), 1, "class should be well formed");
+    assert_eq!(
+        embedded[0].class().this(),
+        "com/example/constants/Constants"
+    );
+}
+
+/// Replaces the bytes of a utf8 constant in a class file with other bytes of the same length
+fn replace_utf8(class: &mut [u8], from: &str, to: &[u8]) {
+    assert_eq!(from.len(), to.len());
+    let mut constant = vec![1];
+    constant.extend((from.len() as u16).to_be_bytes());
+    constant.extend(from.as_bytes());
+    let offset = class
+        .windows(constant.len())
+        .position(|window| window == constant)
+        .expect("the constant should be in the class")
+        + 3;
+    class[offset..offset + to.len()].copy_from_slice(to);
+}
+
+#[test]
+fn invalid_member_utf8() {
+    let bytes =
+        std::fs::read(itest_common::classes().join("com/example/constants/Constants.class"))
+            .unwrap();
+
+    // a lone surrogate can't be decoded, so it's replaced in the name of the field
+    let mut lone_surrogate = bytes.clone();
+    replace_utf8(&mut lone_surrogate, "BIG", &[0xED, 0xA0, 0x80]);
+    let class = parse_bytes(&lone_surrogate[..]).expect("the class should still parse");
+    let field = &class.fields()[1];
+    assert_eq!(field.name(), "\u{FFFD}");
+    assert_eq!(field.signature().jni(), "J");
+
+    let mut invalid_descriptor = bytes;
+    replace_utf8(&mut invalid_descriptor, "J", b"Q");
+    let error = parse_bytes(&invalid_descriptor[..]).expect_err("Q isn't a descriptor");
+    assert!(matches!(error.kind(), ErrorKind::InvalidDescriptor(descriptor) if descriptor == "Q"));
+}
+
+#[test]
+fn class_file_version() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    assert_eq!(square.class_file_version(), (61, 0));
+    assert_eq!(square.java_version(), Some(JavaVersion::Java17));
+    assert!(!square.is_preview());
+}
+
+#[test]
+fn access_flags() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let shape = parser.find("com/example/Shape").unwrap();
+    assert!(shape.access_flags().is_interface());
+    assert!(shape.access_flags().is_abstract());
+    assert!(shape
+        .methods()
+        .iter()
+        .all(|method| method.access_flags().is_abstract()));
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let flags = rectangle.access_flags();
+    assert!(flags.is_public() && !flags.is_interface() && !flags.is_enum());
+    for field in rectangle.fields() {
+        let flags = field.access_flags();
+        assert!(flags.is_private() && flags.is_final() && !flags.is_static());
+    }
+
+    let color = parser.find("com/example/enums/Color").unwrap();
+    assert!(color.access_flags().is_enum());
+    assert!(color
+        .fields()
+        .iter()
+        .any(|field| field.access_flags().is_enum() && field.access_flags().is_static()));
+    assert!(color
+        .methods()
+        .iter()
+        .any(|method| method.name() == "$values" && method.access_flags().is_synthetic()));
+}
+
+#[test]
+fn thrown_exceptions() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let resource = parser.find("com/example/io/Resource").unwrap();
+    let thrown = |name: &str| {
+        resource
+            .methods()
+            .iter()
+            .find(|method| method.name() == name)
+            .unwrap()
+            .thrown_exceptions()
+            .iter()
+            .map(|exception| exception.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        thrown("open"),
+        ["java/io/IOException", "java/lang/InterruptedException"]
+    );
+    assert_eq!(thrown("close"), ["java/io/IOException"]);
+    assert!(thrown("<init>").is_empty());
+}
+
+#[test]
+fn constant_values() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser.find("com/example/constants/Constants").unwrap();
+    let values = class
+        .fields()
+        .iter()
+        .map(|field| field.constant_value())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            Some(JavaConstant::Integer(42)),
+            Some(JavaConstant::Long(1 << 40)),
+            Some(JavaConstant::Float(1.5)),
+            Some(JavaConstant::Double(2.25)),
+            Some(JavaConstant::String("constants")),
+            Some(JavaConstant::String("\u{1F600}\0")),
+        ]
+    );
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    assert!(rectangle
+        .fields()
+        .iter()
+        .all(|field| field.constant_value().is_none()));
+}
+
+#[test]
+fn lone_surrogate_constant() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let class = parser.find("com/example/constants/Surrogates").unwrap();
+    let fields = class.fields();
+    let lone = fields.iter().find(|field| field.name() == "LONE").unwrap();
+    assert_eq!(
+        lone.constant_value(),
+        Some(JavaConstant::String("a\u{FFFD}b"))
+    );
+    let value = lone.get::<ConstantValue>().unwrap();
+    let pool = class.constant_pool();
+    assert_eq!(pool.get_string(value.constant_pool_index()), None);
+    assert_eq!(
+        pool.get_string_lossy(value.constant_pool_index()),
+        Some("a\u{FFFD}b")
+    );
+}
+
+#[test]
+fn typed_attributes() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    assert_eq!(
+        rectangle.get::<SourceFile>().unwrap().path(),
+        Path::new("Rectangle.java")
+    );
+    assert!(rectangle.get::<Code>().is_none());
+
+    let methods = rectangle.methods();
+    let area = methods
+        .iter()
+        .find(|method| method.name() == "getArea")
+        .unwrap();
+    let code = area.get::<Code>().unwrap();
+    assert!(!code.code().is_empty());
+    let lines = code.get::<LineNumberTable>().unwrap();
+    assert!(!lines.entries().is_empty());
+
+    let constants = parser.find("com/example/constants/Constants").unwrap();
+    let fields = constants.fields();
+    let value = fields[0].get::<ConstantValue>().unwrap();
+    assert_eq!(value.value(), JavaConstant::Integer(42));
+
+    let resource = parser.find("com/example/io/Resource").unwrap();
+    let methods = resource.methods();
+    let close = methods.iter().find(|method| method.name() == "close");
+    let exceptions = close.unwrap().get::<Exceptions>().unwrap();
+    assert_eq!(exceptions.exceptions(), ["java/io/IOException"]);
+
+    let outer = parser.find("com/example/nested/Outer").unwrap();
+    let inner_classes = outer.get::<InnerClasses>().unwrap();
+    assert_eq!(inner_classes.classes(), outer.inner_classes());
+
+    let point = parser.find("com/example/records/Point").unwrap();
+    let record = point.get::<Record>().unwrap();
+    assert_eq!(
+        record.components().len(),
+        point.record_components().unwrap().len()
+    );
+
+    let vehicle = parser.find("com/example/sealed/Vehicle").unwrap();
+    let permitted = vehicle.get::<PermittedSubclasses>().unwrap();
+    assert_eq!(
+        permitted.classes(),
+        ["com/example/sealed/Car", "com/example/sealed/Truck"]
+    );
+
+    let annotated = parser.find("com/example/annotations/Annotated").unwrap();
+    let annotations = annotated.get::<RuntimeVisibleAnnotations>().unwrap();
+    assert_eq!(
+        annotations.annotations().len(),
+        annotated.annotations().len()
+    );
+
+    let module_info = parser.find("module-info").unwrap();
+    let packages = module_info.get::<ModulePackages>().unwrap();
+    assert!(packages.packages().contains(&"com/example"));
+}
+
+#[test]
+fn line_numbers() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let methods = square.methods();
+    let constructor = methods
+        .iter()
+        .find(|method| method.name() == "<init>")
+        .unwrap();
+    let code = constructor.get::<Code>().unwrap();
+    let lines = code.get::<LineNumberTable>().unwrap();
+
+    // super(side, side) on line 6 is followed by the implicit return on line 7
+    assert_eq!(
+        lines.ranges().collect::<Vec<_>>(),
+        [(0..6, 6), (6..u16::MAX, 7)]
+    );
+    assert_eq!(lines.pc_to_line(0), Some(6));
+    assert_eq!(lines.pc_to_line(3), Some(6));
+    assert_eq!(lines.pc_to_line(6), Some(7));
+    assert_eq!(lines.line_to_pcs(6), [0]);
+    assert_eq!(lines.line_to_pcs(7), [6]);
+    assert!(lines.line_to_pcs(5).is_empty());
+}
+
+#[test]
+fn cache_policy() {
+    let parser =
+        JavaClassParser::from(itest_common::jar_file()).with_cache_policy(CachePolicy::Disabled);
+    assert_eq!(parser.cache_policy(), CachePolicy::Disabled);
+    let square = parser.find("com/example/Square").unwrap();
+    assert_eq!(square.this(), "com/example/Square");
+    parser.clear_cache();
+    assert_eq!(
+        parser.find("com/example/Square").unwrap().this(),
+        square.this()
+    );
+
+    let parser =
+        JavaClassParser::from(itest_common::jar_file()).with_cache_policy(CachePolicy::Bounded(1));
+    parser.find("com/example/Square").unwrap();
+    parser.find("com/example/Rectangle").unwrap();
+    assert_eq!(
+        parser.find("com/example/Square").unwrap().this(),
+        "com/example/Square"
+    );
+}
+
+#[test]
+fn lazy_parsing() {
+    let eager = JavaClassParser::from(itest_common::jar_file());
+    let parser = JavaClassParser::from(itest_common::jar_file()).with_lazy_parsing(true);
+    let square = parser.find("com/example/Square").unwrap();
+    assert_eq!(square.this(), "com/example/Square");
+    assert_eq!(square.super_name(), "com/example/Rectangle");
+    assert!(!square.is_decoded());
+
+    let expected = eager.find("com/example/Square").unwrap();
+    assert_eq!(square.methods().len(), expected.methods().len());
+    assert!(square.is_decoded());
+    assert!(square.decode().is_ok());
+    assert!(square.decode_error().is_none());
+
+    let bytes = std::fs::read(itest_common::classes().join("com/example/Square.class")).unwrap();
+    let truncated = parse_bytes_lazy(&bytes[..bytes.len() - 4]).unwrap();
+    assert_eq!(truncated.this(), "com/example/Square");
+    assert!(truncated.decode().is_err());
+    assert!(truncated.methods().is_empty());
+    let error = truncated
+        .decode_error()
+        .expect("truncated class should be invalid");
+    match truncated.decode().unwrap_err().kind() {
+        ErrorKind::InvalidClassBody(cached) => assert!(std::ptr::eq(&**cached, error)),
+        other => panic!("expected an invalid class body, got {other:?}"),
+    }
+    assert!(parse_bytes_lazy(&bytes[..16]).is_err());
+}
+
+#[test]
+fn register_classes() {
+    let read = |name: &str| std::fs::read(itest_common::classes().join(name)).unwrap();
+    let mut parser = JavaClassParser::default();
+    assert!(parser.find("com/example/Square").is_err());
+
+    let square = parser
+        .register_bytes("com.example.Square", read("com/example/Square.class"))
+        .unwrap();
+    assert_eq!(square.this(), "com/example/Square");
+    assert!(parser.find_super(&square).is_err());
+
+    let rectangle = JavaClassParser::from(itest_common::jar_file())
+        .find("com/example/Rectangle")
+        .unwrap();
+    parser.register_class(rectangle);
+    let found = parser.find("com/example/Square").unwrap();
+    assert_eq!(
+        parser.find_super(&found).unwrap().unwrap().this(),
+        "com/example/Rectangle"
+    );
+
+    let error = parser
+        .register_bytes("com/example/Circle", read("com/example/Square.class"))
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NameMismatch { .. }));
+}
+
+#[test]
+fn parent_delegation() {
+    let parent = Rc::new(JavaClassParser::from(itest_common::jar_file()));
+    let child = JavaClassParser::default().with_parent(parent.clone());
+    let square = child.find("com/example/Square").unwrap();
+    assert_eq!(
+        child.find_super(&square).unwrap().unwrap().this(),
+        "com/example/Rectangle"
+    );
+    assert!(child.parent().is_some());
+    assert!(matches!(
+        child.find("com/example/Missing").unwrap_err().kind(),
+        ErrorKind::NoClassFound(_)
+    ));
+
+    // classes the parent can't find are found on the child's classpath
+    let child = JavaClassParser::from(itest_common::classes())
+        .with_parent(Rc::new(JavaClassParser::default()));
+    assert_eq!(
+        child.find("com/example/Square").unwrap().this(),
+        "com/example/Square"
+    );
+}
+
+#[test]
+fn missing_classes_are_remembered() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("missing-classes");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("com/example")).unwrap();
+    let parser = JavaClassParser::from(&dir);
+    assert!(parser.find("com/example/Square").is_err());
+
+    std::fs::copy(
+        itest_common::classes().join("com/example/Square.class"),
+        dir.join("com/example/Square.class"),
+    )
+    .unwrap();
+    assert!(matches!(
+        parser.find("com/example/Square").unwrap_err().kind(),
+        ErrorKind::NoClassFound(_)
+    ));
+    parser.clear_missing();
+    assert_eq!(
+        parser.find("com/example/Square").unwrap().this(),
+        "com/example/Square"
+    );
+}
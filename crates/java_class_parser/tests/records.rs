@@ -0,0 +1,74 @@
+use java_class_parser::records::{CanonicalConstructorKind, RecordDeviation};
+use java_class_parser::{HasAttributes, JavaClassParser, Signature};
+
+#[test]
+fn implicit_record_members() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let point = parser
+        .find("com/example/records/Point")
+        .expect("couldn't find point");
+    let members = point.record_members().expect("point is a record");
+
+    let components = members
+        .components()
+        .iter()
+        .map(|component| (component.name(), component.descriptor().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        components,
+        [("x", Signature::Double), ("y", Signature::Double)]
+    );
+    assert_eq!(
+        members.canonical_constructor_kind(),
+        Some(CanonicalConstructorKind::Implicit)
+    );
+    assert!(members.accessor("x").is_some());
+    assert!(members.accessor("y").is_some());
+    assert_eq!(members.deviations(), []);
+}
+
+#[test]
+fn declared_record_members() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let range = parser
+        .find("com/example/records/Range")
+        .expect("couldn't find range");
+    let members = range.record_members().expect("range is a record");
+
+    assert!(members.has_compact_constructor());
+    assert_eq!(
+        members.deviations(),
+        [
+            RecordDeviation::DeclaredCanonicalConstructor(CanonicalConstructorKind::Compact),
+            RecordDeviation::DeclaredAccessor("high"),
+        ]
+    );
+
+    let shape = parser
+        .find("com/example/Shape")
+        .expect("couldn't find shape");
+    assert!(shape.record_members().is_none());
+}
+
+#[test]
+fn record_components() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let tagged = parser.find("com/example/records/Tagged").unwrap();
+    assert!(tagged.is_record());
+    assert!(!parser.find("com/example/Rectangle").unwrap().is_record());
+
+    let components = tagged.record_components().unwrap();
+    assert_eq!(components.len(), 2);
+    let (name, weight) = (&components[0], &components[1]);
+    assert_eq!(
+        name.descriptor(),
+        &Signature::FullyQualifiedClass("java/lang/String")
+    );
+    assert_eq!(name.signature(), None);
+    let annotations = name.annotations();
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].type_name(), "com/example/annotations/Named");
+    assert!(name.get_attribute("RuntimeVisibleAnnotations").is_some());
+    assert!(weight.annotations().is_empty());
+    assert_eq!(weight.attributes().count(), 0);
+}
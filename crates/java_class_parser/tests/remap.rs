@@ -0,0 +1,162 @@
+use java_class_parser::remap::Remapper;
+use java_class_parser::{parse_bytes, parse_file, JavaClassParser, ResolvedConstant};
+
+#[test]
+fn map_names() {
+    let remapper = Remapper::new()
+        .package("com/example", "shaded/com/example")
+        .class("com.example.Square", "com.example.Quadrilateral");
+    assert_eq!(
+        remapper.map_class("com/example/Square").as_str(),
+        "com/example/Quadrilateral"
+    );
+    assert_eq!(
+        remapper
+            .map_class("com/example/nested/Outer$Inner")
+            .as_str(),
+        "shaded/com/example/nested/Outer$Inner"
+    );
+    assert_eq!(
+        remapper.map_class("java/lang/Object").as_str(),
+        "java/lang/Object"
+    );
+    assert_eq!(
+        remapper.map_descriptor("(ILcom/example/Rectangle;[Lcom/example/Square;)V"),
+        "(ILshaded/com/example/Rectangle;[Lcom/example/Quadrilateral;)V"
+    );
+    assert_eq!(
+        remapper.map_signature("Ljava/lang/Object;Ljava/lang/Comparable<Lcom/example/Rectangle;>;"),
+        "Ljava/lang/Object;Ljava/lang/Comparable<Lshaded/com/example/Rectangle;>;"
+    );
+}
+
+#[test]
+fn rename_class() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let remapped = Remapper::new()
+        .class("com/example/Square", "com/example/Quadrilateral")
+        .remap(&square)
+        .unwrap();
+
+    let remapped = parse_bytes(&remapped[..]).unwrap();
+    assert_eq!(remapped.this().as_str(), "com/example/Quadrilateral");
+    assert_eq!(remapped.super_name().as_str(), "com/example/Rectangle");
+    assert_eq!(remapped.methods().len(), square.methods().len());
+    assert!(
+        !remapped
+            .constants()
+            .any(|(_, constant)| constant == ResolvedConstant::Utf8("com/example/Square")),
+        "old name should be compacted away"
+    );
+}
+
+#[test]
+fn relocate_package() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let remapped = Remapper::new()
+        .package("com.example", "shaded.com.example")
+        .remap(&square)
+        .unwrap();
+
+    let remapped = parse_bytes(&remapped[..]).unwrap();
+    assert_eq!(remapped.this().as_str(), "shaded/com/example/Square");
+    assert_eq!(
+        remapped.super_name().as_str(),
+        "shaded/com/example/Rectangle"
+    );
+    assert_eq!(
+        remapped
+            .generic_signature()
+            .expect("square has a generic signature")
+            .to_string(),
+        square
+            .generic_signature()
+            .unwrap()
+            .to_string()
+            .replace("com/example", "shaded/com/example")
+    );
+    let compare_to = remapped
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == "compareTo" && !method.access_flags().is_synthetic())
+        .expect("square should have compareTo");
+    assert!(compare_to
+        .signature()
+        .to_string()
+        .contains("shaded/com/example/Rectangle"));
+
+    let owners = remapped
+        .constants()
+        .filter_map(|(_, constant)| match constant {
+            ResolvedConstant::MethodRef(method) => Some(method.class().as_str().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(owners.contains(&"shaded/com/example/Rectangle".to_string()));
+    assert!(owners.contains(&"shaded/com/example/Square".to_string()));
+    assert!(!owners.iter().any(|owner| owner.starts_with("com/")));
+}
+
+#[test]
+fn rename_method() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let remapper = Remapper::new()
+        .method("com/example/Rectangle", "getArea", "()D", "area")
+        .method("com/example/Square", "getArea", "()D", "area");
+
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let remapped = parse_bytes(&remapper.remap(&rectangle).unwrap()[..]).unwrap();
+    let names = remapped
+        .methods()
+        .iter()
+        .map(|method| method.name().to_string())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&"area".to_string()));
+    assert!(!names.contains(&"getArea".to_string()));
+
+    let square = parser.find("com/example/Square").unwrap();
+    let remapped = parse_bytes(&remapper.remap(&square).unwrap()[..]).unwrap();
+    let called = remapped
+        .constants()
+        .filter_map(|(_, constant)| match constant {
+            ResolvedConstant::MethodRef(method) => Some(method.name().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(called.contains(&"area".to_string()));
+    assert!(!called.contains(&"getArea".to_string()));
+}
+
+#[test]
+fn relocate_every_class() {
+    let remapper = Remapper::new().package("com/example", "shaded/com/example");
+    let files = itest_common::class_files();
+    assert!(!files.is_empty());
+    for file in files {
+        let class = parse_file(&file).unwrap();
+        let remapped = remapper
+            .remap(&class)
+            .unwrap_or_else(|e| panic!("couldn't remap {file:?}: {e}"));
+        let remapped = parse_bytes(&remapped[..]).unwrap();
+        remapped.decode().unwrap();
+        assert_eq!(
+            remapped.this(),
+            &*remapper.map_class(class.this()),
+            "{file:?} wasn't renamed"
+        );
+        assert_eq!(remapped.fields().len(), class.fields().len());
+        assert_eq!(remapped.methods().len(), class.methods().len());
+        for method in remapped.methods() {
+            assert!(
+                !method.signature().to_string().contains("com/example/")
+                    || method
+                        .signature()
+                        .to_string()
+                        .contains("shaded/com/example/"),
+                "{file:?} has an unmapped descriptor"
+            );
+        }
+    }
+}
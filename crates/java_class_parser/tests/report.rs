@@ -0,0 +1,79 @@
+use java_class_parser::report::{api_report, ReportFormat};
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn html_report() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let report = api_report(&parser, ReportFormat::Html).unwrap();
+
+    let index = report.page("index.html").expect("no index page");
+    assert!(index.contains("<a href=\"com.example.html\">com.example</a>"));
+    let package = report.page("com.example.html").expect("no package page");
+    assert!(package.contains("<a href=\"com.example.Square.html\">Square</a>"));
+
+    let square = report
+        .page("com.example.Square.html")
+        .expect("no page for square");
+    assert!(square.contains("<a href=\"com.example.Rectangle.html\">com.example.Rectangle</a>"));
+    assert!(square.contains("<code>java.lang.Object</code>"));
+    assert!(square.contains("<h2>Constructors</h2>"));
+    let rectangle = report.page("com.example.Rectangle.html").unwrap();
+    assert!(rectangle.contains("<h2>Known subtypes</h2>"));
+    assert!(rectangle.contains("com.example.Square.html"));
+}
+
+#[test]
+fn deprecation() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let annotated = parser.find("com/example/annotations/Annotated").unwrap();
+    assert!(annotated.is_deprecated());
+    let fields = annotated.fields();
+    let legacy = fields
+        .iter()
+        .find(|field| field.name() == "legacy")
+        .expect("annotated should have legacy");
+    assert!(legacy.is_deprecated());
+    assert!(annotated
+        .methods()
+        .iter()
+        .all(|method| !method.is_deprecated()));
+
+    let report = api_report(&parser, ReportFormat::Markdown).unwrap();
+    let page = report
+        .page("com.example.annotations.Annotated.md")
+        .expect("no page for annotated");
+    assert!(page.starts_with("[All packages](index.md)"));
+    assert!(page.contains("# Class com.example.annotations.Annotated\n\n**Deprecated.**"));
+    assert!(page.contains("@java.lang.Deprecated public int legacy` **Deprecated.**"));
+    let package = report.page("com.example.annotations.md").unwrap();
+    assert!(package.contains("[Annotated](com.example.annotations.Annotated.md) **Deprecated.**"));
+}
+
+#[test]
+fn only_public_api() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let report = api_report(&parser, ReportFormat::Markdown).unwrap();
+    for (name, page) in report.pages() {
+        assert!(name.ends_with(".md"), "{name} isn't markdown");
+        assert!(!page.contains("private "), "{name} has private members");
+        assert!(
+            !page.contains("<clinit>"),
+            "{name} has a static initializer"
+        );
+    }
+    let constants = report.page("com.example.constants.Constants.md").unwrap();
+    assert!(constants.contains("`public static final int ANSWER`"));
+}
+
+#[test]
+fn write_report() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let report = api_report(&parser, ReportFormat::Html).unwrap();
+    let directory = std::env::temp_dir().join("java_class_parser_report");
+    let _ = std::fs::remove_dir_all(&directory);
+    report.write_to(&directory).unwrap();
+    for (name, page) in report.pages() {
+        assert_eq!(std::fs::read_to_string(directory.join(name)).unwrap(), page);
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,45 @@
+use java_class_parser::resolved::{ClassRef, ResolvedType};
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn resolve_class() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser
+        .resolve("com/example/Square")
+        .expect("couldn't resolve square");
+
+    let super_classes = square
+        .super_classes()
+        .map(|class| (class.name().to_string(), class.is_missing()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        super_classes,
+        [
+            ("com/example/Rectangle".to_string(), false),
+            ("java/lang/Object".to_string(), true)
+        ]
+    );
+
+    let rectangle = square.super_class().and_then(ClassRef::resolved).unwrap();
+    assert!(rectangle
+        .interfaces()
+        .iter()
+        .any(|interface| interface.name() == "com/example/Shape" && !interface.is_missing()));
+    assert!(matches!(
+        square.interfaces(),
+        [ClassRef::Missing(name)] if name == "java/lang/Comparable"
+    ));
+
+    let compare_to = square
+        .methods()
+        .iter()
+        .find(|method| method.name() == "compareTo" && method.return_type().class_ref().is_none())
+        .expect("no compareTo(Rectangle)");
+    assert!(matches!(compare_to.return_type(), ResolvedType::Int));
+    match compare_to.parameters() {
+        [ResolvedType::Class(ClassRef::Resolved(class))] => {
+            assert_eq!(class.this(), "com/example/Rectangle")
+        }
+        other => panic!("unexpected parameters {other:?}"),
+    }
+}
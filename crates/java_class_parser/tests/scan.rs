@@ -0,0 +1,79 @@
+use java_class_parser::scan::{scan, scan_bytes};
+use java_class_parser::transform::{add_attribute, strip_debug_info};
+use java_class_parser::{parse_bytes, parse_file, HasAttributes};
+
+#[test]
+fn scan_embedded_classes() {
+    let classes = itest_common::classes();
+    let point = std::fs::read(classes.join("com/example/records/Point.class")).unwrap();
+    let color = std::fs::read(classes.join("com/example/enums/Color.class")).unwrap();
+
+    let mut blob = b"#!/bin/sh\nexit 0\n".to_vec();
+    let point_offset = blob.len();
+    blob.extend(&point);
+    // a magic that isn't the start of a class file
+    blob.extend([0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x01]);
+    let color_offset = blob.len();
+    blob.extend(&color);
+    blob.extend(b"trailing data");
+
+    let found = scan(&blob[..]).expect("couldn't read blob");
+    let found = found
+        .iter()
+        .map(|embedded| {
+            (
+                embedded.offset(),
+                embedded.len(),
+                embedded.class().this().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            (
+                point_offset,
+                point.len(),
+                "com/example/records/Point".to_string()
+            ),
+            (
+                color_offset,
+                color.len(),
+                "com/example/enums/Color".to_string()
+            )
+        ]
+    );
+}
+
+#[test]
+fn scan_truncated_class() {
+    let point =
+        std::fs::read(itest_common::classes().join("com/example/records/Point.class")).unwrap();
+    for len in 0..point.len() {
+        assert_eq!(scan_bytes(&point[..len]).count(), 0);
+    }
+}
+
+#[test]
+fn scan_class_with_truncated_attributes() {
+    let point =
+        parse_file(itest_common::classes().join("com/example/records/Point.class")).unwrap();
+    let mut bytes = strip_debug_info(&point).unwrap();
+    for (name, info) in [
+        ("SourceFile", vec![]),
+        ("Signature", vec![0]),
+        ("Code", vec![0, 1]),
+    ] {
+        let class = parse_bytes(&bytes[..]).unwrap();
+        bytes = add_attribute(&class, name, |_| Ok(info)).unwrap();
+    }
+
+    let found = scan_bytes(&bytes).collect::<Vec<_>>();
+    assert_eq!(found.len(), 1);
+    let class = found[0].class();
+    assert_eq!(class.this(), point.this());
+    for name in ["SourceFile", "Signature", "Code"] {
+        assert!(class.get_attribute(name).is_none(), "{name} was resolved");
+    }
+    assert_eq!(class.methods().len(), point.methods().len());
+}
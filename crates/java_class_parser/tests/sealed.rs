@@ -0,0 +1,84 @@
+use java_class_parser::sealed::{check, SealedViolation};
+use java_class_parser::{FQName, JavaClassParser};
+use std::path::Path;
+
+#[test]
+fn permitted_subclasses() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let vehicle = parser.find("com/example/sealed/Vehicle").unwrap();
+    assert!(vehicle.is_sealed());
+    assert_eq!(
+        vehicle.permitted_subclasses().unwrap(),
+        ["com/example/sealed/Car", "com/example/sealed/Truck"]
+    );
+    let car = parser.find("com/example/sealed/Car").unwrap();
+    assert!(!car.is_sealed());
+    assert_eq!(car.permitted_subclasses(), None);
+
+    assert_eq!(check(&parser).unwrap(), []);
+}
+
+#[test]
+fn sealed_violations() {
+    let read = |name: &str| std::fs::read(itest_common::classes().join(name)).unwrap();
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("sealed_violations");
+    let package = dir.join("com/example/sealed");
+    std::fs::create_dir_all(&package).unwrap();
+    std::fs::create_dir_all(dir.join("com/example")).unwrap();
+
+    // permits a class that can't be found and a class that doesn't implement it, instead of Car
+    // and Truck
+    let vehicle = read("com/example/sealed/Vehicle.class");
+    let vehicle = itest_common::replace_utf8(
+        &vehicle,
+        "com/example/sealed/Car",
+        "com/example/sealed/Bike",
+    );
+    let vehicle =
+        itest_common::replace_utf8(&vehicle, "com/example/sealed/Truck", "com/example/Square");
+    std::fs::write(package.join("Vehicle.class"), vehicle).unwrap();
+    for class in [
+        "com/example/sealed/Car.class",
+        "com/example/sealed/Truck.class",
+        "com/example/Square.class",
+    ] {
+        std::fs::write(dir.join(class), read(class)).unwrap();
+    }
+
+    let parser = JavaClassParser::from(dir.clone());
+    let violations = check(&parser).unwrap();
+    let name = |name: &str| FQName::new(name).to_fqname_buf();
+    assert_eq!(
+        violations,
+        [
+            SealedViolation::NotPermitted {
+                sealed: name("com/example/sealed/Vehicle"),
+                subclass: name("com/example/sealed/Car"),
+                location: Some(dir.clone()),
+            },
+            SealedViolation::NotPermitted {
+                sealed: name("com/example/sealed/Vehicle"),
+                subclass: name("com/example/sealed/Truck"),
+                location: Some(dir.clone()),
+            },
+            SealedViolation::PermittedNotFound {
+                sealed: name("com/example/sealed/Vehicle"),
+                permitted: name("com/example/sealed/Bike"),
+                location: Some(dir.clone()),
+            },
+            SealedViolation::NotASubclass {
+                sealed: name("com/example/sealed/Vehicle"),
+                permitted: name("com/example/Square"),
+                location: Some(dir.clone()),
+            },
+        ]
+    );
+    assert_eq!(
+        violations[0].to_string(),
+        format!(
+            "com/example/sealed/Car extends sealed com/example/sealed/Vehicle, which doesn't \
+             permit it (in {})",
+            dir.display()
+        )
+    );
+}
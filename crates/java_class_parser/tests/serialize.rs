@@ -0,0 +1,90 @@
+use java_class_parser::compat::diff_classes;
+use java_class_parser::inheritance::inspect;
+use java_class_parser::JavaClassParser;
+use serde_json::json;
+
+#[test]
+fn serialize_inheritance_graph() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let graph = inspect(&square, &parser).unwrap();
+    let value = serde_json::to_value(&graph).unwrap();
+
+    assert_eq!(value["root"], "com/example/Square");
+    assert_eq!(value["roots"], json!(["com/example/Square"]));
+    let types = value["types"].as_array().unwrap();
+    assert_eq!(types.len(), graph.types().count());
+    assert_eq!(
+        types[0],
+        json!({"name": "com/example/Square", "external": false})
+    );
+    assert!(types.contains(&json!({"name": "java/lang/Object", "external": true})));
+    let edges = value["edges"].as_array().unwrap();
+    assert!(edges.contains(&json!({
+        "class": "com/example/Square",
+        "inherits": "com/example/Rectangle",
+        "kind": "Extends"
+    })));
+    assert!(edges.contains(&json!({
+        "class": "com/example/Rectangle",
+        "inherits": "com/example/Shape",
+        "kind": "Implements"
+    })));
+}
+
+#[test]
+fn serialize_class() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let value = serde_json::to_value(&square).unwrap();
+
+    assert_eq!(value["name"], "com/example/Square");
+    assert_eq!(value["super_class"], "com/example/Rectangle");
+    assert_eq!(value["interfaces"], json!(["java/lang/Comparable"]));
+    assert!(value["access_flags"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("PUBLIC")));
+
+    let methods = value["methods"].as_array().unwrap();
+    assert_eq!(methods.len(), square.methods().len());
+    let init = methods
+        .iter()
+        .find(|method| method["name"] == "<init>")
+        .unwrap();
+    assert!(init["descriptor"].as_str().unwrap().ends_with(")V"));
+    let code = init["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|attribute| attribute["name"] == "Code")
+        .unwrap();
+    assert!(code["value"]["code_length"].as_u64().unwrap() > 0);
+
+    let object = parser.find("java/lang/Object");
+    if let Ok(object) = object {
+        assert_eq!(
+            serde_json::to_value(&object).unwrap()["super_class"],
+            json!(null)
+        );
+    }
+}
+
+#[test]
+fn serialize_compat_report() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let rectangle = parser.find("com/example/Rectangle").unwrap();
+    let square = parser.find("com/example/Square").unwrap();
+    let report = diff_classes(&rectangle, &square);
+    let value = serde_json::to_value(&report).unwrap();
+
+    let changes = value.as_array().unwrap();
+    assert_eq!(changes.len(), report.changes().len());
+    assert!(changes.contains(&json!({
+        "class": "com/example/Square",
+        "kind": "MethodRemoved",
+        "description": "removed method getArea()D",
+        "binary_compatible": false,
+        "source_compatible": false,
+    })));
+}
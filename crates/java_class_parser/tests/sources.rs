@@ -0,0 +1,37 @@
+use java_class_parser::sources::SourceIndex;
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn index_source_files() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let index = SourceIndex::build(&parser).unwrap();
+
+    let palette = index
+        .classes("com/example/enums/Palette.java")
+        .iter()
+        .map(|class| class.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(palette.len(), 2);
+    assert!(palette.contains(&"com/example/enums/Palette".to_string()));
+    assert!(palette.contains(&"com/example/enums/Palette$1".to_string()));
+
+    assert_eq!(
+        index.sources_named("Rectangle.java").collect::<Vec<_>>(),
+        ["com/example/Rectangle.java"]
+    );
+    assert!(index.classes("Missing.java").is_empty());
+}
+
+#[test]
+fn find_line() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let index = SourceIndex::build(&parser).unwrap();
+
+    // only the outer class has bytecode for the returns within the switch
+    let classes = index.find_line("com/example/enums/Palette.java", 8);
+    assert_eq!(classes.len(), 1);
+    assert_eq!(classes[0].name(), "com/example/enums/Palette");
+    assert!(index
+        .find_line("com/example/enums/Palette.java", 100)
+        .is_empty());
+}
@@ -0,0 +1,85 @@
+use java_class_parser::attributes::AttributeKind;
+use java_class_parser::stack_map::{StackMapFrame, StackMapTable, VerificationType};
+use java_class_parser::{FQName, HasAttributes, JavaClass, JavaClassParser};
+
+/// Calls `f` with the stack map table of a method
+fn with_stack_map<F: FnOnce(Option<StackMapTable>)>(class: &JavaClass, method: &str, f: F) {
+    let method = class
+        .methods()
+        .into_iter()
+        .find(|m| m.name() == method)
+        .expect("method should exist");
+    let attribute = method.get_attribute("Code").unwrap();
+    let AttributeKind::Code(code) = attribute.kind() else {
+        unreachable!()
+    };
+    f(code.stack_map_table())
+}
+
+#[test]
+fn compact_frames() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let branches = parser.find("com/example/flow/Branches").unwrap();
+
+    with_stack_map(&branches, "sum", |table| {
+        let table = table.unwrap();
+        assert_eq!(
+            table.entries(),
+            [
+                StackMapFrame::Append {
+                    offset_delta: 4,
+                    locals: vec![VerificationType::Integer, VerificationType::Integer],
+                },
+                StackMapFrame::Chop {
+                    offset_delta: 17,
+                    chopped: 1,
+                },
+            ]
+        );
+        let offsets = table.frames().map(|(offset, _)| offset).collect::<Vec<_>>();
+        assert_eq!(offsets, [4, 22]);
+    });
+
+    with_stack_map(&branches, "choose", |table| {
+        assert_eq!(
+            table.unwrap().entries(),
+            [
+                StackMapFrame::Same { offset_delta: 8 },
+                StackMapFrame::SameLocals1StackItem {
+                    offset_delta: 0,
+                    stack: VerificationType::Integer,
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn full_frames() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let range = parser.find("com/example/records/Range").unwrap();
+    with_stack_map(&range, "<init>", |table| {
+        let table = table.unwrap();
+        let [StackMapFrame::Full {
+            offset_delta: 19,
+            locals,
+            stack,
+        }] = table.entries()
+        else {
+            panic!("expected a single full frame, found {:?}", table.entries())
+        };
+        assert_eq!(
+            locals,
+            &[
+                VerificationType::Object(range.this()),
+                VerificationType::Integer,
+                VerificationType::Long,
+                VerificationType::Object(FQName::new("java/lang/Object")),
+            ]
+        );
+        assert!(stack.is_empty());
+    });
+
+    // code without branches has no stack map
+    with_stack_map(&range, "low", |table| assert!(table.is_none()));
+}
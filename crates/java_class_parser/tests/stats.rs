@@ -0,0 +1,58 @@
+use java_class_parser::stats::{ClassStats, DebugInfo, JarStats};
+use java_class_parser::JavaClassParser;
+
+#[test]
+fn class_stats() {
+    let path = itest_common::classes().join("com/example/records/Point.class");
+    let point = JavaClassParser::parse_file(&path).expect("couldn't parse point");
+    let stats = ClassStats::from_class(&point);
+
+    assert_eq!(stats.name(), "com/example/records/Point");
+    assert_eq!(
+        stats.size() as u64,
+        std::fs::metadata(&path).unwrap().len(),
+        "computed size should match the class file"
+    );
+    assert_eq!(stats.fields(), 2);
+    assert!(stats.bytecode_size() > 0);
+    assert!(stats.constant_pool_referenced() <= stats.constant_pool_entries());
+    assert!(stats.constant_pool_utilization() > 0.9);
+    assert!(stats.debug_info().source_file());
+    assert!(stats.debug_info().line_numbers());
+    assert!(stats.attribute_sizes().contains_key("Record"));
+    assert!(stats.attribute_sizes().contains_key("LineNumberTable"));
+}
+
+#[test]
+fn wide_constant_stats() {
+    let path = itest_common::classes().join("com/example/constants/Constants.class");
+    let constants = JavaClassParser::parse_file(&path).expect("couldn't parse constants");
+    let stats = ClassStats::from_class(&constants);
+    assert_eq!(
+        stats.size() as u64,
+        std::fs::metadata(&path).unwrap().len(),
+        "computed size should match the class file"
+    );
+    assert!(stats.constant_pool_referenced() <= stats.constant_pool_entries());
+}
+
+#[test]
+fn jar_stats() {
+    let stats = JarStats::from_jar(itest_common::jar_file()).expect("couldn't read jar");
+    assert!(!stats.classes().is_empty());
+    assert!(stats.resources() > 0, "the manifest is a resource");
+    assert_eq!(
+        stats.methods(),
+        stats
+            .classes()
+            .iter()
+            .map(ClassStats::methods)
+            .sum::<usize>()
+    );
+    assert_eq!(
+        stats.classes_with_debug_info(DebugInfo::source_file),
+        stats.classes().len()
+    );
+    let attributes = stats.attribute_sizes().values().sum::<usize>();
+    assert!(attributes < stats.classes_size());
+}
@@ -0,0 +1,170 @@
+use java_class_parser::attributes::Code;
+use java_class_parser::transform::{
+    add_attribute, compact_constant_pool, retain_attributes, strip_annotations, strip_debug_info,
+    DEBUG_ATTRIBUTES,
+};
+use java_class_parser::{
+    parse_bytes, parse_file, HasAttributes, JavaClass, JavaClassParser, ResolvedConstant,
+};
+
+fn attribute_names<A: HasAttributes>(has_attributes: &A) -> Vec<String> {
+    has_attributes
+        .attributes()
+        .map(|attribute| attribute.attribute_name().to_string())
+        .collect()
+}
+
+#[test]
+fn retain_every_attribute() {
+    let files = itest_common::class_files();
+    assert!(!files.is_empty());
+    for file in files {
+        let bytes = std::fs::read(&file).unwrap();
+        let class = parse_file(&file).unwrap();
+        let retained = retain_attributes(&class, |_| true).unwrap();
+        assert_eq!(retained, bytes, "{file:?} changed");
+    }
+}
+
+#[test]
+fn strip_debug_info_from_class() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let original = square.to_bytes().unwrap();
+    let stripped = strip_debug_info(&square).unwrap();
+    assert!(stripped.len() < original.len());
+
+    let stripped = parse_bytes(&stripped[..]).unwrap();
+    assert_eq!(stripped.this(), square.this());
+    assert!(stripped.get_attribute("SourceFile").is_none());
+    assert_eq!(stripped.methods().len(), square.methods().len());
+    for method in stripped.methods() {
+        let code = method.get::<Code>().expect("method should have code");
+        assert!(!code.code().is_empty());
+        let names = attribute_names(&code);
+        assert!(
+            DEBUG_ATTRIBUTES
+                .iter()
+                .all(|name| !names.iter().any(|n| n == name)),
+            "{names:?}"
+        );
+    }
+}
+
+fn strip(class: &JavaClass, visible: bool) -> JavaClass {
+    parse_bytes(&strip_annotations(class, visible).unwrap()[..]).unwrap()
+}
+
+#[test]
+fn strip_annotations_by_visibility() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let annotated = parser.find("com/example/annotations/Annotated").unwrap();
+    assert!(!annotated.annotations().is_empty());
+    assert!(!annotated.invisible_annotations().is_empty());
+
+    let without_visible = strip(&annotated, true);
+    assert!(without_visible.annotations().is_empty());
+    assert_eq!(
+        without_visible.invisible_annotations().len(),
+        annotated.invisible_annotations().len()
+    );
+    for method in without_visible.methods() {
+        assert!(method.get_attribute("RuntimeVisibleAnnotations").is_none());
+        assert!(method
+            .get_attribute("RuntimeVisibleParameterAnnotations")
+            .is_none());
+        assert!(method
+            .get_attribute("RuntimeVisibleTypeAnnotations")
+            .is_none());
+    }
+
+    let without_invisible = strip(&annotated, false);
+    assert!(without_invisible.invisible_annotations().is_empty());
+    assert_eq!(
+        without_invisible.annotations().len(),
+        annotated.annotations().len()
+    );
+}
+
+#[test]
+fn strip_record_component_annotations() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let tagged = parser.find("com/example/records/Tagged").unwrap();
+    let name = &tagged.record_components().unwrap()[0];
+    assert!(name.get_attribute("RuntimeVisibleAnnotations").is_some());
+
+    let stripped = strip(&tagged, true);
+    let components = stripped
+        .record_components()
+        .expect("should still be a record");
+    assert_eq!(components.len(), 2);
+    assert!(components.iter().all(|component| component
+        .get_attribute("RuntimeVisibleAnnotations")
+        .is_none()));
+}
+
+#[test]
+fn compact_after_stripping() {
+    let files = itest_common::class_files();
+    for file in files {
+        let class = parse_file(&file).unwrap();
+        let compacted = compact_constant_pool(&class).unwrap();
+        let compacted = parse_bytes(&compacted[..]).unwrap();
+        assert_eq!(compacted.this(), class.this(), "{file:?}");
+        assert_eq!(compacted.super_class(), class.super_class(), "{file:?}");
+        assert_eq!(compacted.interfaces(), class.interfaces(), "{file:?}");
+        assert_eq!(compacted.methods().len(), class.methods().len(), "{file:?}");
+    }
+
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let stripped = parse_bytes(&strip_debug_info(&square).unwrap()[..]).unwrap();
+    let compacted = parse_bytes(&compact_constant_pool(&stripped).unwrap()[..]).unwrap();
+    assert!(compacted.constant_pool().len() < stripped.constant_pool().len());
+    let strings = compacted
+        .constants()
+        .filter_map(|(_, constant)| match constant {
+            ResolvedConstant::Utf8(utf8) => Some(utf8),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for name in DEBUG_ATTRIBUTES.iter().chain(&["Square.java", "this"]) {
+        assert!(!strings.contains(name), "{name} wasn't removed");
+    }
+    assert!(strings.contains(&"Code"));
+
+    let original_methods = square.methods();
+    for (method, original) in compacted.methods().iter().zip(&original_methods) {
+        assert_eq!(method.name(), original.name());
+        assert_eq!(method.signature(), original.signature());
+        let code = method.get::<Code>().unwrap();
+        let original_code = original.get::<Code>().unwrap();
+        let members = |code: &Code| {
+            code.resolved_instructions()
+                .map(|instruction| {
+                    let instruction = instruction.unwrap();
+                    (
+                        instruction.opcode(),
+                        format!("{:?}", instruction.constant()),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(members(&code), members(&original_code));
+    }
+}
+
+#[test]
+fn add_source_file() {
+    let parser = JavaClassParser::from(itest_common::jar_file());
+    let square = parser.find("com/example/Square").unwrap();
+    let stripped = parse_bytes(&strip_debug_info(&square).unwrap()[..]).unwrap();
+    let added = add_attribute(&stripped, "SourceFile", |constants| {
+        Ok(constants.utf8("Generated.java")?.to_be_bytes().to_vec())
+    })
+    .unwrap();
+    let added = parse_bytes(&added[..]).unwrap();
+    let source_file = added.get_attribute("SourceFile").unwrap();
+    assert!(format!("{:?}", source_file.kind()).contains("Generated.java"));
+    assert_eq!(added.methods().len(), square.methods().len());
+}
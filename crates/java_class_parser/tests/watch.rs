@@ -0,0 +1,44 @@
+use itest_common::classes;
+use java_class_parser::error::ErrorKind;
+use java_class_parser::{JavaClass, JavaClassParser};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Finds a class until a predicate returns true, giving the watcher time to see changes
+fn find_until<F: FnMut(Option<&JavaClass>) -> bool>(
+    parser: &JavaClassParser,
+    mut predicate: F,
+) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        match parser.find("com/example/Square") {
+            Ok(class) if predicate(Some(&class)) => return true,
+            Err(e) if matches!(e.kind(), ErrorKind::NoClassFound(_)) && predicate(None) => {
+                return true
+            }
+            _ => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+    false
+}
+
+#[test]
+fn watched_classes_are_invalidated() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("watched_classes");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("com/example")).unwrap();
+    let square = dir.join("com/example/Square.class");
+
+    let parser = JavaClassParser::from(&dir).with_watcher().unwrap();
+    assert!(find_until(&parser, |class| class.is_none()));
+
+    // the missing class is looked for again once it's compiled
+    std::fs::copy(classes().join("com/example/Square.class"), &square).unwrap();
+    assert!(find_until(&parser, |class| class
+        .map_or(false, |class| class.this() == "com/example/Square")));
+
+    // the cached class is parsed again once it changes
+    std::fs::copy(classes().join("com/example/Rectangle.class"), &square).unwrap();
+    assert!(find_until(&parser, |class| class
+        .map_or(false, |class| class.this() == "com/example/Rectangle")));
+}
@@ -0,0 +1,12 @@
+use java_class_parser::parse_file;
+
+#[test]
+fn write_unchanged_classes() {
+    let files = itest_common::class_files();
+    assert!(!files.is_empty());
+    for file in files {
+        let bytes = std::fs::read(&file).unwrap();
+        let class = parse_file(&file).unwrap();
+        assert_eq!(class.to_bytes().unwrap(), bytes, "{file:?} changed");
+    }
+}
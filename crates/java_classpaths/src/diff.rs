@@ -0,0 +1,203 @@
+//! Set operations on classpaths, and diffs of the classes they contain.
+//!
+//! A diff compares both the entries of two classpaths and the classes that are visible through
+//! them. A class is visible through the first entry that contains it, so a class can change even
+//! if it's on both classpaths, when a different entry shadows it.
+//!
+//! # Example
+//! ```no_run
+//! # use java_classpaths::Classpath;
+//! let old = Classpath::from_iter(["app.jar", "lib/guava-31.jar"]);
+//! let new = Classpath::from_iter(["app.jar", "lib/guava-32.jar"]);
+//! let diff = old.diff(&new).unwrap();
+//! for class in diff.removed_classes() {
+//!     println!("{class} is no longer on the classpath");
+//! }
+//! ```
+
+use crate::{Classpath, ClasspathError};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// A class that is visible through a different entry in the new classpath
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShadowChange {
+    /// The internal name of the class, such as `com/example/Square`
+    pub name: String,
+    /// The entry the class was found in on the old classpath
+    pub old_entry: PathBuf,
+    /// The entry the class is found in on the new classpath
+    pub new_entry: PathBuf,
+}
+
+/// The differences between two classpaths, created by [`Classpath::diff`]. Classes are sorted by
+/// name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClasspathDiff {
+    added_entries: Vec<PathBuf>,
+    removed_entries: Vec<PathBuf>,
+    added_classes: Vec<String>,
+    removed_classes: Vec<String>,
+    shadow_changes: Vec<ShadowChange>,
+}
+
+impl ClasspathDiff {
+    /// The entries only on the new classpath, in classpath order
+    pub fn added_entries(&self) -> &[PathBuf] {
+        &self.added_entries
+    }
+
+    /// The entries only on the old classpath, in classpath order
+    pub fn removed_entries(&self) -> &[PathBuf] {
+        &self.removed_entries
+    }
+
+    /// The internal names of the classes only on the new classpath
+    pub fn added_classes(&self) -> &[String] {
+        &self.added_classes
+    }
+
+    /// The internal names of the classes only on the old classpath
+    pub fn removed_classes(&self) -> &[String] {
+        &self.removed_classes
+    }
+
+    /// The classes on both classpaths that are found in a different entry
+    pub fn shadow_changes(&self) -> &[ShadowChange] {
+        &self.shadow_changes
+    }
+
+    /// Checks if the classpaths have the same entries and classes
+    pub fn is_empty(&self) -> bool {
+        self.added_entries.is_empty()
+            && self.removed_entries.is_empty()
+            && self.added_classes.is_empty()
+            && self.removed_classes.is_empty()
+            && self.shadow_changes.is_empty()
+    }
+}
+
+impl Display for ClasspathDiff {
+    /// Writes the diff with a line per change, prefixed by `+` for additions, `-` for removals and
+    /// `~` for shadow changes
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.removed_entries {
+            writeln!(f, "- entry {}", entry.display())?;
+        }
+        for entry in &self.added_entries {
+            writeln!(f, "+ entry {}", entry.display())?;
+        }
+        for class in &self.removed_classes {
+            writeln!(f, "- class {class}")?;
+        }
+        for class in &self.added_classes {
+            writeln!(f, "+ class {class}")?;
+        }
+        for change in &self.shadow_changes {
+            writeln!(
+                f,
+                "~ class {} ({} -> {})",
+                change.name,
+                change.old_entry.display(),
+                change.new_entry.display()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Set operations
+impl Classpath {
+    /// Creates a classpath of the entries that are also in `other`, in the order of this classpath
+    ///
+    /// # Example
+    /// ```
+    /// # use java_classpaths::Classpath;
+    /// let cp = Classpath::from_iter(["a.jar", "b.jar", "c.jar"]);
+    /// let other = Classpath::from_iter(["c.jar", "a.jar"]);
+    /// assert_eq!(cp.intersection(&other), Classpath::from_iter(["a.jar", "c.jar"]));
+    /// ```
+    pub fn intersection(&self, other: &Classpath) -> Classpath {
+        let other = other.paths.iter().collect::<HashSet<_>>();
+        self.filter_entries(|path| other.contains(path))
+    }
+
+    /// Creates a classpath of the entries that aren't in `other`, in the order of this classpath
+    ///
+    /// # Example
+    /// ```
+    /// # use java_classpaths::Classpath;
+    /// let cp = Classpath::from_iter(["a.jar", "b.jar", "c.jar"]);
+    /// let other = Classpath::from_iter(["c.jar", "a.jar"]);
+    /// assert_eq!(cp.difference(&other), Classpath::from("b.jar"));
+    /// ```
+    pub fn difference(&self, other: &Classpath) -> Classpath {
+        let other = other.paths.iter().collect::<HashSet<_>>();
+        self.filter_entries(|path| !other.contains(path))
+    }
+
+    /// Compares this classpath with a newer one, by their entries and the classes visible through
+    /// them.
+    ///
+    /// # Error
+    /// Will return an error if an entry of either classpath can't be read
+    pub fn diff(&self, new: &Classpath) -> Result<ClasspathDiff, ClasspathError> {
+        let old_classes = self.class_entries()?;
+        let new_classes = new.class_entries()?;
+
+        let mut diff = ClasspathDiff {
+            added_entries: new.difference(self).paths.into(),
+            removed_entries: self.difference(new).paths.into(),
+            ..Default::default()
+        };
+        for (name, old_entry) in &old_classes {
+            match new_classes.get(name) {
+                None => diff.removed_classes.push(name.clone()),
+                Some(new_entry) if new_entry != old_entry => {
+                    diff.shadow_changes.push(ShadowChange {
+                        name: name.clone(),
+                        old_entry: old_entry.clone(),
+                        new_entry: new_entry.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        diff.added_classes = new_classes
+            .into_keys()
+            .filter(|name| !old_classes.contains_key(name))
+            .collect();
+        Ok(diff)
+    }
+
+    fn filter_entries<F: FnMut(&PathBuf) -> bool>(&self, mut filter: F) -> Classpath {
+        let mut seen = HashSet::new();
+        Classpath {
+            paths: self
+                .paths
+                .iter()
+                .filter(|&path| filter(path) && seen.insert(path))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Maps every class on the classpath to the first entry that contains it
+    fn class_entries(&self) -> Result<BTreeMap<String, PathBuf>, ClasspathError> {
+        let mut classes = BTreeMap::new();
+        for entry in &self.paths {
+            for class in single_entry(entry).classes() {
+                classes.entry(class?).or_insert_with(|| entry.clone());
+            }
+        }
+        Ok(classes)
+    }
+}
+
+/// A classpath of an entry that has already been expanded
+fn single_entry(entry: &Path) -> Classpath {
+    Classpath {
+        paths: VecDeque::from([entry.to_path_buf()]),
+    }
+}
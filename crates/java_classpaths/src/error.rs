@@ -0,0 +1,68 @@
+//! The errors of classpath lookups
+
+use std::io;
+use std::path::{Path, PathBuf};
+use zip::result::ZipError;
+
+/// An error looking up or reading something on a classpath
+#[derive(Debug, thiserror::Error)]
+pub enum ClasspathError {
+    /// The resource isn't on the classpath
+    #[error("{0:?} was not found on the classpath")]
+    NotFound(String),
+    /// An io error occurred
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An archive on the classpath couldn't be read
+    #[error("archive {archive:?} is corrupt: {source}")]
+    ArchiveCorrupt {
+        /// The path of the archive
+        archive: PathBuf,
+        /// Why the archive couldn't be read
+        #[source]
+        source: ZipError,
+    },
+    /// A classpath entry is neither a directory nor an archive
+    #[error("unsupported entry in classpath: {0:?}")]
+    UnsupportedEntry(PathBuf),
+    /// A jar manifest is malformed
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(String),
+    /// A remote archive couldn't be downloaded
+    #[cfg(feature = "http")]
+    #[error("couldn't download {url}: {source}")]
+    Download {
+        /// The url of the archive
+        url: Box<url::Url>,
+        /// Why the archive couldn't be downloaded
+        #[source]
+        source: io::Error,
+    },
+    /// The classpath couldn't be watched for changes
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(#[from] notify::Error),
+}
+
+impl ClasspathError {
+    /// Checks if this error is because something wasn't found, rather than because it couldn't be
+    /// read
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ClasspathError::NotFound(_) => true,
+            ClasspathError::Io(e) => e.kind() == io::ErrorKind::NotFound,
+            _ => false,
+        }
+    }
+
+    /// Converts an error reading an archive
+    pub(crate) fn archive(archive: &Path, error: ZipError) -> Self {
+        match error {
+            ZipError::Io(e) => ClasspathError::Io(e),
+            source => ClasspathError::ArchiveCorrupt {
+                archive: archive.to_path_buf(),
+                source,
+            },
+        }
+    }
+}
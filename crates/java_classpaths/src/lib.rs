@@ -1,15 +1,18 @@
 //! Allows for file system like access to java like classpaths
 //!
 
-use std::collections::{vec_deque, VecDeque};
+use std::borrow::Cow;
+use std::collections::{vec_deque, BTreeMap, HashMap, HashSet, VecDeque};
 use std::convert::Infallible;
 use std::ffi::{OsStr, OsString};
 use std::fmt::{Display, Formatter, Write};
 use std::fs::File;
-use std::io::{ErrorKind, Read};
+use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::ops::{Add, AddAssign};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use std::{io, vec};
 
 use cfg_if::cfg_if;
@@ -18,6 +21,22 @@ use url::Url;
 use zip::result::ZipError;
 use zip::ZipArchive;
 
+pub mod diff;
+pub mod error;
+pub mod manifest;
+#[cfg(feature = "http")]
+mod remote;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+pub use diff::ClasspathDiff;
+pub use error::ClasspathError;
+pub use manifest::Manifest;
+#[cfg(feature = "http")]
+pub use remote::clear_downloads;
+#[cfg(feature = "watch")]
+pub use watch::{ClasspathEvent, ClasspathWatcher};
+
 cfg_if! {
     if #[cfg(windows)] {
         /// The separator between different entries on the classpath. This is different depending on the os.
@@ -30,6 +49,13 @@ cfg_if! {
     }
 }
 
+/// The separator between an archive and the path of an archive nested inside of it, such as
+/// `app.jar!/BOOT-INF/lib/guava.jar`
+pub const NESTED_ARCHIVE_SEPARATOR: &str = "!/";
+
+/// The environment variable holding the default classpath of java
+pub const CLASSPATH_ENV_VAR: &str = "CLASSPATH";
+
 /// A classpath in java
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct Classpath {
@@ -52,6 +78,43 @@ impl Classpath {
         self.paths.len()
     }
 
+    /// Interprets a classpath the way `java -cp` does. Entries are separated by the
+    /// [`CLASSPATH_SEPARATOR`], relative entries are resolved against `base_dir`, and empty entries
+    /// refer to `base_dir` itself.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::path::Path;
+    /// # use java_classpaths::{Classpath, CLASSPATH_SEPARATOR};
+    /// let cp = Classpath::resolve(format!("lib.jar{CLASSPATH_SEPARATOR}"), "/app");
+    /// let entries = cp.into_iter().collect::<Vec<_>>();
+    /// assert_eq!(entries, [Path::new("/app/lib.jar"), Path::new("/app")]);
+    /// ```
+    pub fn resolve<S: AsRef<str>, P: AsRef<Path>>(classpath: S, base_dir: P) -> Self {
+        let base_dir = base_dir.as_ref();
+        Self::from_iter(
+            classpath
+                .as_ref()
+                .split(CLASSPATH_SEPARATOR)
+                .map(|entry| base_dir.join(entry)),
+        )
+    }
+
+    /// Gets the classpath from the [`CLASSPATH`](CLASSPATH_ENV_VAR) environment variable,
+    /// resolved against the current directory. Like java, the classpath is the current directory
+    /// if the variable isn't set.
+    pub fn from_env() -> Self {
+        Self::from_env_in(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// Gets the classpath from the [`CLASSPATH`](CLASSPATH_ENV_VAR) environment variable,
+    /// resolved against `base_dir`. Like java, the classpath is `base_dir` if the variable isn't
+    /// set.
+    pub fn from_env_in<P: AsRef<Path>>(base_dir: P) -> Self {
+        let classpath = std::env::var(CLASSPATH_ENV_VAR).unwrap_or_default();
+        Self::resolve(classpath, base_dir)
+    }
+
     /// Converts this classpath into a usable classpath for java
     ///
     /// # Example
@@ -78,103 +141,430 @@ impl Classpath {
     /// Attempts to get a resource on the classpath.
     ///
     /// Paths will be interpreted with only `/` as a separator. A leading `/` is ignored.
+    /// Entries may be archives nested inside other archives, such as
+    /// `app.jar!/BOOT-INF/lib/guava.jar`.
     ///
-    /// # Return
-    /// Will return `None` is path is not on classpath. Otherwise, `Some(Result)` is returned
-    /// where the resource exists. The result is `Ok` if the inner is actually readable.
+    /// # Error
+    /// Will return a [`NotFound`](ClasspathError::NotFound) error if the path is not on the
+    /// classpath, or another error if an entry before the resource can't be read.
     ///
     /// # Example
     /// ```no_run
     /// # use std::str::FromStr;
     /// # use java_classpaths::Classpath;
-    /// let cp = Classpath::from_str("run.jar");
+    /// let cp = Classpath::from_str("run.jar").unwrap();
     /// let resource = cp.get("META-INF/MANIFEST").expect("manifest not found");
     /// ```
-    pub fn get<P: AsRef<str>>(&self, path: P) -> Option<io::Result<Resource>> {
+    pub fn get<P: AsRef<str>>(&self, path: P) -> Result<Resource, ClasspathError> {
         let stripped = path.as_ref().trim_start_matches("/");
-        for entry in self {
-            if entry.is_dir() {
-                if let Some(ret) = Self::get_in_dir(entry, stripped) {
-                    return Some(ret);
-                }
-            } else {
-                let ext = entry.extension();
-                match ext.and_then(|os| os.to_str()) {
-                    Some("jar") | Some("zip") => match Self::get_in_archive(entry, stripped) {
-                        Ok(Some(resource)) => return Some(Ok(resource)),
-                        Ok(None) => {}
-                        Err(e) => return Some(Err(e)),
-                    },
-                    _ => {}
-                }
-            }
-        }
+        self.paths
+            .iter()
+            .find_map(|entry| Self::get_in_entry(entry, stripped))
+            .unwrap_or_else(|| Err(ClasspathError::NotFound(stripped.to_string())))
+    }
+
+    /// Gets every resource on the classpath with a path, in classpath order. This is useful for
+    /// resources that are expected to be in many entries, such as `META-INF/services` files, and
+    /// mirrors `ClassLoader.getResources` in java.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_classpaths::Classpath;
+    /// let cp = Classpath::from_iter(["app.jar", "lib.jar"]);
+    /// let providers = cp.get_all("META-INF/services/java.sql.Driver");
+    /// ```
+    pub fn get_all<P: AsRef<str>>(&self, path: P) -> Vec<Result<Resource, ClasspathError>> {
+        let stripped = path.as_ref().trim_start_matches("/");
+        self.paths
+            .iter()
+            .filter_map(|entry| Self::get_in_entry(entry, stripped))
+            .collect()
+    }
 
-        None
+    /// Gets a resource in a single classpath entry. Entries that don't exist, or are neither
+    /// directories nor archives, are skipped like in java.
+    fn get_in_entry(entry: &Path, entry_path: &str) -> Option<Result<Resource, ClasspathError>> {
+        if let Some(url) = remote_url(entry) {
+            return Self::get_in_remote(entry, &url, entry_path).transpose();
+        }
+        if entry.is_dir() {
+            return Self::get_in_dir(entry, entry_path);
+        }
+        if is_archive(entry) {
+            Self::get_in_archive(entry, entry_path).transpose()
+        } else {
+            None
+        }
     }
 
-    fn get_in_archive(archive_path: &Path, entry_path: &str) -> io::Result<Option<Resource>> {
-        let archive_file = File::open(archive_path)?;
-        let mut archive = ZipArchive::new(archive_file)
-            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    /// Gets a resource in a remote archive, downloading the archive if it hasn't been downloaded yet
+    fn get_in_remote(
+        entry: &Path,
+        url: &Url,
+        entry_path: &str,
+    ) -> Result<Option<Resource>, ClasspathError> {
+        let archive = download(entry, url)?;
+        let resource = Self::get_in_archive(&archive, entry_path)?;
+        Ok(resource.map(|resource| Resource {
+            url: Url::parse(&format!("jar:{url}!/{entry_path}")).unwrap(),
+            ..resource
+        }))
+    }
 
-        let out = match archive.by_name(entry_path) {
-            Ok(mut entry) => {
-                let mut buffer = vec![];
-                entry.read_to_end(&mut buffer)?;
+    fn get_in_archive(
+        archive_path: &Path,
+        entry_path: &str,
+    ) -> Result<Option<Resource>, ClasspathError> {
+        let index = ArchiveIndex::open(archive_path)?;
+        match index.read(entry_path) {
+            Some(buffer) => {
+                let buffer = buffer?;
                 Ok(Some(Resource {
-                    kind: ResourceKind::ArchiveEntry(VecDeque::from(buffer)),
+                    len: buffer.len() as u64,
+                    last_modified: index.last_modified(entry_path),
+                    kind: ResourceKind::ArchiveEntry(Cursor::new(buffer)),
                     url: Url::parse(&format!(
-                        "jar:file:{archive}!{entry_path}",
+                        "jar:file:{archive}!/{entry_path}",
                         archive = archive_path.to_str().unwrap()
                     ))
                     .unwrap(),
                 }))
             }
-            Err(err) => match err {
-                ZipError::FileNotFound => Ok(None),
-                e => Err(io::Error::new(ErrorKind::InvalidData, e)),
-            },
-        };
-        out
+            None => Ok(None),
+        }
     }
 
-    fn get_in_dir(dir: &Path, entry: &str) -> Option<io::Result<Resource>> {
+    fn get_in_dir(dir: &Path, entry: &str) -> Option<Result<Resource, ClasspathError>> {
         let full_path = dir.join(entry);
-        if full_path.exists() {
-            Some(
-                File::open(&full_path)
-                    .and_then(|file| {
-                        Url::from_file_path(&full_path)
-                            .map_err(|()| {
-                                io::Error::new(
-                                    io::ErrorKind::NotFound,
-                                    format!("{:?} is not valid as a url", full_path),
-                                )
-                            })
-                            .map(|url| (file, url))
-                    })
-                    .map(|(file, url)| Resource {
-                        kind: ResourceKind::Real(file),
-                        url,
-                    }),
-            )
-        } else {
-            None
+        if !full_path.exists() {
+            return None;
+        }
+        let resource = File::open(&full_path).and_then(|file| {
+            let url = Url::from_file_path(&full_path).map_err(|()| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{:?} is not valid as a url", full_path),
+                )
+            })?;
+            let metadata = file.metadata()?;
+            Ok(Resource {
+                len: metadata.len(),
+                last_modified: metadata.modified().ok(),
+                kind: ResourceKind::Real(file),
+                url,
+            })
+        });
+        Some(resource.map_err(ClasspathError::from))
+    }
+}
+
+/// Opens an archive on the classpath. The archive may be nested inside other archives, such as
+/// `app.jar!/BOOT-INF/lib/guava.jar`, in which case the nested archive is read into memory from
+/// the archive containing it.
+///
+/// # Error
+/// Will return an error if any of the archives can't be read, or the nested archive doesn't exist
+pub fn open_archive<P: AsRef<Path>>(path: P) -> Result<ZipArchive<ArchiveReader>, ClasspathError> {
+    let full_path = path.as_ref();
+    let path = full_path
+        .to_str()
+        .ok_or_else(|| ClasspathError::UnsupportedEntry(full_path.to_path_buf()))?;
+    let mut parts = path.split(NESTED_ARCHIVE_SEPARATOR);
+    let outer = parts.next().unwrap_or_default();
+    let mut archive = ZipArchive::new(ArchiveReader::File(File::open(outer)?))
+        .map_err(|e| ClasspathError::archive(Path::new(outer), e))?;
+    for nested in parts {
+        let mut buffer = vec![];
+        match archive.by_name(nested) {
+            Ok(mut entry) => {
+                entry.read_to_end(&mut buffer)?;
+            }
+            Err(ZipError::FileNotFound) => {
+                return Err(ClasspathError::NotFound(format!(
+                    "{path}: no nested archive {nested:?}"
+                )))
+            }
+            Err(e) => return Err(ClasspathError::archive(full_path, e)),
+        }
+        archive = ZipArchive::new(ArchiveReader::Nested(Cursor::new(buffer)))
+            .map_err(|e| ClasspathError::archive(full_path, e))?;
+    }
+    Ok(archive)
+}
+
+/// The number of archives kept indexed by default
+pub const DEFAULT_INDEX_CAPACITY: usize = 128;
+
+/// The archives indexed most recently
+static ARCHIVE_INDICES: Mutex<Option<IndexCache>> = Mutex::new(None);
+
+type FileStamp = (Option<SystemTime>, u64);
+
+/// A cache of archive indices, evicting the least recently used index once full
+#[derive(Debug)]
+struct IndexCache {
+    capacity: usize,
+    /// Every cached index, with the modification time and length of the file containing the
+    /// archive when it was indexed, and the tick it was last used at
+    indices: HashMap<PathBuf, (FileStamp, Arc<ArchiveIndex>, u64)>,
+    /// The path of every cached index, by the tick it was last used at
+    recent: BTreeMap<u64, PathBuf>,
+    tick: u64,
+}
+
+impl IndexCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            indices: HashMap::new(),
+            recent: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Gets the index of an archive, if it was indexed when the file containing it had `stamp`
+    fn get(&mut self, path: &Path, stamp: FileStamp) -> Option<Arc<ArchiveIndex>> {
+        let (indexed, index, last_used) = self.indices.get_mut(path)?;
+        if *indexed != stamp {
+            return None;
+        }
+        self.tick += 1;
+        let path = self.recent.remove(last_used)?;
+        *last_used = self.tick;
+        self.recent.insert(self.tick, path);
+        Some(index.clone())
+    }
+
+    fn insert(&mut self, path: PathBuf, stamp: FileStamp, index: Arc<ArchiveIndex>) {
+        self.remove(&path);
+        if self.capacity == 0 {
+            return;
+        }
+        while self.indices.len() >= self.capacity {
+            let Some(&least_recent) = self.recent.keys().next() else {
+                break;
+            };
+            if let Some(path) = self.recent.remove(&least_recent) {
+                self.indices.remove(&path);
+            }
+        }
+        self.tick += 1;
+        self.recent.insert(self.tick, path.clone());
+        self.indices.insert(path, (stamp, index, self.tick));
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let Some((_, _, last_used)) = self.indices.remove(path) {
+            self.recent.remove(&last_used);
+        }
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.indices.len() > capacity {
+            let Some(&least_recent) = self.recent.keys().next() else {
+                break;
+            };
+            if let Some(path) = self.recent.remove(&least_recent) {
+                self.indices.remove(&path);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.indices.clear();
+        self.recent.clear();
+    }
+}
+
+/// Runs a function with the shared cache of archive indices
+fn with_index_cache<R>(f: impl FnOnce(&mut IndexCache) -> R) -> R {
+    let mut cache = ARCHIVE_INDICES.lock().unwrap_or_else(|e| e.into_inner());
+    f(cache.get_or_insert_with(|| IndexCache::new(DEFAULT_INDEX_CAPACITY)))
+}
+
+/// An index of the entries in an archive on the classpath.
+///
+/// Opening an archive reads its whole central directory, so an archive is only indexed the first
+/// time it's opened. Later calls to [`open`](ArchiveIndex::open) share the same index, until the
+/// archive file changes.
+///
+/// Indices are cached for the whole process, and every cached index keeps its archive open, or in
+/// memory if it's nested inside another archive. Only the [`DEFAULT_INDEX_CAPACITY`] most recently
+/// used archives are kept, which can be changed with
+/// [`set_cache_capacity`](ArchiveIndex::set_cache_capacity), and
+/// [`clear_cache`](ArchiveIndex::clear_cache) closes every cached archive.
+#[derive(Debug)]
+pub struct ArchiveIndex {
+    path: PathBuf,
+    /// The name of every entry, in the order they're stored
+    names: Vec<String>,
+    /// When every entry was last modified, in the order they're stored
+    modified: Vec<Option<SystemTime>>,
+    /// The position of every entry in the central directory of the archive, by name
+    entries: HashMap<String, usize>,
+    archive: Mutex<ZipArchive<ArchiveReader>>,
+}
+
+impl ArchiveIndex {
+    /// Gets the index of an archive, which may be nested inside other archives, indexing it if
+    /// it hasn't been indexed yet.
+    ///
+    /// # Error
+    /// Will return an error if the archive can't be read
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Arc<Self>, ClasspathError> {
+        let path = path.as_ref();
+        let outer = path
+            .to_str()
+            .and_then(|path| path.split(NESTED_ARCHIVE_SEPARATOR).next())
+            .map_or(path, Path::new);
+        let metadata = std::fs::metadata(outer)?;
+        let stamp = (metadata.modified().ok(), metadata.len());
+
+        if let Some(index) = with_index_cache(|cache| cache.get(path, stamp)) {
+            return Ok(index);
+        }
+        let index = Arc::new(Self::index(path)?);
+        with_index_cache(|cache| cache.insert(path.to_path_buf(), stamp, index.clone()));
+        Ok(index)
+    }
+
+    /// Sets how many archives are kept indexed at once, evicting the least recently used indices
+    /// if there are more. Indices aren't cached at all with a capacity of 0.
+    pub fn set_cache_capacity(capacity: usize) {
+        with_index_cache(|cache| cache.set_capacity(capacity));
+    }
+
+    /// Forgets every cached index, closing their archives once they're no longer used. Archives are
+    /// indexed again the next time they're opened.
+    pub fn clear_cache() {
+        with_index_cache(IndexCache::clear);
+    }
+
+    /// Indexes an archive, without caching the index
+    fn index(path: &Path) -> Result<Self, ClasspathError> {
+        let mut archive = open_archive(path)?;
+        let (names, modified) = (0..archive.len())
+            .map(|position| {
+                let entry = archive
+                    .by_index_raw(position)
+                    .map_err(|e| ClasspathError::archive(path, e))?;
+                Ok((
+                    entry.name().to_string(),
+                    archive_time(entry.last_modified()),
+                ))
+            })
+            .collect::<Result<(Vec<_>, Vec<_>), ClasspathError>>()?;
+        let entries = names
+            .iter()
+            .enumerate()
+            .map(|(position, name)| (name.clone(), position))
+            .collect();
+        Ok(Self {
+            path: path.to_path_buf(),
+            names,
+            modified,
+            entries,
+            archive: Mutex::new(archive),
+        })
+    }
+
+    /// The path of the archive
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The number of entries in the archive
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Checks if the archive has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Checks if the archive contains an entry
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// The names of every entry in the archive, in the order they're stored
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// When an entry was last modified, if known. Times in archives have no time zone, and are
+    /// treated as UTC.
+    pub fn last_modified(&self, name: &str) -> Option<SystemTime> {
+        let position = *self.entries.get(name)?;
+        self.modified[position]
+    }
+
+    /// Reads the contents of an entry. Returns `None` if the archive has no such entry.
+    pub fn read(&self, name: &str) -> Option<Result<Vec<u8>, ClasspathError>> {
+        let position = *self.entries.get(name)?;
+        let mut archive = self.archive.lock().unwrap_or_else(|e| e.into_inner());
+        let result = archive
+            .by_index(position)
+            .map_err(|e| ClasspathError::archive(&self.path, e))
+            .and_then(|mut entry| {
+                let mut buffer = vec![];
+                entry.read_to_end(&mut buffer)?;
+                Ok(buffer)
+            });
+        Some(result)
+    }
+}
+
+/// Reads an archive on the classpath, either directly from a file or from memory if it was
+/// nested inside another archive
+#[derive(Debug)]
+pub enum ArchiveReader {
+    /// An archive file
+    File(File),
+    /// An archive read from inside another archive
+    Nested(Cursor<Vec<u8>>),
+}
+
+impl Read for ArchiveReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            ArchiveReader::File(file) => file.read(buf),
+            ArchiveReader::Nested(cursor) => cursor.read(buf),
+        }
+    }
+}
+
+impl Seek for ArchiveReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            ArchiveReader::File(file) => file.seek(pos),
+            ArchiveReader::Nested(cursor) => cursor.seek(pos),
         }
     }
 }
 
 /// Classpath manipulation methods
+///
+/// Like java, an entry ending in `*`, such as `lib/*`, is a wildcard for every jar file in that
+/// directory. Wildcards are expanded when they're added to the classpath, in sorted order.
+///
+/// Like a java `URLClassLoader`, entries may also be urls. `file:` urls are converted to paths
+/// when they're added, and `http:` or `https:` archives are downloaded the first time they're read
+/// if the `http` feature is enabled. Urls can only be added as single entries, since they contain
+/// the `:` classpath separator of unix.
 impl Classpath {
     /// Pushes a new entry to this classpath, at the front.
     pub fn push_front<P: AsRef<Path>>(&mut self, path: P) {
-        self.paths.push_front(path.as_ref().to_path_buf());
+        for path in expand_wildcard(path.as_ref()).into_iter().rev() {
+            self.paths.push_front(path);
+        }
     }
 
     /// Pushes a new entry to this classpath, at the back.
     pub fn push_back<P: AsRef<Path>>(&mut self, path: P) {
-        self.paths.push_back(path.as_ref().to_path_buf());
+        self.paths.extend(expand_wildcard(path.as_ref()));
     }
 
     /// Joins two classpaths together, with the `self` classpath being at the front and the `other`
@@ -186,6 +576,75 @@ impl Classpath {
     }
 }
 
+/// Checks if a path names a jar or zip archive. Like java, the extension is case insensitive.
+fn is_archive(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| {
+        ext.eq_ignore_ascii_case("jar") || ext.eq_ignore_ascii_case("zip")
+    })
+}
+
+/// Gets the url of a remote classpath entry, such as `https://example.com/lib.jar`
+fn remote_url(entry: &Path) -> Option<Url> {
+    let entry = entry.to_str()?;
+    if !entry.starts_with("http://") && !entry.starts_with("https://") {
+        return None;
+    }
+    Url::parse(entry).ok()
+}
+
+/// Gets the local copy of a remote archive. Remote entries are only supported with the `http`
+/// feature, and only archives can be downloaded.
+fn download(entry: &Path, url: &Url) -> Result<PathBuf, ClasspathError> {
+    if !is_archive(Path::new(url.path())) {
+        return Err(ClasspathError::UnsupportedEntry(entry.to_path_buf()));
+    }
+    cfg_if! {
+        if #[cfg(feature = "http")] {
+            remote::download(url)
+        } else {
+            Err(ClasspathError::UnsupportedEntry(entry.to_path_buf()))
+        }
+    }
+}
+
+/// Converts a `file:` url entry, such as `file:///opt/lib.jar`, into a path. Other entries are
+/// left as they are.
+fn file_url_to_path(path: &Path) -> Cow<'_, Path> {
+    path.to_str()
+        .filter(|path| path.starts_with("file:"))
+        .and_then(|url| Url::parse(url).ok()?.to_file_path().ok())
+        .map_or(Cow::Borrowed(path), Cow::Owned)
+}
+
+/// Expands a wildcard entry, such as `lib/*`, into every jar file in its directory, in sorted
+/// order. `file:` urls are converted to paths first, and entries that aren't wildcards are left as
+/// they are. Like java, a missing directory
+/// expands to nothing, and subdirectories aren't searched.
+fn expand_wildcard(path: &Path) -> Vec<PathBuf> {
+    let path = &*file_url_to_path(path);
+    if path.file_name() != Some(OsStr::new("*")) {
+        return vec![path.to_path_buf()];
+    }
+    let dir = match path.parent() {
+        Some(dir) if dir != Path::new("") => dir,
+        _ => Path::new("."),
+    };
+    let mut jars = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .map_or(false, |ext| ext.eq_ignore_ascii_case("jar"))
+        })
+        .collect::<Vec<_>>();
+    jars.sort();
+    jars
+}
+
 impl Display for Classpath {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.as_os_string())
@@ -198,7 +657,10 @@ where
 {
     fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
         Self {
-            paths: iter.into_iter().map(|p| p.as_ref().to_path_buf()).collect(),
+            paths: iter
+                .into_iter()
+                .flat_map(|p| expand_wildcard(p.as_ref()))
+                .collect(),
         }
     }
 }
@@ -279,7 +741,7 @@ impl<'a> IntoIterator for &'a Classpath {
 impl<P: AsRef<Path>> Extend<P> for Classpath {
     fn extend<T: IntoIterator<Item = P>>(&mut self, iter: T) {
         self.paths
-            .extend(iter.into_iter().map(|p| p.as_ref().to_path_buf()))
+            .extend(iter.into_iter().flat_map(|p| expand_wildcard(p.as_ref())))
     }
 }
 
@@ -297,11 +759,204 @@ impl AddAssign for Classpath {
     }
 }
 
+/// Manifest methods
+impl Classpath {
+    /// Adds the entries in the `Class-Path` of the manifest of every jar on this classpath, the way
+    /// the JVM does. Manifest entries are relative to the directory containing the jar, and come
+    /// directly after it. Jars added this way are expanded too, and entries already on the
+    /// classpath or that don't exist are skipped.
+    ///
+    /// # Error
+    /// Will return an error if a manifest can't be read
+    pub fn expand_manifests(&self) -> Result<Self, ClasspathError> {
+        let mut seen = HashSet::new();
+        let mut paths = VecDeque::new();
+        // entries still to visit, with the next one at the end
+        let mut pending = self.paths.iter().rev().cloned().collect::<Vec<_>>();
+        while let Some(path) = pending.pop() {
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let is_jar = is_archive(&path);
+            let is_nested = path
+                .to_str()
+                .map_or(false, |path| path.contains(NESTED_ARCHIVE_SEPARATOR));
+            if is_jar && !is_nested && path.is_file() {
+                if let Some(manifest) = Manifest::from_archive(&path)? {
+                    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                    let entries = manifest
+                        .class_path()
+                        .into_iter()
+                        .filter_map(|url| class_path_url_to_path(dir, url))
+                        .filter(|entry| entry.exists())
+                        .collect::<Vec<_>>();
+                    pending.extend(entries.into_iter().rev());
+                }
+            }
+            paths.push_back(path);
+        }
+        Ok(Self { paths })
+    }
+}
+
+/// Converts a url in the `Class-Path` of a manifest into a path, relative to the directory of the
+/// jar containing the manifest
+fn class_path_url_to_path(dir: &Path, url: &str) -> Option<PathBuf> {
+    if let Ok(url) = Url::parse(url) {
+        return url.to_file_path().ok();
+    }
+    let base = Url::from_directory_path(dir.canonicalize().ok()?).ok()?;
+    base.join(url).ok()?.to_file_path().ok()
+}
+
+/// Enumeration methods
+impl Classpath {
+    /// Lists the path of every resource on the classpath, in classpath order. Directories are
+    /// walked recursively in sorted order, and the entries of archives are listed in the order
+    /// they're stored in. A resource in more than one entry is listed once for each entry.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use java_classpaths::Classpath;
+    /// let cp = Classpath::from("app.jar");
+    /// for resource in cp.resources() {
+    ///     println!("{}", resource.unwrap());
+    /// }
+    /// ```
+    pub fn resources(&self) -> Resources {
+        Resources {
+            entries: self.paths.clone().into_iter(),
+            current: None,
+        }
+    }
+
+    /// Lists the internal name of every class on the classpath, such as `com/example/Square`, in
+    /// classpath order. A class in more than one entry is listed once for each entry.
+    pub fn classes(&self) -> impl Iterator<Item = Result<String, ClasspathError>> {
+        self.resources().filter_map(|resource| match resource {
+            Ok(resource) => resource
+                .strip_suffix(".class")
+                .map(|name| Ok(name.to_string())),
+            Err(e) => Some(Err(e)),
+        })
+    }
+}
+
+/// An iterator over the path of every resource on a classpath, created by
+/// [`Classpath::resources`]
+#[derive(Debug)]
+pub struct Resources {
+    entries: vec_deque::IntoIter<PathBuf>,
+    current: Option<EntryResources>,
+}
+
+impl Iterator for Resources {
+    type Item = Result<String, ClasspathError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(current) = &mut self.current {
+                if let Some(resource) = current.next() {
+                    return Some(resource);
+                }
+            }
+            let entry = self.entries.next()?;
+            let entry = match remote_url(&entry) {
+                Some(url) => match download(&entry, &url) {
+                    Ok(archive) => archive,
+                    Err(e) => {
+                        self.current = None;
+                        return Some(Err(e));
+                    }
+                },
+                None => entry,
+            };
+            self.current = if entry.is_dir() {
+                Some(EntryResources::Directory {
+                    pending: vec![entry.clone()],
+                    root: entry,
+                })
+            } else if is_archive(&entry) {
+                match ArchiveIndex::open(&entry) {
+                    Ok(index) => Some(EntryResources::Archive { index, position: 0 }),
+                    Err(e) => {
+                        self.current = None;
+                        return Some(Err(e));
+                    }
+                }
+            } else {
+                None
+            };
+        }
+    }
+}
+
+/// The remaining resources of a single classpath entry
+#[derive(Debug)]
+enum EntryResources {
+    Directory {
+        root: PathBuf,
+        /// Paths still to visit, with the next one at the end
+        pending: Vec<PathBuf>,
+    },
+    Archive {
+        index: Arc<ArchiveIndex>,
+        position: usize,
+    },
+}
+
+impl Iterator for EntryResources {
+    type Item = Result<String, ClasspathError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            EntryResources::Directory { root, pending } => {
+                while let Some(path) = pending.pop() {
+                    if path.is_dir() {
+                        let entries = std::fs::read_dir(&path).and_then(|entries| {
+                            entries
+                                .map(|entry| entry.map(|entry| entry.path()))
+                                .collect::<Result<Vec<_>, _>>()
+                        });
+                        match entries {
+                            Ok(mut entries) => {
+                                entries.sort();
+                                pending.extend(entries.into_iter().rev());
+                            }
+                            Err(e) => return Some(Err(e.into())),
+                        }
+                    } else if let Ok(relative) = path.strip_prefix(&root) {
+                        let resource = relative
+                            .components()
+                            .map(|component| component.as_os_str().to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join("/");
+                        return Some(Ok(resource));
+                    }
+                }
+                None
+            }
+            EntryResources::Archive { index, position } => {
+                while let Some(name) = index.names.get(*position) {
+                    *position += 1;
+                    // directories aren't resources
+                    if !name.ends_with('/') {
+                        return Some(Ok(name.clone()));
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
 /// A classpath resource. This is some readable entry available on the classpath
 #[derive(Debug)]
 pub struct Resource {
     kind: ResourceKind,
     url: Url,
+    len: u64,
+    last_modified: Option<SystemTime>,
 }
 
 impl Resource {
@@ -309,9 +964,44 @@ impl Resource {
     pub fn url(&self) -> &Url {
         &self.url
     }
+
+    /// The length of the resource in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Checks if the resource is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// When the resource was last modified, if known. Times of archive entries have no time
+    /// zone, and are treated as UTC.
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+    }
+
+    /// Checks if the resource is an entry of an archive, rather than a file in a directory
+    pub fn is_from_archive(&self) -> bool {
+        matches!(self.kind, ResourceKind::ArchiveEntry(_))
+    }
+
+    /// Reads the whole resource, from its start
+    ///
+    /// # Error
+    /// Will return an error if the resource can't be read
+    pub fn bytes(mut self) -> io::Result<Vec<u8>> {
+        if let ResourceKind::ArchiveEntry(cursor) = self.kind {
+            return Ok(cursor.into_inner());
+        }
+        self.rewind()?;
+        let mut buffer = Vec::with_capacity(self.len as usize);
+        self.read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
 }
 
-assert_impl_all!(Resource: io::Read);
+assert_impl_all!(Resource: io::Read, io::Seek);
 
 impl io::Read for Resource {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
@@ -319,10 +1009,19 @@ impl io::Read for Resource {
     }
 }
 
+impl io::Seek for Resource {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match &mut self.kind {
+            ResourceKind::Real(file) => file.seek(pos),
+            ResourceKind::ArchiveEntry(cursor) => cursor.seek(pos),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum ResourceKind {
     Real(File),
-    ArchiveEntry(VecDeque<u8>),
+    ArchiveEntry(Cursor<Vec<u8>>),
 }
 
 assert_impl_all!(ResourceKind: io::Read);
@@ -331,16 +1030,41 @@ impl io::Read for ResourceKind {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match self {
             ResourceKind::Real(file) => file.read(buf),
-            ResourceKind::ArchiveEntry(old_buf) => old_buf.read(buf),
+            ResourceKind::ArchiveEntry(cursor) => cursor.read(buf),
         }
     }
 }
 
+/// Converts the time of an archive entry, which has no time zone, into a system time as if it
+/// were UTC
+fn archive_time(time: zip::DateTime) -> Option<SystemTime> {
+    let (year, month, day) = (
+        i64::from(time.year()),
+        i64::from(time.month()),
+        i64::from(time.day()),
+    );
+    // days since the unix epoch of a date in the proleptic gregorian calendar
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    let seconds = days * 86_400
+        + i64::from(time.hour()) * 3600
+        + i64::from(time.minute()) * 60
+        + i64::from(time.second());
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(u64::try_from(seconds).ok()?))
+}
+
 #[cfg(test)]
 mod tests {
     use std::ffi::OsString;
 
-    use crate::{Classpath, CLASSPATH_SEPARATOR};
+    use crate::{archive_time, ArchiveIndex, Classpath, IndexCache, CLASSPATH_SEPARATOR};
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn as_path() {
@@ -352,6 +1076,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evict_least_recently_used_index() {
+        let jar = itest_common::jar_file();
+        let index = Arc::new(ArchiveIndex::index(&jar).unwrap());
+        let stamp = (None, 0);
+        let mut cache = IndexCache::new(2);
+        cache.insert("a.jar".into(), stamp, index.clone());
+        cache.insert("b.jar".into(), stamp, index.clone());
+        assert!(cache.get(Path::new("a.jar"), stamp).is_some());
+        cache.insert("c.jar".into(), stamp, index.clone());
+        assert_eq!(cache.indices.len(), 2);
+        assert!(cache.get(Path::new("b.jar"), stamp).is_none());
+        assert!(cache.get(Path::new("a.jar"), stamp).is_some());
+        assert!(cache.get(Path::new("a.jar"), (None, 1)).is_none());
+
+        cache.set_capacity(1);
+        assert_eq!(cache.indices.len(), 1);
+        assert!(cache.get(Path::new("a.jar"), stamp).is_some());
+        cache.clear();
+        assert!(cache.recent.is_empty());
+    }
+
     #[test]
     fn join() {
         let cp1 = Classpath::from("path1");
@@ -362,7 +1108,7 @@ mod tests {
     #[test]
     fn add_classpaths() {
         let mut cp = Classpath::new();
-        cp = cp + Classpath::from("path1");
+        cp += Classpath::from("path1");
         cp += Classpath::from_iter(["path2", "path3"]);
         assert_eq!(cp, Classpath::from_iter(["path1", "path2", "path3"]));
     }
@@ -374,4 +1120,17 @@ mod tests {
             .unwrap();
         assert_eq!(classpath, Classpath::from_iter(["path1", "path2"]))
     }
+    #[test]
+    fn archive_times() {
+        let time = zip::DateTime::from_date_and_time(1980, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            archive_time(time),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(315_532_800))
+        );
+        let time = zip::DateTime::from_date_and_time(2024, 2, 29, 12, 30, 15).unwrap();
+        assert_eq!(
+            archive_time(time),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_709_209_815))
+        );
+    }
 }
@@ -0,0 +1,259 @@
+//! Jar manifests, as described by the
+//! [jar file specification](https://docs.oracle.com/en/java/javase/17/docs/specs/jar/jar.html#jar-manifest).
+//!
+//! A manifest is made of sections separated by blank lines. The first section holds the main
+//! attributes of the jar, and every other section holds the attributes of a single entry, named by
+//! its `Name` attribute. Lines are at most 72 bytes long, and longer values are continued on the
+//! next line, starting with a single space.
+
+use crate::{ArchiveIndex, ClasspathError};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// The path of the manifest in a jar file
+pub const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+/// The maximum length of a line in a manifest, in bytes
+const MAX_LINE_LENGTH: usize = 72;
+
+/// The attributes of a section of a manifest. Attribute names are case insensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attributes {
+    values: Vec<(String, String)>,
+}
+
+impl Attributes {
+    /// Gets the value of an attribute
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of an attribute, replacing any previous value
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, name: K, value: V) {
+        let name = name.into();
+        let value = value.into();
+        match self
+            .values
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&name))
+        {
+            Some((_, old)) => *old = value,
+            None => self.values.push((name, value)),
+        }
+    }
+
+    /// Iterates over every attribute, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// The number of attributes
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if there are no attributes
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// A jar manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    main: Attributes,
+    entries: Vec<(String, Attributes)>,
+}
+
+impl Manifest {
+    /// Parses a manifest
+    ///
+    /// # Error
+    /// Will return an error if a line isn't an attribute, or a section other than the first has no
+    /// `Name` attribute
+    pub fn parse(text: &str) -> Result<Self, ClasspathError> {
+        let mut manifest = Manifest::default();
+        let mut section = Attributes::default();
+        let mut is_main = true;
+        let mut last_key: Option<String> = None;
+
+        let mut finish_section =
+            |section: Attributes, is_main: &mut bool| -> Result<(), ClasspathError> {
+                if *is_main {
+                    manifest.main = section;
+                    *is_main = false;
+                } else if !section.is_empty() {
+                    let name = section
+                        .get("Name")
+                        .ok_or_else(|| invalid("entry section has no Name attribute"))?
+                        .to_string();
+                    manifest.entries.push((name, section));
+                }
+                Ok(())
+            };
+
+        for line in text.lines() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() {
+                finish_section(std::mem::take(&mut section), &mut is_main)?;
+                last_key = None;
+            } else if let Some(continuation) = line.strip_prefix(' ') {
+                let key = last_key
+                    .as_ref()
+                    .ok_or_else(|| invalid("continuation line without an attribute"))?;
+                if let Some((_, value)) = section.values.iter_mut().find(|(k, _)| k == key) {
+                    value.push_str(continuation);
+                }
+            } else {
+                let (key, value) = line
+                    .split_once(": ")
+                    .ok_or_else(|| invalid(&format!("invalid manifest line {line:?}")))?;
+                section.insert(key, value);
+                last_key = Some(key.to_string());
+            }
+        }
+        finish_section(section, &mut is_main)?;
+        Ok(manifest)
+    }
+
+    /// Reads the manifest of a jar file, which may be nested inside other jar files. Returns
+    /// `None` if the jar has no manifest.
+    ///
+    /// # Error
+    /// Will return an error if the jar or its manifest can't be read
+    pub fn from_archive<P: AsRef<Path>>(path: P) -> Result<Option<Self>, ClasspathError> {
+        let index = ArchiveIndex::open(path)?;
+        match index.read(MANIFEST_PATH) {
+            Some(bytes) => {
+                let text = String::from_utf8(bytes?)
+                    .map_err(|e| ClasspathError::InvalidManifest(e.to_string()))?;
+                Self::parse(&text).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The main attributes of the jar
+    pub fn main_attributes(&self) -> &Attributes {
+        &self.main
+    }
+
+    /// The main attributes of the jar, to modify
+    pub fn main_attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.main
+    }
+
+    /// Gets the attributes of an entry in the jar
+    pub fn entry(&self, name: &str) -> Option<&Attributes> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, attributes)| attributes)
+    }
+
+    /// Iterates over the attributes of every entry with a section in the manifest
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Attributes)> {
+        self.entries
+            .iter()
+            .map(|(name, attributes)| (name.as_str(), attributes))
+    }
+
+    /// The `Main-Class` of the jar
+    pub fn main_class(&self) -> Option<&str> {
+        self.main.get("Main-Class")
+    }
+
+    /// The relative urls in the `Class-Path` of the jar
+    pub fn class_path(&self) -> Vec<&str> {
+        self.main
+            .get("Class-Path")
+            .map(|class_path| class_path.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Display for Manifest {
+    /// Writes the manifest, splitting lines longer than 72 bytes
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_section(f, &self.main)?;
+        for (_, attributes) in &self.entries {
+            writeln!(f)?;
+            write_section(f, attributes)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_section(f: &mut Formatter<'_>, attributes: &Attributes) -> std::fmt::Result {
+    for (key, value) in attributes.iter() {
+        let line = format!("{key}: {value}");
+        let mut rest = line.as_str();
+        let mut limit = MAX_LINE_LENGTH;
+        loop {
+            if rest.len() <= limit {
+                writeln!(f, "{rest}")?;
+                break;
+            }
+            let mut split = limit;
+            while !rest.is_char_boundary(split) {
+                split -= 1;
+            }
+            writeln!(f, "{}", &rest[..split])?;
+            write!(f, " ")?;
+            rest = &rest[split..];
+            // continuation lines start with a space
+            limit = MAX_LINE_LENGTH - 1;
+        }
+    }
+    Ok(())
+}
+
+fn invalid(message: &str) -> ClasspathError {
+    ClasspathError::InvalidManifest(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "Manifest-Version: 1.0\r\n\
+        Main-Class: com.example.Main\r\n\
+        Class-Path: lib/a.jar\r\n  lib/b.jar\r\n\
+        \r\n\
+        Name: com/example/\r\n\
+        Sealed: true\r\n";
+
+    #[test]
+    fn parse_manifest() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.main_class(), Some("com.example.Main"));
+        assert_eq!(manifest.class_path(), ["lib/a.jar", "lib/b.jar"]);
+        assert_eq!(
+            manifest.main_attributes().get("manifest-version"),
+            Some("1.0")
+        );
+        assert_eq!(
+            manifest.entry("com/example/").unwrap().get("Sealed"),
+            Some("true")
+        );
+        assert!(Manifest::parse("Not an attribute").is_err());
+        assert!(Manifest::parse("A: b\n\nSealed: true\n").is_err());
+    }
+
+    #[test]
+    fn write_long_lines() {
+        let mut manifest = Manifest::default();
+        let long = "x".repeat(200);
+        manifest.main_attributes_mut().insert("Class-Path", &long);
+        let written = manifest.to_string();
+        assert!(written.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+        let parsed = Manifest::parse(&written).unwrap();
+        assert_eq!(parsed.main_attributes().get("Class-Path"), Some(&*long));
+        assert_eq!(parsed, manifest);
+    }
+}
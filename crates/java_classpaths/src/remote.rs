@@ -0,0 +1,127 @@
+//! Downloads remote archives on the classpath. Only available with the `http` feature.
+//!
+//! Archives are downloaded into a new directory in the temporary directory, which only the current
+//! user can access, and are kept until [`clear_downloads`] is called.
+
+use crate::{with_index_cache, ClasspathError};
+use std::collections::HashMap;
+use std::fs::{DirBuilder, File};
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// The archives downloaded by this process
+static DOWNLOADS: Mutex<Option<Downloads>> = Mutex::new(None);
+
+/// The local copy of a remote archive, once it's downloaded
+type Download = Arc<Mutex<Option<PathBuf>>>;
+
+#[derive(Debug, Default)]
+struct Downloads {
+    /// The directory archives are downloaded into, created by the first download
+    dir: Option<PathBuf>,
+    /// Every archive downloaded or being downloaded, by its url
+    archives: HashMap<Url, Download>,
+    /// The number of archives downloaded into the directory, which keeps their names unique
+    count: usize,
+}
+
+impl Downloads {
+    /// Chooses the path to download an archive into, creating the download directory if needed
+    fn reserve(&mut self, url: &Url) -> io::Result<PathBuf> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => self.dir.insert(create_private_dir()?).clone(),
+        };
+        self.count += 1;
+        let name = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default();
+        Ok(dir.join(format!("{}-{name}", self.count)))
+    }
+}
+
+/// Runs a function with the downloads of this process
+fn with_downloads<R>(f: impl FnOnce(&mut Downloads) -> R) -> R {
+    let mut downloads = DOWNLOADS.lock().unwrap_or_else(|e| e.into_inner());
+    f(downloads.get_or_insert_with(Downloads::default))
+}
+
+/// Gets the local copy of a remote archive, downloading it the first time it's needed. Only
+/// downloads of the same archive wait for each other.
+pub(crate) fn download(url: &Url) -> Result<PathBuf, ClasspathError> {
+    let error = |source: io::Error| ClasspathError::Download {
+        url: Box::new(url.clone()),
+        source,
+    };
+    let download =
+        with_downloads(|downloads| downloads.archives.entry(url.clone()).or_default().clone());
+    let mut download = download.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(path) = &*download {
+        return Ok(path.clone());
+    }
+
+    let path = with_downloads(|downloads| downloads.reserve(url)).map_err(error)?;
+    let response = ureq::get(url.as_str())
+        .call()
+        .map_err(|e| error(io::Error::new(io::ErrorKind::Other, e)))?;
+    // written to a temporary file first, so a failed download is never mistaken for an archive
+    let partial = path.with_extension("part");
+    let result = File::options()
+        .write(true)
+        .create_new(true)
+        .open(&partial)
+        .and_then(|mut file| io::copy(&mut response.into_reader(), &mut file))
+        .and_then(|_| std::fs::rename(&partial, &path));
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&partial);
+        return Err(error(e));
+    }
+
+    *download = Some(path.clone());
+    Ok(path)
+}
+
+/// Removes every archive downloaded by this process, and forgets their indices. Remote archives
+/// are downloaded again the next time they're read.
+///
+/// # Error
+/// Will return an error if the downloaded archives can't be removed
+pub fn clear_downloads() -> io::Result<()> {
+    let (dir, archives) = with_downloads(|downloads| {
+        let archives = downloads.archives.drain().collect::<Vec<_>>();
+        (downloads.dir.take(), archives)
+    });
+    let paths = archives
+        .into_iter()
+        .filter_map(|(_, download)| download.lock().unwrap_or_else(|e| e.into_inner()).clone())
+        .collect::<Vec<_>>();
+    with_index_cache(|cache| {
+        for path in &paths {
+            cache.remove(path);
+        }
+    });
+    match dir {
+        Some(dir) => std::fs::remove_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Creates a new directory in the temporary directory that only the current user can access.
+/// Directories that already exist are never reused, so nothing can be planted in them.
+fn create_private_dir() -> io::Result<PathBuf> {
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    let temp = std::env::temp_dir();
+    let mut attempt = 0;
+    loop {
+        let dir = temp.join(format!("java_classpaths-{}-{attempt}", std::process::id()));
+        match builder.create(&dir) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            result => return result.map(|()| dir),
+        }
+    }
+}
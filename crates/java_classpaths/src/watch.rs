@@ -0,0 +1,189 @@
+//! Watches the entries of a classpath for changes, such as classes being recompiled into a
+//! directory or a jar being rebuilt. Only available with the `watch` feature.
+//!
+//! # Example
+//! ```no_run
+//! # use std::time::Duration;
+//! # use java_classpaths::{Classpath, ClasspathEvent};
+//! let cp = Classpath::from_iter(["build/classes", "lib/guava.jar"]);
+//! let watcher = cp.watch().unwrap();
+//! while let Some(event) = watcher.recv_timeout(Duration::from_secs(60)) {
+//!     match event.unwrap() {
+//!         ClasspathEvent::ResourceChanged { resource, .. } => println!("{resource} changed"),
+//!         ClasspathEvent::EntryChanged(entry) => println!("{} changed", entry.display()),
+//!         ClasspathEvent::Rescan => println!("anything may have changed"),
+//!     }
+//! }
+//! ```
+
+use crate::{is_archive, Classpath, ClasspathError, NESTED_ARCHIVE_SEPARATOR};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// A change to something on a classpath
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClasspathEvent {
+    /// A resource in a directory entry was created, modified or removed
+    ResourceChanged {
+        /// The directory entry
+        entry: PathBuf,
+        /// The path of the resource in the entry, such as `com/example/Square.class`
+        resource: String,
+    },
+    /// An archive entry was created, modified or removed, so any of its resources may have changed
+    EntryChanged(PathBuf),
+    /// Some changes were missed, so anything on the classpath may have changed
+    Rescan,
+}
+
+/// Watches the entries of a classpath, created by [`Classpath::watch`]. Directories are watched
+/// recursively, and archives are watched through their parent directory so that they can be
+/// replaced. Entries that don't exist when the watcher is created aren't watched.
+///
+/// Events are queued until they're received, and watching stops when the watcher is dropped.
+pub struct ClasspathWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<Result<ClasspathEvent, ClasspathError>>,
+}
+
+impl ClasspathWatcher {
+    /// Receives the next event if one is queued, without blocking
+    pub fn try_recv(&self) -> Option<Result<ClasspathEvent, ClasspathError>> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits for the next event, for at most `timeout`
+    pub fn recv_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Option<Result<ClasspathEvent, ClasspathError>> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// Receives every queued event, without blocking
+    pub fn try_iter(&self) -> impl Iterator<Item = Result<ClasspathEvent, ClasspathError>> + '_ {
+        self.events.try_iter()
+    }
+}
+
+impl Debug for ClasspathWatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClasspathWatcher").finish_non_exhaustive()
+    }
+}
+
+/// A watched classpath entry
+#[derive(Debug, Clone)]
+struct WatchedEntry {
+    entry: PathBuf,
+    /// The canonical path of the watched file or directory, which is the outer archive of a nested
+    /// archive. Events are reported for the paths that are watched.
+    watched: PathBuf,
+    is_archive: bool,
+}
+
+impl WatchedEntry {
+    fn new(entry: &Path) -> Option<Self> {
+        let outer = entry
+            .to_str()
+            .and_then(|entry| entry.split_once(NESTED_ARCHIVE_SEPARATOR))
+            .map_or(entry, |(outer, _)| Path::new(outer));
+        let watched = outer.canonicalize().ok()?;
+        let is_archive = !watched.is_dir() && is_archive(outer);
+        if !is_archive && !watched.is_dir() {
+            return None;
+        }
+        Some(Self {
+            entry: entry.to_path_buf(),
+            watched,
+            is_archive,
+        })
+    }
+
+    /// Converts a changed path into an event, if the path is part of this entry
+    fn event(&self, path: &Path) -> Option<ClasspathEvent> {
+        if self.is_archive {
+            return (path == self.watched)
+                .then(|| ClasspathEvent::EntryChanged(self.entry.clone()));
+        }
+        let relative = path.strip_prefix(&self.watched).ok()?;
+        if relative.as_os_str().is_empty() {
+            return None;
+        }
+        let resource = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(ClasspathEvent::ResourceChanged {
+            entry: self.entry.clone(),
+            resource,
+        })
+    }
+}
+
+/// Watching
+impl Classpath {
+    /// Starts watching the entries of this classpath for changes
+    ///
+    /// # Error
+    /// Will return an error if the file system can't be watched
+    pub fn watch(&self) -> Result<ClasspathWatcher, ClasspathError> {
+        let entries = self
+            .paths
+            .iter()
+            .filter_map(|entry| WatchedEntry::new(entry))
+            .collect::<Vec<_>>();
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher({
+            let entries = entries.clone();
+            move |event: notify::Result<Event>| {
+                let events = match event {
+                    Ok(event) => classpath_events(&entries, event),
+                    Err(e) => vec![Err(e.into())],
+                };
+                for event in events {
+                    // the watcher has been dropped
+                    let _ = sender.send(event);
+                }
+            }
+        })?;
+        for entry in &entries {
+            if entry.is_archive {
+                if let Some(parent) = entry.watched.parent() {
+                    watcher.watch(parent, RecursiveMode::NonRecursive)?;
+                }
+            } else {
+                watcher.watch(&entry.watched, RecursiveMode::Recursive)?;
+            }
+        }
+        Ok(ClasspathWatcher {
+            _watcher: watcher,
+            events,
+        })
+    }
+}
+
+fn classpath_events(
+    entries: &[WatchedEntry],
+    event: Event,
+) -> Vec<Result<ClasspathEvent, ClasspathError>> {
+    if event.need_rescan() {
+        return vec![Ok(ClasspathEvent::Rescan)];
+    }
+    if !matches!(
+        event.kind,
+        EventKind::Any | EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return vec![];
+    }
+    event
+        .paths
+        .iter()
+        .flat_map(|path| entries.iter().filter_map(|entry| entry.event(path)))
+        .map(Ok)
+        .collect()
+}
@@ -0,0 +1,59 @@
+use java_classpaths::{Classpath, CLASSPATH_ENV_VAR, CLASSPATH_SEPARATOR};
+use std::path::{Path, PathBuf};
+
+fn entries(cp: Classpath) -> Vec<PathBuf> {
+    cp.into_iter().collect()
+}
+
+#[test]
+fn resolve_classpath_string() {
+    let base = Path::new("/base");
+    let cp = Classpath::resolve(
+        format!("classes{0}{0}/opt/lib.jar", CLASSPATH_SEPARATOR),
+        base,
+    );
+    assert_eq!(
+        entries(cp),
+        [
+            base.join("classes"),
+            base.to_path_buf(),
+            PathBuf::from("/opt/lib.jar")
+        ]
+    );
+    assert_eq!(entries(Classpath::resolve("", base)), [base]);
+}
+
+#[test]
+fn classpath_from_env() {
+    let base = Path::new("/base");
+    std::env::set_var(CLASSPATH_ENV_VAR, format!("a.jar{CLASSPATH_SEPARATOR}b"));
+    assert_eq!(
+        entries(Classpath::from_env_in(base)),
+        [base.join("a.jar"), base.join("b")]
+    );
+    std::env::remove_var(CLASSPATH_ENV_VAR);
+    assert_eq!(entries(Classpath::from_env_in(base)), [base]);
+    assert_eq!(
+        entries(Classpath::from_env()),
+        [std::env::current_dir().unwrap()]
+    );
+}
+
+#[test]
+fn file_url_entries() {
+    let jar = itest_common::jar_file();
+    let url = url::Url::from_file_path(&jar).unwrap();
+    let cp = Classpath::from(url.as_str());
+    assert_eq!(entries(cp.clone()), [jar]);
+    assert!(cp.get("TEST_FILE.txt").is_ok());
+}
+
+#[cfg(not(feature = "http"))]
+#[test]
+fn remote_entries_need_http_feature() {
+    let cp = Classpath::from("https://example.com/lib.jar");
+    assert!(matches!(
+        cp.get("TEST_FILE.txt"),
+        Err(java_classpaths::ClasspathError::UnsupportedEntry(_))
+    ));
+}
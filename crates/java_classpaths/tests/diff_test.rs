@@ -0,0 +1,62 @@
+use itest_common::{classes, jar_file};
+use java_classpaths::diff::ShadowChange;
+use java_classpaths::Classpath;
+use std::path::Path;
+
+#[test]
+fn set_operations() {
+    let cp = Classpath::from_iter(["a.jar", "b.jar", "a.jar", "c.jar"]);
+    let other = Classpath::from_iter(["c.jar", "a.jar", "d.jar"]);
+    assert_eq!(
+        cp.intersection(&other),
+        Classpath::from_iter(["a.jar", "c.jar"])
+    );
+    assert_eq!(cp.difference(&other), Classpath::from("b.jar"));
+    assert_eq!(other.difference(&cp), Classpath::from("d.jar"));
+}
+
+#[test]
+fn diff_classes() {
+    let empty = Path::new(env!("CARGO_TARGET_TMPDIR")).join("diff_empty");
+    std::fs::create_dir_all(&empty).unwrap();
+
+    let old = Classpath::from_iter([classes(), jar_file()]);
+    let new = Classpath::from_iter([jar_file(), empty.clone()]);
+    let diff = old.diff(&new).unwrap();
+    assert_eq!(diff.removed_entries(), [classes()]);
+    assert_eq!(diff.added_entries(), std::slice::from_ref(&empty));
+
+    let only_classes = Classpath::from(classes());
+    let jar_classes = Classpath::from(jar_file())
+        .classes()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let dir_classes = only_classes
+        .classes()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(!jar_classes.is_empty());
+    for change in diff.shadow_changes() {
+        assert_eq!(
+            change,
+            &ShadowChange {
+                name: change.name.clone(),
+                old_entry: classes(),
+                new_entry: jar_file(),
+            }
+        );
+    }
+    assert_eq!(
+        diff.shadow_changes().len() + diff.removed_classes().len(),
+        dir_classes.len()
+    );
+    assert!(diff
+        .added_classes()
+        .iter()
+        .all(|c| !dir_classes.contains(c)));
+
+    let removed = Classpath::from(empty).diff(&only_classes).unwrap();
+    assert_eq!(removed.added_classes().len(), dir_classes.len());
+    assert!(removed.to_string().contains("+ class "));
+    assert!(old.diff(&old).unwrap().is_empty());
+}
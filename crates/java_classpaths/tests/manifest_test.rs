@@ -0,0 +1,40 @@
+use java_classpaths::{Classpath, Manifest};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn expand_manifest_class_paths() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("manifests");
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    // manifest entries are resolved to canonical paths
+    let dir = dir.canonicalize().unwrap();
+    let main = dir.join("main.jar");
+    let dep = dir.join("lib/dep.jar");
+    let other = dir.join("other.jar");
+    itest_common::write_jar_at(
+        &main,
+        &[(
+            "META-INF/MANIFEST.MF",
+            "Manifest-Version: 1.0\nMain-Class: com.example.Main\nClass-Path: lib/dep.jar missing.jar\n",
+        )],
+    );
+    itest_common::write_jar_at(
+        &dep,
+        &[(
+            "META-INF/MANIFEST.MF",
+            "Manifest-Version: 1.0\nClass-Path: ../main.jar\n",
+        )],
+    );
+    itest_common::write_jar_at(
+        &other,
+        &[("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n")],
+    );
+
+    let manifest = Manifest::from_archive(&main).unwrap().unwrap();
+    assert_eq!(manifest.main_class(), Some("com.example.Main"));
+
+    let cp = Classpath::from_iter([&main, &other])
+        .expand_manifests()
+        .unwrap();
+    let entries = cp.into_iter().collect::<Vec<PathBuf>>();
+    assert_eq!(entries, [main, dep, other]);
+}
@@ -0,0 +1,52 @@
+use itest_common::jar_file;
+use java_classpaths::{open_archive, ArchiveIndex, Classpath};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Creates a fat jar with the test jar nested under `BOOT-INF/lib`
+fn fat_jar() -> PathBuf {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("classpaths-fat.jar");
+    let mut writer = ZipWriter::new(File::create(&path).unwrap());
+    writer
+        .start_file("BOOT-INF/lib/inner.jar", FileOptions::default())
+        .unwrap();
+    writer
+        .write_all(&std::fs::read(jar_file()).unwrap())
+        .unwrap();
+    writer.finish().unwrap();
+    path
+}
+
+#[test]
+fn read_nested_jar_file() {
+    let fat_jar = fat_jar();
+    let nested = format!("{}!/BOOT-INF/lib/inner.jar", fat_jar.display());
+    let cp = Classpath::from(nested.as_str());
+    let mut text_file = cp.get("TEST_FILE.txt").expect("should be on classpath");
+
+    let mut buffer = String::new();
+    text_file
+        .read_to_string(&mut buffer)
+        .expect("should be readable");
+    assert_eq!(buffer.trim(), "Hello, World!");
+
+    assert!(open_archive(&nested).unwrap().len() > 1);
+    assert!(open_archive(format!("{}!/BOOT-INF/lib/missing.jar", fat_jar.display())).is_err());
+}
+
+#[test]
+fn archive_index_is_shared() {
+    let jar = itest_common::jar_file();
+    let index = ArchiveIndex::open(&jar).unwrap();
+    assert!(Arc::ptr_eq(&index, &ArchiveIndex::open(&jar).unwrap()));
+    assert!(index.contains("com/example/Square.class"));
+    assert!(!index.contains("com/example/Missing.class"));
+    assert_eq!(index.names().count(), index.len());
+    let square = index.read("com/example/Square.class").unwrap().unwrap();
+    assert_eq!(&square[..4], &[0xCA, 0xFE, 0xBA, 0xBE]);
+    assert!(index.read("com/example/Missing.class").is_none());
+}
@@ -1,6 +1,6 @@
 use itest_common::jar_file;
-use java_classpaths::Classpath;
-use std::io::Read;
+use java_classpaths::{Classpath, ClasspathError};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 fn test_resources() -> PathBuf {
@@ -13,10 +13,7 @@ fn test_resources() -> PathBuf {
 #[test]
 fn read_file() {
     let cp = Classpath::from(test_resources());
-    let mut text_file = cp
-        .get("TEST_FILE.txt")
-        .expect("should be on classpath")
-        .expect("should be readable");
+    let mut text_file = cp.get("TEST_FILE.txt").expect("should be on classpath");
 
     let mut buffer = String::new();
     text_file
@@ -29,10 +26,7 @@ fn read_file() {
 #[test]
 fn read_jar_file() {
     let cp = Classpath::from(jar_file());
-    let mut text_file = cp
-        .get("TEST_FILE.txt")
-        .expect("should be on classpath")
-        .expect("should be readable");
+    let mut text_file = cp.get("TEST_FILE.txt").expect("should be on classpath");
 
     let mut buffer = String::new();
     text_file
@@ -41,3 +35,101 @@ fn read_jar_file() {
     let trimmed = buffer.trim();
     assert_eq!(trimmed, "Hello, World!")
 }
+
+#[test]
+fn list_resources() {
+    let cp = Classpath::from_iter([test_resources(), jar_file()]);
+    let resources = cp.resources().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(resources[0], "TEST_FILE.txt");
+    assert_eq!(
+        resources
+            .iter()
+            .filter(|resource| *resource == "TEST_FILE.txt")
+            .count(),
+        2
+    );
+    assert!(resources.iter().all(|resource| !resource.ends_with('/')));
+
+    let classes = cp.classes().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(classes.iter().any(|class| class == "com/example/Square"));
+    assert!(classes.iter().all(|class| !class.ends_with(".class")));
+}
+
+#[test]
+fn get_all_resources() {
+    let cp = Classpath::from_iter([test_resources(), jar_file()]);
+    let resources = cp.get_all("/TEST_FILE.txt");
+    assert_eq!(resources.len(), 2);
+    let urls = resources
+        .into_iter()
+        .map(|resource| {
+            let mut resource = resource.unwrap();
+            let mut buffer = String::new();
+            resource.read_to_string(&mut buffer).unwrap();
+            assert_eq!(buffer.trim(), "Hello, World!");
+            resource.url().clone()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(urls[0].scheme(), "file");
+    assert_eq!(urls[1].scheme(), "jar");
+    // like java, the entry is separated from the archive by `!/`
+    assert!(urls[1].as_str().ends_with(".jar!/TEST_FILE.txt"));
+    assert!(cp.get_all("MISSING.txt").is_empty());
+}
+
+#[test]
+fn resource_metadata() {
+    let cp = Classpath::from(test_resources());
+    let resource = cp.get("TEST_FILE.txt").unwrap();
+    let expected = std::fs::read(test_resources().join("TEST_FILE.txt")).unwrap();
+    assert!(!resource.is_from_archive());
+    assert_eq!(resource.len(), expected.len() as u64);
+    assert!(resource.last_modified().is_some());
+    assert_eq!(resource.bytes().unwrap(), expected);
+
+    let cp = Classpath::from(jar_file());
+    let mut resource = cp.get("TEST_FILE.txt").unwrap();
+    assert!(resource.is_from_archive());
+    assert_eq!(resource.len(), expected.len() as u64);
+    assert!(resource.last_modified().is_some());
+    let mut buffer = String::new();
+    resource.read_to_string(&mut buffer).unwrap();
+    resource.seek(SeekFrom::Start(7)).unwrap();
+    let mut rest = String::new();
+    resource.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, buffer[7..]);
+    assert_eq!(resource.bytes().unwrap(), expected);
+}
+
+#[test]
+fn lookup_errors() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("lookup_errors");
+    std::fs::create_dir_all(&dir).unwrap();
+    let corrupt = dir.join("corrupt.jar");
+    std::fs::write(&corrupt, b"not a zip file").unwrap();
+    let unsupported = dir.join("notes.txt");
+    std::fs::write(&unsupported, b"not an archive").unwrap();
+
+    let cp = Classpath::from(test_resources());
+    let error = cp.get("MISSING.txt").unwrap_err();
+    assert!(error.is_not_found(), "{error:?}");
+
+    let cp = Classpath::from(corrupt.clone());
+    match cp.get("TEST_FILE.txt") {
+        Err(ClasspathError::ArchiveCorrupt { archive, .. }) => assert_eq!(archive, corrupt),
+        other => panic!("expected a corrupt archive, got {other:?}"),
+    }
+
+    // files that aren't archives are skipped, rather than stopping the lookup
+    let cp = Classpath::from_iter([unsupported.clone(), test_resources()]);
+    let resource = cp
+        .get("TEST_FILE.txt")
+        .expect("notes.txt should be skipped");
+    assert_eq!(
+        resource.url().to_file_path().unwrap(),
+        test_resources().join("TEST_FILE.txt")
+    );
+    let cp = Classpath::from(unsupported);
+    let error = cp.get("TEST_FILE.txt").unwrap_err();
+    assert!(error.is_not_found(), "{error:?}");
+}
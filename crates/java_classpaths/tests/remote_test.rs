@@ -0,0 +1,65 @@
+use itest_common::jar_file;
+use java_classpaths::{Classpath, ClasspathError};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+/// Serves the test jar at `/lib.jar` over http, answering every other path with a 404
+fn serve_jar() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let jar = std::fs::read(jar_file()).unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&mut stream);
+            reader.read_line(&mut request_line).unwrap();
+            // skip the headers
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let response = if request_line.starts_with("GET /lib.jar ") {
+                let mut response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    jar.len()
+                )
+                .into_bytes();
+                response.extend(&jar);
+                response
+            } else {
+                b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+            };
+            stream.write_all(&response).unwrap();
+        }
+    });
+    format!("http://{address}")
+}
+
+#[test]
+fn read_remote_jar() {
+    let server = serve_jar();
+    let url = format!("{server}/lib.jar");
+    let cp = Classpath::from(url.as_str());
+    let mut resource = cp.get("TEST_FILE.txt").expect("should be on classpath");
+    let mut buffer = String::new();
+    resource.read_to_string(&mut buffer).unwrap();
+    assert_eq!(buffer.trim(), "Hello, World!");
+    assert_eq!(resource.url().as_str(), format!("jar:{url}!/TEST_FILE.txt"));
+
+    let classes = cp.classes().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(classes.contains(&"com/example/Square".to_string()));
+
+    java_classpaths::clear_downloads().unwrap();
+    assert!(
+        cp.get("TEST_FILE.txt").is_ok(),
+        "should be downloaded again"
+    );
+
+    let missing = Classpath::from(format!("{server}/missing.jar").as_str());
+    assert!(matches!(
+        missing.get("TEST_FILE.txt"),
+        Err(ClasspathError::Download { .. })
+    ));
+    java_classpaths::clear_downloads().unwrap();
+}
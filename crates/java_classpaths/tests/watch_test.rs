@@ -0,0 +1,54 @@
+use itest_common::jar_file;
+use java_classpaths::{Classpath, ClasspathEvent};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Waits for an event matching a predicate
+fn wait_for<F: FnMut(&ClasspathEvent) -> bool>(
+    watcher: &java_classpaths::ClasspathWatcher,
+    mut predicate: F,
+) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+        match watcher.recv_timeout(timeout) {
+            Some(event) => {
+                if predicate(&event.unwrap()) {
+                    return true;
+                }
+            }
+            None => return false,
+        }
+    }
+    false
+}
+
+#[test]
+fn watch_directories_and_archives() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("watch");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("classes/com/example")).unwrap();
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    let classes = dir.join("classes");
+    let jar = dir.join("lib/app.jar");
+    std::fs::copy(jar_file(), &jar).unwrap();
+
+    let cp = Classpath::from_iter([classes.clone(), jar.clone(), dir.join("missing")]);
+    let watcher = cp.watch().unwrap();
+
+    std::fs::write(classes.join("com/example/Square.class"), b"changed").unwrap();
+    assert!(wait_for(&watcher, |event| event
+        == &ClasspathEvent::ResourceChanged {
+            entry: classes.clone(),
+            resource: "com/example/Square.class".to_string(),
+        }));
+
+    std::fs::write(dir.join("lib/other.jar"), b"not on the classpath").unwrap();
+    std::fs::copy(jar_file(), &jar).unwrap();
+    assert!(wait_for(&watcher, |event| {
+        assert_ne!(
+            event,
+            &ClasspathEvent::EntryChanged(dir.join("lib/other.jar"))
+        );
+        event == &ClasspathEvent::EntryChanged(jar.clone())
+    }));
+}
@@ -0,0 +1,34 @@
+use itest_common::jar_file;
+use java_classpaths::{Classpath, CLASSPATH_SEPARATOR};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn expand_wildcard_entries() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("wildcard");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    for name in ["b.jar", "a.JAR", "nested/c.jar"] {
+        std::fs::copy(jar_file(), dir.join(name)).unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), "not a jar").unwrap();
+
+    let cp = Classpath::resolve(
+        format!("first{0}{1}/*{0}last", CLASSPATH_SEPARATOR, dir.display()),
+        "/base",
+    );
+    let entries = cp.into_iter().collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        [
+            PathBuf::from("/base/first"),
+            dir.join("a.JAR"),
+            dir.join("b.jar"),
+            PathBuf::from("/base/last")
+        ]
+    );
+
+    let cp = Classpath::from(format!("{}/*", dir.display()).as_str());
+    assert_eq!(cp.len(), 2);
+    assert!(cp.get("TEST_FILE.txt").is_ok());
+    assert!(Classpath::from(dir.join("missing/*")).is_empty());
+}
@@ -1,24 +1,305 @@
-use clap::Parser;
-use java_class_parser::error::Error;
-use java_class_parser::{JavaClass, JavaClassParser};
+use clap::{Parser, Subcommand, ValueEnum};
+use java_class_parser::attributes::{Code, JavaConstant, LineNumberTable, SourceFile};
+use java_class_parser::compat::{diff_jars, ApiChange};
+use java_class_parser::error::{Error, ErrorKind};
+use java_class_parser::inheritance::{inspect, InheritKind, InheritanceIndex};
+use java_class_parser::javap::JavapOptions;
+use java_class_parser::mapping::{DeobfuscatedMember, Mapping};
+use java_class_parser::{
+    FQName, FQNameBuf, HasAttributes, JavaClass, JavaClassParser, ResolvedConstant, Signature,
+};
 use java_classpaths::Classpath;
-use std::io::{stderr, stdin, stdout, Write};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{self, stdin, stdout, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// The exit code when a class couldn't be found on the classpath, or nothing matched a search
+const EXIT_NOT_FOUND: u8 = 1;
+/// The exit code when a class couldn't be read, or the arguments are invalid
+const EXIT_ERROR: u8 = 2;
+/// The exit code when a new version of an API breaks code using the old version
+const EXIT_BREAKING: u8 = 3;
 
 #[derive(Debug, Parser)]
 struct CliArgs {
     /// The classpath used to parse classes
+    #[clap(long, visible_alias = "cp", global = true, default_value = ".")]
     classpath: Classpath,
+    /// A ProGuard or R8 mapping file, used to show the original names of obfuscated classes
+    #[clap(long, global = true)]
+    mapping: Option<PathBuf>,
+    /// Also find classes in the runtime image of the installed JDK
+    #[clap(long, global = true)]
+    jdk: bool,
+    /// The format of the output
+    #[clap(long, global = true, value_enum, default_value = "text")]
+    format: Format,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Shows everything known about a class
+    Inspect {
+        /// The fully qualified name of the class
+        class: String,
+    },
+    /// Lists the methods of a class
+    Methods {
+        /// The fully qualified name of the class
+        class: String,
+    },
+    /// Lists the fields of a class
+    Fields {
+        /// The fully qualified name of the class
+        class: String,
+    },
+    /// Lists the interfaces a class directly implements
+    Interfaces {
+        /// The fully qualified name of the class
+        class: String,
+    },
+    /// Disassembles a class, like javap
+    Disasm {
+        /// The fully qualified name of the class
+        class: String,
+        /// Show private members
+        #[clap(short = 'p', long)]
+        private: bool,
+        /// Disassemble the code of methods
+        #[clap(short = 'c', long)]
+        code: bool,
+        /// Show the constant pool, descriptors, flags, line number tables and attributes
+        #[clap(short = 'v', long)]
+        verbose: bool,
+    },
+    /// Lists the classes a class depends on grouped by package, or the dependencies of every
+    /// class on the classpath if no class is given. Dependencies that can't be found are flagged.
+    Deps {
+        /// The fully qualified name of the class
+        class: Option<String>,
+        /// Only list the packages each package depends on
+        #[clap(long)]
+        packages: bool,
+    },
+    /// Shows the super classes and interfaces of a class as a tree
+    Tree {
+        /// The fully qualified name of the class
+        class: String,
+        /// Also show the classes on the classpath that extend or implement the class
+        #[clap(long)]
+        descendants: bool,
+        /// Render the hierarchy as a graphviz graph
+        #[clap(long)]
+        dot: bool,
+    },
+    /// Searches every class on the classpath for string literals, referenced classes, methods
+    /// and annotations matching a regular expression. All of them are searched unless some are
+    /// selected.
+    Grep {
+        /// The regular expression to search for
+        pattern: String,
+        /// Search string literals
+        #[clap(long)]
+        strings: bool,
+        /// Search the classes that are referenced
+        #[clap(long)]
+        classes: bool,
+        /// Search the names of declared and called methods
+        #[clap(long)]
+        methods: bool,
+        /// Search the types of annotations
+        #[clap(long)]
+        annotations: bool,
+        /// Treat the pattern as a literal string
+        #[clap(short = 'F', long)]
+        fixed_strings: bool,
+        /// Ignore case when matching
+        #[clap(short = 'i', long)]
+        ignore_case: bool,
+    },
+    /// Compares the public APIs of two jars or classpaths, listing added and removed classes and
+    /// changed members. Exits with 3 if the new API breaks code using the old API.
+    Diff {
+        /// The old version of the API
+        old: String,
+        /// The new version of the API
+        new: String,
+        /// Only list changes that aren't binary or source compatible
+        #[clap(long)]
+        breaking_only: bool,
+    },
+    /// Discover information about classes interactively
+    Repl,
+}
+
+/// What grep searches for
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Target {
+    String,
+    Class,
+    Method,
+    Annotation,
+}
+
+impl Target {
+    fn name(&self) -> &'static str {
+        match self {
+            Target::String => "string",
+            Target::Class => "class",
+            Target::Method => "method",
+            Target::Annotation => "annotation",
+        }
+    }
+}
+
+/// Something grep found in a class
+#[derive(Debug, Eq, PartialEq)]
+struct Found {
+    target: Target,
+    /// The class or member it was found in
+    context: String,
+    line: Option<u16>,
+    text: String,
 }
 
-fn main() -> Result<(), Error> {
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Format {
+    /// Text meant to be read
+    Text,
+    /// JSON, using the serialized form of classes, fields and methods
+    Json,
+}
+
+/// What to show about a class
+#[derive(Debug, Copy, Clone)]
+enum Query {
+    Inspect,
+    Methods,
+    Fields,
+    Interfaces,
+    Disasm(JavapOptions),
+}
+
+fn main() -> ExitCode {
     let args: CliArgs = CliArgs::parse();
-    println!("classpath: {}", args.classpath);
+    match run(args) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            match error.kind() {
+                ErrorKind::NoClassFound(_) => ExitCode::from(EXIT_NOT_FOUND),
+                _ => ExitCode::from(EXIT_ERROR),
+            }
+        }
+    }
+}
+
+fn run(args: CliArgs) -> Result<ExitCode, Error> {
+    let mapping = args.mapping.map(Mapping::from_file).transpose()?;
+    let mapping = mapping.as_ref();
+    let mut parser = JavaClassParser::with_classpath(args.classpath.clone());
+    if args.jdk {
+        parser = parser.with_jdk()?;
+    }
+    let format = args.format;
+    let show = |class: &str, target: Query| query(&parser, mapping, format, class, target);
+
+    match args.command {
+        Command::Inspect { class } => show(&class, Query::Inspect)?,
+        Command::Methods { class } => show(&class, Query::Methods)?,
+        Command::Fields { class } => show(&class, Query::Fields)?,
+        Command::Interfaces { class } => show(&class, Query::Interfaces)?,
+        Command::Disasm {
+            class,
+            private,
+            code,
+            verbose,
+        } => {
+            let options = JavapOptions::new()
+                .private(private)
+                .code(code)
+                .verbose(verbose);
+            show(&class, Query::Disasm(options))?
+        }
+        Command::Deps { class, packages } => {
+            deps(&parser, mapping, format, class.as_deref(), packages)?
+        }
+        Command::Tree {
+            class,
+            descendants,
+            dot,
+        } => tree(&parser, mapping, format, &class, descendants, dot)?,
+        Command::Grep {
+            pattern,
+            strings,
+            classes,
+            methods,
+            annotations,
+            fixed_strings,
+            ignore_case,
+        } => {
+            let pattern = match fixed_strings {
+                true => regex::escape(&pattern),
+                false => pattern,
+            };
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(ignore_case)
+                .build()
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+            let mut targets = [
+                (strings, Target::String),
+                (classes, Target::Class),
+                (methods, Target::Method),
+                (annotations, Target::Annotation),
+            ]
+            .into_iter()
+            .filter(|&(selected, _)| selected)
+            .map(|(_, target)| target)
+            .collect::<Vec<_>>();
+            if targets.is_empty() {
+                targets = vec![
+                    Target::String,
+                    Target::Class,
+                    Target::Method,
+                    Target::Annotation,
+                ];
+            }
+            if !grep(&parser, format, &regex, &targets)? {
+                return Ok(ExitCode::from(EXIT_NOT_FOUND));
+            }
+        }
+        Command::Diff {
+            old,
+            new,
+            breaking_only,
+        } => {
+            if !diff(format, &old, &new, breaking_only)? {
+                return Ok(ExitCode::from(EXIT_BREAKING));
+            }
+        }
+        Command::Repl => repl(&parser, &args.classpath, mapping, format)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn repl(
+    parser: &JavaClassParser,
+    classpath: &Classpath,
+    mapping: Option<&Mapping>,
+    format: Format,
+) -> Result<(), Error> {
+    println!("classpath: {}", classpath);
     println!();
     println!("Discover information about a class by typing it's fully qualified name. Specific information");
-    println!("about it's methods or fields and be discovered by appending :methods or :fields to the name.");
+    println!("about it's methods, fields or interfaces and be discovered by appending :methods, :fields or");
+    println!(":interfaces to the name. Append :javap to disassemble the class.");
     println!("You can exit this program by typing either 'quit' or 'exit'");
 
-    let parser = JavaClassParser::with_classpath(args.classpath);
     let mut lines = stdin().lines();
     loop {
         print!("> ");
@@ -28,46 +309,689 @@ fn main() -> Result<(), Error> {
         } else {
             break;
         };
-        let line = line.replace(".", "/");
         if line == "quit" || line == "exit" {
             break;
         }
-        let (class, target) = match line.split_once(":") {
-            Some((left, right)) => {
-                (left, Some(right))
-            },
-            None => (&*line, None)
+        let (class, target) = match line.split_once(':') {
+            Some((left, right)) => (left, Some(right)),
+            None => (&*line, None),
+        };
+        let target = match target {
+            None => Query::Inspect,
+            Some("methods") => Query::Methods,
+            Some("fields") => Query::Fields,
+            Some("interfaces") => Query::Interfaces,
+            Some("javap") => Query::Disasm(JavapOptions::all()),
+            Some(_) => {
+                println!("only :methods, :fields, :interfaces and :javap are supported");
+                continue;
+            }
         };
 
-        match parser.find(class) {
-            Ok(class) => {
-                match target {
-                    None =>{
-                        println!("{:#?}", class);
-                    }
-                    Some("methods") => {
-                        let methods = class.methods();
-                        for method in methods {
-                            println!("{}: {}", method.name(), method.signature());
+        if let Err(error) = query(parser, mapping, format, class, target) {
+            println!("error: {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints information about a class, given by its fully qualified name
+fn query(
+    parser: &JavaClassParser,
+    mapping: Option<&Mapping>,
+    format: Format,
+    class: &str,
+    query: Query,
+) -> Result<(), Error> {
+    let class = find(parser, mapping, class)?;
+    match format {
+        Format::Text => print_text(&class, mapping, query),
+        Format::Json => print_json(&to_json(&class, mapping, query).map_err(io::Error::from)?)?,
+    }
+    Ok(())
+}
+
+/// Finds a class by its fully qualified name, in either internal or binary form
+fn find(
+    parser: &JavaClassParser,
+    mapping: Option<&Mapping>,
+    class: &str,
+) -> Result<JavaClass, Error> {
+    let class = class.replace('.', "/");
+    // original names are accepted too, and looked up by their obfuscated name
+    let class = mapping
+        .and_then(|mapping| mapping.class_by_original(&*class))
+        .map_or(&*class, |mapping| mapping.obfuscated_name().as_str());
+    parser.find(class)
+}
+
+fn print_text(class: &JavaClass, mapping: Option<&Mapping>, query: Query) {
+    match (query, mapping) {
+        (Query::Inspect, Some(mapping)) => {
+            println!(
+                "original name: {}",
+                class.deobfuscate(mapping).original_name()
+            );
+            println!("{:#?}", class);
+        }
+        (Query::Inspect, None) => {
+            println!("{:#?}", class);
+        }
+        (Query::Methods, Some(mapping)) => {
+            for method in class.deobfuscate(mapping).methods() {
+                print_member(&method);
+            }
+        }
+        (Query::Methods, None) => {
+            for method in class.methods() {
+                println!("{}: {}", method.name(), method.signature());
+            }
+        }
+        (Query::Fields, Some(mapping)) => {
+            for field in class.deobfuscate(mapping).fields() {
+                print_member(&field);
+            }
+        }
+        (Query::Fields, None) => {
+            for field in class.fields() {
+                println!("{}: {}", field.name(), field.signature());
+            }
+        }
+        (Query::Interfaces, Some(mapping)) => {
+            for interface in class.interfaces() {
+                println!("{}", mapping.original_class_name(interface));
+            }
+        }
+        (Query::Interfaces, None) => {
+            for interface in class.interfaces() {
+                println!("{}", interface);
+            }
+        }
+        (Query::Disasm(options), _) => {
+            print!("{}", class.javap_with(options));
+        }
+    }
+}
+
+fn to_json(
+    class: &JavaClass,
+    mapping: Option<&Mapping>,
+    query: Query,
+) -> Result<Value, serde_json::Error> {
+    Ok(match (query, mapping) {
+        (Query::Inspect, Some(mapping)) => {
+            let mut json = serde_json::to_value(class)?;
+            json["original_name"] = json!(class.deobfuscate(mapping).original_name());
+            json
+        }
+        (Query::Inspect, None) => serde_json::to_value(class)?,
+        (Query::Methods, Some(mapping)) => {
+            let methods = class.methods();
+            let members = class.deobfuscate(mapping).methods();
+            let methods = methods
+                .iter()
+                .zip(&members)
+                .map(|(method, member)| Ok(with_original(serde_json::to_value(method)?, member)))
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            Value::Array(methods)
+        }
+        (Query::Methods, None) => serde_json::to_value(class.methods())?,
+        (Query::Fields, Some(mapping)) => {
+            let fields = class.fields();
+            let members = class.deobfuscate(mapping).fields();
+            let fields = fields
+                .iter()
+                .zip(&members)
+                .map(|(field, member)| Ok(with_original(serde_json::to_value(field)?, member)))
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            Value::Array(fields)
+        }
+        (Query::Fields, None) => serde_json::to_value(class.fields())?,
+        (Query::Interfaces, Some(mapping)) => serde_json::to_value(
+            class
+                .interfaces()
+                .into_iter()
+                .map(|interface| mapping.original_class_name(interface))
+                .collect::<Vec<_>>(),
+        )?,
+        (Query::Interfaces, None) => serde_json::to_value(class.interfaces())?,
+        // code isn't part of the serialized form of methods, so the disassembly is kept as text
+        (Query::Disasm(options), _) => json!({
+            "name": class.this(),
+            "disassembly": class.javap_with(options).to_string(),
+        }),
+    })
+}
+
+/// Adds the original name and descriptor of a member to its serialized form
+fn with_original(mut json: Value, member: &DeobfuscatedMember) -> Value {
+    json["original_name"] = json!(member.original_name());
+    json["original_descriptor"] = json!(member.original_descriptor());
+    json
+}
+
+fn print_member(member: &DeobfuscatedMember) {
+    if member.is_renamed() {
+        println!(
+            "{} ({}): {}",
+            member.original_name(),
+            member.name(),
+            member.original_signature()
+        );
+    } else {
+        println!("{}: {}", member.name(), member.original_signature());
+    }
+}
+
+/// Prints the classes that classes depend on, grouped by package
+fn deps(
+    parser: &JavaClassParser,
+    mapping: Option<&Mapping>,
+    format: Format,
+    class: Option<&str>,
+    packages: bool,
+) -> Result<(), Error> {
+    let classes = match class {
+        Some(class) => vec![find(parser, mapping, class)?],
+        None => parser.parse_all().collect::<Result<Vec<_>, _>>()?,
+    };
+    let mut found = HashMap::<FQNameBuf, bool>::new();
+    let mut is_found = |name: &FQName| {
+        *found
+            .entry(name.to_fqname_buf())
+            .or_insert_with(|| parser.find(name).is_ok())
+    };
+
+    if packages {
+        // the classes of each dependency of a package that can't be found
+        let mut dependencies = BTreeMap::<&str, BTreeMap<&str, BTreeSet<&FQName>>>::new();
+        for class in &classes {
+            let package = package_of(original_name(mapping, class.this()));
+            let dependencies = dependencies.entry(package).or_default();
+            for dependency in class.referenced_classes() {
+                let found = is_found(dependency);
+                let dependency = original_name(mapping, dependency);
+                let missing = dependencies.entry(package_of(dependency)).or_default();
+                if !found {
+                    missing.insert(dependency);
+                }
+            }
+            dependencies.remove(package);
+        }
+        match format {
+            Format::Text => {
+                for (package, dependencies) in &dependencies {
+                    println!("{}", display_package(package));
+                    for (dependency, missing) in dependencies {
+                        match missing.len() {
+                            0 => println!("   -> {}", display_package(dependency)),
+                            count => println!(
+                                "   -> {} ({} not found)",
+                                display_package(dependency),
+                                count
+                            ),
                         }
                     }
-                    Some("fields") => {
-                        let fields = class.fields();
-                        for field in fields {
-                            println!("{}: {}", field.name(), field.signature());
+                }
+            }
+            Format::Json => {
+                let json = dependencies
+                    .iter()
+                    .map(|(package, dependencies)| {
+                        let dependencies = dependencies
+                            .iter()
+                            .map(|(dependency, missing)| {
+                                json!({"package": dependency, "not_found": missing})
+                            })
+                            .collect::<Vec<_>>();
+                        json!({"package": package, "dependencies": dependencies})
+                    })
+                    .collect::<Vec<_>>();
+                print_json(&Value::Array(json))?;
+            }
+        }
+        return Ok(());
+    }
+
+    let mut json = vec![];
+    for class in &classes {
+        let mut dependencies = BTreeMap::<&str, Vec<(&FQName, bool)>>::new();
+        for dependency in class.referenced_classes() {
+            let found = is_found(dependency);
+            let dependency = original_name(mapping, dependency);
+            dependencies
+                .entry(package_of(dependency))
+                .or_default()
+                .push((dependency, found));
+        }
+        let name = original_name(mapping, class.this());
+        match format {
+            Format::Text => {
+                println!("{}", name);
+                for (package, dependencies) in &dependencies {
+                    println!("   {}", display_package(package));
+                    for (dependency, found) in dependencies {
+                        match found {
+                            true => println!("      -> {}", dependency),
+                            false => println!("      -> {} (not found)", dependency),
                         }
                     }
-                    Some(_) => {
-                        println!("only :methods and :fields are supported");
+                }
+            }
+            Format::Json => {
+                let dependencies = dependencies
+                    .values()
+                    .flatten()
+                    .map(|(dependency, found)| {
+                        json!({
+                            "name": dependency,
+                            "package": package_of(dependency),
+                            "found": found,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json.push(json!({"name": name, "dependencies": dependencies}));
+            }
+        }
+    }
+    if let Format::Json = format {
+        print_json(&Value::Array(json))?;
+    }
+    Ok(())
+}
+
+fn print_json(json: &Value) -> Result<(), Error> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(json).map_err(io::Error::from)?
+    );
+    Ok(())
+}
+
+/// The name of a class before it was obfuscated, if there's a mapping
+fn original_name<'a>(mapping: Option<&'a Mapping>, name: &'a FQName) -> &'a FQName {
+    mapping.map_or(name, |mapping| mapping.original_class_name(name))
+}
+
+/// The package of a class, which is empty for the unnamed package
+fn package_of(name: &FQName) -> &str {
+    name.package().map_or("", FQName::as_str)
+}
+
+fn display_package(package: &str) -> &str {
+    if package.is_empty() {
+        "<unnamed>"
+    } else {
+        package
+    }
+}
+
+/// Searches every class on the classpath, one classpath entry at a time so that matches can be
+/// listed under the entry they're in. Returns whether anything was found.
+fn grep(
+    parser: &JavaClassParser,
+    format: Format,
+    regex: &Regex,
+    targets: &[Target],
+) -> Result<bool, Error> {
+    let mut any = false;
+    let mut json = vec![];
+    for entry in parser.classpath() {
+        let mut heading = false;
+        for class in JavaClassParser::with_classpath(entry.to_path_buf()).parse_all() {
+            let class = class?;
+            let found = grep_class(&class, regex, targets);
+            if found.is_empty() {
+                continue;
+            }
+            any = true;
+            let source = source_path(&class);
+            match format {
+                Format::Text => {
+                    if !heading {
+                        println!("{}", entry.display());
+                        heading = true;
+                    }
+                    for found in found {
+                        let location = match found.line {
+                            Some(line) => format!("{source}:{line}"),
+                            None => source.clone(),
+                        };
+                        println!(
+                            "{location}: {}: {} {}",
+                            found.context,
+                            found.target.name(),
+                            found.text
+                        );
                     }
                 }
+                Format::Json => json.extend(found.into_iter().map(|found| {
+                    json!({
+                        "entry": entry,
+                        "class": class.this(),
+                        "source": source,
+                        "line": found.line,
+                        "context": found.context,
+                        "kind": found.target.name(),
+                        "text": found.text,
+                    })
+                })),
+            }
+        }
+    }
+    if let Format::Json = format {
+        print_json(&Value::Array(json))?;
+    }
+    Ok(any)
+}
 
+/// Finds the string literals, referenced classes, declared and called methods and annotations
+/// of a class that match a regular expression. Classes are matched by both their internal and
+/// binary names, and references of a class to itself are left out.
+fn grep_class(class: &JavaClass, regex: &Regex, targets: &[Target]) -> Vec<Found> {
+    let name = class.this().as_str();
+    let mut found = vec![];
+    let mut search = |target: Target, context: &str, line: Option<u16>, text: &str| {
+        if !targets.contains(&target) || (target == Target::Class && text == name) {
+            return;
+        }
+        let matches = match target {
+            Target::Class | Target::Annotation => {
+                regex.is_match(text) || regex.is_match(&text.replace('/', "."))
             }
-            Err(error) => {
-                println!("error: {}", error);
+            // called methods are qualified by their class, and method names can't contain dots
+            Target::Method => regex.is_match(text.rsplit('.').next().unwrap_or(text)),
+            Target::String => regex.is_match(text),
+        };
+        let text = match target {
+            Target::String => format!("{text:?}"),
+            _ => text.to_string(),
+        };
+        let next = Found {
+            target,
+            context: context.to_string(),
+            line,
+            text,
+        };
+        // the same class or method is often used several times on a line
+        if matches && !found.contains(&next) {
+            found.push(next);
+        }
+    };
+
+    if let Some(super_class) = class.super_class() {
+        search(Target::Class, name, None, super_class.as_str());
+    }
+    for interface in class.interfaces() {
+        search(Target::Class, name, None, interface.as_str());
+    }
+    for annotation in [class.annotations(), class.invisible_annotations()].concat() {
+        search(
+            Target::Annotation,
+            name,
+            None,
+            annotation.type_name().as_str(),
+        );
+    }
+
+    for field in class.fields() {
+        let context = format!("{name}.{}", field.name());
+        for referenced in signature_classes(field.signature()) {
+            search(Target::Class, &context, None, &referenced);
+        }
+        for annotation in [field.annotations(), field.invisible_annotations()].concat() {
+            search(
+                Target::Annotation,
+                &context,
+                None,
+                annotation.type_name().as_str(),
+            );
+        }
+        if let Some(JavaConstant::String(value)) = field.constant_value() {
+            search(Target::String, &context, None, value);
+        }
+    }
+
+    for method in class.methods() {
+        let context = format!("{name}.{}", method.name());
+        let code = method.get::<Code>();
+        let lines = code.as_ref().and_then(|code| code.get::<LineNumberTable>());
+        let first_line = lines
+            .as_ref()
+            .and_then(|lines| lines.entries().iter().map(|&(_, line)| line).min());
+        search(Target::Method, name, first_line, method.name());
+        for referenced in signature_classes(method.signature()) {
+            search(Target::Class, &context, first_line, &referenced);
+        }
+        for exception in method.thrown_exceptions() {
+            search(Target::Class, &context, first_line, exception.as_str());
+        }
+        for annotation in [method.annotations(), method.invisible_annotations()].concat() {
+            search(
+                Target::Annotation,
+                &context,
+                first_line,
+                annotation.type_name().as_str(),
+            );
+        }
+
+        let Some(code) = code else {
+            continue;
+        };
+        for instruction in code.instructions() {
+            // the rest of the code can't be read after an invalid instruction
+            let Ok(instruction) = instruction else {
+                break;
+            };
+            let Some(constant) = instruction
+                .constant_index()
+                .and_then(|index| class.constant(index))
+            else {
+                continue;
+            };
+            let line = lines.as_ref().and_then(|lines| {
+                u16::try_from(instruction.offset())
+                    .ok()
+                    .and_then(|pc| lines.pc_to_line(pc))
+            });
+            match constant {
+                ResolvedConstant::String(string) => {
+                    search(Target::String, &context, line, string);
+                }
+                ResolvedConstant::Class(referenced) => {
+                    search(Target::Class, &context, line, referenced.as_str());
+                }
+                ResolvedConstant::FieldRef(member) => {
+                    search(Target::Class, &context, line, member.class().as_str());
+                }
+                ResolvedConstant::MethodRef(member)
+                | ResolvedConstant::InterfaceMethodRef(member) => {
+                    search(Target::Class, &context, line, member.class().as_str());
+                    let called = format!("{}.{}", member.class(), member.name());
+                    search(Target::Method, &context, line, &called);
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// The classes in a field or method signature, with array classes given by their elements
+fn signature_classes(signature: &Signature) -> Vec<String> {
+    match signature {
+        Signature::FullyQualifiedClass(name) => vec![name.to_string()],
+        Signature::Array(component) => signature_classes(component),
+        Signature::Method { args, ret_type } => args
+            .iter()
+            .chain(std::iter::once(&**ret_type))
+            .flat_map(signature_classes)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// The path of the source file of a class within its source tree, or of the class file if the
+/// source file isn't known
+fn source_path(class: &JavaClass) -> String {
+    match (class.get::<SourceFile>(), class.this().package()) {
+        (Some(source), Some(package)) => format!("{}/{}", package, source.path().display()),
+        (Some(source), None) => source.path().display().to_string(),
+        (None, _) => format!("{}.class", class.this()),
+    }
+}
+
+/// A line of a tree printed by `tree`, with the lines below it
+struct TreeNode {
+    label: String,
+    children: Vec<TreeNode>,
+}
+
+/// Prints the hierarchy of a class, as trees of its ancestors and descendants, or as a graph
+fn tree(
+    parser: &JavaClassParser,
+    mapping: Option<&Mapping>,
+    format: Format,
+    class: &str,
+    descendants: bool,
+    dot: bool,
+) -> Result<(), Error> {
+    let class = find(parser, mapping, class)?;
+    let mut graph = inspect(&class, parser)?;
+    let index = match descendants {
+        true => Some(InheritanceIndex::build(parser)?),
+        false => None,
+    };
+
+    if dot || matches!(format, Format::Json) {
+        // descendants are added as roots, so their edges to the class are in the graph
+        if let Some(index) = &index {
+            for descendant in index.subtypes_of(class.this()) {
+                graph.extend(&parser.find(descendant)?, parser)?;
             }
         }
+        match dot {
+            true => print!("{}", graph.to_dot()),
+            false => print_json(&serde_json::to_value(&graph).map_err(io::Error::from)?)?,
+        }
+        return Ok(());
     }
 
+    let mut supertypes = HashMap::<&FQName, Vec<(&FQName, InheritKind)>>::new();
+    for (subtype, supertype, kind) in graph.edges() {
+        supertypes
+            .entry(subtype)
+            .or_default()
+            .push((supertype, kind));
+    }
+    let external = graph.external_types().collect::<HashSet<_>>();
+    let ancestors = TreeNode {
+        label: original_name(mapping, class.this()).to_string(),
+        children: ancestor_nodes(class.this(), &supertypes, &external, mapping),
+    };
+    print_tree(&ancestors);
+
+    if let Some(index) = &index {
+        let mut path = vec![class.this()];
+        let descendants = TreeNode {
+            label: original_name(mapping, class.this()).to_string(),
+            children: descendant_nodes(index, &mut path, mapping),
+        };
+        println!();
+        print_tree(&descendants);
+    }
     Ok(())
 }
+
+fn ancestor_nodes(
+    name: &FQName,
+    supertypes: &HashMap<&FQName, Vec<(&FQName, InheritKind)>>,
+    external: &HashSet<&FQName>,
+    mapping: Option<&Mapping>,
+) -> Vec<TreeNode> {
+    supertypes
+        .get(name)
+        .into_iter()
+        .flatten()
+        .map(|&(supertype, kind)| {
+            let kind = match kind {
+                InheritKind::Extends => "extends",
+                InheritKind::Implements => "implements",
+            };
+            let mut label = format!("{kind} {}", original_name(mapping, supertype));
+            if external.contains(supertype) {
+                label.push_str(" (not found)");
+            }
+            TreeNode {
+                label,
+                children: ancestor_nodes(supertype, supertypes, external, mapping),
+            }
+        })
+        .collect()
+}
+
+/// The types that extend or implement the last type of a path down the hierarchy. Types already
+/// on the path are skipped, in case classes on the classpath inherit each other.
+fn descendant_nodes<'a>(
+    index: &'a InheritanceIndex,
+    path: &mut Vec<&'a FQName>,
+    mapping: Option<&Mapping>,
+) -> Vec<TreeNode> {
+    let name = *path.last().expect("path starts at the inspected class");
+    let mut nodes = vec![];
+    for (subtype, kind) in index.direct_subtypes(name) {
+        if path.contains(&subtype) {
+            continue;
+        }
+        let kind = match kind {
+            InheritKind::Extends => "extended by",
+            InheritKind::Implements => "implemented by",
+        };
+        path.push(subtype);
+        let children = descendant_nodes(index, path, mapping);
+        path.pop();
+        nodes.push(TreeNode {
+            label: format!("{kind} {}", original_name(mapping, subtype)),
+            children,
+        });
+    }
+    nodes
+}
+
+fn print_tree(root: &TreeNode) {
+    println!("{}", root.label);
+    print_children(&root.children, "");
+}
+
+fn print_children(children: &[TreeNode], prefix: &str) {
+    for (index, child) in children.iter().enumerate() {
+        let last = index + 1 == children.len();
+        let (branch, indent) = match last {
+            true => ("└── ", "    "),
+            false => ("├── ", "│   "),
+        };
+        println!("{prefix}{branch}{}", child.label);
+        print_children(&child.children, &format!("{prefix}{indent}"));
+    }
+}
+
+/// Prints the changes between two versions of an API. Returns whether the new version is
+/// compatible with the old version.
+fn diff(format: Format, old: &str, new: &str, breaking_only: bool) -> Result<bool, Error> {
+    let report = diff_jars(old, new)?;
+    let changes = report
+        .changes()
+        .iter()
+        .filter(|change| !breaking_only || !change.is_compatible())
+        .collect::<Vec<&ApiChange>>();
+    match format {
+        Format::Text => {
+            for change in changes {
+                println!("{change}");
+            }
+        }
+        Format::Json => print_json(&serde_json::to_value(changes).map_err(io::Error::from)?)?,
+    }
+    Ok(report.is_binary_compatible() && report.is_source_compatible())
+}